@@ -0,0 +1,182 @@
+//! Benchmarks the cached read path of `MarketPriceStore`
+//!
+//! Compares the lock-free `ArcSwap` store against the previous design,
+//! which took a map-level and a slot-level tokio `RwLock` on every read.
+//! The contended group needs several cores to be meaningful: lock-free
+//! readers never write shared memory, so their advantage grows with the
+//! number of readers running in parallel.
+//!
+//! Also compares cloning every price against sharing the copy-on-write
+//! snapshot when reading all assets at once.
+//!
+//! Run with `cargo bench --bench store_read`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use futures::executor::block_on;
+use market_price_sdk::store::MarketPriceStore;
+use market_price_sdk::{Asset, PriceData};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// The previous store layout: a locked map of individually locked slots
+struct RwLockStore {
+    prices: RwLock<HashMap<Asset, Arc<RwLock<Option<PriceData>>>>>,
+}
+
+impl RwLockStore {
+    async fn update_price(&self, asset: Asset, price_data: PriceData) {
+        let prices = self.prices.read().await;
+        if let Some(slot) = prices.get(&asset) {
+            *slot.write().await = Some(price_data);
+        }
+    }
+
+    async fn get_price(&self, asset: Asset) -> Option<PriceData> {
+        let prices = self.prices.read().await;
+        let slot = prices.get(&asset)?.read().await;
+        slot.clone()
+    }
+}
+
+fn sample_price() -> PriceData {
+    PriceData::new(Asset::SOL, 150.0, "bench".to_string())
+}
+
+fn baseline_store() -> RwLockStore {
+    RwLockStore {
+        prices: RwLock::new(HashMap::from([(
+            Asset::SOL,
+            Arc::new(RwLock::new(Some(sample_price()))),
+        )])),
+    }
+}
+
+fn bench_get_price(c: &mut Criterion) {
+    let store = MarketPriceStore::new();
+    block_on(store.update_price(Asset::SOL, sample_price()));
+    let baseline = baseline_store();
+
+    let mut group = c.benchmark_group("get_price");
+    group.bench_function("arc_swap", |b| {
+        b.iter(|| block_on(store.get_price(black_box(Asset::SOL))))
+    });
+    group.bench_function("rwlock_baseline", |b| {
+        b.iter(|| block_on(baseline.get_price(black_box(Asset::SOL))))
+    });
+    group.finish();
+}
+
+/// Runs `iters` reads on each of several reader threads while a writer
+/// thread keeps updating the same asset, returning the slowest reader's time
+fn parallel_reads<R, W>(iters: u64, read: R, write: W) -> Duration
+where
+    R: Fn() + Send + Sync + 'static,
+    W: Fn() + Send + 'static,
+{
+    let readers = thread::available_parallelism().map_or(4, |n| n.get().max(2));
+    let read = Arc::new(read);
+    let running = Arc::new(AtomicBool::new(true));
+
+    let writer = {
+        let running = running.clone();
+        thread::spawn(move || {
+            while running.load(Ordering::Relaxed) {
+                write();
+            }
+        })
+    };
+
+    let handles: Vec<_> = (0..readers)
+        .map(|_| {
+            let read = read.clone();
+            thread::spawn(move || {
+                let start = Instant::now();
+                for _ in 0..iters {
+                    read();
+                }
+                start.elapsed()
+            })
+        })
+        .collect();
+
+    let elapsed = handles
+        .into_iter()
+        .map(|h| h.join().unwrap())
+        .max()
+        .unwrap_or_default();
+
+    running.store(false, Ordering::Relaxed);
+    writer.join().unwrap();
+    elapsed
+}
+
+/// Concurrent readers racing a writer on the same asset, as happens when
+/// many strategy tasks read while a streaming provider delivers ticks
+fn bench_get_price_contended(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_price_contended");
+
+    let store = Arc::new(MarketPriceStore::new());
+    block_on(store.update_price(Asset::SOL, sample_price()));
+    group.bench_function("arc_swap", |b| {
+        b.iter_custom(|iters| {
+            let reader = store.clone();
+            let writer = store.clone();
+            parallel_reads(
+                iters,
+                move || {
+                    let _ = black_box(block_on(reader.get_price(Asset::SOL)));
+                },
+                move || {
+                    block_on(writer.update_price(Asset::SOL, sample_price()));
+                },
+            )
+        })
+    });
+
+    let baseline = Arc::new(baseline_store());
+    group.bench_function("rwlock_baseline", |b| {
+        b.iter_custom(|iters| {
+            let reader = baseline.clone();
+            let writer = baseline.clone();
+            parallel_reads(
+                iters,
+                move || {
+                    black_box(block_on(reader.get_price(Asset::SOL)));
+                },
+                move || block_on(writer.update_price(Asset::SOL, sample_price())),
+            )
+        })
+    });
+
+    group.finish();
+}
+
+/// Reading every asset at once: cloning the map versus sharing the snapshot
+fn bench_get_all_prices(c: &mut Criterion) {
+    let store = MarketPriceStore::new();
+    for asset in Asset::all() {
+        let price = PriceData::new(*asset, 150.0, "bench".to_string());
+        block_on(store.update_price(*asset, price));
+    }
+
+    let mut group = c.benchmark_group("get_all_prices");
+    group.bench_function("clone", |b| {
+        b.iter(|| black_box(block_on(store.get_all_prices())))
+    });
+    group.bench_function("arc_snapshot", |b| {
+        b.iter(|| black_box(store.get_all_prices_arc()))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_get_price,
+    bench_get_price_contended,
+    bench_get_all_prices
+);
+criterion_main!(benches);
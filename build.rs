@@ -0,0 +1,5 @@
+fn main() {
+    #[cfg(feature = "uniffi")]
+    uniffi::generate_scaffolding("src/market_price_sdk.udl")
+        .expect("failed to generate the UniFFI scaffolding");
+}
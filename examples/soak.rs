@@ -0,0 +1,284 @@
+//! Soak test: runs a tracker against a simulated provider for hours and
+//! checks that memory and task counts stay flat
+//!
+//! ```bash
+//! cargo run --release --example soak -- [duration_secs] [sample_secs]
+//! ```
+//!
+//! The provider is a random walk wrapped in a `ChaosProvider`, so failed,
+//! slow, stale and spiking fetches are part of the load. Alongside the
+//! tracker's own polling, the test refreshes every 50ms, reads prices and
+//! history every millisecond, drains an update subscription, and drops a
+//! `RequestTimer` every millisecond. Resident memory and live task counts
+//! are sampled throughout; after a warm-up the first sample is the
+//! baseline, and the process exits non-zero if the last sample has grown
+//! past the allowance.
+
+use market_price_sdk::metrics::{MetricsCollector, RequestTimer};
+use market_price_sdk::providers::{ChaosConfig, ChaosProvider};
+use market_price_sdk::{tasks, Asset, MarketPriceProvider, MarketPriceTracker, PriceData};
+use market_price_sdk::{ProviderError, TrackerConfig};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default run length: four hours
+const DEFAULT_DURATION_SECS: u64 = 4 * 3600;
+/// Default time between samples
+const DEFAULT_SAMPLE_SECS: u64 = 60;
+/// Longest warm-up before the baseline sample
+const MAX_WARMUP: Duration = Duration::from_secs(600);
+/// Resident memory the process may gain over the baseline
+const MAX_RSS_GROWTH_KB: u64 = 32 * 1024;
+/// Live tasks the process may gain over the baseline, covering tasks that
+/// are briefly in flight when a sample is taken
+const MAX_TASK_GROWTH: usize = 64;
+
+/// Random walk over every asset, starting at 100 USD
+struct SimulatedProvider {
+    prices: Mutex<HashMap<Asset, f64>>,
+    state: AtomicU64,
+}
+
+impl SimulatedProvider {
+    fn new() -> Self {
+        Self {
+            prices: Mutex::new(Asset::all().iter().map(|asset| (*asset, 100.0)).collect()),
+            state: AtomicU64::new(0x2545_F491_4F6C_DD1D),
+        }
+    }
+
+    /// Uniform draw in `[-1, 1)` from a xorshift generator
+    fn step(&self) -> f64 {
+        let mut x = self.state.load(Ordering::Relaxed);
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state.store(x, Ordering::Relaxed);
+        (x >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }
+}
+
+#[async_trait::async_trait]
+impl MarketPriceProvider for SimulatedProvider {
+    async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+        let prices = self.fetch_prices(&[asset]).await?;
+        prices
+            .get(&asset)
+            .cloned()
+            .ok_or_else(|| ProviderError::UnsupportedAsset(asset.symbol().to_string()))
+    }
+
+    async fn fetch_prices(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        let mut prices = self.prices.lock().unwrap();
+        Ok(assets
+            .iter()
+            .map(|asset| {
+                let price = prices.entry(*asset).or_insert(100.0);
+                *price = (*price * (1.0 + self.step() * 0.001)).max(0.01);
+                (
+                    *asset,
+                    PriceData::new(*asset, *price, "simulated".to_string()),
+                )
+            })
+            .collect())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "simulated"
+    }
+}
+
+/// Resources in use at one point of the run
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    elapsed: Duration,
+    rss_kb: Option<u64>,
+    runtime_tasks: usize,
+    sdk_tasks: usize,
+    history_len: usize,
+}
+
+/// Resident set size from the `VmRSS` line of `/proc/self/status`, on Linux
+fn rss_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+fn sample(tracker: &MarketPriceTracker, start: Instant) -> Sample {
+    Sample {
+        elapsed: start.elapsed(),
+        rss_kb: rss_kb(),
+        runtime_tasks: tokio::runtime::Handle::current()
+            .metrics()
+            .num_alive_tasks(),
+        sdk_tasks: tasks::list().len(),
+        history_len: tracker
+            .get_history(Asset::SOL, Duration::from_secs(24 * 3600))
+            .len(),
+    }
+}
+
+fn report(label: &str, sample: &Sample) {
+    println!(
+        "{:<9} t={:>6}s rss={:>8} KB runtime_tasks={:>4} sdk_tasks={:>4} sol_history={:>6}",
+        label,
+        sample.elapsed.as_secs(),
+        sample
+            .rss_kb
+            .map_or_else(|| "n/a".to_string(), |kb| kb.to_string()),
+        sample.runtime_tasks,
+        sample.sdk_tasks,
+        sample.history_len,
+    );
+}
+
+#[tokio::main]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+    let duration = Duration::from_secs(
+        args.next()
+            .and_then(|arg| arg.parse().ok())
+            .unwrap_or(DEFAULT_DURATION_SECS),
+    );
+    let sample_every = Duration::from_secs(
+        args.next()
+            .and_then(|arg| arg.parse().ok())
+            .unwrap_or(DEFAULT_SAMPLE_SECS)
+            .max(1),
+    );
+    let warmup = (duration / 10).min(MAX_WARMUP);
+
+    let provider = Arc::new(ChaosProvider::new(
+        Arc::new(SimulatedProvider::new()),
+        ChaosConfig {
+            seed: 1,
+            max_latency: Duration::from_millis(50),
+            error_probability: 0.02,
+            spike_probability: 0.001,
+            ..Default::default()
+        },
+    ));
+    let config = TrackerConfig::default();
+    let history_capacity = config.history_capacity;
+    let tracker = MarketPriceTracker::global_named_with("soak", provider, config).await;
+
+    println!(
+        "Soaking for {}s (warm-up {}s, sample every {}s)",
+        duration.as_secs(),
+        warmup.as_secs(),
+        sample_every.as_secs()
+    );
+
+    let refresher = {
+        let tracker = tracker.clone();
+        tokio::spawn(async move {
+            loop {
+                let _ = tracker.refresh_now().await;
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+    };
+    let reader = {
+        let tracker = tracker.clone();
+        tokio::spawn(async move {
+            loop {
+                for asset in Asset::all() {
+                    let _ = tracker.get_price(*asset).await;
+                }
+                let _ = tracker.get_history(Asset::SOL, Duration::from_secs(60));
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+    };
+    let subscriber = {
+        let mut updates = tracker.subscribe();
+        tokio::spawn(async move {
+            loop {
+                if let Err(tokio::sync::broadcast::error::RecvError::Closed) = updates.recv().await
+                {
+                    break;
+                }
+            }
+        })
+    };
+    let timers = tokio::spawn(async move {
+        let collector = Arc::new(MetricsCollector::new("soak"));
+        loop {
+            let mut timer = RequestTimer::new(collector.clone());
+            timer.mark_success();
+            drop(timer);
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    });
+
+    let start = Instant::now();
+    let mut baseline = None;
+    let mut last = sample(&tracker, start);
+    report("start", &last);
+    while start.elapsed() < duration {
+        tokio::time::sleep(sample_every.min(duration.saturating_sub(start.elapsed()))).await;
+        last = sample(&tracker, start);
+        if baseline.is_none() && last.elapsed >= warmup {
+            baseline = Some(last);
+            report("baseline", &last);
+        } else {
+            report("sample", &last);
+        }
+    }
+
+    for task in [refresher, reader, subscriber, timers] {
+        task.abort();
+    }
+    tracker.shutdown();
+
+    let baseline = baseline.unwrap_or(last);
+    let mut failures = Vec::new();
+    if let (Some(base), Some(end)) = (baseline.rss_kb, last.rss_kb) {
+        if end > base + MAX_RSS_GROWTH_KB {
+            failures.push(format!(
+                "resident memory grew by {} KB (allowed {} KB)",
+                end - base,
+                MAX_RSS_GROWTH_KB
+            ));
+        }
+    }
+    if last.runtime_tasks > baseline.runtime_tasks + MAX_TASK_GROWTH {
+        failures.push(format!(
+            "live runtime tasks grew from {} to {}",
+            baseline.runtime_tasks, last.runtime_tasks
+        ));
+    }
+    if last.sdk_tasks > baseline.sdk_tasks + MAX_TASK_GROWTH {
+        failures.push(format!(
+            "live SDK tasks grew from {} to {}",
+            baseline.sdk_tasks, last.sdk_tasks
+        ));
+    }
+    if last.history_len > history_capacity {
+        failures.push(format!(
+            "SOL history holds {} entries (capacity {})",
+            last.history_len, history_capacity
+        ));
+    }
+
+    if failures.is_empty() {
+        println!("Soak passed");
+    } else {
+        for failure in &failures {
+            eprintln!("Soak failed: {}", failure);
+        }
+        std::process::exit(1);
+    }
+}
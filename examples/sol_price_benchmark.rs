@@ -36,7 +36,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!();
 
     // 3. Benchmark Memory Latency
-    println!("2. Benchmarking Memory Latency (fetching from internal RwLock cache)...");
+    println!("2. Benchmarking Memory Latency (fetching from internal lock-free cache)...");
     let iterations = 10_000;
     let mut total_memory_latency = std::time::Duration::default();
     
@@ -46,7 +46,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let start_bench = Instant::now();
     for _ in 0..iterations {
         let start_mem = Instant::now();
-        // This involves a read lock on the store and a read lock on the asset slot
+        // Lock-free load from the asset's ArcSwap slot plus a clone of the price
         let _ = tracker.get_price(Asset::SOL).await?;
         total_memory_latency += start_mem.elapsed();
     }
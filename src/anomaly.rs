@@ -0,0 +1,257 @@
+//! Anomaly detection on incoming ticks
+//!
+//! A detector compares each incoming price with the asset's recent history
+//! in the store. Flagged ticks are marked [`Quality::Suspect`]; with
+//! [`AnomalyConfig::withhold_until_confirmed`] they are also kept out of
+//! the store until a tick from a different source agrees with them.
+//!
+//! Detection needs the price history, so it is inactive when
+//! `TrackerConfig::history_capacity` is 0.
+//!
+//! # Example
+//! ```
+//! use market_price_sdk::anomaly::{AnomalyConfig, MadDetector};
+//! use market_price_sdk::TrackerConfig;
+//! use std::sync::Arc;
+//!
+//! let config = TrackerConfig {
+//!     anomaly: Some(AnomalyConfig::new(Arc::new(MadDetector::default()))),
+//!     ..Default::default()
+//! };
+//! ```
+//!
+//! [`Quality::Suspect`]: crate::types::Quality::Suspect
+
+use crate::types::PriceData;
+use std::sync::Arc;
+
+/// Why a tick was flagged
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    /// How far the tick's return lies from recent behaviour, in units of
+    /// the detector's dispersion measure
+    pub score: f64,
+    /// Name of the detector that flagged it
+    pub detector: &'static str,
+}
+
+/// Flags suspicious ticks against recent history
+pub trait AnomalyDetector: Send + Sync {
+    /// Checks a tick against recent accepted prices (oldest first)
+    ///
+    /// Returns `None` when the tick looks normal or there is not enough
+    /// history to judge.
+    fn detect(&self, price: &PriceData, recent: &[PriceData]) -> Option<Anomaly>;
+
+    /// Number of recent prices the detector wants to see
+    fn window(&self) -> usize;
+}
+
+/// Log returns between consecutive prices, followed by the return of the
+/// new tick relative to the last accepted price
+fn returns(price: &PriceData, recent: &[PriceData]) -> Option<(Vec<f64>, f64)> {
+    let last = recent.last()?;
+    if last.price_usd <= 0.0 || price.price_usd <= 0.0 {
+        return None;
+    }
+    let history = recent
+        .windows(2)
+        .filter(|w| w[0].price_usd > 0.0 && w[1].price_usd > 0.0)
+        .map(|w| (w[1].price_usd / w[0].price_usd).ln())
+        .collect();
+    Some((history, (price.price_usd / last.price_usd).ln()))
+}
+
+/// Flags ticks whose return is more than `threshold` standard deviations
+/// from the recent mean return
+#[derive(Debug, Clone)]
+pub struct ZScoreDetector {
+    /// Number of recent prices considered
+    pub window: usize,
+    /// Score above which a tick is flagged
+    pub threshold: f64,
+    /// Minimum number of recent returns before flagging anything
+    pub min_samples: usize,
+    /// Floor on the dispersion, in basis points, so a flat history does
+    /// not flag every small move
+    pub min_dispersion_bps: f64,
+}
+
+impl Default for ZScoreDetector {
+    fn default() -> Self {
+        Self {
+            window: 50,
+            threshold: 6.0,
+            min_samples: 10,
+            min_dispersion_bps: 5.0,
+        }
+    }
+}
+
+impl AnomalyDetector for ZScoreDetector {
+    fn detect(&self, price: &PriceData, recent: &[PriceData]) -> Option<Anomaly> {
+        let (history, latest) = returns(price, recent)?;
+        if history.len() < self.min_samples {
+            return None;
+        }
+
+        let n = history.len() as f64;
+        let mean = history.iter().sum::<f64>() / n;
+        let std_dev = (history.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n).sqrt();
+        let score = (latest - mean).abs() / std_dev.max(self.min_dispersion_bps / 10_000.0);
+
+        (score > self.threshold).then_some(Anomaly {
+            score,
+            detector: "z-score",
+        })
+    }
+
+    fn window(&self) -> usize {
+        self.window
+    }
+}
+
+/// Flags ticks whose return is more than `threshold` scaled median
+/// absolute deviations from the recent median return
+///
+/// More robust than [`ZScoreDetector`] when the window itself contains
+/// outliers.
+#[derive(Debug, Clone)]
+pub struct MadDetector {
+    /// Number of recent prices considered
+    pub window: usize,
+    /// Score above which a tick is flagged
+    pub threshold: f64,
+    /// Minimum number of recent returns before flagging anything
+    pub min_samples: usize,
+    /// Floor on the dispersion, in basis points, so a flat history does
+    /// not flag every small move
+    pub min_dispersion_bps: f64,
+}
+
+impl Default for MadDetector {
+    fn default() -> Self {
+        Self {
+            window: 50,
+            threshold: 8.0,
+            min_samples: 10,
+            min_dispersion_bps: 5.0,
+        }
+    }
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+impl AnomalyDetector for MadDetector {
+    fn detect(&self, price: &PriceData, recent: &[PriceData]) -> Option<Anomaly> {
+        let (mut history, latest) = returns(price, recent)?;
+        if history.len() < self.min_samples {
+            return None;
+        }
+
+        let center = median(&mut history);
+        let mut deviations: Vec<f64> = history.iter().map(|r| (r - center).abs()).collect();
+        // 1.4826 makes the MAD a consistent estimator of the standard deviation
+        let mad = 1.4826 * median(&mut deviations);
+        let score = (latest - center).abs() / mad.max(self.min_dispersion_bps / 10_000.0);
+
+        (score > self.threshold).then_some(Anomaly {
+            score,
+            detector: "mad",
+        })
+    }
+
+    fn window(&self) -> usize {
+        self.window
+    }
+}
+
+/// Anomaly detection settings for the store
+#[derive(Clone)]
+pub struct AnomalyConfig {
+    /// Detector applied to every incoming tick
+    pub detector: Arc<dyn AnomalyDetector>,
+    /// Keep flagged ticks out of the store until a tick from a different
+    /// source lands within `confirmation_tolerance_bps` of them
+    pub withhold_until_confirmed: bool,
+    /// How close a confirming tick must be to the withheld one, in basis points
+    pub confirmation_tolerance_bps: f64,
+}
+
+impl AnomalyConfig {
+    /// Flags suspicious ticks without withholding them
+    pub fn new(detector: Arc<dyn AnomalyDetector>) -> Self {
+        Self {
+            detector,
+            withhold_until_confirmed: false,
+            confirmation_tolerance_bps: 50.0,
+        }
+    }
+
+    /// Also withholds flagged ticks until another source confirms them
+    pub fn withholding(mut self) -> Self {
+        self.withhold_until_confirmed = true;
+        self
+    }
+
+    /// Returns true if `confirmation` corroborates the withheld `suspect`
+    pub(crate) fn confirms(&self, suspect: &PriceData, confirmation: &PriceData) -> bool {
+        if suspect.source == confirmation.source || suspect.price_usd == 0.0 {
+            return false;
+        }
+        let deviation_bps =
+            (confirmation.price_usd - suspect.price_usd).abs() / suspect.price_usd.abs() * 10_000.0;
+        deviation_bps <= self.confirmation_tolerance_bps
+    }
+}
+
+impl std::fmt::Debug for AnomalyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnomalyConfig")
+            .field("withhold_until_confirmed", &self.withhold_until_confirmed)
+            .field(
+                "confirmation_tolerance_bps",
+                &self.confirmation_tolerance_bps,
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Asset;
+
+    fn ticks(prices: &[f64]) -> Vec<PriceData> {
+        prices
+            .iter()
+            .map(|p| PriceData::new(Asset::SOL, *p, "test".to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_detectors_flag_spike() {
+        let recent = ticks(&[
+            100.0, 100.1, 99.9, 100.2, 100.0, 100.1, 99.8, 100.0, 100.1, 100.0, 99.9, 100.0,
+        ]);
+        let normal = PriceData::new(Asset::SOL, 100.15, "test".to_string());
+        let spike = PriceData::new(Asset::SOL, 120.0, "test".to_string());
+
+        for detector in [
+            Arc::new(ZScoreDetector::default()) as Arc<dyn AnomalyDetector>,
+            Arc::new(MadDetector::default()),
+        ] {
+            assert!(detector.detect(&normal, &recent).is_none());
+            assert!(detector.detect(&spike, &recent).is_some());
+            assert!(detector.detect(&spike, &recent[..3]).is_none());
+        }
+    }
+}
@@ -0,0 +1,369 @@
+//! Blocking facade for applications without an async runtime
+//!
+//! [`blocking::MarketPriceTracker`](MarketPriceTracker) mirrors the main
+//! tracker's API with plain blocking methods. Each instance owns a small
+//! multi-threaded tokio runtime: the tracker's polling loop and streaming
+//! connections run on its worker threads, and every call blocks the caller
+//! until the underlying future completes.
+//!
+//! Reads that only consult the store are cheap; calls that may hit a
+//! provider (`get_price` with read-through, `refresh_now`, `self_test`)
+//! block for as long as the request takes.
+//!
+//! The methods panic when called from within an async runtime, as
+//! `Runtime::block_on` does. Async code should use
+//! [`crate::MarketPriceTracker`] directly, or reach it through
+//! [`MarketPriceTracker::inner`].
+//!
+//! # Example
+//! ```no_run
+//! use market_price_sdk::{blocking::MarketPriceTracker, Asset};
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let tracker = MarketPriceTracker::global();
+//!     let sol = tracker.get_price(Asset::SOL)?;
+//!     println!("SOL: ${:.2}", sol.price_usd);
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    config::TrackerConfig,
+    error::{PriceError, ProviderError},
+    provider::MarketPriceProvider,
+    self_test::SelfTestReport,
+    store::PriceStoreReader,
+    subscription::{PriceSubscription, SubscriptionConfig},
+    tracker,
+    types::{Asset, ComponentHealth, PriceData, PriceSnapshot},
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+static GLOBAL_TRACKER: OnceLock<Arc<MarketPriceTracker>> = OnceLock::new();
+
+/// Worker threads of each facade's runtime; the tracker's own work is
+/// light, so two are enough to keep polling while a caller is blocked
+const WORKER_THREADS: usize = 2;
+
+/// Market price tracker with a blocking API
+pub struct MarketPriceTracker {
+    inner: Arc<tracker::MarketPriceTracker>,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl MarketPriceTracker {
+    /// Returns the blocking global singleton
+    ///
+    /// On first call this starts the facade's runtime and, on it, the async
+    /// [`global`](tracker::MarketPriceTracker::global) tracker with its
+    /// background polling. Subsequent calls return the same instance.
+    ///
+    /// # Panics
+    /// Panics if called from within an async runtime or if the runtime's
+    /// threads cannot be started.
+    pub fn global() -> Arc<Self> {
+        Self::global_with(TrackerConfig::default())
+    }
+
+    /// Returns the blocking global singleton once it holds its first price
+    ///
+    /// See [`global_ready`](tracker::MarketPriceTracker::global_ready).
+    pub fn global_ready() -> Arc<Self> {
+        let tracker = Self::global();
+        tracker.ready();
+        tracker
+    }
+
+    /// Returns the blocking global singleton, creating it with `config`
+    ///
+    /// As with the async [`global_with`](tracker::MarketPriceTracker::global_with),
+    /// the configuration only applies if this call creates the instance.
+    pub fn global_with(config: TrackerConfig) -> Arc<Self> {
+        GLOBAL_TRACKER
+            .get_or_init(|| {
+                let runtime = build_runtime();
+                let inner = runtime.block_on(tracker::MarketPriceTracker::global_with(config));
+                Arc::new(Self { inner, runtime })
+            })
+            .clone()
+    }
+
+    /// Creates a tracker with the environment-selected provider, as
+    /// [`MarketPriceTracker::new`](tracker::MarketPriceTracker::new) does
+    pub fn new() -> Self {
+        let runtime = build_runtime();
+        let inner = runtime.block_on(tracker::MarketPriceTracker::new());
+        Self {
+            inner: Arc::new(inner),
+            runtime,
+        }
+    }
+
+    /// Creates a tracker with a custom provider
+    pub fn with_provider(provider: Arc<dyn MarketPriceProvider>) -> Self {
+        Self::with_config(provider, TrackerConfig::default())
+    }
+
+    /// Creates a tracker with a custom provider and configuration
+    pub fn with_config(provider: Arc<dyn MarketPriceProvider>, config: TrackerConfig) -> Self {
+        let runtime = build_runtime();
+        let inner = {
+            let _guard = runtime.enter();
+            tracker::MarketPriceTracker::with_config(provider, config)
+        };
+        Self {
+            inner: Arc::new(inner),
+            runtime,
+        }
+    }
+
+    /// Creates a tracker with a custom provider and configuration and
+    /// starts its background polling, as the singletons do
+    pub fn start_with(provider: Arc<dyn MarketPriceProvider>, config: TrackerConfig) -> Self {
+        let tracker = Self::with_config(provider, config);
+        {
+            let _guard = tracker.runtime.enter();
+            tracker.inner.start_background_task();
+        }
+        tracker
+    }
+
+    /// Returns the async tracker behind the facade
+    pub fn inner(&self) -> &Arc<tracker::MarketPriceTracker> {
+        &self.inner
+    }
+
+    /// Blocks until the store holds at least one price
+    ///
+    /// See [`ready`](tracker::MarketPriceTracker::ready).
+    pub fn ready(&self) {
+        self.block_on(self.inner.ready());
+    }
+
+    /// Gets the current price for an asset
+    ///
+    /// See [`MarketPriceTracker::get_price`](tracker::MarketPriceTracker::get_price).
+    pub fn get_price(&self, asset: Asset) -> Result<PriceData, PriceError> {
+        self.block_on(self.inner.get_price(asset))
+    }
+
+    /// Gets the current price for an asset, fetching it within `budget` if
+    /// needed
+    ///
+    /// See [`MarketPriceTracker::get_price_within`](tracker::MarketPriceTracker::get_price_within).
+    pub fn get_price_within(
+        &self,
+        asset: Asset,
+        budget: Duration,
+    ) -> Result<PriceData, PriceError> {
+        self.block_on(self.inner.get_price_within(asset, budget))
+    }
+
+    /// Gets the current prices for several assets, one result per asset
+    pub fn get_prices(&self, assets: &[Asset]) -> HashMap<Asset, Result<PriceData, PriceError>> {
+        self.block_on(self.inner.get_prices(assets))
+    }
+
+    /// Gets all fresh prices
+    pub fn get_all_prices(&self) -> HashMap<Asset, PriceData> {
+        self.block_on(self.inner.get_all_prices())
+    }
+
+    /// Returns the cached price for an asset without contacting a provider
+    pub fn try_get_price(&self, asset: Asset) -> Option<PriceData> {
+        self.inner.try_get_price_sync(asset)
+    }
+
+    /// Gets the price the tracker held for an asset at a point in time
+    pub fn get_price_at(
+        &self,
+        asset: Asset,
+        timestamp: DateTime<Utc>,
+    ) -> Result<PriceData, PriceError> {
+        self.inner.get_price_at(asset, timestamp)
+    }
+
+    /// Returns the recorded prices of an asset over the last `window`
+    pub fn get_history(&self, asset: Asset, window: Duration) -> Vec<PriceData> {
+        self.inner.get_history(asset, window)
+    }
+
+    /// Subscribes to price updates through a blocking queue
+    ///
+    /// See [`subscribe_with`](tracker::MarketPriceTracker::subscribe_with).
+    pub fn subscribe_with(&self, config: SubscriptionConfig) -> Subscription {
+        let _guard = self.runtime.enter();
+        Subscription {
+            inner: self.inner.subscribe_with(config),
+            handle: self.runtime.handle().clone(),
+        }
+    }
+
+    /// Returns a read-only handle to the price cache
+    pub fn store_handle(&self) -> PriceStoreReader {
+        self.inner.store_handle()
+    }
+
+    /// Returns a serializable snapshot of all stored prices
+    pub fn snapshot(&self) -> PriceSnapshot {
+        self.inner.snapshot()
+    }
+
+    /// Returns true if the stored price for an asset is stale or missing
+    pub fn is_stale(&self, asset: Asset) -> bool {
+        self.block_on(self.inner.is_stale(asset))
+    }
+
+    /// Returns the name of the active provider
+    pub fn provider_name(&self) -> &str {
+        self.inner.provider_name()
+    }
+
+    /// Fetches all enabled assets now, blocking until the fetch completes
+    pub fn refresh_now(&self) -> Result<(), ProviderError> {
+        self.block_on(self.inner.refresh_now())
+    }
+
+    /// Sends one request to every configured provider and reports the result
+    pub fn self_test(&self) -> SelfTestReport {
+        self.block_on(self.inner.self_test())
+    }
+
+    /// Reports the tracker's health
+    pub fn health_check(&self) -> ComponentHealth {
+        self.block_on(self.inner.health_check())
+    }
+
+    /// Stops the tracker's background tasks
+    pub fn shutdown(&self) {
+        self.inner.shutdown();
+    }
+
+    /// Replaces the provider, blocking until fetches still running
+    /// against the previous one have finished
+    ///
+    /// See [`MarketPriceTracker::set_provider`](tracker::MarketPriceTracker::set_provider).
+    pub fn set_provider(&self, provider: Arc<dyn MarketPriceProvider>) {
+        self.block_on(self.inner.set_provider(provider));
+    }
+
+    /// Stops all outbound provider requests, keeping the cached prices
+    ///
+    /// See [`MarketPriceTracker::pause`](tracker::MarketPriceTracker::pause).
+    pub fn pause(&self) {
+        self.inner.pause();
+    }
+
+    /// Resumes provider requests after [`pause`](Self::pause)
+    pub fn resume(&self) {
+        self.inner.resume();
+    }
+
+    /// Returns true while provider requests are paused
+    pub fn is_paused(&self) -> bool {
+        self.inner.is_paused()
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+}
+
+impl Default for MarketPriceTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for MarketPriceTracker {
+    fn drop(&mut self) {
+        self.inner.shutdown();
+    }
+}
+
+/// Price subscription with blocking receives
+///
+/// Iterating yields updates until the tracker shuts down.
+pub struct Subscription {
+    inner: PriceSubscription,
+    handle: tokio::runtime::Handle,
+}
+
+impl Subscription {
+    /// Waits for the next update; `None` once the tracker has shut down
+    pub fn recv(&mut self) -> Option<PriceData> {
+        self.handle.block_on(self.inner.recv())
+    }
+
+    /// Waits up to `timeout` for the next update
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Option<PriceData> {
+        // The timer registers with the runtime, so it is created inside it
+        self.handle
+            .block_on(async { tokio::time::timeout(timeout, self.inner.recv()).await })
+            .ok()
+            .flatten()
+    }
+
+    /// Returns the next queued update without waiting
+    pub fn try_recv(&mut self) -> Option<PriceData> {
+        self.inner.try_recv()
+    }
+
+    /// Updates dropped for this subscriber so far
+    pub fn missed(&self) -> u64 {
+        self.inner.missed()
+    }
+}
+
+impl Iterator for Subscription {
+    type Item = PriceData;
+
+    fn next(&mut self) -> Option<PriceData> {
+        self.recv()
+    }
+}
+
+fn build_runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(WORKER_THREADS)
+        .thread_name("market-price-sdk")
+        .enable_all()
+        .build()
+        .expect("failed to start the market price tracker runtime")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::mock::MockProvider;
+
+    #[test]
+    fn test_blocking_reads_after_refresh() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_provider(provider);
+
+        assert!(tracker.try_get_price(Asset::SOL).is_none());
+        tracker.refresh_now().unwrap();
+        assert_eq!(tracker.get_price(Asset::SOL).unwrap().price_usd, 150.0);
+        assert_eq!(tracker.get_all_prices().len(), 1);
+        assert!(!tracker.is_stale(Asset::SOL));
+    }
+
+    #[test]
+    fn test_blocking_subscription() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_provider(provider);
+        let mut updates = tracker.subscribe_with(SubscriptionConfig::default());
+
+        assert!(updates.recv_timeout(Duration::from_millis(10)).is_none());
+        tracker.refresh_now().unwrap();
+        let update = updates.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(update.price_usd, 150.0);
+    }
+}
@@ -0,0 +1,177 @@
+//! Trading hours for assets that do not trade around the clock
+//!
+//! Crypto trades 24/7, so a price is stale once it is older than its
+//! asset's threshold. Reference assets such as [`Asset::SPY`] stop
+//! updating when their market closes: Friday's closing price is still the
+//! current price on Saturday. The store measures a price's age through a
+//! [`TradingCalendar`] and only counts time the market was open, so such a
+//! price is not reported stale, or evicted, over a weekend.
+//!
+//! [`StandardCalendar`] covers the built-in assets; implement
+//! [`TradingCalendar`] to add exchange holidays or other markets, and set
+//! it as [`TrackerConfig::trading_calendar`](crate::TrackerConfig::trading_calendar).
+
+use crate::types::{Asset, PriceData};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use std::fmt::Debug;
+
+/// When an asset's market is open
+pub trait TradingCalendar: Send + Sync + Debug {
+    /// Returns when the trading session in progress at `at` opened, or
+    /// `None` if the market is closed at `at`
+    ///
+    /// Markets that never close return [`DateTime::<Utc>::MIN_UTC`].
+    fn session_start(&self, asset: Asset, at: DateTime<Utc>) -> Option<DateTime<Utc>>;
+
+    /// Returns true if `asset` trades at `at`
+    fn is_open(&self, asset: Asset, at: DateTime<Utc>) -> bool {
+        self.session_start(asset, at).is_some()
+    }
+}
+
+/// Calendar of a market that never closes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlwaysOpen;
+
+impl TradingCalendar for AlwaysOpen {
+    fn session_start(&self, _asset: Asset, _at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Some(DateTime::<Utc>::MIN_UTC)
+    }
+}
+
+/// Regular trading hours of the built-in assets
+///
+/// - Crypto: always open
+/// - [`Asset::SPY`]: NYSE regular session, 09:30 to 16:00 New York time,
+///   Monday to Friday
+/// - [`Asset::XAU`]: CME metals, Sunday 18:00 to Friday 17:00 New York
+///   time, with a daily break from 17:00 to 18:00
+///
+/// Exchange holidays are not modelled; wrap this calendar to add them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StandardCalendar;
+
+impl TradingCalendar for StandardCalendar {
+    fn session_start(&self, asset: Asset, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match asset {
+            Asset::SPY => us_equity_session(at),
+            Asset::XAU => metals_session(at),
+            _ => Some(DateTime::<Utc>::MIN_UTC),
+        }
+    }
+}
+
+/// Age of a price counting only time its market was open
+///
+/// While the market is closed the age is zero; once it reopens, the age
+/// runs from the later of the price's timestamp and the session open.
+pub fn trading_age(
+    calendar: &dyn TradingCalendar,
+    price: &PriceData,
+    now: DateTime<Utc>,
+) -> std::time::Duration {
+    match calendar.session_start(price.asset, now) {
+        Some(start) => (now - price.last_updated.max(start))
+            .to_std()
+            .unwrap_or_default(),
+        None => std::time::Duration::ZERO,
+    }
+}
+
+/// Offset of New York time from UTC: EDT from the second Sunday of March
+/// to the first Sunday of November (both at 02:00 local), EST otherwise
+fn new_york_offset(at: DateTime<Utc>) -> Duration {
+    let year = at.year();
+    let dst_start = nth_sunday(year, 3, 2)
+        .and_hms_opt(7, 0, 0)
+        .map(|t| t.and_utc());
+    let dst_end = nth_sunday(year, 11, 1)
+        .and_hms_opt(6, 0, 0)
+        .map(|t| t.and_utc());
+    match (dst_start, dst_end) {
+        (Some(start), Some(end)) if at >= start && at < end => Duration::hours(-4),
+        _ => Duration::hours(-5),
+    }
+}
+
+/// The `n`th Sunday of a month
+fn nth_sunday(year: i32, month: u32, n: u8) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, n)
+        .expect("every month has at least four Sundays")
+}
+
+/// Converts a New York wall-clock time to UTC, using the offset in force at `at`
+fn from_new_york(date: NaiveDate, time: NaiveTime, at: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&date.and_time(time)) - new_york_offset(at)
+}
+
+fn us_equity_session(at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let local = at + new_york_offset(at);
+    let open = NaiveTime::from_hms_opt(9, 30, 0)?;
+    let close = NaiveTime::from_hms_opt(16, 0, 0)?;
+    let weekday = !matches!(local.weekday(), Weekday::Sat | Weekday::Sun);
+    (weekday && local.time() >= open && local.time() < close)
+        .then(|| from_new_york(local.date_naive(), open, at))
+}
+
+fn metals_session(at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let local = at + new_york_offset(at);
+    let reopen = NaiveTime::from_hms_opt(18, 0, 0)?;
+    let daily_close = NaiveTime::from_hms_opt(17, 0, 0)?;
+    let time = local.time();
+    let closed = match local.weekday() {
+        Weekday::Sat => true,
+        Weekday::Sun => time < reopen,
+        Weekday::Fri => time >= daily_close,
+        _ => time >= daily_close && time < reopen,
+    };
+    if closed {
+        return None;
+    }
+    let session_date = if time >= reopen {
+        local.date_naive()
+    } else {
+        local.date_naive().pred_opt()?
+    };
+    Some(from_new_york(session_date, reopen, at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_friday_close_is_not_stale_over_the_weekend() {
+        let calendar = StandardCalendar;
+        // Friday 2024-05-10, 15:59:30 New York (EDT)
+        let mut close = PriceData::new(Asset::SPY, 520.0, "test".to_string());
+        close.last_updated = utc("2024-05-10T19:59:30Z");
+
+        let saturday = utc("2024-05-11T12:00:00Z");
+        assert!(!calendar.is_open(Asset::SPY, saturday));
+        assert_eq!(
+            trading_age(&calendar, &close, saturday),
+            std::time::Duration::ZERO
+        );
+
+        // Monday's open starts the clock again
+        let monday = utc("2024-05-13T13:35:00Z");
+        assert_eq!(
+            calendar.session_start(Asset::SPY, monday),
+            Some(utc("2024-05-13T13:30:00Z"))
+        );
+        assert_eq!(
+            trading_age(&calendar, &close, monday),
+            std::time::Duration::from_secs(300)
+        );
+
+        // Gold reopens Sunday evening; crypto never closes
+        assert!(!calendar.is_open(Asset::XAU, utc("2024-05-12T21:00:00Z")));
+        assert!(calendar.is_open(Asset::XAU, utc("2024-05-12T22:30:00Z")));
+        assert!(calendar.is_open(Asset::SOL, saturday));
+    }
+}
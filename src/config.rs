@@ -0,0 +1,414 @@
+//! Runtime configuration for the market price tracker
+//!
+//! Defaults mirror the compile-time constants in `constants.rs`, so a
+//! tracker built with `TrackerConfig::default()` behaves exactly like one
+//! built with `MarketPriceTracker::with_provider()`.
+
+use crate::{
+    anomaly::AnomalyConfig,
+    calendar::{StandardCalendar, TradingCalendar},
+    constants::{HISTORY_CAPACITY, HISTORY_RETENTION_SECS, REQUEST_TIMEOUT_SECS},
+    degraded::DegradedConfig,
+    error::{ConfigError, ConfigIssue},
+    event_id::{EventIdGenerator, UuidV7Generator},
+    metrics::{LatencyHistogramConfig, StatusThresholds},
+    outage::OutageGrace,
+    read_through::ReadThroughConfig,
+    registry::ProviderRegistry,
+    runtime::Runtime,
+    types::Asset,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// Environment variable naming the provider `MarketPriceTracker::new()`
+/// starts with, resolved through the [`ProviderRegistry`]
+pub const PROVIDER_ENV_VAR: &str = "MARKET_PRICE_PROVIDER";
+
+/// Environment variable naming a provider to trial alongside the one
+/// `MarketPriceTracker::new()` starts with, through a
+/// [`ShadowProvider`](crate::providers::ShadowProvider)
+pub const SHADOW_PROVIDER_ENV_VAR: &str = "MARKET_PRICE_SHADOW_PROVIDER";
+
+/// Environment variable selecting the [`Environment`] for `MarketPriceTracker::new()`
+pub const ENVIRONMENT_ENV_VAR: &str = "MARKET_PRICE_ENVIRONMENT";
+
+/// Environment variable that, set to `1`, `true` or `yes`, starts trackers
+/// paused (see [`TrackerConfig::start_paused`])
+pub const PAUSED_ENV_VAR: &str = "MARKET_PRICE_PAUSED";
+
+/// Deployment environment providers connect to
+///
+/// | Provider | `Mainnet` | `Testnet` | `Demo` |
+/// |----------|-----------|-----------|--------|
+/// | Hyperliquid | mainnet API | testnet API | testnet API |
+/// | CoinGecko | public API, or Pro API with keys | demo API keys | demo API keys |
+///
+/// Providers without a separate non-production endpoint (Hermes, Sanctum)
+/// ignore the environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Environment {
+    /// Production endpoints
+    #[default]
+    Mainnet,
+    /// Test networks, for integration tests against real venues
+    Testnet,
+    /// Free or demo API tiers, for staging deployments
+    Demo,
+}
+
+impl Environment {
+    /// Reads the environment from `MARKET_PRICE_ENVIRONMENT`, defaulting to mainnet
+    pub fn from_env() -> Self {
+        match std::env::var(ENVIRONMENT_ENV_VAR) {
+            Ok(value) => value.parse().unwrap_or_else(|_| {
+                tracing::warn!(
+                    environment = %value,
+                    "Unknown environment specified. Defaulting to mainnet."
+                );
+                Environment::Mainnet
+            }),
+            Err(_) => Environment::Mainnet,
+        }
+    }
+
+    /// Returns true for non-production environments
+    pub fn is_test(&self) -> bool {
+        *self != Environment::Mainnet
+    }
+}
+
+impl FromStr for Environment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" | "production" => Ok(Environment::Mainnet),
+            "testnet" => Ok(Environment::Testnet),
+            "demo" => Ok(Environment::Demo),
+            _ => Err(format!("unknown environment: {}", s)),
+        }
+    }
+}
+
+/// Configuration for a [`MarketPriceTracker`](crate::MarketPriceTracker)
+#[derive(Debug, Clone)]
+pub struct TrackerConfig {
+    /// Maximum accepted confidence/price ratio for updates that carry a
+    /// confidence interval (e.g. Pyth). Updates above it are rejected.
+    /// `None` accepts every update.
+    pub max_confidence_ratio: Option<f64>,
+
+    /// Smallest move, in basis points of the stored price, that updates the
+    /// store; smaller moves are dropped along with their events. `None`
+    /// stores every tick.
+    ///
+    /// An unchanged price is still rewritten once it is half its stale
+    /// threshold old, so it never goes stale for lack of movement.
+    pub min_change_bps: Option<f64>,
+
+    /// Executor used for background polling and retry backoff
+    pub runtime: Runtime,
+
+    /// What the polling loop does when a fetch runs past the next
+    /// scheduled poll
+    ///
+    /// Polls are scheduled at fixed multiples of the refresh interval from
+    /// startup, so fetch latency does not shift the cadence. The default,
+    /// `Skip`, polls once straight away and then rejoins the schedule;
+    /// `Delay` restarts the schedule from the late poll; `Burst` catches up
+    /// on every missed poll back to back.
+    pub missed_poll_behavior: MissedTickBehavior,
+
+    /// Upper bound of a random delay before the initial fetch, so a fleet
+    /// of instances started together does not poll a provider in lockstep;
+    /// zero fetches straight away
+    pub poll_start_jitter: Duration,
+
+    /// Fixed delay before the initial fetch, added to the jitter, for
+    /// deterministic spreading such as `pod ordinal × 2s`
+    pub poll_phase_offset: Duration,
+
+    /// How long accepted prices are kept for point-in-time lookups
+    pub history_retention: Duration,
+
+    /// Maximum number of historical prices kept per asset; 0 disables history
+    pub history_capacity: usize,
+
+    /// Anomaly detection applied to incoming ticks; `None` disables it
+    pub anomaly: Option<AnomalyConfig>,
+
+    /// How often to emit [`MarketPriceEvent::Heartbeat`] events for every
+    /// stored asset, whether or not its price moved; `None` disables them
+    ///
+    /// [`MarketPriceEvent::Heartbeat`]: crate::types::MarketPriceEvent::Heartbeat
+    pub heartbeat_interval: Option<Duration>,
+
+    /// Bounded on-demand fetches for `get_price` cache misses, shared
+    /// between concurrent callers and paused after a failure; `None` keeps
+    /// the unbounded direct provider call
+    pub read_through: Option<ReadThroughConfig>,
+
+    /// Time allowed for each fetch attempt against the provider, including
+    /// every HTTP request it makes
+    pub request_timeout: Duration,
+
+    /// Trading hours used to age prices: time the asset's market is closed
+    /// does not count towards its stale threshold
+    pub trading_calendar: Arc<dyn TradingCalendar>,
+
+    /// How often to check stored prices against their stale threshold,
+    /// emitting a [`MarketPriceEvent::PriceWentStale`] once per asset as it
+    /// crosses it; `None` (the default) disables the check
+    ///
+    /// A price evicted before the check sees it is only reported as
+    /// expired.
+    ///
+    /// [`MarketPriceEvent::PriceWentStale`]: crate::types::MarketPriceEvent::PriceWentStale
+    pub staleness_check_interval: Option<Duration>,
+
+    /// How often to sweep stale prices out of the store, emitting a
+    /// [`MarketPriceEvent::PriceExpired`] for each; `None` keeps stale
+    /// prices until they are overwritten
+    ///
+    /// [`MarketPriceEvent::PriceExpired`]: crate::types::MarketPriceEvent::PriceExpired
+    pub eviction_interval: Option<Duration>,
+
+    /// Whether the eviction sweep also drops assets that are no longer
+    /// enabled, along with their history
+    pub evict_disabled_assets: bool,
+
+    /// Precision and rotation of the provider latency histograms
+    pub latency_histogram: LatencyHistogramConfig,
+
+    /// Updates buffered for [`subscribe`](crate::MarketPriceTracker::subscribe)
+    /// receivers before the slowest ones start missing updates
+    pub update_channel_capacity: usize,
+
+    /// Events buffered for event subscribers and sinks before the slowest
+    /// ones start missing events
+    pub event_channel_capacity: usize,
+
+    /// Source of [`MarketPriceEvent`] ids: time-ordered UUIDv7s by
+    /// default, or a [`SeededGenerator`] for reproducible replays
+    ///
+    /// [`MarketPriceEvent`]: crate::types::MarketPriceEvent
+    /// [`SeededGenerator`]: crate::event_id::SeededGenerator
+    pub event_ids: Arc<dyn EventIdGenerator>,
+
+    /// Serve the last known price, marked degraded, when an asset is stale
+    /// and no provider can refresh it; `None` fails instead
+    pub degraded: Option<DegradedConfig>,
+
+    /// Tolerate older prices for a while when polls start failing, so a
+    /// short provider blip does not flip prices to stale; `None` keeps the
+    /// plain stale thresholds
+    pub outage_grace: Option<OutageGrace>,
+
+    /// When poll outcomes move the provider between healthy, degraded and
+    /// unavailable, each change being announced as a
+    /// [`ProviderStatusChanged`](crate::MarketPriceEvent::ProviderStatusChanged)
+    /// event
+    pub provider_status: StatusThresholds,
+
+    /// Start with provider requests paused, as if
+    /// [`pause`](crate::MarketPriceTracker::pause) had been called before
+    /// the first fetch. Defaults to whether `MARKET_PRICE_PAUSED` is set.
+    pub start_paused: bool,
+}
+
+impl Default for TrackerConfig {
+    fn default() -> Self {
+        Self {
+            max_confidence_ratio: None,
+            min_change_bps: None,
+            runtime: Runtime::default(),
+            missed_poll_behavior: MissedTickBehavior::Skip,
+            poll_start_jitter: Duration::ZERO,
+            poll_phase_offset: Duration::ZERO,
+            history_retention: Duration::from_secs(HISTORY_RETENTION_SECS),
+            history_capacity: HISTORY_CAPACITY,
+            anomaly: None,
+            heartbeat_interval: None,
+            read_through: None,
+            request_timeout: Duration::from_secs(REQUEST_TIMEOUT_SECS),
+            trading_calendar: Arc::new(StandardCalendar),
+            staleness_check_interval: None,
+            eviction_interval: None,
+            evict_disabled_assets: false,
+            latency_histogram: LatencyHistogramConfig::default(),
+            update_channel_capacity: 1000,
+            event_channel_capacity: 1000,
+            event_ids: Arc::new(UuidV7Generator),
+            degraded: None,
+            outage_grace: None,
+            provider_status: StatusThresholds::default(),
+            start_paused: std::env::var(PAUSED_ENV_VAR)
+                .is_ok_and(|value| matches!(value.to_lowercase().as_str(), "1" | "true" | "yes")),
+        }
+    }
+}
+
+impl TrackerConfig {
+    /// Checks the configuration and the `MARKET_PRICE_*` environment
+    /// variables, reporting every problem at once
+    ///
+    /// Checks that `MARKET_PRICE_PROVIDER` names a provider in the global
+    /// [`ProviderRegistry`], that `MARKET_PRICE_ENVIRONMENT` is a known
+    /// environment, and that intervals and thresholds are in range. The
+    /// tracker itself falls back to defaults on such problems, so call this
+    /// at startup or in CI to catch them.
+    ///
+    /// # Example
+    /// ```
+    /// use market_price_sdk::TrackerConfig;
+    ///
+    /// if let Err(e) = TrackerConfig::default().validate() {
+    ///     for issue in &e.issues {
+    ///         eprintln!("{}", issue);
+    ///     }
+    /// }
+    /// ```
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.validate_with_assets(&[])
+    }
+
+    /// Like [`validate`](Self::validate), also checking that every symbol
+    /// in `assets` names a known asset
+    ///
+    /// Useful for asset lists kept in the application's own configuration.
+    pub fn validate_with_assets(&self, assets: &[&str]) -> Result<(), ConfigError> {
+        let providers: Vec<String> = [PROVIDER_ENV_VAR, SHADOW_PROVIDER_ENV_VAR]
+            .into_iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .collect();
+        let providers: Vec<&str> = providers.iter().map(String::as_str).collect();
+        let environment = std::env::var(ENVIRONMENT_ENV_VAR).ok();
+        self.check(
+            &providers,
+            environment.as_deref(),
+            assets,
+            ProviderRegistry::global(),
+        )
+    }
+
+    fn check(
+        &self,
+        providers: &[&str],
+        environment: Option<&str>,
+        assets: &[&str],
+        registry: &ProviderRegistry,
+    ) -> Result<(), ConfigError> {
+        let mut issues = Vec::new();
+
+        for name in providers.iter().filter(|name| !registry.contains(name)) {
+            issues.push(ConfigIssue::UnknownProvider {
+                name: name.to_string(),
+                known: registry.names(),
+            });
+        }
+        if let Some(name) = environment.filter(|name| name.parse::<Environment>().is_err()) {
+            issues.push(ConfigIssue::UnknownEnvironment(name.to_string()));
+        }
+        for symbol in assets {
+            if symbol.parse::<Asset>().is_err() {
+                issues.push(ConfigIssue::UnknownAsset(symbol.to_string()));
+            }
+        }
+
+        let mut out_of_range = |field, value: String, expected| {
+            issues.push(ConfigIssue::OutOfRange {
+                field,
+                value,
+                expected,
+            });
+        };
+        if self.request_timeout.is_zero() {
+            out_of_range("request_timeout", "0s".to_string(), "more than zero");
+        }
+        let intervals = [
+            ("heartbeat_interval", self.heartbeat_interval),
+            ("staleness_check_interval", self.staleness_check_interval),
+            ("eviction_interval", self.eviction_interval),
+            (
+                "read_through.timeout",
+                self.read_through.as_ref().map(|r| r.timeout),
+            ),
+        ];
+        for (field, interval) in intervals {
+            if interval.is_some_and(|interval| interval.is_zero()) {
+                out_of_range(field, "0s".to_string(), "more than zero, or None");
+            }
+        }
+        if let Some(ratio) = self
+            .max_confidence_ratio
+            .filter(|ratio| !(ratio.is_finite() && *ratio > 0.0))
+        {
+            out_of_range(
+                "max_confidence_ratio",
+                ratio.to_string(),
+                "a positive number, or None",
+            );
+        }
+        if let Some(bps) = self
+            .min_change_bps
+            .filter(|bps| !(bps.is_finite() && *bps >= 0.0))
+        {
+            out_of_range("min_change_bps", bps.to_string(), "zero or more, or None");
+        }
+        if let Some(drift) = self
+            .degraded
+            .as_ref()
+            .map(|degraded| degraded.drift_bps_per_sqrt_minute)
+            .filter(|drift| !(drift.is_finite() && *drift >= 0.0))
+        {
+            out_of_range(
+                "degraded.drift_bps_per_sqrt_minute",
+                drift.to_string(),
+                "zero or more",
+            );
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError { issues })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_reports_every_issue() {
+        let registry = ProviderRegistry::with_builtins();
+        let valid = TrackerConfig::default();
+        assert!(valid
+            .check(&["CoinGecko"], Some("demo"), &["SOL"], &registry)
+            .is_ok());
+
+        let config = TrackerConfig {
+            heartbeat_interval: Some(Duration::ZERO),
+            min_change_bps: Some(-1.0),
+            ..Default::default()
+        };
+        let error = config
+            .check(&["coingeko"], Some("prod"), &["SOL", "SHIB"], &registry)
+            .unwrap_err();
+        assert_eq!(error.issues.len(), 5);
+        assert!(matches!(
+            &error.issues[0],
+            ConfigIssue::UnknownProvider { name, known }
+                if name == "coingeko" && known.contains(&"coingecko".to_string())
+        ));
+        assert_eq!(
+            error.issues[2],
+            ConfigIssue::UnknownAsset("SHIB".to_string())
+        );
+        assert!(error.to_string().contains("heartbeat_interval is 0s"));
+    }
+}
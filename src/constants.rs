@@ -12,6 +12,12 @@ pub const REFRESH_INTERVAL_SECS: u64 = 60;
 /// How long before price data is considered stale (in seconds)
 pub const STALE_THRESHOLD_SECS: u64 = 300;
 
+/// How long accepted prices are kept for point-in-time lookups (in seconds)
+pub const HISTORY_RETENTION_SECS: u64 = 3600;
+
+/// Maximum number of historical prices kept per asset
+pub const HISTORY_CAPACITY: usize = 3600;
+
 /// HTTP request timeout when fetching prices (in seconds)
 pub const REQUEST_TIMEOUT_SECS: u64 = 10;
 
@@ -24,17 +30,79 @@ pub const INITIAL_BACKOFF_MS: u64 = 1000;
 /// Maximum backoff delay for retries (in milliseconds)
 pub const MAX_BACKOFF_MS: u64 = 30000;
 
+/// Gap in basis points above which a shadow provider selected through
+/// `MARKET_PRICE_SHADOW_PROVIDER` counts as diverging
+pub const SHADOW_MAX_DEVIATION_BPS: f64 = 50.0;
+
+/// Window of recent latencies a provider's p95 is taken over when picking
+/// a provider for a latency budget (in seconds)
+pub const LATENCY_BUDGET_WINDOW_SECS: u64 = 300;
+
+/// Per-asset requests kept in flight by providers without a batch endpoint
+pub const FETCH_CONCURRENCY: usize = 8;
+
+/// How often to fetch funding rates and open interest (in seconds)
+pub const DERIVATIVES_REFRESH_INTERVAL_SECS: u64 = 60;
+
+/// How long before derivatives data is considered stale (in seconds)
+pub const DERIVATIVES_STALE_THRESHOLD_SECS: u64 = 180;
+
+/// How often to fetch network fee estimates (in seconds)
+pub const FEE_REFRESH_INTERVAL_SECS: u64 = 10;
+
+/// How long before a fee estimate is considered stale (in seconds)
+pub const FEE_STALE_THRESHOLD_SECS: u64 = 60;
+
+/// How often to fetch fiat exchange rates (in seconds)
+pub const FX_REFRESH_INTERVAL_SECS: u64 = 3600;
+
+/// How long before fetched exchange rates are considered stale (in seconds)
+pub const FX_STALE_THRESHOLD_SECS: u64 = 86400;
+
 /// Assets to track by default
 pub const ENABLED_ASSETS: &[Asset] = &[Asset::SOL, Asset::BTC];
 
 /// CoinGecko API base URL
 pub const COINGECKO_API_URL: &str = "https://api.coingecko.com/api/v3";
 
+/// CoinGecko Pro API base URL, used when API keys are configured
+pub const COINGECKO_PRO_API_URL: &str = "https://pro-api.coingecko.com/api/v3";
+
+/// Daily share of the 10,000 monthly calls on CoinGecko's free Demo plan
+pub const COINGECKO_DEMO_REQUESTS_PER_DAY: u64 = 333;
+
 /// CoinGecko API endpoint for simple price queries
 pub const COINGECKO_SIMPLE_PRICE_ENDPOINT: &str = "/simple/price";
 
 /// Hyperliquid API base URL
 pub const HYPERLIQUID_API_URL: &str = "https://api.hyperliquid.xyz/info";
 
+/// Hyperliquid testnet API base URL
+pub const HYPERLIQUID_TESTNET_API_URL: &str = "https://api.hyperliquid-testnet.xyz/info";
+
+/// Pyth Hermes API base URL
+pub const HERMES_API_URL: &str = "https://hermes.pyth.network";
+
+/// Hermes endpoint for the server-sent events price stream
+pub const HERMES_STREAM_ENDPOINT: &str = "/v2/updates/price/stream";
+
+/// Hermes endpoint for the latest price snapshot (REST)
+pub const HERMES_LATEST_ENDPOINT: &str = "/v2/updates/price/latest";
+
+/// Frankfurter API base URL (ECB reference exchange rates)
+pub const FRANKFURTER_API_URL: &str = "https://api.frankfurter.app";
+
+/// Sanctum API base URL (liquid staking token exchange rates)
+pub const SANCTUM_API_URL: &str = "https://sanctum-s-api.fly.dev";
+
+/// Sanctum endpoint for the current SOL value of LSTs
+pub const SANCTUM_SOL_VALUE_ENDPOINT: &str = "/v1/sol-value/current";
+
+/// Solana mainnet public JSON-RPC endpoint
+pub const SOLANA_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+
+/// Ethereum mainnet public JSON-RPC endpoint
+pub const ETHEREUM_RPC_URL: &str = "https://ethereum-rpc.publicnode.com";
+
 /// User agent for HTTP requests
 pub const USER_AGENT: &str = "solana-sniper-bot/0.1.0";
@@ -0,0 +1,236 @@
+//! API keys for providers with authenticated tiers
+//!
+//! A [`Credentials`] holds one or more keys for a provider and hands out the
+//! current one. When a provider answers 401, 403 or 429, the caller rotates
+//! to the next key. Once every key has been tried, the list is reloaded from
+//! its source, so keys rotated in the environment, a file or a secrets
+//! manager take effect without a restart.
+//!
+//! # Example
+//! ```no_run
+//! use market_price_sdk::credentials::Credentials;
+//! use market_price_sdk::providers::CoinGeckoProvider;
+//!
+//! # fn example() -> Result<(), market_price_sdk::ProviderError> {
+//! let credentials = Credentials::from_env("COINGECKO_API_KEYS")?;
+//! let provider = CoinGeckoProvider::new()?.with_credentials(credentials);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::ProviderError;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Callback returning the current list of keys
+pub type KeyLoader = Arc<dyn Fn() -> Result<Vec<String>, ProviderError> + Send + Sync>;
+
+/// Where keys are loaded from
+#[derive(Clone)]
+pub enum CredentialSource {
+    /// Fixed list of keys
+    Static(Vec<String>),
+    /// Comma-separated keys in an environment variable
+    Env(String),
+    /// File with one key per line; blank lines and `#` comments are ignored
+    File(PathBuf),
+    /// User callback, e.g. backed by a secrets manager
+    Callback(KeyLoader),
+}
+
+impl CredentialSource {
+    /// Reads the keys currently provided by this source
+    pub fn load(&self) -> Result<Vec<String>, ProviderError> {
+        let keys = match self {
+            CredentialSource::Static(keys) => keys.clone(),
+            CredentialSource::Env(var) => std::env::var(var)
+                .map_err(|e| ProviderError::Credentials(format!("{}: {}", var, e)))?
+                .split(',')
+                .map(str::to_string)
+                .collect(),
+            CredentialSource::File(path) => std::fs::read_to_string(path)
+                .map_err(|e| ProviderError::Credentials(format!("{}: {}", path.display(), e)))?
+                .lines()
+                .filter(|line| !line.trim_start().starts_with('#'))
+                .map(str::to_string)
+                .collect(),
+            CredentialSource::Callback(load) => load()?,
+        };
+
+        let keys: Vec<String> = keys
+            .into_iter()
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .collect();
+        if keys.is_empty() {
+            return Err(ProviderError::Credentials(format!(
+                "no API keys found in {:?}",
+                self
+            )));
+        }
+        Ok(keys)
+    }
+}
+
+impl std::fmt::Debug for CredentialSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CredentialSource::Static(keys) => write!(f, "Static({} keys)", keys.len()),
+            CredentialSource::Env(var) => write!(f, "Env({})", var),
+            CredentialSource::File(path) => write!(f, "File({})", path.display()),
+            CredentialSource::Callback(_) => f.write_str("Callback"),
+        }
+    }
+}
+
+/// Rotating set of API keys for one provider
+pub struct Credentials {
+    source: CredentialSource,
+    keys: RwLock<Vec<String>>,
+    current: AtomicUsize,
+    /// Rotations since the keys were last loaded
+    rotations: AtomicUsize,
+}
+
+impl Credentials {
+    /// Loads keys from a source
+    pub fn from_source(source: CredentialSource) -> Result<Self, ProviderError> {
+        let keys = source.load()?;
+        Ok(Self {
+            source,
+            keys: RwLock::new(keys),
+            current: AtomicUsize::new(0),
+            rotations: AtomicUsize::new(0),
+        })
+    }
+
+    /// Uses a fixed list of keys
+    pub fn new(keys: Vec<String>) -> Result<Self, ProviderError> {
+        Self::from_source(CredentialSource::Static(keys))
+    }
+
+    /// Loads comma-separated keys from an environment variable
+    pub fn from_env(var: &str) -> Result<Self, ProviderError> {
+        Self::from_source(CredentialSource::Env(var.to_string()))
+    }
+
+    /// Loads keys from a file, one per line
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self, ProviderError> {
+        Self::from_source(CredentialSource::File(path.into()))
+    }
+
+    /// Loads keys from a user callback
+    pub fn from_callback<F>(load: F) -> Result<Self, ProviderError>
+    where
+        F: Fn() -> Result<Vec<String>, ProviderError> + Send + Sync + 'static,
+    {
+        Self::from_source(CredentialSource::Callback(Arc::new(load)))
+    }
+
+    /// Returns the key to use for the next request
+    pub fn current(&self) -> String {
+        self.current_with_index().1
+    }
+
+    /// Returns the key to use for the next request along with its index,
+    /// which is passed to [`rotate`](Self::rotate) if the key is rejected
+    pub fn current_with_index(&self) -> (usize, String) {
+        let keys = self.keys.read().expect("credentials lock poisoned");
+        let index = self.current.load(Ordering::Relaxed);
+        (index, keys[index % keys.len()].clone())
+    }
+
+    /// Number of keys currently loaded
+    pub fn key_count(&self) -> usize {
+        self.keys.read().expect("credentials lock poisoned").len()
+    }
+
+    /// Moves to the next key after the key at index `rejected`, as returned
+    /// by [`current_with_index`](Self::current_with_index), failed
+    ///
+    /// Concurrent callers that used the same rejected key rotate only once.
+    /// After a full cycle the keys are reloaded from the source; if that
+    /// fails, the previous keys stay in use.
+    pub fn rotate(&self, rejected: usize) {
+        if self
+            .current
+            .compare_exchange(
+                rejected,
+                rejected.wrapping_add(1),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_err()
+        {
+            return;
+        }
+
+        let len = self.key_count();
+        tracing::warn!(source = ?self.source, "API key rejected, rotating to the next key");
+
+        if self.rotations.fetch_add(1, Ordering::Relaxed) + 1 >= len {
+            if let Err(e) = self.reload() {
+                tracing::warn!(error = %e, "Failed to reload API keys");
+            }
+        }
+    }
+
+    /// Reloads the keys from the source and starts again from the first one
+    pub fn reload(&self) -> Result<(), ProviderError> {
+        let keys = self.source.load()?;
+        *self.keys.write().expect("credentials lock poisoned") = keys;
+        self.current.store(0, Ordering::Relaxed);
+        self.rotations.store(0, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Returns true if a response status means the key should be rotated
+    pub fn should_rotate(status: u16) -> bool {
+        matches!(status, 401 | 403 | 429)
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("source", &self.source)
+            .field("keys", &self.key_count())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_rotation_and_reload() {
+        let generation = Arc::new(Mutex::new(0));
+        let counter = generation.clone();
+        let credentials = Credentials::from_callback(move || {
+            let n = *counter.lock().unwrap();
+            Ok(vec![format!("a{n}"), format!(" b{n} "), String::new()])
+        })
+        .unwrap();
+
+        assert_eq!(credentials.key_count(), 2);
+        let (first, key) = credentials.current_with_index();
+        assert_eq!(key, "a0");
+        credentials.rotate(first);
+        // A stale rejection of an already-rotated key is ignored
+        credentials.rotate(first);
+        let (second, key) = credentials.current_with_index();
+        assert_eq!(key, "b0");
+
+        *generation.lock().unwrap() = 1;
+        credentials.rotate(second);
+        assert_eq!(credentials.current(), "a1");
+
+        assert!(matches!(
+            Credentials::new(vec![" ".to_string()]),
+            Err(ProviderError::Credentials(_))
+        ));
+    }
+}
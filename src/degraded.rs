@@ -0,0 +1,90 @@
+//! Degraded mode: serving the last known price through an outage
+//!
+//! By default [`MarketPriceTracker::get_price`](crate::MarketPriceTracker::get_price)
+//! fails once an asset's price is past its stale threshold and no provider
+//! can refresh it. With [`TrackerConfig::degraded`](crate::TrackerConfig::degraded)
+//! set, it instead returns the last known price, up to
+//! [`DegradedConfig::max_age`] old, marked [`Quality::Degraded`].
+//!
+//! A degraded price's `confidence` is widened by an extrapolation bound
+//! that grows with the square root of its age, as a random walk's spread
+//! does: `price * drift_bps_per_sqrt_minute / 10_000 * sqrt(age in
+//! minutes)`, added to any confidence the price already carried. Time the
+//! asset's market is closed does not count towards the age.
+//!
+//! Degraded prices are meant for consumers that can live with an
+//! approximate number, such as dashboards and logs. Anything that trades
+//! should check `quality` and refuse [`Quality::Degraded`].
+//!
+//! # Example
+//! ```
+//! use market_price_sdk::degraded::DegradedConfig;
+//! use market_price_sdk::TrackerConfig;
+//!
+//! let config = TrackerConfig {
+//!     degraded: Some(DegradedConfig::default()),
+//!     ..Default::default()
+//! };
+//! ```
+
+use crate::types::{PriceData, Quality};
+use std::time::Duration;
+
+/// Limits on serving degraded prices
+#[derive(Debug, Clone)]
+pub struct DegradedConfig {
+    /// Oldest last known price that is still served; older ones fail as
+    /// stale
+    pub max_age: Duration,
+    /// Extrapolation bound after one minute, in basis points of the price
+    pub drift_bps_per_sqrt_minute: f64,
+}
+
+impl Default for DegradedConfig {
+    fn default() -> Self {
+        Self {
+            max_age: Duration::from_secs(3600),
+            drift_bps_per_sqrt_minute: 20.0,
+        }
+    }
+}
+
+impl DegradedConfig {
+    /// Half-width, in USD, of the range a price `age` old may have
+    /// drifted within
+    pub fn extrapolation_bound(&self, price_usd: f64, age: Duration) -> f64 {
+        let minutes = age.as_secs_f64() / 60.0;
+        price_usd.abs() * self.drift_bps_per_sqrt_minute / 10_000.0 * minutes.sqrt()
+    }
+
+    /// Marks a last known price `age` old as degraded, or returns `None`
+    /// if it is older than [`max_age`](Self::max_age)
+    pub fn degrade(&self, mut price: PriceData, age: Duration) -> Option<PriceData> {
+        if age > self.max_age {
+            return None;
+        }
+        let bound = self.extrapolation_bound(price.price_usd, age);
+        price.confidence = Some(price.confidence.unwrap_or(0.0) + bound);
+        price.quality = Quality::Degraded;
+        Some(price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Asset;
+
+    #[test]
+    fn test_bound_grows_with_square_root_of_age() {
+        let config = DegradedConfig::default();
+        let price = PriceData::new(Asset::SOL, 100.0, "test".to_string()).with_confidence(0.05);
+
+        let degraded = config
+            .degrade(price.clone(), Duration::from_secs(4 * 60))
+            .unwrap();
+        assert_eq!(degraded.quality, Quality::Degraded);
+        assert!((degraded.confidence.unwrap() - 0.45).abs() < 1e-9);
+        assert!(config.degrade(price, Duration::from_secs(3601)).is_none());
+    }
+}
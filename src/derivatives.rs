@@ -0,0 +1,278 @@
+//! Perpetual futures data (funding rate, open interest)
+//!
+//! [`DerivativesTracker`] is a sibling of the spot price tracker: it polls a
+//! [`DerivativesProvider`] on its own cadence and applies its own staleness
+//! rule, since funding and open interest move far more slowly than prices.
+//!
+//! # Example
+//! ```no_run
+//! use market_price_sdk::{derivatives::DerivativesTracker, Asset};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let tracker = DerivativesTracker::hyperliquid()?;
+//! tracker.start();
+//! tracker.refresh_now().await?;
+//! println!("SOL funding: {:.6}", tracker.get_funding_rate(Asset::SOL)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    constants::{
+        DERIVATIVES_REFRESH_INTERVAL_SECS, DERIVATIVES_STALE_THRESHOLD_SECS, ENABLED_ASSETS,
+    },
+    error::{PriceError, ProviderError},
+    poller::{Poll, Poller, PollerConfig},
+    providers::HyperliquidProvider,
+    runtime::Runtime,
+    types::Asset,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Funding and open interest for a perpetual market
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DerivativesData {
+    /// The underlying asset
+    pub asset: Asset,
+
+    /// Current funding rate as a fraction per funding interval
+    /// (hourly on Hyperliquid)
+    pub funding_rate: f64,
+
+    /// Open interest in units of the asset
+    pub open_interest: f64,
+
+    /// Mark price in USD, if reported
+    pub mark_price_usd: Option<f64>,
+
+    /// Last updated timestamp
+    pub last_updated: DateTime<Utc>,
+
+    /// Data source
+    pub source: String,
+}
+
+impl DerivativesData {
+    /// Open interest in USD, valued at the mark price
+    pub fn open_interest_usd(&self) -> Option<f64> {
+        self.mark_price_usd.map(|mark| mark * self.open_interest)
+    }
+}
+
+/// Source of perpetual futures data
+#[async_trait]
+pub trait DerivativesProvider: Send + Sync {
+    /// Fetches funding and open interest for the given assets
+    ///
+    /// Assets without a perpetual market are omitted from the result.
+    async fn fetch_derivatives(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, DerivativesData>, ProviderError>;
+
+    /// Returns the name of this provider
+    fn provider_name(&self) -> &'static str;
+}
+
+/// Configuration for a [`DerivativesTracker`]
+#[derive(Debug, Clone)]
+pub struct DerivativesConfig {
+    /// Assets to poll
+    pub assets: Vec<Asset>,
+    /// How often to poll the provider
+    pub refresh_interval: Duration,
+    /// How old data may be before reads fail as stale
+    pub stale_threshold: Duration,
+    /// Executor used for the polling task, and clock for staleness
+    pub runtime: Runtime,
+}
+
+impl Default for DerivativesConfig {
+    fn default() -> Self {
+        Self {
+            assets: ENABLED_ASSETS.to_vec(),
+            refresh_interval: Duration::from_secs(DERIVATIVES_REFRESH_INTERVAL_SECS),
+            stale_threshold: Duration::from_secs(DERIVATIVES_STALE_THRESHOLD_SECS),
+            runtime: Runtime::default(),
+        }
+    }
+}
+
+/// Polls a [`DerivativesProvider`] into the latest data per asset
+struct DerivativesPoll {
+    provider: Arc<dyn DerivativesProvider>,
+    assets: Vec<Asset>,
+}
+
+#[async_trait]
+impl Poll for DerivativesPoll {
+    type State = HashMap<Asset, DerivativesData>;
+
+    async fn poll(
+        &self,
+        data: &RwLock<HashMap<Asset, DerivativesData>>,
+    ) -> Result<(), ProviderError> {
+        let fetched = self.provider.fetch_derivatives(&self.assets).await?;
+        tracing::debug!(
+            provider = self.provider.provider_name(),
+            count = fetched.len(),
+            "Updated derivatives data"
+        );
+        data.write()
+            .expect("derivatives lock poisoned")
+            .extend(fetched);
+        Ok(())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.provider.provider_name()
+    }
+}
+
+/// Tracks funding rates and open interest for perpetual markets
+pub struct DerivativesTracker {
+    poller: Poller<DerivativesPoll>,
+}
+
+impl DerivativesTracker {
+    /// Creates a derivatives tracker backed by Hyperliquid
+    pub fn hyperliquid() -> Result<Self, ProviderError> {
+        Ok(Self::new(
+            Arc::new(HyperliquidProvider::new()?),
+            DerivativesConfig::default(),
+        ))
+    }
+
+    /// Creates a derivatives tracker with a custom provider and configuration
+    pub fn new(provider: Arc<dyn DerivativesProvider>, config: DerivativesConfig) -> Self {
+        Self {
+            poller: Poller::new(
+                DerivativesPoll {
+                    provider,
+                    assets: config.assets,
+                },
+                HashMap::new(),
+                PollerConfig {
+                    task_name: "derivatives.poll",
+                    refresh_interval: config.refresh_interval,
+                    stale_threshold: config.stale_threshold,
+                    runtime: config.runtime,
+                },
+            ),
+        }
+    }
+
+    /// Starts the background polling task
+    pub fn start(&self) {
+        self.poller.start();
+    }
+
+    /// Fetches fresh data immediately, outside the polling cadence
+    pub async fn refresh_now(&self) -> Result<(), ProviderError> {
+        self.poller.refresh_now().await
+    }
+
+    /// Gets funding and open interest for an asset
+    ///
+    /// # Returns
+    /// The latest data, or an error if not available or stale
+    pub fn get(&self, asset: Asset) -> Result<DerivativesData, PriceError> {
+        let entry = self
+            .poller
+            .read(|data| data.get(&asset).cloned())
+            .ok_or_else(|| PriceError::not_available(asset.symbol()))?;
+        self.poller
+            .check_fresh(asset.symbol(), entry.last_updated)?;
+        Ok(entry)
+    }
+
+    /// Gets the current funding rate for an asset's perpetual market
+    pub fn get_funding_rate(&self, asset: Asset) -> Result<f64, PriceError> {
+        self.get(asset).map(|data| data.funding_rate)
+    }
+
+    /// Gets the current open interest (in units of the asset)
+    pub fn get_open_interest(&self, asset: Asset) -> Result<f64, PriceError> {
+        self.get(asset).map(|data| data.open_interest)
+    }
+
+    /// Returns the name of the current provider
+    pub fn provider_name(&self) -> &str {
+        self.poller.source().provider_name()
+    }
+
+    /// Stops the background polling task
+    pub fn shutdown(&self) {
+        self.poller.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::MockClock;
+
+    struct FixedProvider(DerivativesData);
+
+    #[async_trait]
+    impl DerivativesProvider for FixedProvider {
+        async fn fetch_derivatives(
+            &self,
+            _assets: &[Asset],
+        ) -> Result<HashMap<Asset, DerivativesData>, ProviderError> {
+            Ok(HashMap::from([(self.0.asset, self.0.clone())]))
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "fixed"
+        }
+    }
+
+    fn sample() -> DerivativesData {
+        DerivativesData {
+            asset: Asset::SOL,
+            funding_rate: 0.0000125,
+            open_interest: 1000.0,
+            mark_price_usd: Some(150.0),
+            last_updated: Utc::now(),
+            source: "fixed".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_funding_and_staleness() {
+        let data = sample();
+        let clock = Arc::new(MockClock::new(data.last_updated));
+        let tracker = DerivativesTracker::new(
+            Arc::new(FixedProvider(data)),
+            DerivativesConfig {
+                runtime: Runtime::default().with_clock(clock.clone()),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            tracker.get_funding_rate(Asset::SOL),
+            Err(PriceError::NotAvailable { .. })
+        ));
+
+        tracker.refresh_now().await.unwrap();
+        assert_eq!(tracker.get_funding_rate(Asset::SOL).unwrap(), 0.0000125);
+        assert_eq!(tracker.get_open_interest(Asset::SOL).unwrap(), 1000.0);
+        assert_eq!(
+            tracker.get(Asset::SOL).unwrap().open_interest_usd(),
+            Some(150_000.0)
+        );
+
+        // Staleness follows the configured clock
+        clock.advance(Duration::from_secs(DERIVATIVES_STALE_THRESHOLD_SECS + 1));
+        assert!(matches!(
+            tracker.get_open_interest(Asset::SOL),
+            Err(PriceError::Stale { .. })
+        ));
+    }
+}
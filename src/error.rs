@@ -1,5 +1,7 @@
 //! Error types for the Global Market Price Tracker
 
+use crate::types::Asset;
+use chrono::{DateTime, Utc};
 use std::time::Duration;
 use thiserror::Error;
 
@@ -29,6 +31,181 @@ pub enum ProviderError {
     /// Timeout waiting for response
     #[error("Request timeout")]
     Timeout,
+
+    /// API keys could not be loaded
+    #[error("Credentials error: {0}")]
+    Credentials(String),
+
+    /// Response did not include a price for a requested asset
+    #[error("No price returned for {0}")]
+    MissingPrice(String),
+
+    /// Daily request quota used up
+    #[error("Daily request quota exhausted for {0}")]
+    QuotaExhausted(String),
+
+    /// Response carried a price that is not finite and positive
+    #[error("Invalid price for {asset}: {raw}")]
+    InvalidPrice {
+        /// Asset the price was for
+        asset: Asset,
+        /// The value as the provider sent it
+        raw: String,
+    },
+
+    /// No provider factory is registered under the name
+    #[error("Unknown provider: {0}")]
+    UnknownProvider(String),
+
+    /// Provider requests are paused by the tracker's kill switch
+    #[error("Provider requests are paused")]
+    Paused,
+
+    /// No provider's recent latency fits the caller's budget
+    #[error("No provider fits the {0:?} latency budget")]
+    OverBudget(std::time::Duration),
+}
+
+impl ProviderError {
+    /// Returns true if retrying the same request may succeed
+    pub fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            ProviderError::UnsupportedAsset(_)
+                | ProviderError::Credentials(_)
+                | ProviderError::MissingPrice(_)
+                | ProviderError::QuotaExhausted(_)
+                | ProviderError::InvalidPrice { .. }
+                | ProviderError::UnknownProvider(_)
+                | ProviderError::Paused
+                | ProviderError::OverBudget(_)
+        )
+    }
+
+    /// Maps a reqwest error, keeping timeouts apart from other network
+    /// failures
+    ///
+    /// The request URL is stripped, so query parameters never reach logs or
+    /// health reports.
+    pub(crate) fn from_reqwest(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            ProviderError::Timeout
+        } else {
+            ProviderError::NetworkError(error.without_url())
+        }
+    }
+
+    /// Copies the error, so one failure can be reported for several assets
+    ///
+    /// Network errors cannot be cloned and are copied as `ApiError`s
+    /// carrying the same message.
+    pub fn replicate(&self) -> Self {
+        match self {
+            ProviderError::NetworkError(e) => {
+                ProviderError::ApiError(format!("Network error: {}", e))
+            }
+            ProviderError::InvalidResponse(s) => ProviderError::InvalidResponse(s.clone()),
+            ProviderError::RateLimitExceeded => ProviderError::RateLimitExceeded,
+            ProviderError::UnsupportedAsset(s) => ProviderError::UnsupportedAsset(s.clone()),
+            ProviderError::ApiError(s) => ProviderError::ApiError(s.clone()),
+            ProviderError::Timeout => ProviderError::Timeout,
+            ProviderError::Credentials(s) => ProviderError::Credentials(s.clone()),
+            ProviderError::MissingPrice(s) => ProviderError::MissingPrice(s.clone()),
+            ProviderError::QuotaExhausted(s) => ProviderError::QuotaExhausted(s.clone()),
+            ProviderError::InvalidPrice { asset, raw } => ProviderError::InvalidPrice {
+                asset: *asset,
+                raw: raw.clone(),
+            },
+            ProviderError::UnknownProvider(s) => ProviderError::UnknownProvider(s.clone()),
+            ProviderError::Paused => ProviderError::Paused,
+            ProviderError::OverBudget(budget) => ProviderError::OverBudget(*budget),
+        }
+    }
+}
+
+/// Errors that can occur when encoding or decoding versioned payloads
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    /// Payload is not valid JSON for the expected type
+    #[error("Invalid payload: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Payload was written by a newer, incompatible schema version
+    #[error("Unsupported schema version {found} (supported up to {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+}
+
+/// Errors that can occur when publishing to an event sink
+#[derive(Debug, Error)]
+pub enum SinkError {
+    /// Could not connect to the destination
+    #[error("Sink connection failed: {0}")]
+    Connect(String),
+
+    /// Destination rejected or failed to receive the payload
+    #[error("Publish failed: {0}")]
+    Publish(String),
+
+    /// Payload could not be encoded
+    #[error("Encoding failed: {0}")]
+    Encode(#[from] SchemaError),
+}
+
+/// Errors that can occur when writing or replaying an event journal
+#[derive(Debug, Error)]
+pub enum JournalError {
+    /// The journal file could not be opened, read or written
+    #[error("Journal I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// An event could not be encoded
+    #[error("Journal encoding failed: {0}")]
+    Encode(#[from] SchemaError),
+
+    /// A line of the journal is not a valid event, typically one cut short
+    /// by a crash while it was being written
+    #[error("Corrupt journal entry on line {line}: {source}")]
+    Corrupt { line: u64, source: SchemaError },
+}
+
+/// A configuration setting that failed validation
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ConfigIssue {
+    /// `MARKET_PRICE_PROVIDER` names no registered provider
+    #[error("Unknown provider {name:?} (registered: {})", .known.join(", "))]
+    UnknownProvider {
+        /// The name as configured
+        name: String,
+        /// Names registered in the provider registry
+        known: Vec<String>,
+    },
+
+    /// `MARKET_PRICE_ENVIRONMENT` is not a known environment
+    #[error("Unknown environment {0:?} (expected mainnet, testnet or demo)")]
+    UnknownEnvironment(String),
+
+    /// An asset symbol that does not parse
+    #[error("Unknown asset {0:?}")]
+    UnknownAsset(String),
+
+    /// A numeric or duration setting outside its valid range
+    #[error("{field} is {value}, expected {expected}")]
+    OutOfRange {
+        /// The setting, as named in `TrackerConfig`
+        field: &'static str,
+        /// The configured value
+        value: String,
+        /// The valid range
+        expected: &'static str,
+    },
+}
+
+/// Configuration rejected by validation, listing every problem found
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Invalid configuration: {}", .issues.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+pub struct ConfigError {
+    /// The problems, in the order they were checked
+    pub issues: Vec<ConfigIssue>,
 }
 
 /// Errors that can occur when retrieving price data
@@ -46,6 +223,17 @@ pub enum PriceError {
     #[error("All providers failed: {0}")]
     ProviderFailure(String),
 
+    /// No recorded price close enough to the requested time
+    #[error("No recorded price for {asset} near {timestamp}")]
+    NoHistoricalPrice {
+        asset: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Feed identifier is malformed
+    #[error("Invalid feed ID: {feed_id}")]
+    InvalidFeedId { feed_id: String },
+
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
@@ -72,6 +260,21 @@ impl PriceError {
         Self::ProviderFailure(msg.into())
     }
 
+    /// Creates a NoHistoricalPrice error
+    pub fn no_historical_price(asset: &str, timestamp: DateTime<Utc>) -> Self {
+        Self::NoHistoricalPrice {
+            asset: asset.to_string(),
+            timestamp,
+        }
+    }
+
+    /// Creates an InvalidFeedId error
+    pub fn invalid_feed_id(feed_id: &str) -> Self {
+        Self::InvalidFeedId {
+            feed_id: feed_id.to_string(),
+        }
+    }
+
     /// Creates an Internal error
     pub fn internal(msg: impl Into<String>) -> Self {
         Self::Internal(msg.into())
@@ -0,0 +1,91 @@
+//! Pyth price feed ID resolution with runtime overrides
+//!
+//! Providers resolve feed IDs through [`pyth_feed_id`], which consults the
+//! [`SymbolRegistry`] before falling back to [`Asset::pyth_feed_id`]. This
+//! allows pointing an asset at a different feed (e.g. a testnet feed)
+//! without a new release.
+
+use crate::{
+    error::PriceError,
+    symbols::{SymbolRegistry, SymbolSource},
+    types::Asset,
+};
+
+/// Normalizes a feed ID to lowercase hex with a `0x` prefix
+///
+/// Returns `None` if the input is not a 32-byte hex string.
+pub fn normalize_feed_id(feed_id: &str) -> Option<String> {
+    let hex = feed_id.trim().trim_start_matches("0x").to_lowercase();
+    if hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(format!("0x{}", hex))
+    } else {
+        None
+    }
+}
+
+/// Overrides the Pyth feed ID used for an asset
+///
+/// # Arguments
+/// * `asset` - The asset to override
+/// * `feed_id` - A 32-byte hex feed ID, with or without `0x` prefix
+pub fn set_pyth_feed_id(asset: Asset, feed_id: &str) -> Result<(), PriceError> {
+    SymbolRegistry::global().set(SymbolSource::Pyth, asset, feed_id)
+}
+
+/// Removes the feed ID override for an asset, restoring the built-in mapping
+pub fn clear_pyth_feed_id(asset: Asset) {
+    SymbolRegistry::global().clear(SymbolSource::Pyth, asset);
+}
+
+/// Resolves the Pyth feed ID for an asset, honouring runtime overrides
+pub fn pyth_feed_id(asset: Asset) -> Option<String> {
+    SymbolRegistry::global().pyth_feed_id(asset)
+}
+
+/// Lists assets without a resolvable Pyth feed ID
+pub fn missing_pyth_feed_ids() -> Vec<Asset> {
+    Asset::all()
+        .iter()
+        .copied()
+        .filter(|asset| pyth_feed_id(*asset).is_none())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_assets_have_feed_ids() {
+        assert!(missing_pyth_feed_ids().is_empty());
+        for asset in Asset::all() {
+            let feed_id = asset.pyth_feed_id().unwrap();
+            assert_eq!(normalize_feed_id(feed_id).as_deref(), Some(feed_id));
+        }
+    }
+
+    #[test]
+    fn test_override_feed_id() {
+        // A local registry, since other tests resolve feeds through the
+        // global one in parallel
+        let registry = SymbolRegistry::new();
+        let testnet = "AB".repeat(32);
+        assert!(registry
+            .set(SymbolSource::Pyth, Asset::WETH, "not-a-feed")
+            .is_err());
+
+        registry
+            .set(SymbolSource::Pyth, Asset::WETH, &testnet)
+            .unwrap();
+        assert_eq!(
+            registry.pyth_feed_id(Asset::WETH),
+            Some(format!("0x{}", "ab".repeat(32)))
+        );
+
+        registry.clear(SymbolSource::Pyth, Asset::WETH);
+        assert_eq!(
+            registry.pyth_feed_id(Asset::WETH).as_deref(),
+            Asset::WETH.pyth_feed_id()
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! Network fee data (priority fees, base fee)
+//!
+//! [`FeeTracker`] polls a [`FeeProvider`] on its own cadence, next to the
+//! price tracker. Solana priority fees come from
+//! [`SolanaFeeProvider`](crate::providers::SolanaFeeProvider); Ethereum
+//! base and priority fees from
+//! [`EthereumFeeProvider`](crate::providers::EthereumFeeProvider).
+//!
+//! # Example
+//! ```no_run
+//! use market_price_sdk::fees::{FeeTracker, Percentile};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let tracker = FeeTracker::solana()?;
+//! tracker.start();
+//! tracker.refresh_now().await?;
+//! let fee = tracker.get_priority_fee(Percentile::P75)?;
+//! println!("p75 priority fee: {} micro-lamports/CU", fee);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    constants::{FEE_REFRESH_INTERVAL_SECS, FEE_STALE_THRESHOLD_SECS},
+    error::{PriceError, ProviderError},
+    poller::{Poll, Poller, PollerConfig},
+    providers::SolanaFeeProvider,
+    runtime::Runtime,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Fee percentiles reported by fee providers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Percentile {
+    /// 25th percentile
+    P25,
+    /// Median
+    P50,
+    /// 75th percentile
+    P75,
+    /// 90th percentile
+    P90,
+    /// 99th percentile
+    P99,
+}
+
+impl Percentile {
+    /// Get the percentile as a number between 0 and 100
+    pub fn value(&self) -> f64 {
+        match self {
+            Percentile::P25 => 25.0,
+            Percentile::P50 => 50.0,
+            Percentile::P75 => 75.0,
+            Percentile::P90 => 90.0,
+            Percentile::P99 => 99.0,
+        }
+    }
+
+    /// Get all percentiles
+    pub fn all() -> &'static [Percentile] {
+        &[
+            Percentile::P25,
+            Percentile::P50,
+            Percentile::P75,
+            Percentile::P90,
+            Percentile::P99,
+        ]
+    }
+}
+
+/// Unit of the values in a [`FeeEstimate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeeUnit {
+    /// Solana compute unit price
+    MicroLamportsPerComputeUnit,
+    /// Ethereum gas price
+    Gwei,
+}
+
+/// A snapshot of network fees
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    /// Priority fee (tip) per percentile of recent transactions
+    pub priority_fees: HashMap<Percentile, f64>,
+
+    /// Protocol base fee, where the network has one (Ethereum)
+    pub base_fee: Option<f64>,
+
+    /// Unit of all fee values
+    pub unit: FeeUnit,
+
+    /// Last updated timestamp
+    pub last_updated: DateTime<Utc>,
+
+    /// Data source
+    pub source: String,
+}
+
+/// Source of network fee data
+#[async_trait]
+pub trait FeeProvider: Send + Sync {
+    /// Fetches the current fee estimate
+    async fn fetch_fees(&self) -> Result<FeeEstimate, ProviderError>;
+
+    /// Returns the name of this provider
+    fn provider_name(&self) -> &'static str;
+}
+
+/// Configuration for a [`FeeTracker`]
+#[derive(Debug, Clone)]
+pub struct FeeConfig {
+    /// How often to poll the provider
+    pub refresh_interval: Duration,
+    /// How old an estimate may be before reads fail as stale
+    pub stale_threshold: Duration,
+    /// Executor used for the polling task, and clock for staleness
+    pub runtime: Runtime,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval: Duration::from_secs(FEE_REFRESH_INTERVAL_SECS),
+            stale_threshold: Duration::from_secs(FEE_STALE_THRESHOLD_SECS),
+            runtime: Runtime::default(),
+        }
+    }
+}
+
+/// Polls a [`FeeProvider`] into the latest estimate
+struct FeePoll(Arc<dyn FeeProvider>);
+
+#[async_trait]
+impl Poll for FeePoll {
+    type State = Option<FeeEstimate>;
+
+    async fn poll(&self, latest: &RwLock<Option<FeeEstimate>>) -> Result<(), ProviderError> {
+        let estimate = self.0.fetch_fees().await?;
+        tracing::debug!(
+            provider = self.0.provider_name(),
+            base_fee = estimate.base_fee,
+            "Updated fee estimate"
+        );
+        *latest.write().expect("fee lock poisoned") = Some(estimate);
+        Ok(())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.0.provider_name()
+    }
+}
+
+/// Tracks network fees from a single provider
+pub struct FeeTracker {
+    poller: Poller<FeePoll>,
+}
+
+impl FeeTracker {
+    /// Creates a fee tracker for Solana priority fees on the public RPC
+    pub fn solana() -> Result<Self, ProviderError> {
+        Ok(Self::new(
+            Arc::new(SolanaFeeProvider::new()?),
+            FeeConfig::default(),
+        ))
+    }
+
+    /// Creates a fee tracker with a custom provider and configuration
+    pub fn new(provider: Arc<dyn FeeProvider>, config: FeeConfig) -> Self {
+        Self {
+            poller: Poller::new(
+                FeePoll(provider),
+                None,
+                PollerConfig {
+                    task_name: "fees.poll",
+                    refresh_interval: config.refresh_interval,
+                    stale_threshold: config.stale_threshold,
+                    runtime: config.runtime,
+                },
+            ),
+        }
+    }
+
+    /// Starts the background polling task
+    pub fn start(&self) {
+        self.poller.start();
+    }
+
+    /// Fetches a fresh estimate immediately, outside the polling cadence
+    pub async fn refresh_now(&self) -> Result<(), ProviderError> {
+        self.poller.refresh_now().await
+    }
+
+    /// Gets the latest fee estimate
+    ///
+    /// # Returns
+    /// The latest estimate, or an error if not available or stale
+    pub fn get_fees(&self) -> Result<FeeEstimate, PriceError> {
+        let estimate = self
+            .poller
+            .read(Option::clone)
+            .ok_or_else(|| PriceError::not_available(self.provider_name()))?;
+        self.poller
+            .check_fresh(self.provider_name(), estimate.last_updated)?;
+        Ok(estimate)
+    }
+
+    /// Gets the priority fee at a percentile of recent transactions
+    pub fn get_priority_fee(&self, percentile: Percentile) -> Result<f64, PriceError> {
+        self.get_fees()?
+            .priority_fees
+            .get(&percentile)
+            .copied()
+            .ok_or_else(|| PriceError::not_available(self.provider_name()))
+    }
+
+    /// Gets the protocol base fee, for networks that have one
+    pub fn get_base_fee(&self) -> Result<f64, PriceError> {
+        self.get_fees()?
+            .base_fee
+            .ok_or_else(|| PriceError::not_available(self.provider_name()))
+    }
+
+    /// Returns the name of the current provider
+    pub fn provider_name(&self) -> &str {
+        self.poller.source().provider_name()
+    }
+
+    /// Stops the background polling task
+    pub fn shutdown(&self) {
+        self.poller.shutdown();
+    }
+}
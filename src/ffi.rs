@@ -0,0 +1,434 @@
+//! C ABI for non-Rust consumers (`ffi` feature)
+//!
+//! Exposes the tracker to C, C++ or Python (`ctypes`/`cffi`) execution
+//! components through `extern "C"` functions and `#[repr(C)]` structs.
+//! The tracker runs on the [`blocking`](crate::blocking) facade's own
+//! runtime, so callers need no async machinery and may call from any
+//! thread.
+//!
+//! Build the library and its header with:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! cbindgen --config cbindgen.toml --output include/market_price_sdk.h
+//! ```
+//!
+//! # Layout stability
+//!
+//! Struct fields and enum discriminants are only ever appended to. Assets
+//! are passed as their index in [`Asset::all`], where new assets are
+//! likewise added at the end; [`mp_asset_count`] and [`mp_asset_symbol`]
+//! enumerate them. Absent values are `NaN` for prices and `-1` for assets.
+//!
+//! # Example
+//! ```c
+//! MpTracker *tracker = mp_tracker_global();
+//! MpPrice price;
+//! if (mp_get_price(tracker, 0 /* SOL */, &price) == MP_STATUS_OK) {
+//!     printf("SOL: $%.2f\n", price.price_usd);
+//! }
+//! MpEvent event;
+//! while (mp_poll_event(tracker, &event) == MP_STATUS_OK) {
+//!     /* ... */
+//! }
+//! mp_tracker_free(tracker);
+//! ```
+
+use crate::{
+    blocking::MarketPriceTracker,
+    error::PriceError,
+    types::{Asset, MarketPriceEvent, PriceData},
+};
+use std::ffi::{c_char, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, LazyLock, Mutex};
+use tokio::sync::broadcast::{self, error::TryRecvError};
+
+/// Result code of every fallible call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MpStatus {
+    /// The call succeeded and wrote its output
+    Ok = 0,
+    /// No price has been fetched for the asset yet
+    NotAvailable = 1,
+    /// The stored price is older than the asset's stale threshold
+    Stale = 2,
+    /// No event is waiting
+    Empty = 3,
+    /// A null pointer or unknown asset was passed
+    InvalidArgument = 4,
+    /// Any other failure, including a panic inside the SDK
+    Error = 5,
+}
+
+/// Kind of an [`MpEvent`], one per [`MarketPriceEvent`] variant
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MpEventKind {
+    PriceUpdated = 0,
+    PriceFetchFailed = 1,
+    ProviderStatusChanged = 2,
+    ProviderDivergence = 3,
+    PriceExpired = 4,
+    Heartbeat = 5,
+    SubscriberLagged = 6,
+    PriceWentStale = 7,
+    PriceRecovered = 8,
+}
+
+/// A price as stored by the tracker
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct MpPrice {
+    /// Asset index, see [`mp_asset_symbol`]
+    pub asset: i32,
+    /// Price in USD
+    pub price_usd: f64,
+    /// Confidence interval in USD, or `NaN`
+    pub confidence: f64,
+    /// Last update, in milliseconds since the Unix epoch
+    pub last_updated_ms: i64,
+    /// Per-asset sequence number
+    pub sequence: u64,
+}
+
+/// A tracker event, flattened to the fields shared across kinds
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct MpEvent {
+    pub kind: MpEventKind,
+    /// Asset index, or `-1` for provider and subscriber events
+    pub asset: i32,
+    /// Price the event carries, or `NaN`
+    pub price_usd: f64,
+    /// Sequence number of the price the event refers to, or 0
+    pub sequence: u64,
+    /// When the event was emitted, in milliseconds since the Unix epoch
+    pub timestamp_ms: i64,
+}
+
+/// Opaque tracker handle
+pub struct MpTracker {
+    tracker: Arc<MarketPriceTracker>,
+    events: Mutex<broadcast::Receiver<MarketPriceEvent>>,
+}
+
+impl MpTracker {
+    fn new(tracker: Arc<MarketPriceTracker>) -> Self {
+        let events = Mutex::new(tracker.inner().subscribe_events());
+        Self { tracker, events }
+    }
+}
+
+/// NUL-terminated symbols, indexed like [`Asset::all`]
+static SYMBOLS: LazyLock<Vec<CString>> = LazyLock::new(|| {
+    Asset::all()
+        .iter()
+        .map(|asset| CString::new(asset.symbol()).expect("asset symbols have no NUL"))
+        .collect()
+});
+
+/// Returns a handle to the global tracker, starting it on first call
+///
+/// Returns null if the tracker could not be started. Each handle receives
+/// the events emitted after it was created; free it with
+/// [`mp_tracker_free`].
+#[no_mangle]
+pub extern "C" fn mp_tracker_global() -> *mut MpTracker {
+    catch_unwind(|| Box::into_raw(Box::new(MpTracker::new(MarketPriceTracker::global()))))
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// Releases a handle returned by [`mp_tracker_global`]
+///
+/// # Safety
+/// `tracker` must be null or a handle not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn mp_tracker_free(tracker: *mut MpTracker) {
+    if !tracker.is_null() {
+        drop(Box::from_raw(tracker));
+    }
+}
+
+/// Number of assets, one past the largest valid asset index
+#[no_mangle]
+pub extern "C" fn mp_asset_count() -> i32 {
+    Asset::all().len() as i32
+}
+
+/// Returns the display symbol of an asset, or null for an unknown index
+///
+/// The string is static and must not be freed.
+#[no_mangle]
+pub extern "C" fn mp_asset_symbol(asset: i32) -> *const c_char {
+    usize::try_from(asset)
+        .ok()
+        .and_then(|index| SYMBOLS.get(index))
+        .map_or(std::ptr::null(), |symbol| symbol.as_ptr())
+}
+
+/// Writes the current price of `asset` to `out`
+///
+/// Reads the store only; it never waits on a provider.
+///
+/// # Safety
+/// `tracker` must be a live handle and `out` must point to writable
+/// memory for one [`MpPrice`].
+#[no_mangle]
+pub unsafe extern "C" fn mp_get_price(
+    tracker: *const MpTracker,
+    asset: i32,
+    out: *mut MpPrice,
+) -> MpStatus {
+    let (Some(tracker), Some(asset)) = (tracker.as_ref(), asset_at(asset)) else {
+        return MpStatus::InvalidArgument;
+    };
+    if out.is_null() {
+        return MpStatus::InvalidArgument;
+    }
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        tracker.tracker.inner().store().get_price_sync(asset)
+    }));
+    match result {
+        Ok(Ok(price)) => {
+            out.write(price_to_ffi(&price));
+            MpStatus::Ok
+        }
+        Ok(Err(PriceError::NotAvailable { .. })) => MpStatus::NotAvailable,
+        Ok(Err(PriceError::Stale { .. })) => MpStatus::Stale,
+        _ => MpStatus::Error,
+    }
+}
+
+/// Writes the next pending event to `out`, or returns
+/// [`MpStatus::Empty`] without blocking
+///
+/// Events missed because the handle fell behind are skipped.
+///
+/// # Safety
+/// `tracker` must be a live handle and `out` must point to writable
+/// memory for one [`MpEvent`].
+#[no_mangle]
+pub unsafe extern "C" fn mp_poll_event(tracker: *const MpTracker, out: *mut MpEvent) -> MpStatus {
+    let Some(tracker) = tracker.as_ref() else {
+        return MpStatus::InvalidArgument;
+    };
+    if out.is_null() {
+        return MpStatus::InvalidArgument;
+    }
+
+    let Ok(mut events) = tracker.events.lock() else {
+        return MpStatus::Error;
+    };
+    loop {
+        match events.try_recv() {
+            Ok(event) => {
+                out.write(event_to_ffi(&event));
+                return MpStatus::Ok;
+            }
+            Err(TryRecvError::Lagged(_)) => continue,
+            Err(TryRecvError::Empty) | Err(TryRecvError::Closed) => return MpStatus::Empty,
+        }
+    }
+}
+
+fn asset_at(index: i32) -> Option<Asset> {
+    usize::try_from(index)
+        .ok()
+        .and_then(|index| Asset::all().get(index).copied())
+}
+
+fn asset_index(asset: Asset) -> i32 {
+    Asset::all()
+        .iter()
+        .position(|a| *a == asset)
+        .map_or(-1, |index| index as i32)
+}
+
+fn price_to_ffi(price: &PriceData) -> MpPrice {
+    MpPrice {
+        asset: asset_index(price.asset),
+        price_usd: price.price_usd,
+        confidence: price.confidence.unwrap_or(f64::NAN),
+        last_updated_ms: price.last_updated.timestamp_millis(),
+        sequence: price.sequence,
+    }
+}
+
+fn event_to_ffi(event: &MarketPriceEvent) -> MpEvent {
+    use MarketPriceEvent as E;
+
+    let (kind, asset, price_usd, sequence, timestamp) = match event {
+        E::PriceUpdated {
+            asset,
+            new_price_usd,
+            sequence,
+            timestamp,
+            ..
+        } => (
+            MpEventKind::PriceUpdated,
+            Some(*asset),
+            *new_price_usd,
+            *sequence,
+            timestamp,
+        ),
+        E::PriceFetchFailed {
+            asset, timestamp, ..
+        } => (
+            MpEventKind::PriceFetchFailed,
+            Some(*asset),
+            f64::NAN,
+            0,
+            timestamp,
+        ),
+        E::ProviderStatusChanged { timestamp, .. } => (
+            MpEventKind::ProviderStatusChanged,
+            None,
+            f64::NAN,
+            0,
+            timestamp,
+        ),
+        E::ProviderDivergence {
+            asset,
+            primary_price_usd,
+            timestamp,
+            ..
+        } => (
+            MpEventKind::ProviderDivergence,
+            Some(*asset),
+            *primary_price_usd,
+            0,
+            timestamp,
+        ),
+        E::PriceExpired {
+            asset,
+            last_price_usd,
+            sequence,
+            timestamp,
+            ..
+        } => (
+            MpEventKind::PriceExpired,
+            Some(*asset),
+            *last_price_usd,
+            *sequence,
+            timestamp,
+        ),
+        E::Heartbeat {
+            asset,
+            sequence,
+            timestamp,
+            ..
+        } => (
+            MpEventKind::Heartbeat,
+            Some(*asset),
+            f64::NAN,
+            *sequence,
+            timestamp,
+        ),
+        E::SubscriberLagged { timestamp, .. } => {
+            (MpEventKind::SubscriberLagged, None, f64::NAN, 0, timestamp)
+        }
+        E::PriceWentStale {
+            asset,
+            sequence,
+            timestamp,
+            ..
+        } => (
+            MpEventKind::PriceWentStale,
+            Some(*asset),
+            f64::NAN,
+            *sequence,
+            timestamp,
+        ),
+        E::PriceRecovered {
+            asset,
+            price_usd,
+            sequence,
+            timestamp,
+            ..
+        } => (
+            MpEventKind::PriceRecovered,
+            Some(*asset),
+            *price_usd,
+            *sequence,
+            timestamp,
+        ),
+    };
+
+    MpEvent {
+        kind,
+        asset: asset.map_or(-1, asset_index),
+        price_usd,
+        sequence,
+        timestamp_ms: timestamp.timestamp_millis(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::mock::MockProvider;
+    use std::mem::MaybeUninit;
+
+    #[test]
+    fn test_prices_and_events_cross_the_abi() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = Arc::new(MarketPriceTracker::with_provider(provider));
+        let handle = Box::into_raw(Box::new(MpTracker::new(tracker.clone())));
+        let sol = asset_index(Asset::SOL);
+
+        unsafe {
+            let mut price = MaybeUninit::<MpPrice>::uninit();
+            assert_eq!(
+                mp_get_price(handle, sol, price.as_mut_ptr()),
+                MpStatus::NotAvailable
+            );
+            assert_eq!(
+                mp_get_price(handle, mp_asset_count(), price.as_mut_ptr()),
+                MpStatus::InvalidArgument
+            );
+
+            tracker.refresh_now().unwrap();
+            assert_eq!(mp_get_price(handle, sol, price.as_mut_ptr()), MpStatus::Ok);
+            assert_eq!(price.assume_init().price_usd, 150.0);
+
+            let mut event = MaybeUninit::<MpEvent>::uninit();
+            assert_eq!(mp_poll_event(handle, event.as_mut_ptr()), MpStatus::Empty);
+
+            let symbol = std::ffi::CStr::from_ptr(mp_asset_symbol(sol));
+            assert_eq!(symbol.to_str().unwrap(), "SOL");
+            mp_tracker_free(handle);
+        }
+    }
+
+    #[test]
+    fn test_events_flatten_to_shared_fields() {
+        let now = chrono::Utc::now();
+        let event = event_to_ffi(&MarketPriceEvent::PriceRecovered {
+            id: uuid::Uuid::new_v4(),
+            asset: Asset::BTC,
+            price_usd: 64000.0,
+            previous_update: now,
+            sequence: 7,
+            timestamp: now,
+        });
+        assert_eq!(event.kind, MpEventKind::PriceRecovered);
+        assert_eq!(
+            (event.asset, event.price_usd),
+            (asset_index(Asset::BTC), 64000.0)
+        );
+        assert_eq!(event.sequence, 7);
+
+        let lagged = event_to_ffi(&MarketPriceEvent::SubscriberLagged {
+            id: uuid::Uuid::new_v4(),
+            subscriber: "sink".to_string(),
+            missed: 3,
+            timestamp: now,
+        });
+        assert_eq!(lagged.asset, -1);
+        assert!(lagged.price_usd.is_nan());
+    }
+}
@@ -0,0 +1,232 @@
+//! Human-readable price formatting and on-chain unit conversions
+//!
+//! # Example
+//! ```
+//! use market_price_sdk::format::{format_usd, PriceFormat};
+//!
+//! assert_eq!(format_usd(64231.5), "$64,231.50");
+//! assert_eq!(format_usd(0.00001234), "$0.0₄1234");
+//! assert_eq!(PriceFormat::eu().format(64231.5), "64.231,50 $");
+//! ```
+
+use crate::types::PriceData;
+
+/// Lamports in one SOL
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Satoshis in one BTC
+pub const SATS_PER_BTC: u64 = 100_000_000;
+
+/// Subscript digits used for runs of leading zeros
+const SUBSCRIPT_DIGITS: [char; 10] = ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'];
+
+/// Currency and locale conventions for formatting prices
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceFormat {
+    /// Currency symbol, e.g. "$"
+    pub currency_symbol: String,
+    /// Place the symbol after the number ("64.231,50 €") instead of before
+    pub symbol_after: bool,
+    /// Separator between groups of thousands, `None` for no grouping
+    pub thousands_separator: Option<char>,
+    /// Separator between the integer and fractional parts
+    pub decimal_separator: char,
+    /// Decimal places for prices of at least 1
+    pub decimals: usize,
+    /// Significant digits shown for prices below 1
+    pub small_price_digits: usize,
+    /// Runs of at least this many leading fractional zeros are collapsed
+    /// into subscript notation ($0.00001234 → $0.0₄1234)
+    pub subscript_min_zeros: usize,
+}
+
+impl PriceFormat {
+    /// US dollar formatting: "$64,231.50"
+    pub fn usd() -> Self {
+        Self {
+            currency_symbol: "$".to_string(),
+            symbol_after: false,
+            thousands_separator: Some(','),
+            decimal_separator: '.',
+            decimals: 2,
+            small_price_digits: 4,
+            subscript_min_zeros: 4,
+        }
+    }
+
+    /// Continental European conventions for US dollar amounts: "64.231,50 $"
+    ///
+    /// Only the separators and symbol placement change; the amount is not
+    /// converted. To show euros, convert first (see [`crate::fx`]) and set
+    /// `currency_symbol` to "€".
+    pub fn eu() -> Self {
+        Self {
+            symbol_after: true,
+            thousands_separator: Some('.'),
+            decimal_separator: ',',
+            ..Self::usd()
+        }
+    }
+
+    /// Formats an amount using these conventions
+    pub fn format(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+
+        let sign = if value < 0.0 { "-" } else { "" };
+        let number = self.format_abs(value.abs());
+
+        if self.symbol_after {
+            format!("{}{} {}", sign, number, self.currency_symbol)
+        } else {
+            format!("{}{}{}", sign, self.currency_symbol, number)
+        }
+    }
+
+    fn format_abs(&self, value: f64) -> String {
+        if value == 0.0 || value >= 1.0 {
+            return self.format_fixed(value, self.decimals);
+        }
+
+        // Leading zeros after the decimal point, e.g. 4 for 0.00001234
+        let zeros = (-value.log10()).ceil().max(1.0) as usize - 1;
+        let fixed = format!("{:.*}", zeros + self.small_price_digits, value);
+
+        // Rounding may have carried into the integer part (0.99999 → 1.0000)
+        let Some(fraction) = fixed.strip_prefix("0.") else {
+            return self.format_fixed(value, self.decimals);
+        };
+
+        let zeros = fraction.len() - fraction.trim_start_matches('0').len();
+        let digits = fraction[zeros..].trim_end_matches('0');
+
+        if zeros >= self.subscript_min_zeros {
+            let subscript: String = zeros
+                .to_string()
+                .chars()
+                .map(|c| SUBSCRIPT_DIGITS[c.to_digit(10).unwrap_or(0) as usize])
+                .collect();
+            format!("0{}0{}{}", self.decimal_separator, subscript, digits)
+        } else {
+            // Keep at least the usual number of decimals ($0.50, not $0.5)
+            let width = (zeros + digits.len()).max(self.decimals);
+            format!(
+                "0{}{:0<width$}",
+                self.decimal_separator,
+                &fraction[..zeros + digits.len()],
+                width = width
+            )
+        }
+    }
+
+    fn format_fixed(&self, value: f64, decimals: usize) -> String {
+        let fixed = format!("{:.*}", decimals, value);
+        let (integer, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+
+        let mut grouped = String::with_capacity(integer.len() + integer.len() / 3);
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                if let Some(separator) = self.thousands_separator {
+                    grouped.push(separator);
+                }
+            }
+            grouped.push(c);
+        }
+
+        if fraction.is_empty() {
+            grouped
+        } else {
+            format!("{}{}{}", grouped, self.decimal_separator, fraction)
+        }
+    }
+}
+
+impl Default for PriceFormat {
+    fn default() -> Self {
+        Self::usd()
+    }
+}
+
+/// Formats a USD amount, e.g. "$64,231.50" or "$0.0₄1234"
+pub fn format_usd(value: f64) -> String {
+    PriceFormat::usd().format(value)
+}
+
+/// Converts lamports to SOL
+pub fn lamports_to_sol(lamports: u64) -> f64 {
+    lamports as f64 / LAMPORTS_PER_SOL as f64
+}
+
+/// Converts SOL to lamports, rounding to the nearest lamport
+pub fn sol_to_lamports(sol: f64) -> u64 {
+    (sol * LAMPORTS_PER_SOL as f64).round() as u64
+}
+
+/// Converts satoshis to BTC
+pub fn sats_to_btc(sats: u64) -> f64 {
+    sats as f64 / SATS_PER_BTC as f64
+}
+
+/// Converts BTC to satoshis, rounding to the nearest satoshi
+pub fn btc_to_sats(btc: f64) -> u64 {
+    (btc * SATS_PER_BTC as f64).round() as u64
+}
+
+impl PriceData {
+    /// Formats the price in USD, e.g. "$64,231.50"
+    pub fn format(&self) -> String {
+        format_usd(self.price_usd)
+    }
+
+    /// Formats the price using custom currency/locale conventions
+    pub fn format_with(&self, format: &PriceFormat) -> String {
+        format.format(self.price_usd)
+    }
+
+    /// USD value of an amount of the asset
+    pub fn value_of(&self, amount: f64) -> f64 {
+        amount * self.price_usd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_usd() {
+        assert_eq!(format_usd(64231.5), "$64,231.50");
+        assert_eq!(format_usd(1234567.891), "$1,234,567.89");
+        assert_eq!(format_usd(150.0), "$150.00");
+        assert_eq!(format_usd(0.0), "$0.00");
+        assert_eq!(format_usd(-42.5), "-$42.50");
+        assert_eq!(format_usd(0.5), "$0.50");
+        assert_eq!(format_usd(0.1234567), "$0.1235");
+        assert_eq!(format_usd(0.001234), "$0.001234");
+        assert_eq!(format_usd(0.00001234), "$0.0₄1234");
+        assert_eq!(format_usd(0.000000000012), "$0.0₁₀12");
+        assert_eq!(format_usd(0.999999), "$1.00");
+    }
+
+    #[test]
+    fn test_format_locale() {
+        let eu = PriceFormat::eu();
+        assert_eq!(eu.format(64231.5), "64.231,50 $");
+        assert_eq!(eu.format(0.00001234), "0,0₄1234 $");
+
+        let euros = PriceFormat {
+            currency_symbol: "€".to_string(),
+            ..PriceFormat::eu()
+        };
+        assert_eq!(euros.format(64231.5), "64.231,50 €");
+    }
+
+    #[test]
+    fn test_unit_conversions() {
+        assert_eq!(lamports_to_sol(1_500_000_000), 1.5);
+        assert_eq!(sol_to_lamports(0.000005), 5_000);
+        assert_eq!(sats_to_btc(50_000), 0.0005);
+        assert_eq!(btc_to_sats(0.0005), 50_000);
+    }
+}
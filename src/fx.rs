@@ -0,0 +1,271 @@
+//! Fiat exchange rates for quoting prices in local currencies
+//!
+//! Prices are tracked in USD. [`FxStore`] polls an [`FxProvider`] for the
+//! USD value of a few fiat currencies, by default the ECB reference rates
+//! published through [`FrankfurterProvider`](crate::providers::FrankfurterProvider),
+//! and converts USD amounts with them.
+//!
+//! # Example
+//! ```no_run
+//! use market_price_sdk::fx::{Currency, FxStore};
+//! use market_price_sdk::{Asset, MarketPriceTracker};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let fx = FxStore::frankfurter()?;
+//! fx.start();
+//! fx.refresh_now().await?;
+//!
+//! let sol = MarketPriceTracker::global().await.get_price(Asset::SOL).await?;
+//! println!("SOL: €{:.2}", fx.convert(sol.price_usd, Currency::EUR)?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    constants::{FX_REFRESH_INTERVAL_SECS, FX_STALE_THRESHOLD_SECS},
+    error::{PriceError, ProviderError},
+    providers::FrankfurterProvider,
+    runtime::Runtime,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Fiat currencies prices can be quoted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Currency {
+    /// US dollar, the currency prices are tracked in
+    USD,
+    /// Euro
+    EUR,
+    /// Pound sterling
+    GBP,
+    /// Japanese yen
+    JPY,
+}
+
+impl Currency {
+    /// Get the ISO 4217 code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::USD => "USD",
+            Currency::EUR => "EUR",
+            Currency::GBP => "GBP",
+            Currency::JPY => "JPY",
+        }
+    }
+
+    /// Get all currencies other than USD
+    pub fn foreign() -> &'static [Currency] {
+        &[Currency::EUR, Currency::GBP, Currency::JPY]
+    }
+
+    /// Looks a currency up by its ISO 4217 code, in any case
+    pub fn from_code(code: &str) -> Option<Currency> {
+        [Currency::USD]
+            .iter()
+            .chain(Self::foreign())
+            .copied()
+            .find(|currency| currency.code().eq_ignore_ascii_case(code))
+    }
+}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Exchange rate of one currency against USD
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FxRate {
+    /// The currency
+    pub currency: Currency,
+
+    /// USD value of one unit of the currency (e.g. 1.08 for EUR)
+    pub usd_per_unit: f64,
+
+    /// Day the rate was published for
+    pub as_of: NaiveDate,
+
+    /// When the rate was fetched
+    pub last_updated: DateTime<Utc>,
+
+    /// Data source
+    pub source: String,
+}
+
+impl FxRate {
+    /// Converts a USD amount into this currency
+    pub fn from_usd(&self, usd: f64) -> f64 {
+        usd / self.usd_per_unit
+    }
+}
+
+/// Source of fiat exchange rates
+#[async_trait]
+pub trait FxProvider: Send + Sync {
+    /// Fetches the current USD rate of each currency
+    async fn fetch_rates(
+        &self,
+        currencies: &[Currency],
+    ) -> Result<HashMap<Currency, FxRate>, ProviderError>;
+
+    /// Returns the name of this provider
+    fn provider_name(&self) -> &'static str;
+}
+
+/// Configuration for an [`FxStore`]
+#[derive(Debug, Clone)]
+pub struct FxConfig {
+    /// Currencies to fetch rates for
+    pub currencies: Vec<Currency>,
+    /// How often to poll the provider
+    pub refresh_interval: Duration,
+    /// How long after fetching a rate reads fail as stale
+    pub stale_threshold: Duration,
+    /// Executor and clock used for polling and staleness
+    pub runtime: Runtime,
+}
+
+impl Default for FxConfig {
+    fn default() -> Self {
+        Self {
+            currencies: Currency::foreign().to_vec(),
+            refresh_interval: Duration::from_secs(FX_REFRESH_INTERVAL_SECS),
+            stale_threshold: Duration::from_secs(FX_STALE_THRESHOLD_SECS),
+            runtime: Runtime::default(),
+        }
+    }
+}
+
+/// Latest exchange rates from a single provider
+pub struct FxStore {
+    provider: Arc<dyn FxProvider>,
+    config: FxConfig,
+    rates: Arc<RwLock<HashMap<Currency, FxRate>>>,
+    shutdown_tx: broadcast::Sender<()>,
+}
+
+impl FxStore {
+    /// Creates a store for ECB reference rates from Frankfurter
+    pub fn frankfurter() -> Result<Self, ProviderError> {
+        Ok(Self::new(
+            Arc::new(FrankfurterProvider::new()?),
+            FxConfig::default(),
+        ))
+    }
+
+    /// Creates a store with a custom provider and configuration
+    pub fn new(provider: Arc<dyn FxProvider>, config: FxConfig) -> Self {
+        let (shutdown_tx, _) = broadcast::channel(1);
+        Self {
+            provider,
+            config,
+            rates: Arc::new(RwLock::new(HashMap::new())),
+            shutdown_tx,
+        }
+    }
+
+    /// Starts the background polling task
+    pub fn start(&self) {
+        let provider = self.provider.clone();
+        let rates = self.rates.clone();
+        let currencies = self.config.currencies.clone();
+        let interval = self.config.refresh_interval;
+        let runtime = self.config.runtime.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        self.config.runtime.spawn_named("fx.poll", async move {
+            tracing::info!(
+                provider = provider.provider_name(),
+                refresh_interval_secs = interval.as_secs(),
+                "Starting FX rate background task"
+            );
+
+            loop {
+                if let Err(e) = Self::fetch_and_update(&provider, &rates, &currencies).await {
+                    tracing::warn!(error = %e, "Failed to fetch exchange rates");
+                }
+
+                tokio::select! {
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!("FX rate background task shutting down");
+                        break;
+                    }
+                    _ = runtime.sleep(interval) => {}
+                }
+            }
+        });
+    }
+
+    async fn fetch_and_update(
+        provider: &Arc<dyn FxProvider>,
+        rates: &RwLock<HashMap<Currency, FxRate>>,
+        currencies: &[Currency],
+    ) -> Result<(), ProviderError> {
+        let fetched = provider.fetch_rates(currencies).await?;
+        tracing::debug!(
+            provider = provider.provider_name(),
+            count = fetched.len(),
+            "Updated exchange rates"
+        );
+        rates.write().expect("fx lock poisoned").extend(fetched);
+        Ok(())
+    }
+
+    /// Fetches fresh rates immediately, outside the polling cadence
+    pub async fn refresh_now(&self) -> Result<(), ProviderError> {
+        Self::fetch_and_update(&self.provider, &self.rates, &self.config.currencies).await
+    }
+
+    /// Gets the latest rate for a currency
+    ///
+    /// USD always has a rate of 1.
+    ///
+    /// # Returns
+    /// The latest rate, or an error if not available or stale
+    pub fn get_rate(&self, currency: Currency) -> Result<FxRate, PriceError> {
+        let now = self.config.runtime.now();
+        if currency == Currency::USD {
+            return Ok(FxRate {
+                currency,
+                usd_per_unit: 1.0,
+                as_of: now.date_naive(),
+                last_updated: now,
+                source: "identity".to_string(),
+            });
+        }
+
+        let rates = self.rates.read().expect("fx lock poisoned");
+        let rate = rates
+            .get(&currency)
+            .ok_or_else(|| PriceError::not_available(currency.code()))?;
+
+        let age = (now - rate.last_updated).to_std().unwrap_or_default();
+        if age > self.config.stale_threshold {
+            return Err(PriceError::stale(currency.code(), age));
+        }
+
+        Ok(rate.clone())
+    }
+
+    /// Converts a USD amount into `currency` at the latest rate
+    pub fn convert(&self, usd: f64, currency: Currency) -> Result<f64, PriceError> {
+        Ok(self.get_rate(currency)?.from_usd(usd))
+    }
+
+    /// Returns the name of the current provider
+    pub fn provider_name(&self) -> &str {
+        self.provider.provider_name()
+    }
+
+    /// Stops the background polling task
+    pub fn shutdown(&self) {
+        let _ = self.shutdown_tx.send(());
+    }
+}
@@ -0,0 +1,206 @@
+//! Per-asset price history for point-in-time lookups
+
+use crate::{
+    error::PriceError,
+    types::{Asset, PriceData},
+};
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Options for [`MarketPriceTracker::get_price_at_with`](crate::MarketPriceTracker::get_price_at_with)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceAtOptions {
+    /// Linearly interpolate between the samples either side of the
+    /// timestamp instead of returning the nearest one
+    pub interpolate: bool,
+    /// Maximum distance between the timestamp and the samples used;
+    /// lookups that would rely on older or newer data fail instead
+    pub max_gap: Duration,
+}
+
+impl Default for PriceAtOptions {
+    fn default() -> Self {
+        Self {
+            interpolate: false,
+            max_gap: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Bounded, time-ordered buffer of accepted prices for one asset
+#[derive(Debug)]
+pub(crate) struct PriceHistory {
+    asset: Asset,
+    samples: VecDeque<PriceData>,
+    capacity: usize,
+    retention: Duration,
+}
+
+impl PriceHistory {
+    pub(crate) fn new(asset: Asset, capacity: usize, retention: Duration) -> Self {
+        Self {
+            asset,
+            samples: VecDeque::new(),
+            capacity,
+            retention,
+        }
+    }
+
+    /// Records a sample, keeping the buffer ordered by `last_updated` and
+    /// dropping samples beyond the capacity or the retention window before
+    /// `now`
+    pub(crate) fn push(&mut self, price: PriceData, now: DateTime<Utc>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        match self.samples.back() {
+            Some(last) if last.last_updated > price.last_updated => {
+                let index = self
+                    .samples
+                    .partition_point(|sample| sample.last_updated <= price.last_updated);
+                self.samples.insert(index, price);
+            }
+            _ => self.samples.push_back(price),
+        }
+
+        while self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+        if let Ok(retention) = chrono::Duration::from_std(self.retention) {
+            let cutoff = now - retention;
+            while self
+                .samples
+                .front()
+                .is_some_and(|sample| sample.last_updated < cutoff)
+            {
+                self.samples.pop_front();
+            }
+        }
+    }
+
+    /// Returns the most recent `n` samples, oldest first
+    pub(crate) fn recent(&self, n: usize) -> Vec<PriceData> {
+        let skip = self.samples.len().saturating_sub(n);
+        self.samples.iter().skip(skip).cloned().collect()
+    }
+
+    /// Returns samples recorded within `[from, to]`, oldest first
+    pub(crate) fn range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<PriceData> {
+        self.samples
+            .iter()
+            .filter(|sample| sample.last_updated >= from && sample.last_updated <= to)
+            .cloned()
+            .collect()
+    }
+
+    /// Resolves the price at a point in time
+    pub(crate) fn at(
+        &self,
+        timestamp: DateTime<Utc>,
+        options: &PriceAtOptions,
+    ) -> Result<PriceData, PriceError> {
+        let index = self
+            .samples
+            .partition_point(|sample| sample.last_updated <= timestamp);
+        let before = index.checked_sub(1).and_then(|i| self.samples.get(i));
+        let after = self.samples.get(index);
+
+        let gap = |sample: &PriceData| {
+            (sample.last_updated - timestamp)
+                .abs()
+                .to_std()
+                .unwrap_or(Duration::MAX)
+        };
+        let within = |sample: &&PriceData| gap(sample) <= options.max_gap;
+
+        if options.interpolate {
+            if let (Some(before), Some(after)) = (before.filter(within), after.filter(within)) {
+                let span = (after.last_updated - before.last_updated).num_microseconds();
+                let elapsed = (timestamp - before.last_updated).num_microseconds();
+                if let (Some(span), Some(elapsed)) = (span, elapsed) {
+                    let weight = if span == 0 {
+                        0.0
+                    } else {
+                        elapsed as f64 / span as f64
+                    };
+                    let mut price = before.clone();
+                    price.price_usd += (after.price_usd - before.price_usd) * weight;
+                    price.last_updated = timestamp;
+                    return Ok(price);
+                }
+            }
+        }
+
+        let nearest = match (before, after) {
+            (Some(b), Some(a)) if gap(a) < gap(b) => Some(a),
+            (Some(b), _) => Some(b),
+            (None, a) => a,
+        };
+
+        nearest
+            .filter(within)
+            .cloned()
+            .ok_or_else(|| PriceError::no_historical_price(self.asset.symbol(), timestamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(price_usd: f64, at: DateTime<Utc>) -> PriceData {
+        let mut price = PriceData::new(Asset::SOL, price_usd, "test".to_string());
+        price.last_updated = at;
+        price
+    }
+
+    #[test]
+    fn test_price_at() {
+        let t0 = Utc::now() - chrono::Duration::seconds(30);
+        let mut history = PriceHistory::new(Asset::SOL, 100, Duration::from_secs(3600));
+        history.push(sample(100.0, t0), t0);
+        history.push(sample(120.0, t0 + chrono::Duration::seconds(20)), t0);
+        // Out-of-order sample is slotted into place
+        history.push(sample(110.0, t0 + chrono::Duration::seconds(10)), t0);
+
+        let nearest = PriceAtOptions::default();
+        let at = |secs| t0 + chrono::Duration::seconds(secs);
+        assert_eq!(history.at(at(4), &nearest).unwrap().price_usd, 100.0);
+        assert_eq!(history.at(at(6), &nearest).unwrap().price_usd, 110.0);
+
+        let interpolate = PriceAtOptions {
+            interpolate: true,
+            ..Default::default()
+        };
+        assert!((history.at(at(15), &interpolate).unwrap().price_usd - 115.0).abs() < 1e-9);
+
+        let strict = PriceAtOptions {
+            max_gap: Duration::from_secs(5),
+            ..Default::default()
+        };
+        assert!(matches!(
+            history.at(at(-60), &strict),
+            Err(PriceError::NoHistoricalPrice { .. })
+        ));
+        assert_eq!(history.range(at(5), at(25)).len(), 2);
+    }
+
+    #[test]
+    fn test_capacity_and_retention() {
+        let now = Utc::now();
+        let mut history = PriceHistory::new(Asset::SOL, 2, Duration::from_secs(60));
+        history.push(sample(1.0, now - chrono::Duration::seconds(120)), now);
+        history.push(sample(2.0, now), now);
+        history.push(sample(3.0, now), now);
+        history.push(sample(4.0, now), now);
+
+        let prices: Vec<f64> = history
+            .range(now - chrono::Duration::hours(1), now)
+            .iter()
+            .map(|p| p.price_usd)
+            .collect();
+        assert_eq!(prices, vec![3.0, 4.0]);
+    }
+}
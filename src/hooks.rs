@@ -0,0 +1,43 @@
+//! Store update hooks
+//!
+//! Hooks run on every write to the price store, for both polled and
+//! streamed prices, after the built-in acceptance rules. They are called
+//! inline on the update path, so keep them fast; hand work off to a channel
+//! when forwarding to external systems.
+//!
+//! # Example
+//! ```no_run
+//! use market_price_sdk::{hooks::PriceUpdateHook, MarketPriceTracker, PriceData};
+//!
+//! struct RejectNonPositive;
+//!
+//! impl PriceUpdateHook for RejectNonPositive {
+//!     fn before_update(&self, price: PriceData) -> Option<PriceData> {
+//!         (price.price_usd > 0.0).then_some(price)
+//!     }
+//! }
+//!
+//! # async fn example() {
+//! let tracker = MarketPriceTracker::global().await;
+//! tracker.add_update_hook(Box::new(RejectNonPositive));
+//! # }
+//! ```
+
+use crate::types::PriceData;
+
+/// Middleware invoked around each store write
+///
+/// Hooks run in registration order. Each `before_update` receives the
+/// output of the previous one.
+pub trait PriceUpdateHook: Send + Sync {
+    /// Called before a price is stored
+    ///
+    /// Return the price to store (possibly modified, e.g. enriched), or
+    /// `None` to reject the update.
+    fn before_update(&self, price: PriceData) -> Option<PriceData> {
+        Some(price)
+    }
+
+    /// Called after a price has been stored
+    fn after_update(&self, _price: &PriceData) {}
+}
@@ -0,0 +1,481 @@
+//! HTTP client configuration shared by all providers
+//!
+//! The TLS backend is selected at compile time with the `rustls` (default)
+//! or `native-tls` cargo features. Proxies are configured at runtime, either
+//! explicitly or via the `MARKET_PRICE_PROXY` environment variable. SOCKS5
+//! proxy URLs (`socks5://...`) require the `socks` feature.
+//!
+//! Providers created with their default constructors share the clients of
+//! [`HttpClientFactory::global()`], and therefore one connection pool.
+//!
+//! Request/response providers talk HTTP through the [`HttpTransport`] trait,
+//! implemented for `reqwest::Client`, so they can be unit-tested against an
+//! in-memory fake or run on a different HTTP client.
+//!
+//! Providers that accept several base URLs (e.g. an API and its mirror)
+//! hold them in [`BaseUrls`], which moves on to the next URL after a network
+//! error or 5xx response.
+//!
+//! Requests made inside [`with_deadline`] are bounded by that deadline: the
+//! `reqwest::Client` transport sets each request's timeout to the time left.
+
+use crate::{
+    constants::{REQUEST_TIMEOUT_SECS, USER_AGENT},
+    error::ProviderError,
+};
+use async_trait::async_trait;
+use reqwest::{Client, Proxy};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{LazyLock, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Environment variable holding the proxy URL for all provider requests
+pub const PROXY_ENV_VAR: &str = "MARKET_PRICE_PROXY";
+
+tokio::task_local! {
+    static DEADLINE: Instant;
+}
+
+/// Runs `future` with a deadline bounding every HTTP request it makes
+///
+/// Nested deadlines keep the earlier of the two. Within the deadline the
+/// per-request timeout replaces the client's configured timeout.
+pub async fn with_deadline<F: Future>(deadline: Instant, future: F) -> F::Output {
+    let deadline = DEADLINE
+        .try_with(|outer| (*outer).min(deadline))
+        .unwrap_or(deadline);
+    DEADLINE.scope(deadline, future).await
+}
+
+/// Time left before the current task's deadline, if one is set
+pub fn time_remaining() -> Option<Duration> {
+    DEADLINE
+        .try_with(|deadline| deadline.saturating_duration_since(Instant::now()))
+        .ok()
+}
+
+/// Applies the current deadline to a request, failing if it already passed
+fn bounded(request: reqwest::RequestBuilder) -> Result<reqwest::RequestBuilder, ProviderError> {
+    match time_remaining() {
+        Some(remaining) if remaining.is_zero() => Err(ProviderError::Timeout),
+        Some(remaining) => Ok(request.timeout(remaining)),
+        None => Ok(request),
+    }
+}
+
+/// Settings used to build provider HTTP clients
+#[derive(Debug, Clone)]
+pub struct HttpClientConfig {
+    /// Overall request timeout (`None` disables it, e.g. for long-lived streams)
+    pub timeout: Option<Duration>,
+    /// User agent sent with every request
+    pub user_agent: String,
+    /// Proxy URL for all requests (`http://`, `https://` or `socks5://`)
+    pub proxy: Option<String>,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Some(Duration::from_secs(REQUEST_TIMEOUT_SECS)),
+            user_agent: USER_AGENT.to_string(),
+            proxy: std::env::var(PROXY_ENV_VAR)
+                .ok()
+                .filter(|proxy| !proxy.is_empty()),
+        }
+    }
+}
+
+impl HttpClientConfig {
+    /// Builds a reqwest client from this configuration
+    pub fn build(&self) -> Result<Client, ProviderError> {
+        let mut builder = Client::builder().user_agent(&self.user_agent);
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(proxy) = &self.proxy {
+            tracing::debug!(proxy = %proxy, "Using proxy for provider requests");
+            builder = builder.proxy(Proxy::all(proxy).map_err(ProviderError::NetworkError)?);
+        }
+
+        builder.build().map_err(ProviderError::NetworkError)
+    }
+}
+
+/// Builds provider HTTP clients once and hands out clones sharing one pool
+///
+/// Cloning a reqwest `Client` is cheap and shares its connection pool, so
+/// every provider built from the same factory reuses sockets, proxy and
+/// TLS settings.
+pub struct HttpClientFactory {
+    config: HttpClientConfig,
+    client: OnceLock<Client>,
+    streaming_client: OnceLock<Client>,
+}
+
+static GLOBAL_FACTORY: LazyLock<HttpClientFactory> =
+    LazyLock::new(|| HttpClientFactory::new(HttpClientConfig::default()));
+
+impl HttpClientFactory {
+    /// Creates a factory for the given configuration
+    pub fn new(config: HttpClientConfig) -> Self {
+        Self {
+            config,
+            client: OnceLock::new(),
+            streaming_client: OnceLock::new(),
+        }
+    }
+
+    /// Returns the process-wide factory built from the default configuration
+    pub fn global() -> &'static HttpClientFactory {
+        &GLOBAL_FACTORY
+    }
+
+    /// Returns the configuration clients are built from
+    pub fn config(&self) -> &HttpClientConfig {
+        &self.config
+    }
+
+    /// Returns the shared client for request/response calls
+    pub fn client(&self) -> Result<Client, ProviderError> {
+        Self::get_or_build(&self.client, &self.config)
+    }
+
+    /// Returns the shared client for long-lived streams
+    ///
+    /// Identical to [`client`](Self::client) except that it has no overall
+    /// request timeout, which would otherwise cut streams off.
+    pub fn streaming_client(&self) -> Result<Client, ProviderError> {
+        let config = HttpClientConfig {
+            timeout: None,
+            ..self.config.clone()
+        };
+        Self::get_or_build(&self.streaming_client, &config)
+    }
+
+    fn get_or_build(
+        cell: &OnceLock<Client>,
+        config: &HttpClientConfig,
+    ) -> Result<Client, ProviderError> {
+        if let Some(client) = cell.get() {
+            return Ok(client.clone());
+        }
+        let client = config.build()?;
+        Ok(cell.get_or_init(|| client).clone())
+    }
+}
+
+/// A buffered HTTP response
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response body
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Creates a response from a status code and body
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+        }
+    }
+
+    /// Returns true for 2xx statuses
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Maps 429 to `RateLimitExceeded` and other non-2xx statuses to `ApiError`
+    pub fn error_for_status(self) -> Result<Self, ProviderError> {
+        if self.status == 429 {
+            return Err(ProviderError::RateLimitExceeded);
+        }
+        if !self.is_success() {
+            return Err(ProviderError::ApiError(format!(
+                "HTTP {}: {}",
+                self.status, self.body
+            )));
+        }
+        Ok(self)
+    }
+}
+
+/// Base URLs for one provider, tried in turn when one becomes unreachable
+///
+/// Requests go to the current URL until it fails with a network error or
+/// a 5xx status; the failing request is then retried against the next URL,
+/// which stays current for later requests.
+#[derive(Debug)]
+pub struct BaseUrls {
+    urls: Vec<String>,
+    current: AtomicUsize,
+}
+
+impl BaseUrls {
+    /// Creates the list from a primary URL followed by fallbacks
+    ///
+    /// # Panics
+    /// If `urls` is empty
+    pub fn new<I, S>(urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let urls: Vec<String> = urls
+            .into_iter()
+            .map(|url| url.into().trim_end_matches('/').to_string())
+            .collect();
+        assert!(!urls.is_empty(), "at least one base URL is required");
+        Self {
+            urls,
+            current: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the URL requests currently go to
+    pub fn current(&self) -> &str {
+        &self.urls[self.current.load(Ordering::Relaxed) % self.urls.len()]
+    }
+
+    /// Returns every configured URL, primary first
+    pub fn urls(&self) -> &[String] {
+        &self.urls
+    }
+
+    /// Sends a request built from a base URL, failing over to the next URL
+    /// on network errors and 5xx responses until every URL has been tried
+    pub async fn send<F, Fut>(&self, request: F) -> Result<HttpResponse, ProviderError>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<HttpResponse, ProviderError>>,
+    {
+        let mut attempts_left = self.urls.len();
+        loop {
+            let index = self.current.load(Ordering::Relaxed);
+            let base = self.urls[index % self.urls.len()].clone();
+            let result = request(base.clone()).await;
+
+            let failed = match &result {
+                Ok(response) => response.status >= 500,
+                Err(ProviderError::NetworkError(_) | ProviderError::Timeout) => true,
+                Err(_) => false,
+            };
+            attempts_left -= 1;
+            if !failed || attempts_left == 0 {
+                return result;
+            }
+
+            // Concurrent requests that failed on the same URL rotate only once
+            let _ = self.current.compare_exchange(
+                index,
+                index.wrapping_add(1),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+            tracing::warn!(
+                failed = %base,
+                next = self.current(),
+                "Base URL unreachable, failing over"
+            );
+        }
+    }
+}
+
+/// Minimal HTTP access used by request/response providers
+#[async_trait]
+pub trait HttpTransport: Send + Sync {
+    /// Sends a GET request
+    async fn get(&self, url: &str) -> Result<HttpResponse, ProviderError> {
+        self.get_with_headers(url, &[]).await
+    }
+
+    /// Sends a GET request with extra headers, e.g. credentials that must
+    /// stay out of the URL
+    async fn get_with_headers(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<HttpResponse, ProviderError>;
+
+    /// Sends a POST request with a JSON body
+    async fn post_json(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<HttpResponse, ProviderError>;
+}
+
+impl HttpResponse {
+    async fn from_reqwest(response: reqwest::Response) -> Result<Self, ProviderError> {
+        let status = response.status().as_u16();
+        let body = response.text().await.map_err(ProviderError::from_reqwest)?;
+        Ok(Self { status, body })
+    }
+}
+
+#[async_trait]
+impl HttpTransport for Client {
+    async fn get_with_headers(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<HttpResponse, ProviderError> {
+        let request = headers
+            .iter()
+            .fold(Client::get(self, url), |request, (name, value)| {
+                request.header(*name, *value)
+            });
+        let response = bounded(request)?
+            .send()
+            .await
+            .map_err(ProviderError::from_reqwest)?;
+        HttpResponse::from_reqwest(response).await
+    }
+
+    async fn post_json(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<HttpResponse, ProviderError> {
+        let response = bounded(Client::post(self, url).json(body))?
+            .send()
+            .await
+            .map_err(ProviderError::from_reqwest)?;
+        HttpResponse::from_reqwest(response).await
+    }
+}
+
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// A request captured by [`FakeTransport`]
+    #[derive(Debug, Clone)]
+    pub struct RecordedRequest {
+        pub method: &'static str,
+        pub url: String,
+        pub headers: Vec<(String, String)>,
+        pub body: Option<serde_json::Value>,
+    }
+
+    /// In-memory transport replaying canned responses in order
+    #[derive(Default)]
+    pub struct FakeTransport {
+        responses: Mutex<VecDeque<Result<HttpResponse, ProviderError>>>,
+        requests: Mutex<Vec<RecordedRequest>>,
+    }
+
+    impl FakeTransport {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn push_response(&self, status: u16, body: &str) {
+            self.responses
+                .lock()
+                .unwrap()
+                .push_back(Ok(HttpResponse::new(status, body)));
+        }
+
+        pub fn push_error(&self, error: ProviderError) {
+            self.responses.lock().unwrap().push_back(Err(error));
+        }
+
+        pub fn requests(&self) -> Vec<RecordedRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        fn respond(&self, request: RecordedRequest) -> Result<HttpResponse, ProviderError> {
+            self.requests.lock().unwrap().push(request);
+            self.responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| Err(ProviderError::ApiError("no canned response".to_string())))
+        }
+    }
+
+    #[async_trait]
+    impl HttpTransport for FakeTransport {
+        async fn get_with_headers(
+            &self,
+            url: &str,
+            headers: &[(&str, &str)],
+        ) -> Result<HttpResponse, ProviderError> {
+            self.respond(RecordedRequest {
+                method: "GET",
+                url: url.to_string(),
+                headers: headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect(),
+                body: None,
+            })
+        }
+
+        async fn post_json(
+            &self,
+            url: &str,
+            body: &serde_json::Value,
+        ) -> Result<HttpResponse, ProviderError> {
+            self.respond(RecordedRequest {
+                method: "POST",
+                url: url.to_string(),
+                headers: Vec::new(),
+                body: Some(body.clone()),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_base_urls_fail_over() {
+        let urls = BaseUrls::new(["https://a/", "https://b"]);
+        let response = urls
+            .send(|base| async move {
+                match base.as_str() {
+                    "https://a" => Ok(HttpResponse::new(503, "")),
+                    _ => Ok(HttpResponse::new(200, base)),
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.body, "https://b");
+        assert_eq!(urls.current(), "https://b");
+
+        let response = urls
+            .send(|_| async { Ok(HttpResponse::new(502, "")) })
+            .await
+            .unwrap();
+        assert_eq!(response.status, 502);
+    }
+
+    #[tokio::test]
+    async fn test_nested_deadlines_keep_the_earlier() {
+        assert!(time_remaining().is_none());
+
+        let soon = Instant::now() + Duration::from_secs(1);
+        let later = soon + Duration::from_secs(60);
+        let remaining = with_deadline(soon, with_deadline(later, async { time_remaining() })).await;
+        assert!(remaining.unwrap() <= Duration::from_secs(1));
+
+        let expired = with_deadline(Instant::now(), async {
+            bounded(Client::new().get("http://localhost")).map(|_| ())
+        })
+        .await;
+        assert!(matches!(expired, Err(ProviderError::Timeout)));
+    }
+}
@@ -0,0 +1,202 @@
+//! Synthetic index assets computed from tracked constituents
+//!
+//! An [`IndexDefinition`] is a named basket holding a fixed number of units
+//! of each constituent. Its price is the basket value divided by the
+//! divisor:
+//!
+//! ```text
+//! price = Σ units(asset) × price(asset) / divisor
+//! ```
+//!
+//! Register indices with
+//! [`MarketPriceTracker::add_index`](crate::MarketPriceTracker::add_index);
+//! [`get_index_price`](crate::MarketPriceTracker::get_index_price) and
+//! [`get_index_prices`](crate::MarketPriceTracker::get_index_prices) value
+//! them on demand against the latest cached constituent prices.
+//! Constituents must be among the assets the tracker polls.
+//!
+//! Indices are not assets: they are never stored, so they are not served by
+//! `get_price`, broadcast to `subscribe` or `watch` receivers, or included
+//! in snapshots. Poll `get_index_price` to follow one.
+//!
+//! # Example
+//! ```
+//! use market_price_sdk::index::IndexDefinition;
+//! use market_price_sdk::Asset;
+//!
+//! // A majors basket worth 100 USD at the given reference prices,
+//! // weighted 50% BTC, 30% ETH, 20% SOL
+//! let majors = IndexDefinition::weighted(
+//!     "MAJORS",
+//!     &[(Asset::BTC, 0.5), (Asset::ETH, 0.3), (Asset::SOL, 0.2)],
+//!     |asset| match asset {
+//!         Asset::BTC => Some(64_000.0),
+//!         Asset::ETH => Some(3_200.0),
+//!         Asset::SOL => Some(150.0),
+//!         _ => None,
+//!     },
+//!     100.0,
+//! )
+//! .unwrap();
+//! assert_eq!(majors.components.len(), 3);
+//! ```
+
+use crate::{
+    error::PriceError,
+    types::{Asset, PriceData, Quality},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One constituent of an index
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct IndexComponent {
+    /// Constituent asset
+    pub asset: Asset,
+    /// Units of the asset held by the basket
+    pub units: f64,
+}
+
+/// A named basket of assets priced by the SDK
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexDefinition {
+    /// Name the index is queried by
+    pub name: String,
+    /// Constituents and their units
+    pub components: Vec<IndexComponent>,
+    /// Basket value is divided by this to get the index price
+    pub divisor: f64,
+}
+
+impl IndexDefinition {
+    /// Creates an empty index with a divisor of 1
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            components: Vec::new(),
+            divisor: 1.0,
+        }
+    }
+
+    /// Adds `units` of `asset` to the basket
+    pub fn with_component(mut self, asset: Asset, units: f64) -> Self {
+        self.components.push(IndexComponent { asset, units });
+        self
+    }
+
+    /// Sets the divisor applied to the basket value
+    pub fn with_divisor(mut self, divisor: f64) -> Self {
+        self.divisor = divisor;
+        self
+    }
+
+    /// Creates a basket worth `base_value` at the reference prices, with
+    /// each asset making up the given share of that value
+    ///
+    /// Weights are normalized to sum to one. Returns `None` if a reference
+    /// price is missing or not positive, or if the weights do not sum to a
+    /// positive number.
+    pub fn weighted(
+        name: impl Into<String>,
+        weights: &[(Asset, f64)],
+        reference_price: impl Fn(Asset) -> Option<f64>,
+        base_value: f64,
+    ) -> Option<Self> {
+        let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut index = Self::new(name);
+        for (asset, weight) in weights {
+            let price = reference_price(*asset).filter(|p| *p > 0.0)?;
+            index = index.with_component(*asset, base_value * weight / total / price);
+        }
+        Some(index)
+    }
+
+    /// Equal-weighted USDC/USDT composite, which trades near 1 USD
+    pub fn stablecoin_composite() -> Self {
+        Self::new("STABLE")
+            .with_component(Asset::USDC, 0.5)
+            .with_component(Asset::USDT, 0.5)
+    }
+
+    /// Values the basket, looking up each constituent with `price`
+    ///
+    /// Fails with the first constituent error, so a stale or missing
+    /// constituent makes the whole index unavailable.
+    pub fn compute(
+        &self,
+        price: impl Fn(Asset) -> Result<PriceData, PriceError>,
+    ) -> Result<IndexPrice, PriceError> {
+        if self.components.is_empty() || self.divisor == 0.0 {
+            return Err(PriceError::not_available(&self.name));
+        }
+
+        let mut value = 0.0;
+        let mut last_updated: Option<DateTime<Utc>> = None;
+        let mut quality = Quality::Excellent;
+        for component in &self.components {
+            let data = price(component.asset)?;
+            value += component.units * data.price_usd;
+            last_updated = Some(match last_updated {
+                Some(oldest) => oldest.min(data.last_updated),
+                None => data.last_updated,
+            });
+            quality = quality.min(data.quality);
+        }
+
+        Ok(IndexPrice {
+            name: self.name.clone(),
+            price_usd: value / self.divisor,
+            last_updated: last_updated.unwrap_or_else(Utc::now),
+            quality,
+        })
+    }
+}
+
+/// Computed price of an index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexPrice {
+    /// Index name
+    pub name: String,
+    /// Index price in USD
+    pub price_usd: f64,
+    /// Timestamp of the oldest constituent price
+    pub last_updated: DateTime<Utc>,
+    /// Lowest quality grade among the constituents
+    pub quality: Quality,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_value_and_missing_constituent() {
+        let prices = |asset| match asset {
+            Asset::USDC => Ok(PriceData::new(asset, 1.0, "test".to_string())),
+            Asset::USDT => Ok(PriceData::new(asset, 0.998, "test".to_string())),
+            _ => Err(PriceError::not_available(asset.symbol())),
+        };
+
+        let stable = IndexDefinition::stablecoin_composite()
+            .compute(prices)
+            .unwrap();
+        assert!((stable.price_usd - 0.999).abs() < 1e-12);
+        assert_eq!(stable.quality, Quality::Fair);
+
+        let basket = IndexDefinition::weighted(
+            "USD_SOL",
+            &[(Asset::USDC, 1.0), (Asset::SOL, 1.0)],
+            |_| Some(1.0),
+            2.0,
+        )
+        .unwrap();
+        assert!(matches!(
+            basket.compute(prices),
+            Err(PriceError::NotAvailable { .. })
+        ));
+    }
+}
@@ -0,0 +1,245 @@
+//! Append-only journal of tracker events
+//!
+//! An [`EventJournal`] records every [`MarketPriceEvent`] it receives as one
+//! line of [`Versioned`] JSON, the same encoding the event sinks publish.
+//! Registered with
+//! [`MarketPriceTracker::add_event_sink`](crate::MarketPriceTracker::add_event_sink),
+//! it keeps a durable record of everything the tracker emitted, and
+//! [`EventJournal::replay`] streams back the events of a time range for
+//! post-incident forensics.
+//!
+//! The file is only ever appended to. A crash mid-write leaves at most one
+//! truncated last line, which replay reports as
+//! [`JournalError::Corrupt`] before carrying on with later lines. Rotation
+//! and retention are left to the operator: move the file away and the
+//! journal carries on in a fresh one on the next open.
+//!
+//! # Example
+//! ```no_run
+//! use futures::StreamExt;
+//! use market_price_sdk::journal::EventJournal;
+//! use market_price_sdk::MarketPriceTracker;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let journal = Arc::new(EventJournal::open("events.jsonl").await?);
+//! MarketPriceTracker::global().await.add_event_sink(journal.clone());
+//!
+//! // Later: everything emitted during the last hour
+//! let since = chrono::Utc::now() - chrono::Duration::hours(1);
+//! let mut events = journal.replay(since..);
+//! while let Some(event) = events.next().await {
+//!     println!("{}", event?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    error::{JournalError, SinkError},
+    schema::Versioned,
+    sinks::PriceEventSink,
+    types::MarketPriceEvent,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt};
+use std::io::SeekFrom;
+use std::ops::RangeBounds;
+use std::path::{Path, PathBuf};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader, Lines};
+use tokio::sync::Mutex;
+
+/// Event journal backed by a JSON-lines file
+pub struct EventJournal {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl EventJournal {
+    /// Opens the journal at `path`, creating the file if needed
+    ///
+    /// Events already in the file are kept; new ones are appended after them.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, JournalError> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+        // Close a line torn by a crash, so the next event starts on its own
+        if ends_mid_line(&path).await? {
+            file.write_all(b"\n").await?;
+        }
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Returns the path of the journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends one event
+    ///
+    /// The line is written with a single call and flushed before
+    /// returning, so concurrent appends never interleave.
+    pub async fn append(&self, event: &MarketPriceEvent) -> Result<(), JournalError> {
+        let mut line = Versioned::new(event).to_json()?;
+        line.push('\n');
+
+        let mut file = self.file.lock().await;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// Streams the recorded events emitted within `range`, in file order
+    ///
+    /// Reads a snapshot of the file from the start; events appended while
+    /// the replay runs may or may not be included. Lines that fail to
+    /// decode are yielded as errors and skipped.
+    pub fn replay<R>(
+        &self,
+        range: R,
+    ) -> impl Stream<Item = Result<MarketPriceEvent, JournalError>> + Send + Unpin + 'static
+    where
+        R: RangeBounds<DateTime<Utc>> + Send + 'static,
+    {
+        let path = self.path.clone();
+        stream::once(async move { File::open(path).await })
+            .flat_map(|opened| match opened {
+                Ok(file) => entries(BufReader::new(file).lines()).left_stream(),
+                Err(e) => stream::iter([Err(e.into())]).right_stream(),
+            })
+            .filter(move |entry| {
+                let keep = match entry {
+                    Ok(event) => range.contains(&event.timestamp()),
+                    Err(_) => true,
+                };
+                std::future::ready(keep)
+            })
+            .boxed()
+    }
+}
+
+/// Returns true if the file's last byte is not a newline
+async fn ends_mid_line(path: &Path) -> std::io::Result<bool> {
+    let mut file = File::open(path).await?;
+    if file.metadata().await?.len() == 0 {
+        return Ok(false);
+    }
+    file.seek(SeekFrom::End(-1)).await?;
+    Ok(file.read_u8().await? != b'\n')
+}
+
+/// Decodes journal lines, stopping after the first read error
+fn entries(
+    lines: Lines<BufReader<File>>,
+) -> impl Stream<Item = Result<MarketPriceEvent, JournalError>> {
+    stream::unfold(Some((lines, 0u64)), |state| async move {
+        let (mut lines, mut number) = state?;
+        loop {
+            number += 1;
+            match lines.next_line().await {
+                Ok(Some(line)) if line.trim().is_empty() => continue,
+                Ok(Some(line)) => {
+                    let entry = Versioned::<MarketPriceEvent>::from_json(&line)
+                        .map(Versioned::into_inner)
+                        .map_err(|source| JournalError::Corrupt {
+                            line: number,
+                            source,
+                        });
+                    return Some((entry, Some((lines, number))));
+                }
+                Ok(None) => return None,
+                Err(e) => return Some((Err(e.into()), None)),
+            }
+        }
+    })
+}
+
+#[async_trait]
+impl PriceEventSink for EventJournal {
+    async fn publish(&self, event: &MarketPriceEvent) -> Result<(), SinkError> {
+        self.append(event).await.map_err(|e| match e {
+            JournalError::Encode(e) => SinkError::Encode(e),
+            e => SinkError::Publish(e.to_string()),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "journal"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Asset;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_replays_range_and_reports_torn_lines() {
+        let path = std::env::temp_dir().join(format!("journal-{}.jsonl", Uuid::new_v4()));
+        let journal = EventJournal::open(&path).await.unwrap();
+        let start = Utc::now();
+        let at = |secs| start + chrono::Duration::seconds(secs);
+        for secs in 0..3 {
+            journal
+                .append(&MarketPriceEvent::Heartbeat {
+                    id: Uuid::new_v4(),
+                    asset: Asset::SOL,
+                    last_updated: at(secs),
+                    sequence: secs as u64,
+                    timestamp: at(secs),
+                })
+                .await
+                .unwrap();
+        }
+
+        let replayed: Vec<_> = journal.replay(at(1)..).collect().await;
+        let timestamps: Vec<_> = replayed
+            .iter()
+            .map(|entry| entry.as_ref().unwrap().timestamp())
+            .collect();
+        assert_eq!(timestamps, vec![at(1), at(2)]);
+
+        // A write cut short by a crash
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .unwrap()
+            .write_all(br#"{"schema_version":1,"type":"HEART"#)
+            .await
+            .unwrap();
+        let replayed: Vec<_> = journal.replay(..).collect().await;
+        assert_eq!(replayed.len(), 4);
+        assert!(matches!(
+            replayed[3],
+            Err(JournalError::Corrupt { line: 4, .. })
+        ));
+
+        // Reopening starts the next event on a fresh line
+        let journal = EventJournal::open(&path).await.unwrap();
+        journal
+            .append(&MarketPriceEvent::Heartbeat {
+                id: Uuid::new_v4(),
+                asset: Asset::SOL,
+                last_updated: at(3),
+                sequence: 3,
+                timestamp: at(3),
+            })
+            .await
+            .unwrap();
+        let replayed: Vec<_> = journal.replay(at(3)..).collect().await;
+        assert_eq!(replayed.len(), 2);
+        assert!(replayed[1].is_ok());
+
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+}
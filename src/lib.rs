@@ -33,19 +33,73 @@
 //! # }
 //! ```
 
+pub mod anomaly;
+pub mod blocking;
+pub mod calendar;
+pub mod config;
 pub mod constants;
+pub mod credentials;
+pub mod degraded;
+pub mod derivatives;
 pub mod error;
+pub mod event_id;
+pub mod feeds;
+pub mod fees;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod format;
+pub mod fx;
+pub mod history;
+pub mod hooks;
+pub mod http;
+pub mod index;
+pub mod journal;
 pub mod metrics;
+#[cfg(feature = "uniffi")]
+mod mobile;
+pub mod outage;
+pub mod parse;
+mod poller;
 pub mod provider;
 pub mod providers;
+#[cfg(feature = "python")]
+mod python;
+pub mod quality;
+pub mod read_through;
+pub mod registry;
+pub mod replication;
+pub mod rpc;
+pub mod runtime;
+pub mod schema;
+pub mod self_test;
+pub mod sinks;
+pub mod smoothing;
+pub mod stats;
 pub mod store;
+pub mod subscription;
+pub mod symbols;
+pub mod tasks;
 pub mod tracker;
 pub mod types;
 
 // Re-export commonly used types
-pub use error::{PriceError, ProviderError};
-pub use metrics::ProviderMetrics;
+pub use config::{Environment, TrackerConfig};
+pub use error::{ConfigError, PriceError, ProviderError, SchemaError, SinkError};
+pub use metrics::{
+    AssetMetrics, ConnectionEvent, ConnectionStats, FetchHealth, LatencyHistogramConfig,
+    ProviderMetrics, StatusThresholds, StreamMetrics,
+};
+pub use provider::{MarketPriceProvider, ProviderCapabilities};
+pub use store::PriceStoreReader;
 pub use tracker::MarketPriceTracker;
 pub use types::{
-    Asset, ComponentHealth, HealthStatus, MarketPriceEvent, PriceData, ProviderStatus,
+    Asset, ComponentHealth, DetailedPrices, HealthStatus, MarketPriceEvent, PriceData,
+    PriceSnapshot, ProviderStatus, Quality, SourceInfo, SourceMethod,
 };
+
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
+
+// The UniFFI scaffolding refers to its type tag by crate path
+#[cfg(feature = "uniffi")]
+use mobile::UniFfiTag;
@@ -1,14 +1,45 @@
 //! Provider health metrics collection and reporting
 //!
-//! Tracks latency histograms and success rates for price providers.
+//! Tracks latency histograms and success rates for price providers, the
+//! outcome of their most recent fetches, plus connection lifecycle counters
+//! and update rates for streaming providers.
+//!
+//! Latencies of successful requests are recorded in HDR histograms, which
+//! keep every percentile accurate to the configured number of significant
+//! figures regardless of load. A new histogram is started every
+//! [`LatencyHistogramConfig::rotation_interval`], and histograms older than
+//! [`MAX_METRICS_WINDOW`] are dropped.
 
-use std::collections::VecDeque;
-use std::sync::Arc;
+use crate::error::ProviderError;
+use crate::event_id::EventIdGenerator;
+use crate::runtime::Runtime;
+use crate::types::{Asset, MarketPriceEvent, ProviderStatus};
+use arc_swap::ArcSwapOption;
+use chrono::{DateTime, Utc};
+use hdrhistogram::Histogram;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, mpsc, RwLock};
+
+/// Width of the buckets request and error counts are kept in
+const RATE_BUCKET: Duration = Duration::from_secs(10);
+
+/// How long request and error counts are kept for windowed views
+pub const MAX_METRICS_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Window the per-minute rates in [`MetricsCollector::get_metrics`] cover
+const RATE_WINDOW: Duration = Duration::from_secs(60);
 
-/// Maximum number of samples to keep for metrics calculation
-const MAX_SAMPLES: usize = 100;
+/// Window [`StreamMetrics::updates_per_sec`] is averaged over, in seconds
+const STREAM_RATE_WINDOW_SECS: u64 = 60;
+
+/// Most timed requests the aggregator task records under one lock
+const AGGREGATE_BATCH: usize = 256;
+
+/// Duration and success of one request timed by a [`RequestTimer`]
+type TimedRequest = (Duration, bool);
 
 /// Metrics for a single provider
 #[derive(Debug, Clone)]
@@ -17,14 +48,30 @@ pub struct ProviderMetrics {
     pub provider_name: String,
     /// 50th percentile latency in milliseconds
     pub latency_p50_ms: f64,
+    /// 90th percentile latency in milliseconds
+    pub latency_p90_ms: f64,
+    /// 95th percentile latency in milliseconds
+    pub latency_p95_ms: f64,
     /// 99th percentile latency in milliseconds
     pub latency_p99_ms: f64,
+    /// 99.9th percentile latency in milliseconds
+    pub latency_p999_ms: f64,
     /// Success rate (0.0 to 1.0)
     pub success_rate: f64,
     /// Total number of requests tracked
     pub total_requests: u64,
     /// Number of failed requests
     pub failed_requests: u64,
+    /// Requests per minute over the last minute (or the requested window)
+    pub requests_per_min: f64,
+    /// Failed requests per minute over the last minute (or the requested window)
+    pub errors_per_min: f64,
+    /// Connection lifecycle counters (streaming providers only)
+    pub connection: Option<ConnectionStats>,
+    /// Daily request budget (providers wrapped in a `QuotaProvider` only)
+    pub quota: Option<QuotaUsage>,
+    /// Update rates and gaps (streaming providers only)
+    pub stream: Option<StreamMetrics>,
 }
 
 impl ProviderMetrics {
@@ -33,93 +80,722 @@ impl ProviderMetrics {
         Self {
             provider_name: provider_name.to_string(),
             latency_p50_ms: 0.0,
+            latency_p90_ms: 0.0,
+            latency_p95_ms: 0.0,
             latency_p99_ms: 0.0,
+            latency_p999_ms: 0.0,
             success_rate: 1.0,
             total_requests: 0,
             failed_requests: 0,
+            requests_per_min: 0.0,
+            errors_per_min: 0.0,
+            connection: None,
+            quota: None,
+            stream: None,
         }
     }
 }
 
-/// Internal sample for latency tracking
+/// Update counters for a single asset
+///
+/// Aggregate provider metrics can look healthy while one asset has stopped
+/// updating; these counters make that visible.
 #[derive(Debug, Clone)]
-struct LatencySample {
-    duration_ms: f64,
-    success: bool,
+pub struct AssetMetrics {
+    /// The asset these counters describe
+    pub asset: Asset,
+    /// Updates received from the provider, including rejected ones
+    pub updates_received: u64,
+    /// Updates that passed screening and were stored
+    pub updates_accepted: u64,
+    /// When the last accepted update was stored
+    pub last_update_at: Option<DateTime<Utc>>,
+    /// Time since the last accepted update
+    pub last_update_age: Option<Duration>,
+    /// Polls in a row that returned no price for this asset
+    pub consecutive_misses: u64,
+    /// Polls that returned no price for this asset (lifetime)
+    pub total_misses: u64,
+}
+
+/// Connection lifecycle events emitted by streaming providers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The stream connected successfully
+    Connected,
+    /// The stream disconnected
+    Disconnected {
+        /// Why the stream ended
+        reason: String,
+    },
+    /// A message received on the stream could not be parsed
+    ParseError,
+}
+
+/// Requests used against a provider's daily quota
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaUsage {
+    /// Requests allowed per UTC day
+    pub limit: u64,
+    /// Requests made today
+    pub used: u64,
+    /// Requests left today
+    pub remaining: u64,
+    /// Remaining requests at or below which the quota counts as nearly
+    /// exhausted
+    pub reserve: u64,
+    /// When the count starts over
+    pub resets_at: DateTime<Utc>,
+}
+
+impl QuotaUsage {
+    /// Returns true once only the reserve is left, so load should move to
+    /// other providers
+    pub fn is_nearly_exhausted(&self) -> bool {
+        self.remaining <= self.reserve
+    }
+}
+
+/// Connection lifecycle counters for a streaming provider
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionStats {
+    /// Whether the stream is currently connected
+    pub connected: bool,
+    /// Number of successful connections (lifetime)
+    pub connects: u64,
+    /// Number of disconnections (lifetime)
+    pub disconnects: u64,
+    /// Number of messages that failed to parse (lifetime)
+    pub parse_errors: u64,
+    /// When the stream last connected
+    pub last_connected_at: Option<DateTime<Utc>>,
+    /// Reason for the most recent disconnection
+    pub last_disconnect_reason: Option<String>,
+}
+
+impl ConnectionStats {
+    /// Applies a lifecycle event that happened at `now` to the counters
+    pub fn record(&mut self, event: &ConnectionEvent, now: DateTime<Utc>) {
+        match event {
+            ConnectionEvent::Connected => {
+                self.connected = true;
+                self.connects += 1;
+                self.last_connected_at = Some(now);
+            }
+            ConnectionEvent::Disconnected { reason } => {
+                self.connected = false;
+                self.disconnects += 1;
+                self.last_disconnect_reason = Some(reason.clone());
+            }
+            ConnectionEvent::ParseError => {
+                self.parse_errors += 1;
+            }
+        }
+    }
+}
+
+/// Records connection lifecycle events and fans them out to subscribers
+pub struct ConnectionMonitor {
+    stats: std::sync::RwLock<ConnectionStats>,
+    events_tx: broadcast::Sender<ConnectionEvent>,
+}
+
+impl ConnectionMonitor {
+    /// Creates a new connection monitor
+    pub fn new() -> Self {
+        let (events_tx, _) = broadcast::channel(100);
+        Self {
+            stats: std::sync::RwLock::new(ConnectionStats::default()),
+            events_tx,
+        }
+    }
+
+    /// Records a lifecycle event that happened at `now` and broadcasts it
+    /// to subscribers
+    pub fn record(&self, event: ConnectionEvent, now: DateTime<Utc>) {
+        self.stats.write().unwrap().record(&event, now);
+        let _ = self.events_tx.send(event);
+    }
+
+    /// Returns a snapshot of the current counters
+    pub fn stats(&self) -> ConnectionStats {
+        self.stats.read().unwrap().clone()
+    }
+
+    /// Subscribes to lifecycle events
+    pub fn subscribe(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.events_tx.subscribe()
+    }
+}
+
+impl Default for ConnectionMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Update flow of a streaming provider
+#[derive(Debug, Clone, Default)]
+pub struct StreamMetrics {
+    /// Updates received (lifetime)
+    pub total_updates: u64,
+    /// Updates per second over the last minute, per asset
+    pub updates_per_sec: HashMap<Asset, f64>,
+    /// Longest time between two consecutive updates of the same asset
+    /// (lifetime)
+    pub largest_gap: Option<Duration>,
+    /// Asset the largest gap was seen on
+    pub largest_gap_asset: Option<Asset>,
+    /// Connections after the first one (lifetime)
+    pub reconnects: u64,
+    /// Messages that failed to parse (lifetime)
+    pub parse_errors: u64,
+}
+
+/// Update counts for one asset of a stream
+struct AssetStream {
+    total: u64,
+    last_at: Instant,
+    /// Updates per second since the monitor started, newest last, covering
+    /// at most [`STREAM_RATE_WINDOW_SECS`]
+    per_second: VecDeque<(u64, u64)>,
+}
+
+/// Tracks the updates a streaming provider receives
+///
+/// Reconnects and parse errors are counted by the provider's
+/// [`ConnectionMonitor`] and combined in [`metrics`](Self::metrics).
+pub struct StreamMonitor {
+    started: Instant,
+    assets: std::sync::Mutex<HashMap<Asset, AssetStream>>,
+    largest_gap: std::sync::Mutex<Option<(Duration, Asset)>>,
+}
+
+impl StreamMonitor {
+    /// Creates a new stream monitor
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            assets: std::sync::Mutex::new(HashMap::new()),
+            largest_gap: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Records an update received for `asset`
+    pub fn record_update(&self, asset: Asset) {
+        let now = Instant::now();
+        let second = now.duration_since(self.started).as_secs();
+        let mut assets = self.assets.lock().unwrap();
+        let stream = assets.entry(asset).or_insert_with(|| AssetStream {
+            total: 0,
+            last_at: now,
+            per_second: VecDeque::new(),
+        });
+        if stream.total > 0 {
+            let gap = now.duration_since(stream.last_at);
+            let mut largest_gap = self.largest_gap.lock().unwrap();
+            if largest_gap.is_none_or(|(largest, _)| gap > largest) {
+                *largest_gap = Some((gap, asset));
+            }
+        }
+        stream.total += 1;
+        stream.last_at = now;
+        match stream.per_second.back_mut() {
+            Some((at, count)) if *at == second => *count += 1,
+            _ => stream.per_second.push_back((second, 1)),
+        }
+        while stream
+            .per_second
+            .front()
+            .is_some_and(|(at, _)| second - at >= STREAM_RATE_WINDOW_SECS)
+        {
+            stream.per_second.pop_front();
+        }
+    }
+
+    /// Returns the update flow, with reconnects and parse errors taken
+    /// from `connection`
+    pub fn metrics(&self, connection: &ConnectionStats) -> StreamMetrics {
+        let second = self.started.elapsed().as_secs();
+        // Before a full window has passed, average over the time elapsed
+        let window = (second + 1).min(STREAM_RATE_WINDOW_SECS) as f64;
+        let assets = self.assets.lock().unwrap();
+        let largest_gap = *self.largest_gap.lock().unwrap();
+        StreamMetrics {
+            total_updates: assets.values().map(|stream| stream.total).sum(),
+            updates_per_sec: assets
+                .iter()
+                .map(|(asset, stream)| {
+                    let recent: u64 = stream
+                        .per_second
+                        .iter()
+                        .filter(|(at, _)| second - at < STREAM_RATE_WINDOW_SECS)
+                        .map(|(_, count)| count)
+                        .sum();
+                    (*asset, recent as f64 / window)
+                })
+                .collect(),
+            largest_gap: largest_gap.map(|(gap, _)| gap),
+            largest_gap_asset: largest_gap.map(|(_, asset)| asset),
+            reconnects: connection.connects.saturating_sub(1),
+            parse_errors: connection.parse_errors,
+        }
+    }
+}
+
+impl Default for StreamMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs of fetch outcomes that move a provider between statuses
+///
+/// A healthy provider is degraded after `degraded_after_failures` failed
+/// fetches in a row and unavailable after `unavailable_after_failures` more.
+/// It is only healthy again after `healthy_after_successes` successful
+/// fetches in a row, so a flapping provider does not flip back and forth.
+#[derive(Debug, Clone)]
+pub struct StatusThresholds {
+    /// Failures in a row that degrade a healthy provider
+    pub degraded_after_failures: u64,
+    /// Further failures in a row that make a degraded provider unavailable
+    pub unavailable_after_failures: u64,
+    /// Successes in a row that make a provider healthy again
+    pub healthy_after_successes: u64,
+    /// How long failover passes over an unavailable provider after its last
+    /// failure before trying it in its usual place again
+    pub retry_unavailable_after: Duration,
+}
+
+impl Default for StatusThresholds {
+    fn default() -> Self {
+        Self {
+            degraded_after_failures: 2,
+            unavailable_after_failures: 3,
+            healthy_after_successes: 3,
+            retry_unavailable_after: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Outcome of a provider's recent fetches
+#[derive(Debug, Clone, Serialize)]
+pub struct FetchHealth {
+    /// Provider name
+    pub provider_name: String,
+    /// Status according to the [`StatusThresholds`] in use
+    pub status: ProviderStatus,
+    /// When a fetch last returned at least one price
+    pub last_successful_fetch: Option<DateTime<Utc>>,
+    /// Error of the most recent failed fetch
+    pub last_error: Option<String>,
+    /// When the most recent failed fetch happened
+    pub last_error_at: Option<DateTime<Utc>>,
+    /// Fetches that failed since the last successful one
+    pub consecutive_failures: u64,
+    /// Fetches that succeeded since the last failed one
+    pub consecutive_successes: u64,
+}
+
+impl FetchHealth {
+    /// Creates the health record of a provider that has not fetched yet,
+    /// which counts as healthy
+    pub fn new(provider_name: &str) -> Self {
+        Self {
+            provider_name: provider_name.to_string(),
+            status: ProviderStatus::Healthy,
+            last_successful_fetch: None,
+            last_error: None,
+            last_error_at: None,
+            consecutive_failures: 0,
+            consecutive_successes: 0,
+        }
+    }
+
+    /// Records a fetch that returned prices, or failed with `error`, at `now`
+    ///
+    /// Returns the new status if the fetch changed it.
+    pub fn record(
+        &mut self,
+        error: Option<&ProviderError>,
+        thresholds: &StatusThresholds,
+        now: DateTime<Utc>,
+    ) -> Option<ProviderStatus> {
+        let status = match error {
+            Some(e) => {
+                self.last_error = Some(e.to_string());
+                self.last_error_at = Some(now);
+                self.consecutive_failures += 1;
+                self.consecutive_successes = 0;
+                let unavailable_after =
+                    thresholds.degraded_after_failures + thresholds.unavailable_after_failures;
+                if self.consecutive_failures >= unavailable_after {
+                    ProviderStatus::Unavailable
+                } else if self.consecutive_failures >= thresholds.degraded_after_failures
+                    && self.status == ProviderStatus::Healthy
+                {
+                    ProviderStatus::Degraded
+                } else {
+                    self.status
+                }
+            }
+            None => {
+                self.last_successful_fetch = Some(now);
+                self.consecutive_failures = 0;
+                self.consecutive_successes += 1;
+                if self.consecutive_successes >= thresholds.healthy_after_successes {
+                    ProviderStatus::Healthy
+                } else {
+                    self.status
+                }
+            }
+        };
+        (status != self.status).then(|| {
+            self.status = status;
+            status
+        })
+    }
+
+    /// Returns true while the provider is unavailable and its last failure
+    /// is less than `retry_after` before `now`
+    pub fn is_cooling_down(&self, retry_after: Duration, now: DateTime<Utc>) -> bool {
+        self.status == ProviderStatus::Unavailable
+            && self.last_error_at.is_some_and(|at| {
+                (now - at)
+                    .to_std()
+                    .map_or(true, |elapsed| elapsed < retry_after)
+            })
+    }
+}
+
+/// Settings for the latency histograms kept by [`MetricsCollector`]
+#[derive(Debug, Clone)]
+pub struct LatencyHistogramConfig {
+    /// Significant decimal digits kept per value (1-5); 3 means latencies
+    /// are accurate to 0.1%
+    pub significant_figures: u8,
+    /// How long each histogram collects samples before a new one is started;
+    /// windowed queries have this granularity
+    pub rotation_interval: Duration,
+}
+
+impl Default for LatencyHistogramConfig {
+    fn default() -> Self {
+        Self {
+            significant_figures: 3,
+            rotation_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Successful request latencies recorded during one rotation interval
+struct LatencyWindow {
+    start: Instant,
+    /// Latencies in microseconds
+    histogram: Histogram<u64>,
+}
+
+/// Request and error counts for one [`RATE_BUCKET`]
+#[derive(Debug, Clone)]
+struct RateBucket {
+    start: Instant,
+    requests: u64,
+    failures: u64,
 }
 
 /// Collects and computes metrics for providers
 pub struct MetricsCollector {
     /// Provider name
     provider_name: String,
-    /// Rolling window of latency samples
-    samples: Arc<RwLock<VecDeque<LatencySample>>>,
+    /// Histogram settings
+    config: LatencyHistogramConfig,
+    /// Latency histograms over the last [`MAX_METRICS_WINDOW`], oldest first
+    latencies: Arc<RwLock<VecDeque<LatencyWindow>>>,
     /// Total requests (lifetime)
     total_requests: Arc<RwLock<u64>>,
     /// Failed requests (lifetime)
     failed_requests: Arc<RwLock<u64>>,
+    /// Request counts over the last [`MAX_METRICS_WINDOW`], oldest first
+    buckets: Arc<RwLock<VecDeque<RateBucket>>>,
+    /// Outcome of the most recent fetches
+    fetch_health: Arc<std::sync::RwLock<FetchHealth>>,
+    /// When fetch outcomes change the provider's status
+    status_thresholds: StatusThresholds,
+    /// Where status changes are announced
+    status_events: Option<(
+        broadcast::Sender<MarketPriceEvent>,
+        Arc<dyn EventIdGenerator>,
+    )>,
+    /// Queue of the aggregator task recording [`RequestTimer`]s, once started
+    timed_requests: ArcSwapOption<mpsc::UnboundedSender<TimedRequest>>,
+    /// Clock that fetch outcomes and status changes are timestamped with
+    runtime: Runtime,
 }
 
 impl MetricsCollector {
     /// Creates a new metrics collector for a provider
     pub fn new(provider_name: &str) -> Self {
+        Self::with_config(provider_name, LatencyHistogramConfig::default())
+    }
+
+    /// Creates a metrics collector with custom histogram settings
+    pub fn with_config(provider_name: &str, config: LatencyHistogramConfig) -> Self {
         Self {
             provider_name: provider_name.to_string(),
-            samples: Arc::new(RwLock::new(VecDeque::with_capacity(MAX_SAMPLES))),
+            config,
+            latencies: Arc::new(RwLock::new(VecDeque::new())),
             total_requests: Arc::new(RwLock::new(0)),
             failed_requests: Arc::new(RwLock::new(0)),
+            buckets: Arc::new(RwLock::new(VecDeque::new())),
+            fetch_health: Arc::new(std::sync::RwLock::new(FetchHealth::new(provider_name))),
+            status_thresholds: StatusThresholds::default(),
+            status_events: None,
+            timed_requests: ArcSwapOption::empty(),
+            runtime: Runtime::default(),
         }
     }
 
+    /// Timestamps fetch outcomes and status changes with `runtime`'s clock
+    /// instead of the system clock
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    /// Sets when fetch outcomes change the provider's status
+    pub fn with_status_thresholds(mut self, thresholds: StatusThresholds) -> Self {
+        self.status_thresholds = thresholds;
+        self
+    }
+
+    /// Sends a [`MarketPriceEvent::ProviderStatusChanged`] to `event_tx`
+    /// whenever the provider's status changes
+    pub(crate) fn with_event_sender(
+        mut self,
+        event_tx: broadcast::Sender<MarketPriceEvent>,
+        event_ids: Arc<dyn EventIdGenerator>,
+    ) -> Self {
+        self.status_events = Some((event_tx, event_ids));
+        self
+    }
+
     /// Records a request with its duration and success status
     pub async fn record_request(&self, duration: Duration, success: bool) {
-        let duration_ms = duration.as_secs_f64() * 1000.0;
+        self.record_requests(&[(duration, success)]).await;
+    }
+
+    /// Records several requests, taking each lock once
+    async fn record_requests(&self, requests: &[TimedRequest]) {
+        let now = Instant::now();
+        let failures = requests.iter().filter(|(_, success)| !success).count() as u64;
 
         // Update totals
         {
             let mut total = self.total_requests.write().await;
-            *total += 1;
+            *total += requests.len() as u64;
         }
 
-        if !success {
+        if failures > 0 {
             let mut failed = self.failed_requests.write().await;
-            *failed += 1;
+            *failed += failures;
+        }
+
+        // Failed requests often end in a timeout, which would skew latencies
+        if requests.iter().any(|(_, success)| *success) {
+            let mut latencies = self.latencies.write().await;
+            expire(&mut latencies, now, |w| w.start);
+            let current = match latencies.back_mut() {
+                Some(window)
+                    if now.duration_since(window.start) < self.config.rotation_interval =>
+                {
+                    window
+                }
+                _ => {
+                    latencies.push_back(LatencyWindow {
+                        start: now,
+                        histogram: self.new_histogram(),
+                    });
+                    latencies.back_mut().expect("just pushed")
+                }
+            };
+            for (duration, _) in requests.iter().filter(|(_, success)| *success) {
+                current
+                    .histogram
+                    .saturating_record(duration.as_micros().min(u64::MAX as u128) as u64);
+            }
         }
 
-        // Add sample to rolling window
+        // Count into the current rate bucket, expiring old ones
         {
-            let mut samples = self.samples.write().await;
-            if samples.len() >= MAX_SAMPLES {
-                samples.pop_front();
+            let mut buckets = self.buckets.write().await;
+            expire(&mut buckets, now, |b| b.start);
+            match buckets.back_mut() {
+                Some(bucket) if now.duration_since(bucket.start) < RATE_BUCKET => {
+                    bucket.requests += requests.len() as u64;
+                    bucket.failures += failures;
+                }
+                _ => buckets.push_back(RateBucket {
+                    start: now,
+                    requests: requests.len() as u64,
+                    failures,
+                }),
             }
-            samples.push_back(LatencySample {
-                duration_ms,
-                success,
+        }
+    }
+
+    /// Queues a timed request for the aggregator task, starting the task
+    /// if none is running
+    ///
+    /// A new task is also started if the previous one is gone, e.g. because
+    /// the runtime it ran on shut down.
+    fn record_timed(self: &Arc<Self>, mut request: TimedRequest) {
+        loop {
+            let current = self.timed_requests.load_full();
+            if let Some(tx) = &current {
+                match tx.send(request) {
+                    Ok(()) => return,
+                    Err(mpsc::error::SendError(unsent)) => request = unsent,
+                }
+            }
+
+            // Only the caller that installs the new queue starts its task;
+            // the others retry against the winner's queue
+            let (tx, rx) = mpsc::unbounded_channel();
+            let tx = Arc::new(tx);
+            let previous = self
+                .timed_requests
+                .compare_and_swap(&current, Some(tx.clone()));
+            let installed = match (&*previous, &current) {
+                (Some(previous), Some(current)) => Arc::ptr_eq(previous, current),
+                (None, None) => true,
+                _ => false,
+            };
+            if installed {
+                let _ = tx.send(request);
+                self.runtime
+                    .spawn_named("metrics.aggregate", aggregate(Arc::downgrade(self), rx));
+                return;
+            }
+        }
+    }
+
+    /// Records whether a fetch from `provider_name` returned any price, and
+    /// the error if not
+    ///
+    /// A fetch from a different provider than the last one starts its
+    /// health record over, so a replaced provider's failures are not held
+    /// against its successor.
+    pub fn record_fetch(&self, provider_name: &str, error: Option<&ProviderError>) {
+        let now = self.runtime.now();
+        let changed = {
+            let mut health = self.fetch_health.write().unwrap();
+            if health.provider_name != provider_name {
+                *health = FetchHealth::new(provider_name);
+            }
+            health.record(error, &self.status_thresholds, now)
+        };
+        let Some(status) = changed else {
+            return;
+        };
+        tracing::info!(
+            provider = provider_name,
+            status = ?status,
+            "Provider status changed"
+        );
+        if let Some((event_tx, event_ids)) = &self.status_events {
+            let _ = event_tx.send(MarketPriceEvent::ProviderStatusChanged {
+                id: event_ids.next_id(),
+                provider: provider_name.to_string(),
+                status,
+                timestamp: now,
             });
         }
     }
 
-    /// Computes current metrics from collected samples
+    /// Returns the outcome of the most recent fetches
+    pub fn fetch_health(&self) -> FetchHealth {
+        self.fetch_health.read().unwrap().clone()
+    }
+
+    /// Computes lifetime counters, with latencies over the last
+    /// [`MAX_METRICS_WINDOW`] and rates over the last minute
     pub async fn get_metrics(&self) -> ProviderMetrics {
-        let samples = self.samples.read().await;
         let total = *self.total_requests.read().await;
         let failed = *self.failed_requests.read().await;
+        self.compute(None, total, failed).await
+    }
 
-        if samples.is_empty() {
-            return ProviderMetrics::empty(&self.provider_name);
+    /// Computes metrics over the last `window` only
+    ///
+    /// Counts and rates have 10-second granularity, latencies have the
+    /// histogram rotation interval's granularity, and both cover at most
+    /// [`MAX_METRICS_WINDOW`].
+    pub async fn get_metrics_for(&self, window: Duration) -> ProviderMetrics {
+        let (total, failed) = self.counts_within(window).await;
+        self.compute(Some(window), total, failed).await
+    }
+
+    /// Returns the latency in milliseconds at `percentile` (0-100) over the
+    /// last `window`, or 0 if no successful request was recorded
+    pub async fn latency_percentile(&self, percentile: f64, window: Duration) -> f64 {
+        let histogram = self.merged_latencies(window).await;
+        quantile_ms(&histogram, percentile)
+    }
+
+    /// Clears all samples and counters
+    pub async fn reset(&self) {
+        self.latencies.write().await.clear();
+        self.buckets.write().await.clear();
+        *self.total_requests.write().await = 0;
+        *self.failed_requests.write().await = 0;
+        let mut health = self.fetch_health.write().unwrap();
+        *health = FetchHealth::new(&health.provider_name);
+    }
+
+    fn new_histogram(&self) -> Histogram<u64> {
+        let sigfig = self.config.significant_figures.clamp(1, 5);
+        // Up to an hour, in microseconds; longer values are clamped
+        Histogram::new_with_bounds(1, 3_600_000_000, sigfig).expect("valid histogram bounds")
+    }
+
+    /// Combines the latency histograms overlapping `window`
+    async fn merged_latencies(&self, window: Duration) -> Histogram<u64> {
+        let now = Instant::now();
+        let mut merged = self.new_histogram();
+        for latency in self.latencies.read().await.iter() {
+            if now.duration_since(latency.start) < window + self.config.rotation_interval {
+                // Both histograms share the same bounds, so adding cannot fail
+                let _ = merged.add(&latency.histogram);
+            }
         }
+        merged
+    }
 
-        // Extract successful latencies for percentile calculation
-        let mut latencies: Vec<f64> = samples
+    /// Sums the request and failure counts of buckets overlapping `window`
+    async fn counts_within(&self, window: Duration) -> (u64, u64) {
+        let now = Instant::now();
+        self.buckets
+            .read()
+            .await
             .iter()
-            .filter(|s| s.success)
-            .map(|s| s.duration_ms)
-            .collect();
+            .filter(|b| now.duration_since(b.start) < window + RATE_BUCKET)
+            .fold((0, 0), |(requests, failures), b| {
+                (requests + b.requests, failures + b.failures)
+            })
+    }
 
-        latencies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    async fn compute(&self, window: Option<Duration>, total: u64, failed: u64) -> ProviderMetrics {
+        let rate_window = window.unwrap_or(RATE_WINDOW).min(MAX_METRICS_WINDOW);
+        let (rate_requests, rate_failures) = match window {
+            Some(_) => (total, failed),
+            None => self.counts_within(rate_window).await,
+        };
+        let minutes = rate_window.as_secs_f64().max(1.0) / 60.0;
 
-        let p50 = percentile(&latencies, 50.0);
-        let p99 = percentile(&latencies, 99.0);
+        let latencies = self
+            .merged_latencies(window.unwrap_or(MAX_METRICS_WINDOW))
+            .await;
 
         let success_rate = if total > 0 {
             (total - failed) as f64 / total as f64
@@ -129,17 +805,43 @@ impl MetricsCollector {
 
         ProviderMetrics {
             provider_name: self.provider_name.clone(),
-            latency_p50_ms: p50,
-            latency_p99_ms: p99,
+            latency_p50_ms: quantile_ms(&latencies, 50.0),
+            latency_p90_ms: quantile_ms(&latencies, 90.0),
+            latency_p95_ms: quantile_ms(&latencies, 95.0),
+            latency_p99_ms: quantile_ms(&latencies, 99.0),
+            latency_p999_ms: quantile_ms(&latencies, 99.9),
             success_rate,
             total_requests: total,
             failed_requests: failed,
+            requests_per_min: rate_requests as f64 / minutes,
+            errors_per_min: rate_failures as f64 / minutes,
+            connection: None,
+            quota: None,
+            stream: None,
         }
     }
 }
 
+/// Drops entries that started more than [`MAX_METRICS_WINDOW`] ago
+fn expire<T>(entries: &mut VecDeque<T>, now: Instant, start: impl Fn(&T) -> Instant) {
+    while entries
+        .front()
+        .is_some_and(|e| now.duration_since(start(e)) > MAX_METRICS_WINDOW)
+    {
+        entries.pop_front();
+    }
+}
+
+/// Reads a percentile (0-100) from a microsecond histogram, in milliseconds
+fn quantile_ms(histogram: &Histogram<u64>, percentile: f64) -> f64 {
+    if histogram.is_empty() {
+        return 0.0;
+    }
+    histogram.value_at_quantile(percentile / 100.0) as f64 / 1000.0
+}
+
 /// Calculate percentile from sorted values
-fn percentile(sorted_values: &[f64], p: f64) -> f64 {
+pub(crate) fn percentile(sorted_values: &[f64], p: f64) -> f64 {
     if sorted_values.is_empty() {
         return 0.0;
     }
@@ -148,7 +850,27 @@ fn percentile(sorted_values: &[f64], p: f64) -> f64 {
     sorted_values[idx.min(sorted_values.len() - 1)]
 }
 
+/// Records the requests queued by a collector's [`RequestTimer`]s, in
+/// batches, until the collector is dropped
+async fn aggregate(
+    collector: Weak<MetricsCollector>,
+    mut rx: mpsc::UnboundedReceiver<TimedRequest>,
+) {
+    let mut batch = Vec::with_capacity(AGGREGATE_BATCH);
+    while rx.recv_many(&mut batch, AGGREGATE_BATCH).await > 0 {
+        let Some(collector) = collector.upgrade() else {
+            return;
+        };
+        collector.record_requests(&batch).await;
+        batch.clear();
+    }
+}
+
 /// RAII guard for timing requests
+///
+/// Dropping the timer queues its duration and outcome on a channel, without
+/// blocking or spawning; one aggregator task per collector, started by the
+/// first timer, records them shortly after.
 pub struct RequestTimer {
     start: Instant,
     collector: Arc<MetricsCollector>,
@@ -173,14 +895,8 @@ impl RequestTimer {
 
 impl Drop for RequestTimer {
     fn drop(&mut self) {
-        let duration = self.start.elapsed();
-        let collector = self.collector.clone();
-        let success = self.success;
-
-        // Spawn a task to record the metric asynchronously
-        tokio::spawn(async move {
-            collector.record_request(duration, success).await;
-        });
+        self.collector
+            .record_timed((self.start.elapsed(), self.success));
     }
 }
 
@@ -211,10 +927,167 @@ mod tests {
         assert!(metrics.success_rate > 0.6 && metrics.success_rate < 0.7);
     }
 
+    #[tokio::test]
+    async fn test_windowed_metrics_and_reset() {
+        let collector = MetricsCollector::new("test");
+        collector
+            .record_request(Duration::from_millis(100), true)
+            .await;
+        collector
+            .record_request(Duration::from_millis(300), false)
+            .await;
+
+        let recent = collector.get_metrics_for(Duration::from_secs(120)).await;
+        assert_eq!(recent.total_requests, 2);
+        assert_eq!(recent.failed_requests, 1);
+        assert_eq!(recent.requests_per_min, 1.0);
+        assert_eq!(recent.errors_per_min, 0.5);
+        // Only successful requests count towards latency
+        assert!((recent.latency_p999_ms - 100.0).abs() < 0.1);
+
+        let lifetime = collector.get_metrics().await;
+        assert_eq!(lifetime.requests_per_min, 2.0);
+
+        collector.reset().await;
+        let metrics = collector.get_metrics().await;
+        assert_eq!(metrics.total_requests, 0);
+        assert_eq!(metrics.requests_per_min, 0.0);
+        assert_eq!(metrics.success_rate, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_histogram_percentiles() {
+        let collector = MetricsCollector::new("test");
+        for ms in 1..=1000 {
+            collector
+                .record_request(Duration::from_millis(ms), true)
+                .await;
+        }
+
+        let metrics = collector.get_metrics().await;
+        let close = |actual: f64, expected: f64| (actual - expected).abs() <= expected * 0.002;
+        assert!(close(metrics.latency_p50_ms, 500.0));
+        assert!(close(metrics.latency_p90_ms, 900.0));
+        assert!(close(metrics.latency_p99_ms, 990.0));
+        assert!(close(metrics.latency_p999_ms, 1000.0));
+        let p95 = collector
+            .latency_percentile(95.0, Duration::from_secs(60))
+            .await;
+        assert!(close(p95, 950.0));
+    }
+
+    #[test]
+    fn test_connection_stats() {
+        let monitor = ConnectionMonitor::new();
+        let mut rx = monitor.subscribe();
+
+        let now = Utc::now();
+        monitor.record(ConnectionEvent::Connected, now);
+        monitor.record(ConnectionEvent::ParseError, now);
+        monitor.record(
+            ConnectionEvent::Disconnected {
+                reason: "eof".to_string(),
+            },
+            now,
+        );
+
+        let stats = monitor.stats();
+        assert!(!stats.connected);
+        assert_eq!(stats.connects, 1);
+        assert_eq!(stats.disconnects, 1);
+        assert_eq!(stats.parse_errors, 1);
+        assert_eq!(stats.last_disconnect_reason.as_deref(), Some("eof"));
+        assert_eq!(rx.try_recv().unwrap(), ConnectionEvent::Connected);
+    }
+
+    #[test]
+    fn test_stream_metrics() {
+        let monitor = StreamMonitor::new();
+        monitor.record_update(Asset::SOL);
+        monitor.record_update(Asset::BTC);
+        std::thread::sleep(Duration::from_millis(20));
+        monitor.record_update(Asset::SOL);
+        monitor.record_update(Asset::SOL);
+
+        let connection = ConnectionStats {
+            connects: 3,
+            parse_errors: 2,
+            ..Default::default()
+        };
+        let metrics = monitor.metrics(&connection);
+        assert_eq!(metrics.total_updates, 4);
+        assert_eq!(metrics.updates_per_sec[&Asset::SOL], 3.0);
+        assert!(metrics.largest_gap.unwrap() >= Duration::from_millis(20));
+        assert_eq!(metrics.largest_gap_asset, Some(Asset::SOL));
+        assert_eq!(metrics.reconnects, 2);
+        assert_eq!(metrics.parse_errors, 2);
+    }
+
     #[test]
     fn test_percentile() {
         let values = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
         assert_eq!(percentile(&values, 50.0), 6.0);
         assert_eq!(percentile(&values, 99.0), 10.0);
     }
+
+    #[test]
+    fn test_status_changes_with_hysteresis() {
+        let thresholds = StatusThresholds::default();
+        let mut health = FetchHealth::new("test");
+        let error = ProviderError::Timeout;
+        let now = Utc::now();
+
+        assert_eq!(health.record(Some(&error), &thresholds, now), None);
+        assert_eq!(
+            health.record(Some(&error), &thresholds, now),
+            Some(ProviderStatus::Degraded)
+        );
+        // A lone success neither restores the provider nor spares it the
+        // full run of failures
+        assert_eq!(health.record(None, &thresholds, now), None);
+        for _ in 0..4 {
+            assert_eq!(health.record(Some(&error), &thresholds, now), None);
+        }
+        assert_eq!(
+            health.record(Some(&error), &thresholds, now),
+            Some(ProviderStatus::Unavailable)
+        );
+        assert!(health.is_cooling_down(thresholds.retry_unavailable_after, now));
+        let later = now + chrono::Duration::from_std(thresholds.retry_unavailable_after).unwrap();
+        assert!(!health.is_cooling_down(thresholds.retry_unavailable_after, later));
+
+        assert_eq!(health.record(None, &thresholds, now), None);
+        assert_eq!(health.record(None, &thresholds, now), None);
+        assert_eq!(
+            health.record(None, &thresholds, now),
+            Some(ProviderStatus::Healthy)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_timers_share_one_aggregator() {
+        let collector = Arc::new(MetricsCollector::new("test"));
+        drop(RequestTimer::new(collector.clone()));
+        let queue = collector.timed_requests.load_full().unwrap();
+        for i in 1..500 {
+            let mut timer = RequestTimer::new(collector.clone());
+            if i % 5 != 0 {
+                timer.mark_success();
+            }
+        }
+        for _ in 0..1000 {
+            if collector.get_metrics().await.total_requests == 500 {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+
+        let metrics = collector.get_metrics().await;
+        assert_eq!(metrics.total_requests, 500);
+        assert_eq!(metrics.failed_requests, 100);
+        assert!(Arc::ptr_eq(
+            &queue,
+            &collector.timed_requests.load_full().unwrap()
+        ));
+    }
 }
@@ -0,0 +1,166 @@
+//! UniFFI bindings for Kotlin and Swift (`uniffi` feature)
+//!
+//! Implements the interface declared in `src/market_price_sdk.udl` so
+//! monitoring apps can embed a tracker and show the same prices the
+//! trading services see. Only request/response providers are offered:
+//! mobile networks drop long-lived streams, and a dashboard refreshing
+//! every poll interval is fresh enough.
+//!
+//! The tracker runs on the [`blocking`](crate::blocking) facade, so every
+//! method blocks; call them off the main thread (a coroutine on
+//! `Dispatchers.IO`, a Swift `Task`).
+
+use crate::{
+    blocking::MarketPriceTracker,
+    config::TrackerConfig,
+    error::PriceError,
+    provider::MarketPriceProvider,
+    providers::{CoinGeckoProvider, FailoverProvider, HyperliquidProvider},
+    types::{Asset, HealthStatus, PriceData},
+};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Errors surfaced to Kotlin and Swift; only the message crosses over
+#[derive(Debug, thiserror::Error)]
+pub enum MobileError {
+    #[error("{0}")]
+    UnknownAsset(String),
+    #[error("{0}")]
+    NotAvailable(String),
+    #[error("{0}")]
+    Stale(String),
+    #[error("{0}")]
+    Provider(String),
+}
+
+impl From<PriceError> for MobileError {
+    fn from(e: PriceError) -> Self {
+        match e {
+            PriceError::NotAvailable { .. } => Self::NotAvailable(e.to_string()),
+            PriceError::Stale { .. } => Self::Stale(e.to_string()),
+            _ => Self::Provider(e.to_string()),
+        }
+    }
+}
+
+/// Provider a [`MobileTracker`] polls
+pub enum RestProvider {
+    CoinGecko,
+    Hyperliquid,
+    Failover,
+}
+
+pub struct MobilePrice {
+    pub asset: String,
+    pub price_usd: f64,
+    pub price_change_24h: Option<f64>,
+    pub last_updated: SystemTime,
+    pub source: String,
+    pub confidence: Option<f64>,
+    pub sequence: u64,
+}
+
+impl From<PriceData> for MobilePrice {
+    fn from(price: PriceData) -> Self {
+        Self {
+            asset: price.asset.symbol().to_string(),
+            price_usd: price.price_usd,
+            price_change_24h: price.price_change_24h,
+            last_updated: price.last_updated.into(),
+            source: price.source,
+            confidence: price.confidence,
+            sequence: price.sequence,
+        }
+    }
+}
+
+pub struct MobileHealth {
+    pub healthy: bool,
+    pub status: String,
+    pub message: Option<String>,
+    pub provider: String,
+    pub last_checked: SystemTime,
+}
+
+/// Tracker polling a REST provider in the background
+pub struct MobileTracker {
+    inner: MarketPriceTracker,
+}
+
+impl MobileTracker {
+    pub fn new(provider: RestProvider) -> Result<Self, MobileError> {
+        let provider_error = |e: crate::error::ProviderError| MobileError::Provider(e.to_string());
+        let provider: Arc<dyn MarketPriceProvider> = match provider {
+            RestProvider::CoinGecko => Arc::new(CoinGeckoProvider::new().map_err(provider_error)?),
+            RestProvider::Hyperliquid => {
+                Arc::new(HyperliquidProvider::new().map_err(provider_error)?)
+            }
+            RestProvider::Failover => Arc::new(FailoverProvider::new(vec![
+                Arc::new(HyperliquidProvider::new().map_err(provider_error)?),
+                Arc::new(CoinGeckoProvider::new().map_err(provider_error)?),
+            ])),
+        };
+        Ok(Self {
+            inner: MarketPriceTracker::start_with(provider, TrackerConfig::default()),
+        })
+    }
+
+    pub fn get_price(&self, asset: &str) -> Result<MobilePrice, MobileError> {
+        let asset = asset.parse::<Asset>().map_err(MobileError::UnknownAsset)?;
+        Ok(self.inner.get_price(asset)?.into())
+    }
+
+    pub fn get_all_prices(&self) -> Vec<MobilePrice> {
+        let mut prices: Vec<MobilePrice> = self
+            .inner
+            .get_all_prices()
+            .into_values()
+            .map(Into::into)
+            .collect();
+        prices.sort_by(|a, b| a.asset.cmp(&b.asset));
+        prices
+    }
+
+    pub fn refresh_now(&self) -> Result<(), MobileError> {
+        self.inner
+            .refresh_now()
+            .map_err(|e| MobileError::Provider(e.to_string()))
+    }
+
+    pub fn health(&self) -> MobileHealth {
+        let health = self.inner.health_check();
+        MobileHealth {
+            healthy: matches!(health.status, HealthStatus::Healthy),
+            status: format!("{:?}", health.status),
+            message: health.message,
+            provider: self.inner.provider_name().to_string(),
+            last_checked: health.last_checked.into(),
+        }
+    }
+
+    pub fn provider_name(&self) -> String {
+        self.inner.provider_name().to_string()
+    }
+
+    pub fn shutdown(&self) {
+        self.inner.shutdown();
+    }
+}
+
+pub fn supported_assets() -> Vec<String> {
+    Asset::all()
+        .iter()
+        .map(|asset| asset.symbol().to_string())
+        .collect()
+}
+
+pub(crate) use scaffolding::UniFfiTag;
+
+/// Generated from `market_price_sdk.udl` by the build script
+#[allow(clippy::empty_line_after_doc_comments)]
+mod scaffolding {
+    use super::*;
+
+    uniffi::include_scaffolding!("market_price_sdk");
+}
@@ -0,0 +1,62 @@
+//! Stale grace during short provider outages
+//!
+//! A price normally goes stale the moment it is older than its asset's
+//! threshold. When the provider drops out for a poll or two, prices flap
+//! between fresh and stale, and every consumer sees the blip. With
+//! [`TrackerConfig::outage_grace`](crate::TrackerConfig::outage_grace) set,
+//! an asset whose polls have been failing for less than
+//! [`OutageGrace::window`] has its stale threshold extended by
+//! [`OutageGrace::extension`]. Once the outage has lasted longer than the
+//! window, the normal threshold applies again and reads fail as stale.
+//!
+//! An outage starts at the first poll that returns no price for the asset
+//! and ends at the next one that does. Streamed updates do not end it.
+//!
+//! # Example
+//! ```
+//! use market_price_sdk::outage::OutageGrace;
+//! use market_price_sdk::TrackerConfig;
+//! use std::time::Duration;
+//!
+//! let config = TrackerConfig {
+//!     outage_grace: Some(OutageGrace {
+//!         window: Duration::from_secs(300),
+//!         extension: Duration::from_secs(60),
+//!     }),
+//!     ..Default::default()
+//! };
+//! ```
+
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// How much staleness is tolerated while the provider is failing
+#[derive(Debug, Clone)]
+pub struct OutageGrace {
+    /// How long into an outage the extension applies
+    pub window: Duration,
+    /// Added to the asset's stale threshold during the window
+    pub extension: Duration,
+}
+
+impl Default for OutageGrace {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(300),
+            extension: Duration::from_secs(60),
+        }
+    }
+}
+
+impl OutageGrace {
+    /// Extension of the stale threshold at `now` for an outage that began
+    /// at `since`, or zero once it has outlasted the window
+    pub fn extension_at(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+        let elapsed = (now - since).to_std().unwrap_or_default();
+        if elapsed < self.window {
+            self.extension
+        } else {
+            Duration::ZERO
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Number decoding shared by the provider response parsers
+//!
+//! Provider payloads are decoded in two steps: the envelope into one raw
+//! JSON fragment per entry, then each entry on its own. Numbers inside a
+//! fragment are only read in the second step, so an entry with a
+//! malformed, truncated or out-of-range value fails its own asset rather
+//! than the whole response.
+//!
+//! Prices arrive as JSON numbers, as decimal strings (Hyperliquid) or as
+//! an integer mantissa with a base-10 exponent (Pyth). The helpers here
+//! turn all three into a finite `f64` or a [`ProviderError::InvalidResponse`];
+//! none of them panic or let `NaN` or infinity through.
+//!
+//! Providers read prices through [`price`], [`fixed_point_price`] or
+//! [`check_price`], which also reject zero and negative values. A value
+//! that reads as a number but cannot be a price fails with
+//! [`ProviderError::InvalidPrice`], so a `"0"` mid never reaches a
+//! division downstream.
+
+use crate::error::ProviderError;
+use crate::types::Asset;
+use serde_json::value::RawValue;
+
+/// Largest exponent magnitude accepted for fixed-point prices; anything
+/// beyond it is a corrupt field rather than a price
+pub const MAX_EXPONENT: i32 = 30;
+
+/// Parses a decimal string such as `"145.23"` or `"1e-5"`
+///
+/// Rejects empty strings, `NaN`, infinities and values too large for an
+/// `f64`.
+pub fn decimal(raw: &str) -> Result<f64, ProviderError> {
+    let trimmed = raw.trim();
+    // f64's FromStr also accepts "NaN", "inf" and "infinity"
+    let numeric = trimmed
+        .bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'-' | b'+' | b'e' | b'E'));
+    match trimmed.parse::<f64>() {
+        Ok(value) if numeric && value.is_finite() => Ok(value),
+        _ => Err(invalid(raw)),
+    }
+}
+
+/// Parses a JSON number, or a decimal string, from a raw JSON fragment
+pub fn number(raw: &RawValue) -> Result<f64, ProviderError> {
+    if let Ok(text) = serde_json::from_str::<String>(raw.get()) {
+        return decimal(&text);
+    }
+    match serde_json::from_str::<f64>(raw.get()) {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(invalid(raw.get())),
+    }
+}
+
+/// Converts a fixed-point value, an integer mantissa times `10^expo`
+///
+/// The mantissa must be an optionally signed run of digits; exponents
+/// beyond [`MAX_EXPONENT`] are rejected.
+pub fn fixed_point(mantissa: &str, expo: i32) -> Result<f64, ProviderError> {
+    let digits = mantissa.strip_prefix('-').unwrap_or(mantissa);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid(mantissa));
+    }
+    if expo.unsigned_abs() > MAX_EXPONENT.unsigned_abs() {
+        return Err(ProviderError::InvalidResponse(format!(
+            "Exponent {} out of range",
+            expo
+        )));
+    }
+
+    // Parsing "<mantissa>e<expo>" rounds once, where multiplying by
+    // 10^expo would round twice
+    let value = format!("{}e{}", mantissa, expo)
+        .parse::<f64>()
+        .map_err(|_| invalid(mantissa))?;
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(invalid(mantissa))
+    }
+}
+
+/// Parses a price for `asset` from a raw JSON fragment
+pub fn price(asset: Asset, raw: &RawValue) -> Result<f64, ProviderError> {
+    let text = serde_json::from_str::<String>(raw.get()).unwrap_or_else(|_| raw.get().to_string());
+    checked(asset, &text, number(raw))
+}
+
+/// Converts a fixed-point price for `asset`, as [`fixed_point`] does
+pub fn fixed_point_price(asset: Asset, mantissa: &str, expo: i32) -> Result<f64, ProviderError> {
+    checked(
+        asset,
+        &format!("{}e{}", mantissa, expo),
+        fixed_point(mantissa, expo),
+    )
+}
+
+/// Checks a price computed by the provider itself, e.g. from pool reserves
+pub fn check_price(asset: Asset, value: f64) -> Result<f64, ProviderError> {
+    checked(asset, &value.to_string(), Ok(value))
+}
+
+/// Turns a parsed value into a price, keeping syntax errors as they are
+fn checked(
+    asset: Asset,
+    raw: &str,
+    parsed: Result<f64, ProviderError>,
+) -> Result<f64, ProviderError> {
+    match parsed {
+        Ok(value) if value.is_finite() && value > 0.0 => Ok(value),
+        Ok(_) => Err(invalid_price(asset, raw)),
+        // Reads as a float, just not a finite one: NaN, infinity, overflow
+        Err(_) if raw.trim().parse::<f64>().is_ok() => Err(invalid_price(asset, raw)),
+        Err(e) => Err(e),
+    }
+}
+
+fn invalid_price(asset: Asset, raw: &str) -> ProviderError {
+    ProviderError::InvalidPrice {
+        asset,
+        raw: shown(raw),
+    }
+}
+
+/// Error for a value that is not a usable number
+fn invalid(raw: &str) -> ProviderError {
+    ProviderError::InvalidResponse(format!("Not a valid number: {:?}", shown(raw)))
+}
+
+/// Truncates a raw value for error messages
+fn shown(raw: &str) -> String {
+    const MAX_SHOWN: usize = 64;
+    raw.chars().take(MAX_SHOWN).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_rejects_non_finite_and_malformed() {
+        assert_eq!(decimal(" 145.23 ").unwrap(), 145.23);
+        for raw in [
+            "",
+            "NaN",
+            "nan",
+            "inf",
+            "-Infinity",
+            "1e400",
+            "0x10",
+            "12,5",
+        ] {
+            assert!(decimal(raw).is_err(), "{raw:?} accepted");
+        }
+
+        let raw = |json: &str| RawValue::from_string(json.to_string()).unwrap();
+        assert_eq!(number(&raw("150.5")).unwrap(), 150.5);
+        assert_eq!(number(&raw(r#""150.5""#)).unwrap(), 150.5);
+        assert!(number(&raw("1e400")).is_err());
+        assert!(number(&raw("null")).is_err());
+
+        assert!((fixed_point("14523000000", -8).unwrap() - 145.23).abs() < 1e-9);
+        assert!(fixed_point("1", 400).is_err());
+        assert!(fixed_point("", -8).is_err());
+        assert!(fixed_point("12.5", -8).is_err());
+    }
+
+    #[test]
+    fn test_rejects_unusable_prices() {
+        let raw = |json: &str| RawValue::from_string(json.to_string()).unwrap();
+        assert_eq!(price(Asset::SOL, &raw(r#""150.5""#)).unwrap(), 150.5);
+        for json in [r#""0""#, "0", "-1.5", r#""NaN""#, r#""inf""#, "1e400"] {
+            assert!(
+                matches!(
+                    price(Asset::SOL, &raw(json)),
+                    Err(ProviderError::InvalidPrice {
+                        asset: Asset::SOL,
+                        ..
+                    })
+                ),
+                "{json} accepted"
+            );
+        }
+        assert!(matches!(
+            price(Asset::SOL, &raw("null")),
+            Err(ProviderError::InvalidResponse(_))
+        ));
+
+        assert!(fixed_point_price(Asset::BTC, "0", -8).is_err());
+        assert!(check_price(Asset::SOL, f64::NAN).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn decimal_never_panics_or_returns_non_finite(raw in any::<String>()) {
+            if let Ok(value) = decimal(&raw) {
+                prop_assert!(value.is_finite());
+            }
+        }
+
+        #[test]
+        fn decimal_round_trips_finite_floats(value in any::<f64>().prop_filter("finite", |v| v.is_finite())) {
+            prop_assert_eq!(decimal(&value.to_string()).unwrap(), value);
+        }
+
+        #[test]
+        fn fixed_point_matches_scaled_mantissa(mantissa in any::<i64>(), expo in -MAX_EXPONENT..=MAX_EXPONENT) {
+            let value = fixed_point(&mantissa.to_string(), expo).unwrap();
+            let expected = mantissa as f64 * 10f64.powi(expo);
+            prop_assert!((value - expected).abs() <= expected.abs() * 1e-12);
+        }
+
+        #[test]
+        fn fixed_point_never_panics(mantissa in "-?[0-9]{0,400}", expo in any::<i32>()) {
+            if let Ok(value) = fixed_point(&mantissa, expo) {
+                prop_assert!(value.is_finite());
+            }
+        }
+    }
+}
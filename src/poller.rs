@@ -0,0 +1,131 @@
+//! Background polling shared by the fee and derivatives trackers
+//!
+//! A [`Poller`] repeats one fetch on a fixed cadence, merging each result
+//! into state readers consult, and judges staleness by its runtime's
+//! clock.
+
+use crate::{
+    error::{PriceError, ProviderError},
+    runtime::Runtime,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// The fetch a [`Poller`] repeats
+#[async_trait]
+pub(crate) trait Poll: Send + Sync + 'static {
+    /// Data kept between polls
+    type State: Send + Sync + 'static;
+
+    /// Fetches once and merges the result into `state`
+    async fn poll(&self, state: &RwLock<Self::State>) -> Result<(), ProviderError>;
+
+    /// Returns the name of the provider polled
+    fn provider_name(&self) -> &'static str;
+}
+
+/// Cadence and staleness rule of a [`Poller`]
+pub(crate) struct PollerConfig {
+    /// Name the polling task is registered under in [`crate::tasks`]
+    pub task_name: &'static str,
+    /// How often to poll
+    pub refresh_interval: Duration,
+    /// How old data may be before reads fail as stale
+    pub stale_threshold: Duration,
+    /// Executor and clock used for polling and staleness
+    pub runtime: Runtime,
+}
+
+/// Polls a [`Poll`] source in the background until shut down
+pub(crate) struct Poller<P: Poll> {
+    source: Arc<P>,
+    config: PollerConfig,
+    state: Arc<RwLock<P::State>>,
+    shutdown_tx: broadcast::Sender<()>,
+}
+
+impl<P: Poll> Poller<P> {
+    pub(crate) fn new(source: P, state: P::State, config: PollerConfig) -> Self {
+        let (shutdown_tx, _) = broadcast::channel(1);
+        Self {
+            source: Arc::new(source),
+            config,
+            state: Arc::new(RwLock::new(state)),
+            shutdown_tx,
+        }
+    }
+
+    /// Starts the background polling task
+    pub(crate) fn start(&self) {
+        let source = self.source.clone();
+        let state = self.state.clone();
+        let task_name = self.config.task_name;
+        let interval = self.config.refresh_interval;
+        let runtime = self.config.runtime.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        self.config.runtime.spawn_named(task_name, async move {
+            tracing::info!(
+                task = task_name,
+                provider = source.provider_name(),
+                refresh_interval_secs = interval.as_secs(),
+                "Starting polling task"
+            );
+
+            loop {
+                if let Err(e) = source.poll(&state).await {
+                    tracing::warn!(task = task_name, error = %e, "Poll failed");
+                }
+
+                tokio::select! {
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!(task = task_name, "Polling task shutting down");
+                        break;
+                    }
+                    _ = runtime.sleep(interval) => {}
+                }
+            }
+        });
+    }
+
+    /// Polls immediately, outside the cadence
+    pub(crate) async fn refresh_now(&self) -> Result<(), ProviderError> {
+        self.source.poll(&self.state).await
+    }
+
+    /// Reads the polled state
+    pub(crate) fn read<R>(&self, f: impl FnOnce(&P::State) -> R) -> R {
+        f(&self.state.read().expect("poller lock poisoned"))
+    }
+
+    /// Fails as stale, naming `what`, if data updated at `last_updated` is
+    /// older than the stale threshold
+    pub(crate) fn check_fresh(
+        &self,
+        what: &str,
+        last_updated: DateTime<Utc>,
+    ) -> Result<(), PriceError> {
+        let age = self
+            .config
+            .runtime
+            .now()
+            .signed_duration_since(last_updated);
+        let age = Duration::from_secs(age.num_seconds().max(0) as u64);
+        if age > self.config.stale_threshold {
+            return Err(PriceError::stale(what, age));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn source(&self) -> &P {
+        &self.source
+    }
+
+    /// Stops the background polling task
+    pub(crate) fn shutdown(&self) {
+        let _ = self.shutdown_tx.send(());
+    }
+}
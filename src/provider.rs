@@ -2,14 +2,157 @@
 
 use crate::{
     error::ProviderError,
+    http,
+    metrics::{ConnectionStats, FetchHealth, QuotaUsage, StreamMetrics},
     store::MarketPriceStore,
-    types::{Asset, PriceData},
+    types::{Asset, MarketPriceEvent, PriceData},
 };
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::broadcast;
 
+/// What a provider can serve, so callers can route requests up front
+/// instead of discovering gaps through failed fetches
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderCapabilities {
+    /// Assets the provider can price
+    pub supported_assets: Vec<Asset>,
+    /// Whether prices carry `price_change_24h`
+    pub change_24h: bool,
+    /// Whether the provider reports 24h trading volume
+    pub volume_24h: bool,
+    /// Whether prices are pushed through
+    /// [`start_streaming`](MarketPriceProvider::start_streaming)
+    pub streaming: bool,
+    /// Requests per minute the upstream API allows, if known
+    pub max_requests_per_minute: Option<u32>,
+    /// Most assets a single fetch can carry; `None` if unbounded
+    pub max_batch_size: Option<usize>,
+}
+
+impl Default for ProviderCapabilities {
+    fn default() -> Self {
+        Self {
+            supported_assets: Asset::crypto().collect(),
+            change_24h: false,
+            volume_24h: false,
+            streaming: false,
+            max_requests_per_minute: None,
+            max_batch_size: None,
+        }
+    }
+}
+
+impl ProviderCapabilities {
+    /// Returns true if the provider can price `asset`
+    pub fn supports(&self, asset: Asset) -> bool {
+        self.supported_assets.contains(&asset)
+    }
+}
+
+/// The stricter of two optional limits, where `None` means unlimited
+pub(crate) fn tighter<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Runs a fetch under an optional deadline, failing with
+/// [`ProviderError::Timeout`] if it has already passed
+pub(crate) async fn within_deadline<T>(
+    deadline: Option<Instant>,
+    fetch: impl Future<Output = Result<T, ProviderError>>,
+) -> Result<T, ProviderError> {
+    match deadline {
+        Some(deadline) if Instant::now() >= deadline => Err(ProviderError::Timeout),
+        Some(deadline) => http::with_deadline(deadline, fetch).await,
+        None => fetch.await,
+    }
+}
+
+/// Splits a batch result into one result per requested asset
+///
+/// Assets missing from a successful batch fail with
+/// [`ProviderError::MissingPrice`]; a failed batch fails every asset.
+pub fn per_asset(
+    assets: &[Asset],
+    result: Result<HashMap<Asset, PriceData>, ProviderError>,
+) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+    match result {
+        Ok(mut prices) => assets
+            .iter()
+            .map(|asset| {
+                let price = prices
+                    .remove(asset)
+                    .ok_or_else(|| ProviderError::MissingPrice(asset.symbol().to_string()));
+                (*asset, price)
+            })
+            .collect(),
+        Err(e) => assets
+            .iter()
+            .map(|asset| (*asset, Err(e.replicate())))
+            .collect(),
+    }
+}
+
+/// Fetches each asset with its own request, keeping at most
+/// `concurrency` requests in flight
+///
+/// For providers without a batch endpoint, so a fetch of many assets
+/// costs the time of a few round-trips instead of one per asset. Results
+/// come back per asset, ready to return from
+/// [`fetch_prices_partial`](MarketPriceProvider::fetch_prices_partial);
+/// [`any_prices`] turns them into a `fetch_prices` result. Providers
+/// default `concurrency` to [`FETCH_CONCURRENCY`](crate::constants::FETCH_CONCURRENCY);
+/// zero is treated as one.
+pub async fn fetch_each<F, Fut>(
+    assets: &[Asset],
+    concurrency: usize,
+    fetch: F,
+) -> HashMap<Asset, Result<PriceData, ProviderError>>
+where
+    F: Fn(Asset) -> Fut,
+    Fut: Future<Output = Result<PriceData, ProviderError>>,
+{
+    let fetch = &fetch;
+    stream::iter(assets.iter().copied())
+        .map(|asset| async move { (asset, fetch(asset).await) })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// Keeps the prices that were fetched, failing only if none were
+///
+/// The error returned is the first failure in `assets` order.
+pub fn any_prices(
+    assets: &[Asset],
+    mut results: HashMap<Asset, Result<PriceData, ProviderError>>,
+) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+    let mut prices = HashMap::new();
+    let mut first_error = None;
+    for asset in assets {
+        match results.remove(asset) {
+            Some(Ok(price)) => {
+                prices.insert(*asset, price);
+            }
+            Some(Err(e)) => {
+                first_error.get_or_insert(e);
+            }
+            None => {}
+        }
+    }
+    match first_error {
+        Some(e) if prices.is_empty() => Err(e),
+        _ => Ok(prices),
+    }
+}
+
 /// Trait for market price providers
 ///
 /// Implementations can fetch cryptocurrency prices from various sources
@@ -39,9 +182,64 @@ pub trait MarketPriceProvider: Send + Sync {
         assets: &[Asset],
     ) -> Result<HashMap<Asset, PriceData>, ProviderError>;
 
+    /// Fetches prices for multiple assets, giving up at `deadline`
+    ///
+    /// The default implementation runs [`fetch_prices`](Self::fetch_prices)
+    /// under [`http::with_deadline`], so HTTP requests made through the
+    /// `reqwest` transport are cut off at the deadline. Fails with
+    /// [`ProviderError::Timeout`] if the deadline has already passed.
+    async fn fetch_prices_with_deadline(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        within_deadline(deadline, self.fetch_prices(assets)).await
+    }
+
+    /// Fetches prices for multiple assets, with a result for each asset
+    ///
+    /// Lets callers keep the prices that arrived and retry only the assets
+    /// that failed. The default implementation splits the result of
+    /// [`fetch_prices_with_deadline`](Self::fetch_prices_with_deadline)
+    /// with [`per_asset`]; providers whose responses can fail per entry
+    /// override it to report those failures individually.
+    async fn fetch_prices_partial(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+        per_asset(
+            assets,
+            self.fetch_prices_with_deadline(assets, deadline).await,
+        )
+    }
+
+    /// Fetches the prices used to seed an empty store on startup
+    ///
+    /// Defaults to [`fetch_prices`](Self::fetch_prices). Providers that
+    /// combine several sources override it to query them all at once
+    /// instead of one after another.
+    async fn bootstrap(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        self.fetch_prices(assets).await
+    }
+
     /// Returns the name of this provider
     fn provider_name(&self) -> &'static str;
 
+    /// Describes what this provider can serve
+    ///
+    /// Defaults to every cryptocurrency asset (no reference assets) with no
+    /// optional fields and no known limits.
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            streaming: self.is_streaming(),
+            ..Default::default()
+        }
+    }
+
     /// Returns true if this is a streaming provider (e.g. gRPC, SSE)
     fn is_streaming(&self) -> bool {
         false
@@ -55,17 +253,47 @@ pub trait MarketPriceProvider: Send + Sync {
     ) {
         // Default no-op for non-streaming providers
     }
+
+    /// Returns connection lifecycle counters for streaming providers
+    fn connection_stats(&self) -> Option<ConnectionStats> {
+        None
+    }
+
+    /// Returns update rates and gaps for streaming providers
+    fn stream_metrics(&self) -> Option<StreamMetrics> {
+        None
+    }
+
+    /// Returns usage against the provider's daily request quota, if metered
+    fn quota_usage(&self) -> Option<QuotaUsage> {
+        None
+    }
+
+    /// Returns the fetch health of each provider this one fails over
+    /// between, in failover order
+    fn failover_health(&self) -> Option<Vec<FetchHealth>> {
+        None
+    }
+
+    /// Subscribes to events raised by the provider itself (e.g. divergence
+    /// between sources). The tracker forwards these to its own subscribers.
+    fn subscribe_events(&self) -> Option<broadcast::Receiver<MarketPriceEvent>> {
+        None
+    }
 }
 
 #[cfg(test)]
 pub mod mock {
     use super::*;
     use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     /// Mock provider for testing
     pub struct MockProvider {
         responses: Arc<Mutex<HashMap<Asset, Result<PriceData, ProviderError>>>>,
         call_count: Arc<Mutex<usize>>,
+        supported: Mutex<Option<Vec<Asset>>>,
+        delay: Mutex<Option<Duration>>,
     }
 
     impl Default for MockProvider {
@@ -79,6 +307,8 @@ pub mod mock {
             Self {
                 responses: Arc::new(Mutex::new(HashMap::new())),
                 call_count: Arc::new(Mutex::new(0)),
+                supported: Mutex::new(None),
+                delay: Mutex::new(None),
             }
         }
 
@@ -91,48 +321,54 @@ pub mod mock {
             self.responses.lock().unwrap().insert(asset, Err(error));
         }
 
+        /// Restricts the assets reported by `capabilities`
+        pub fn set_supported(&self, assets: &[Asset]) {
+            *self.supported.lock().unwrap() = Some(assets.to_vec());
+        }
+
+        /// Delays every fetch, e.g. to simulate a stuck provider
+        pub fn set_delay(&self, delay: Duration) {
+            *self.delay.lock().unwrap() = Some(delay);
+        }
+
         pub fn call_count(&self) -> usize {
             *self.call_count.lock().unwrap()
         }
-    }
 
-    #[async_trait]
-    impl MarketPriceProvider for MockProvider {
-        async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+        async fn wait(&self) {
+            let delay = *self.delay.lock().unwrap();
+            if let Some(delay) = delay {
+                tokio::time::sleep(delay).await;
+            }
+        }
+
+        fn respond(&self, asset: Asset) -> Result<PriceData, ProviderError> {
             *self.call_count.lock().unwrap() += 1;
             let responses = self.responses.lock().unwrap();
             match responses.get(&asset) {
                 Some(Ok(price)) => Ok(price.clone()),
-                Some(Err(err)) => {
-                    // Manual "clone" of ProviderError since it doesn't implement Clone
-                    match err {
-                        ProviderError::NetworkError(e) => Err(ProviderError::ApiError(format!(
-                            "Network error (cloned): {}",
-                            e
-                        ))),
-                        ProviderError::InvalidResponse(s) => {
-                            Err(ProviderError::InvalidResponse(s.clone()))
-                        }
-                        ProviderError::RateLimitExceeded => Err(ProviderError::RateLimitExceeded),
-                        ProviderError::UnsupportedAsset(s) => {
-                            Err(ProviderError::UnsupportedAsset(s.clone()))
-                        }
-                        ProviderError::ApiError(s) => Err(ProviderError::ApiError(s.clone())),
-                        ProviderError::Timeout => Err(ProviderError::Timeout),
-                    }
-                }
+                Some(Err(err)) => Err(err.replicate()),
                 None => Err(ProviderError::UnsupportedAsset(asset.symbol().to_string())),
             }
         }
+    }
+
+    #[async_trait]
+    impl MarketPriceProvider for MockProvider {
+        async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+            self.wait().await;
+            self.respond(asset)
+        }
 
         async fn fetch_prices(
             &self,
             assets: &[Asset],
         ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
             *self.call_count.lock().unwrap() += 1;
+            self.wait().await;
             let mut result = HashMap::new();
             for asset in assets {
-                if let Ok(price) = self.fetch_price(*asset).await {
+                if let Ok(price) = self.respond(*asset) {
                     result.insert(*asset, price);
                 }
             }
@@ -145,8 +381,73 @@ pub mod mock {
             }
         }
 
+        async fn fetch_prices_partial(
+            &self,
+            assets: &[Asset],
+            deadline: Option<Instant>,
+        ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return per_asset(assets, Err(ProviderError::Timeout));
+            }
+            *self.call_count.lock().unwrap() += 1;
+            self.wait().await;
+            assets
+                .iter()
+                .map(|asset| (*asset, self.respond(*asset)))
+                .collect()
+        }
+
         fn provider_name(&self) -> &'static str {
             "mock"
         }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            match self.supported.lock().unwrap().clone() {
+                Some(supported_assets) => ProviderCapabilities {
+                    supported_assets,
+                    ..Default::default()
+                },
+                None => ProviderCapabilities::default(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fetch_each_bounds_concurrency() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let start = tokio::time::Instant::now();
+
+        let results = fetch_each(Asset::all(), 4, |asset| {
+            let (in_flight, peak) = (&in_flight, &peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                if asset == Asset::BTC {
+                    Err(ProviderError::Timeout)
+                } else {
+                    Ok(PriceData::new(asset, 1.0, "test".to_string()))
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(results.len(), Asset::all().len());
+        assert_eq!(peak.load(Ordering::SeqCst), 4);
+        let rounds = Asset::all().len().div_ceil(4) as u32;
+        assert_eq!(start.elapsed(), Duration::from_millis(100) * rounds);
+
+        let prices = any_prices(Asset::all(), results).unwrap();
+        assert_eq!(prices.len(), Asset::all().len() - 1);
+        assert!(!prices.contains_key(&Asset::BTC));
     }
 }
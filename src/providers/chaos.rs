@@ -0,0 +1,459 @@
+//! Chaos price provider implementation
+
+use crate::{
+    error::ProviderError,
+    metrics::{ConnectionStats, FetchHealth, QuotaUsage, StreamMetrics},
+    provider::{MarketPriceProvider, ProviderCapabilities},
+    runtime::Runtime,
+    store::MarketPriceStore,
+    types::{Asset, MarketPriceEvent, PriceData},
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// Faults a [`ChaosProvider`] injects, and how often
+///
+/// Probabilities are per fetch for latency, per fetch or per asset for
+/// errors (see [`ChaosProvider`]), and per returned price for stale
+/// timestamps and spikes.
+#[derive(Debug, Clone)]
+pub struct ChaosConfig {
+    /// Seed of the fault sequence
+    pub seed: u64,
+    /// Probability that a fetch is delayed
+    pub latency_probability: f64,
+    /// Upper bound of an injected delay, drawn uniformly below it
+    pub max_latency: Duration,
+    /// Probability that a fetch, or an asset of a partial fetch, fails
+    /// without reaching the wrapped provider
+    pub error_probability: f64,
+    /// Probability that a price's timestamp is pushed into the past
+    pub stale_probability: f64,
+    /// How far a stale price's timestamp is pushed back
+    pub stale_by: Duration,
+    /// Probability that a price jumps up or down
+    pub spike_probability: f64,
+    /// Size of a jump, in basis points of the price
+    pub spike_bps: f64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            latency_probability: 0.1,
+            max_latency: Duration::from_secs(2),
+            error_probability: 0.05,
+            stale_probability: 0.05,
+            stale_by: Duration::from_secs(120),
+            spike_probability: 0.01,
+            spike_bps: 1000.0,
+        }
+    }
+}
+
+/// Faults a [`ChaosProvider`] has injected so far
+#[derive(Debug, Clone, Default)]
+pub struct ChaosStats {
+    /// Fetches made through the provider
+    pub fetches: u64,
+    /// Fetches that were delayed
+    pub delayed: u64,
+    /// Fetches, or assets of partial fetches, that were failed
+    pub errors: u64,
+    /// Prices returned with a stale timestamp
+    pub stale_prices: u64,
+    /// Prices returned with a spike
+    pub spiked_prices: u64,
+}
+
+/// Price provider that injects faults around another provider
+///
+/// Each fetch may be delayed, failed with a transient error, or have some
+/// of its prices backdated or moved by a spike, at the rates set in
+/// [`ChaosConfig`]. Partial fetches, as the tracker's polling makes, fail
+/// assets one by one instead, so some succeed while others error. Delays
+/// sleep on the configured [`Runtime`]'s timer. Faults are drawn from a generator seeded with
+/// [`ChaosConfig::seed`], so a run that makes the same calls in the same
+/// order sees the same faults. Meant for resilience testing of failover
+/// setups and of consumers; streamed updates and everything other than
+/// fetches pass through untouched.
+///
+/// # Example
+/// ```no_run
+/// use market_price_sdk::providers::{ChaosConfig, ChaosProvider, CoinGeckoProvider};
+/// use market_price_sdk::MarketPriceTracker;
+/// use std::sync::Arc;
+///
+/// let chaos = Arc::new(ChaosProvider::new(
+///     Arc::new(CoinGeckoProvider::default()),
+///     ChaosConfig {
+///         seed: 42,
+///         error_probability: 0.2,
+///         ..Default::default()
+///     },
+/// ));
+/// let tracker = MarketPriceTracker::with_provider(chaos.clone());
+///
+/// // Later, to see what the tracker went through
+/// let stats = chaos.stats();
+/// ```
+pub struct ChaosProvider {
+    inner: Arc<dyn MarketPriceProvider>,
+    config: ChaosConfig,
+    rng: Mutex<SplitMix64>,
+    stats: Mutex<ChaosStats>,
+    runtime: Runtime,
+}
+
+impl ChaosProvider {
+    /// Creates a chaos provider
+    ///
+    /// # Arguments
+    /// * `inner` - Provider whose fetches are disturbed
+    /// * `config` - Which faults to inject and how often
+    pub fn new(inner: Arc<dyn MarketPriceProvider>, config: ChaosConfig) -> Self {
+        Self {
+            inner,
+            rng: Mutex::new(SplitMix64(config.seed)),
+            config,
+            stats: Mutex::new(ChaosStats::default()),
+            runtime: Runtime::default(),
+        }
+    }
+
+    /// Sleeps injected delays on `runtime`'s timer instead of tokio's
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    /// Returns the faults injected so far
+    pub fn stats(&self) -> ChaosStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    /// Returns true with probability `probability`
+    fn roll(&self, probability: f64) -> bool {
+        probability > 0.0 && self.rng.lock().unwrap().next_f64() < probability
+    }
+
+    /// Uniform draw in `[0, 1)`
+    fn draw(&self) -> f64 {
+        self.rng.lock().unwrap().next_f64()
+    }
+
+    /// Delays a fetch at the configured rate
+    async fn delay(&self) {
+        if !self.roll(self.config.latency_probability) {
+            return;
+        }
+        let delay = self.config.max_latency.mul_f64(self.draw());
+        self.stats.lock().unwrap().delayed += 1;
+        tracing::debug!(
+            provider = self.inner.provider_name(),
+            delay_ms = delay.as_millis() as u64,
+            "Chaos provider delaying fetch"
+        );
+        self.runtime.sleep(delay).await;
+    }
+
+    /// Draws whether to fail a fetch or asset, and with which error
+    fn fail(&self) -> Option<ProviderError> {
+        if !self.roll(self.config.error_probability) {
+            return None;
+        }
+        self.stats.lock().unwrap().errors += 1;
+        Some(self.injected_error())
+    }
+
+    /// One of the transient errors a real provider fails with
+    fn injected_error(&self) -> ProviderError {
+        match (self.draw() * 4.0) as u32 {
+            0 => ProviderError::Timeout,
+            1 => ProviderError::RateLimitExceeded,
+            2 => ProviderError::ApiError("HTTP 503: injected by chaos provider".to_string()),
+            _ => ProviderError::InvalidResponse("injected by chaos provider".to_string()),
+        }
+    }
+
+    /// Backdates and spikes prices at the configured rates
+    fn disturb(&self, price: &mut PriceData) {
+        if self.roll(self.config.stale_probability) {
+            price.last_updated -= chrono::Duration::from_std(self.config.stale_by)
+                .unwrap_or(chrono::Duration::zero());
+            self.stats.lock().unwrap().stale_prices += 1;
+        }
+        if self.roll(self.config.spike_probability) {
+            let direction = if self.draw() < 0.5 { -1.0 } else { 1.0 };
+            price.price_usd *= 1.0 + direction * self.config.spike_bps / 10_000.0;
+            #[cfg(feature = "decimal")]
+            {
+                price.price_decimal = None;
+            }
+            self.stats.lock().unwrap().spiked_prices += 1;
+        }
+    }
+}
+
+/// SplitMix64, a small generator good enough for drawing faults
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform draw in `[0, 1)`
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[async_trait]
+impl MarketPriceProvider for ChaosProvider {
+    async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+        let prices = self.fetch_prices(&[asset]).await?;
+        prices
+            .get(&asset)
+            .cloned()
+            .ok_or_else(|| ProviderError::UnsupportedAsset(asset.symbol().to_string()))
+    }
+
+    async fn fetch_prices(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        self.stats.lock().unwrap().fetches += 1;
+        self.delay().await;
+        if let Some(error) = self.fail() {
+            tracing::debug!(
+                provider = self.inner.provider_name(),
+                error = %error,
+                "Chaos provider failing fetch"
+            );
+            return Err(error);
+        }
+
+        let mut prices = self.inner.fetch_prices(assets).await?;
+        // Sorted so the draws do not depend on the map's iteration order
+        let mut assets: Vec<Asset> = prices.keys().copied().collect();
+        assets.sort_by_key(|asset| asset.symbol());
+        for asset in assets {
+            if let Some(price) = prices.get_mut(&asset) {
+                self.disturb(price);
+            }
+        }
+        Ok(prices)
+    }
+
+    async fn fetch_prices_partial(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+        self.stats.lock().unwrap().fetches += 1;
+        self.delay().await;
+
+        let mut results = HashMap::new();
+        let mut passed = Vec::new();
+        for asset in assets {
+            match self.fail() {
+                Some(error) => {
+                    tracing::debug!(
+                        provider = self.inner.provider_name(),
+                        asset = asset.symbol(),
+                        error = %error,
+                        "Chaos provider failing asset"
+                    );
+                    results.insert(*asset, Err(error));
+                }
+                None => passed.push(*asset),
+            }
+        }
+        if passed.is_empty() {
+            return results;
+        }
+
+        let mut fetched = self.inner.fetch_prices_partial(&passed, deadline).await;
+        for asset in passed {
+            if let Some(mut result) = fetched.remove(&asset) {
+                if let Ok(price) = &mut result {
+                    self.disturb(price);
+                }
+                results.insert(asset, result);
+            }
+        }
+        results
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.inner.provider_name()
+    }
+
+    fn is_streaming(&self) -> bool {
+        self.inner.is_streaming()
+    }
+
+    fn start_streaming(
+        &self,
+        store: Arc<MarketPriceStore>,
+        update_tx: broadcast::Sender<PriceData>,
+    ) {
+        self.inner.start_streaming(store, update_tx);
+    }
+
+    fn connection_stats(&self) -> Option<ConnectionStats> {
+        self.inner.connection_stats()
+    }
+
+    fn stream_metrics(&self) -> Option<StreamMetrics> {
+        self.inner.stream_metrics()
+    }
+
+    fn quota_usage(&self) -> Option<QuotaUsage> {
+        self.inner.quota_usage()
+    }
+
+    fn failover_health(&self) -> Option<Vec<FetchHealth>> {
+        self.inner.failover_health()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn subscribe_events(&self) -> Option<broadcast::Receiver<MarketPriceEvent>> {
+        self.inner.subscribe_events()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::mock::MockProvider;
+
+    #[tokio::test]
+    async fn test_same_seed_injects_same_faults() {
+        let inner = Arc::new(MockProvider::new());
+        inner.set_price(Asset::SOL, 150.0);
+        inner.set_price(Asset::BTC, 60000.0);
+        let config = ChaosConfig {
+            seed: 7,
+            latency_probability: 0.0,
+            error_probability: 0.3,
+            stale_probability: 0.3,
+            spike_probability: 0.3,
+            ..Default::default()
+        };
+        let first = ChaosProvider::new(inner.clone(), config.clone());
+        let second = ChaosProvider::new(inner, config);
+
+        for _ in 0..20 {
+            let a = first.fetch_prices(&[Asset::SOL, Asset::BTC]).await;
+            let b = second.fetch_prices(&[Asset::SOL, Asset::BTC]).await;
+            match (a, b) {
+                (Ok(a), Ok(b)) => {
+                    for asset in [Asset::SOL, Asset::BTC] {
+                        assert_eq!(a[&asset].price_usd, b[&asset].price_usd);
+                    }
+                }
+                (Err(_), Err(_)) => {}
+                _ => panic!("same seed diverged"),
+            }
+        }
+        let stats = first.stats();
+        assert_eq!(stats.fetches, 20);
+        assert!(stats.errors > 0 && stats.errors < 20);
+        assert!(stats.spiked_prices > 0);
+        assert!(stats.stale_prices > 0);
+    }
+
+    #[tokio::test]
+    async fn test_spike_moves_price_by_configured_size() {
+        let inner = Arc::new(MockProvider::new());
+        inner.set_price(Asset::SOL, 100.0);
+        let chaos = ChaosProvider::new(
+            inner,
+            ChaosConfig {
+                latency_probability: 0.0,
+                error_probability: 0.0,
+                stale_probability: 0.0,
+                spike_probability: 1.0,
+                spike_bps: 500.0,
+                ..Default::default()
+            },
+        );
+
+        let price = chaos.fetch_price(Asset::SOL).await.unwrap();
+        assert!((price.price_usd - 95.0).abs() < 1e-9 || (price.price_usd - 105.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_partial_fetch_fails_assets_independently() {
+        let inner = Arc::new(MockProvider::new());
+        let assets = [Asset::SOL, Asset::BTC, Asset::ETH, Asset::DOGE];
+        for asset in assets {
+            inner.set_price(asset, 1.0);
+        }
+        let chaos = ChaosProvider::new(
+            inner,
+            ChaosConfig {
+                seed: 3,
+                latency_probability: 0.0,
+                error_probability: 0.5,
+                ..Default::default()
+            },
+        );
+
+        let mut mixed = false;
+        for _ in 0..10 {
+            let results = chaos.fetch_prices_partial(&assets, None).await;
+            assert_eq!(results.len(), assets.len());
+            let failed = results.values().filter(|result| result.is_err()).count();
+            mixed |= failed > 0 && failed < assets.len();
+        }
+        assert!(mixed);
+        assert!(chaos.stats().errors > 0);
+    }
+
+    #[tokio::test]
+    async fn test_delays_sleep_on_the_runtime_timer() {
+        struct RecordingTimer(Mutex<Vec<Duration>>);
+        impl crate::runtime::Timer for RecordingTimer {
+            fn sleep(&self, duration: Duration) -> futures::future::BoxFuture<'static, ()> {
+                self.0.lock().unwrap().push(duration);
+                Box::pin(async {})
+            }
+        }
+
+        let inner = Arc::new(MockProvider::new());
+        inner.set_price(Asset::SOL, 100.0);
+        let timer = Arc::new(RecordingTimer(Mutex::new(Vec::new())));
+        let chaos = ChaosProvider::new(
+            inner,
+            ChaosConfig {
+                latency_probability: 1.0,
+                max_latency: Duration::from_secs(3600),
+                error_probability: 0.0,
+                ..Default::default()
+            },
+        )
+        .with_runtime(Runtime::new(
+            Arc::new(crate::runtime::TokioExecutor),
+            timer.clone(),
+        ));
+
+        chaos.fetch_price(Asset::SOL).await.unwrap();
+        chaos.fetch_prices_partial(&[Asset::SOL], None).await;
+        assert_eq!(timer.0.lock().unwrap().len(), 2);
+        assert_eq!(chaos.stats().delayed, 2);
+    }
+}
@@ -1,82 +1,253 @@
 //! CoinGecko price provider implementation
 
 use crate::{
-    constants::{
-        COINGECKO_API_URL, COINGECKO_SIMPLE_PRICE_ENDPOINT, REQUEST_TIMEOUT_SECS, USER_AGENT,
-    },
+    config::Environment,
+    constants::{COINGECKO_API_URL, COINGECKO_PRO_API_URL, COINGECKO_SIMPLE_PRICE_ENDPOINT},
+    credentials::Credentials,
     error::ProviderError,
-    provider::MarketPriceProvider,
+    http::{BaseUrls, HttpClientConfig, HttpClientFactory, HttpResponse, HttpTransport},
+    parse,
+    provider::{self, MarketPriceProvider, ProviderCapabilities},
+    symbols::SymbolRegistry,
     types::{Asset, PriceData},
 };
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::Deserialize;
+use serde_json::value::RawValue;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Rate limit of the public and demo CoinGecko APIs
+const COINGECKO_PUBLIC_REQUESTS_PER_MINUTE: u32 = 30;
 
 /// CoinGecko API response for simple price queries
+///
+/// Entries are parsed one by one, so a malformed entry only fails its own
+/// asset.
 #[derive(Debug, Deserialize)]
-struct CoinGeckoResponse {
-    #[serde(flatten)]
-    prices: HashMap<String, CoinGeckoPriceData>,
-}
+struct CoinGeckoResponse(HashMap<String, Box<RawValue>>);
 
 #[derive(Debug, Deserialize)]
 struct CoinGeckoPriceData {
-    usd: f64,
+    usd: Box<RawValue>,
 }
 
 /// CoinGecko price provider
 pub struct CoinGeckoProvider {
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
+    base_urls: BaseUrls,
+    credentials: Option<Arc<Credentials>>,
+    environment: Environment,
 }
 
 impl CoinGeckoProvider {
-    /// Creates a new CoinGecko provider
+    /// Creates a new CoinGecko provider using the shared HTTP client
     pub fn new() -> Result<Self, ProviderError> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-            .user_agent(USER_AGENT)
-            .build()
-            .map_err(ProviderError::NetworkError)?;
+        Ok(Self::with_client(HttpClientFactory::global().client()?))
+    }
+
+    /// Creates a new CoinGecko provider with custom HTTP settings (proxy, timeout)
+    pub fn with_http_config(config: &HttpClientConfig) -> Result<Self, ProviderError> {
+        Ok(Self::with_client(config.build()?))
+    }
+
+    /// Creates a new CoinGecko provider using an existing HTTP client
+    pub fn with_client(client: Client) -> Self {
+        Self::with_transport(Arc::new(client))
+    }
+
+    /// Creates a new CoinGecko provider on top of a custom HTTP transport
+    pub fn with_transport(transport: Arc<dyn HttpTransport>) -> Self {
+        Self {
+            transport,
+            base_urls: BaseUrls::new([COINGECKO_API_URL]),
+            credentials: None,
+            environment: Environment::default(),
+        }
+    }
+
+    /// Sends requests to these base URLs, failing over in order
+    pub fn with_base_urls(mut self, base_urls: BaseUrls) -> Self {
+        self.base_urls = base_urls;
+        self
+    }
+
+    /// Authenticates with the given keys, rotating on 401/403/429
+    ///
+    /// On mainnet this switches from the public API to the Pro API unless
+    /// custom base URLs were configured.
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = Some(Arc::new(credentials));
+        self.reset_base_urls();
+        self
+    }
+
+    /// Selects Pro keys (mainnet) or demo keys (testnet/demo)
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = environment;
+        self.reset_base_urls();
+        self
+    }
 
-        Ok(Self { client })
+    /// Points the default base URL at the API matching the environment and
+    /// credentials, leaving custom base URLs alone
+    fn reset_base_urls(&mut self) {
+        let urls = self.base_urls.urls();
+        if urls != [COINGECKO_API_URL] && urls != [COINGECKO_PRO_API_URL] {
+            return;
+        }
+        let pro = self.credentials.is_some() && !self.environment.is_test();
+        self.base_urls = BaseUrls::new([if pro {
+            COINGECKO_PRO_API_URL
+        } else {
+            COINGECKO_API_URL
+        }]);
     }
 
     /// Builds the CoinGecko API URL for fetching prices
-    fn build_url(&self, assets: &[Asset]) -> String {
+    fn build_url(&self, base_url: &str, assets: &[Asset]) -> String {
         let ids = assets
             .iter()
-            .map(|a| a.coingecko_id())
+            .filter_map(|a| SymbolRegistry::global().coingecko_id(*a))
             .collect::<Vec<_>>()
             .join(",");
 
         format!(
             "{}{}?ids={}&vs_currencies=usd",
-            COINGECKO_API_URL, COINGECKO_SIMPLE_PRICE_ENDPOINT, ids
+            base_url, COINGECKO_SIMPLE_PRICE_ENDPOINT, ids
         )
     }
 
-    /// Parses the CoinGecko response into price data
+    /// Header carrying the API key for the configured tier
+    fn api_key_header(&self) -> &'static str {
+        if self.environment.is_test() {
+            "x-cg-demo-api-key"
+        } else {
+            "x-cg-pro-api-key"
+        }
+    }
+
+    /// Sends the price request to the current base URL with the current key
+    async fn send(
+        &self,
+        assets: &[Asset],
+        api_key: Option<&str>,
+    ) -> Result<HttpResponse, ProviderError> {
+        self.base_urls
+            .send(|base_url| {
+                let url = self.build_url(&base_url, assets);
+                async move {
+                    match api_key {
+                        Some(key) => {
+                            self.transport
+                                .get_with_headers(&url, &[(self.api_key_header(), key)])
+                                .await
+                        }
+                        None => self.transport.get(&url).await,
+                    }
+                }
+            })
+            .await
+    }
+
+    /// Sends the price request, rotating through API keys that are rejected
+    async fn request(&self, assets: &[Asset]) -> Result<String, ProviderError> {
+        let Some(credentials) = &self.credentials else {
+            tracing::debug!(
+                provider = "coingecko",
+                base_url = self.base_urls.current(),
+                "Fetching prices from CoinGecko"
+            );
+            return Ok(self.send(assets, None).await?.error_for_status()?.body);
+        };
+
+        let mut attempts_left = credentials.key_count();
+        loop {
+            let (index, key) = credentials.current_with_index();
+            tracing::debug!(
+                provider = "coingecko",
+                base_url = self.base_urls.current(),
+                "Fetching prices from CoinGecko with API key"
+            );
+            let response = self.send(assets, Some(&key)).await?;
+
+            if Credentials::should_rotate(response.status) {
+                credentials.rotate(index);
+                attempts_left -= 1;
+                if attempts_left > 0 {
+                    continue;
+                }
+            }
+            return Ok(response.error_for_status()?.body);
+        }
+    }
+
+    /// Parses the CoinGecko response into a result per requested asset
     fn parse_response(
         &self,
-        response: CoinGeckoResponse,
+        mut response: CoinGeckoResponse,
         assets: &[Asset],
-    ) -> HashMap<Asset, PriceData> {
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
         let mut result = HashMap::new();
 
         for asset in assets {
-            let id = asset.coingecko_id();
-            if let Some(price_data) = response.prices.get(id) {
+            let Some(id) = SymbolRegistry::global().coingecko_id(*asset) else {
                 result.insert(
                     *asset,
-                    PriceData::new(*asset, price_data.usd, self.provider_name().to_string()),
+                    Err(ProviderError::UnsupportedAsset(asset.symbol().to_string())),
                 );
-            }
+                continue;
+            };
+            let price = match response.0.remove(&id) {
+                Some(entry) => serde_json::from_str::<CoinGeckoPriceData>(entry.get())
+                    .map_err(|e| ProviderError::InvalidResponse(e.to_string()))
+                    .and_then(|entry| parse::price(*asset, &entry.usd))
+                    .map(|usd| PriceData::new(*asset, usd, self.provider_name().to_string()))
+                    .map_err(|e| match e {
+                        ProviderError::InvalidPrice { .. } => e,
+                        e => ProviderError::InvalidResponse(format!(
+                            "Malformed CoinGecko entry for {}: {}",
+                            id, e
+                        )),
+                    }),
+                None => Err(ProviderError::MissingPrice(asset.symbol().to_string())),
+            };
+            result.insert(*asset, price);
         }
 
         result
     }
+
+    /// Requests prices and parses a result per requested asset
+    async fn fetch_entries(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, Result<PriceData, ProviderError>>, ProviderError> {
+        let registry = SymbolRegistry::global();
+        if assets
+            .iter()
+            .all(|asset| registry.coingecko_id(*asset).is_none())
+        {
+            return Err(ProviderError::UnsupportedAsset(
+                "CoinGecko lists none of the requested assets".to_string(),
+            ));
+        }
+
+        // Rate limiting and other HTTP errors are mapped by error_for_status
+        let response_text = self.request(assets).await?;
+
+        let coingecko_response: CoinGeckoResponse =
+            serde_json::from_str(&response_text).map_err(|e| {
+                ProviderError::InvalidResponse(format!(
+                    "Failed to parse CoinGecko response: {}. Response: {}",
+                    e, response_text
+                ))
+            })?;
+
+        Ok(self.parse_response(coingecko_response, assets))
+    }
 }
 
 impl Default for CoinGeckoProvider {
@@ -103,41 +274,12 @@ impl MarketPriceProvider for CoinGeckoProvider {
             return Ok(HashMap::new());
         }
 
-        let url = self.build_url(assets);
-        tracing::debug!(url = %url, "Fetching prices from CoinGecko");
-
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(ProviderError::NetworkError)?;
-
-        // Check for rate limiting
-        if response.status().as_u16() == 429 {
-            return Err(ProviderError::RateLimitExceeded);
-        }
-
-        // Check for other errors
-        if !response.status().is_success() {
-            return Err(ProviderError::ApiError(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            )));
-        }
-
-        let response_text = response.text().await.map_err(ProviderError::NetworkError)?;
-
-        let coingecko_response: CoinGeckoResponse =
-            serde_json::from_str(&response_text).map_err(|e| {
-                ProviderError::InvalidResponse(format!(
-                    "Failed to parse CoinGecko response: {}. Response: {}",
-                    e, response_text
-                ))
-            })?;
-
-        let prices = self.parse_response(coingecko_response, assets);
+        let prices: HashMap<Asset, PriceData> = self
+            .fetch_entries(assets)
+            .await?
+            .into_iter()
+            .filter_map(|(asset, price)| Some((asset, price.ok()?)))
+            .collect();
 
         if prices.is_empty() {
             return Err(ProviderError::InvalidResponse(
@@ -145,13 +287,179 @@ impl MarketPriceProvider for CoinGeckoProvider {
             ));
         }
 
-        tracing::debug!(count = prices.len(), "Successfully fetched prices from CoinGecko");
+        tracing::debug!(
+            provider = "coingecko",
+            count = prices.len(),
+            "Successfully fetched prices from CoinGecko"
+        );
 
         Ok(prices)
     }
 
+    async fn fetch_prices_partial(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+        match provider::within_deadline(deadline, self.fetch_entries(assets)).await {
+            Ok(prices) => prices,
+            Err(e) => provider::per_asset(assets, Err(e)),
+        }
+    }
+
     fn provider_name(&self) -> &'static str {
         "coingecko"
     }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        // Paid plans have plan-specific limits
+        let public = self.credentials.is_none() || self.environment.is_test();
+        ProviderCapabilities {
+            max_requests_per_minute: public.then_some(COINGECKO_PUBLIC_REQUESTS_PER_MINUTE),
+            ..Default::default()
+        }
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::fake::FakeTransport;
+
+    #[tokio::test]
+    async fn test_fetch_prices_parses_response() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(200, r#"{"solana":{"usd":150.5},"bitcoin":{"usd":64000.0}}"#);
+        let provider = CoinGeckoProvider::with_transport(transport.clone());
+
+        let prices = provider
+            .fetch_prices(&[Asset::SOL, Asset::BTC])
+            .await
+            .unwrap();
+
+        assert_eq!(prices[&Asset::SOL].price_usd, 150.5);
+        assert_eq!(prices[&Asset::BTC].price_usd, 64000.0);
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0]
+            .url
+            .ends_with("?ids=solana,bitcoin&vs_currencies=usd"));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_entry_fails_only_its_asset() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(200, r#"{"solana":{"usd":150.5},"bitcoin":{"usd":null}}"#);
+        let provider = CoinGeckoProvider::with_transport(transport);
+
+        let prices = provider
+            .fetch_prices_partial(&[Asset::SOL, Asset::BTC, Asset::ETH], None)
+            .await;
+
+        assert_eq!(prices[&Asset::SOL].as_ref().unwrap().price_usd, 150.5);
+        assert!(matches!(
+            prices[&Asset::BTC],
+            Err(ProviderError::InvalidResponse(_))
+        ));
+        assert!(matches!(
+            prices[&Asset::ETH],
+            Err(ProviderError::MissingPrice(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prices_maps_errors() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(429, "");
+        transport.push_response(200, "{not json");
+        transport.push_error(ProviderError::Timeout);
+        let provider = CoinGeckoProvider::with_transport(transport);
+
+        assert!(matches!(
+            provider.fetch_prices(&[Asset::SOL]).await,
+            Err(ProviderError::RateLimitExceeded)
+        ));
+        assert!(matches!(
+            provider.fetch_prices(&[Asset::SOL]).await,
+            Err(ProviderError::InvalidResponse(_))
+        ));
+        assert!(matches!(
+            provider.fetch_prices(&[Asset::SOL]).await,
+            Err(ProviderError::Timeout)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_mirror() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(503, "");
+        transport.push_response(200, r#"{"solana":{"usd":150.5}}"#);
+        let provider = CoinGeckoProvider::with_transport(transport.clone())
+            .with_base_urls(BaseUrls::new(["https://primary", "https://mirror"]));
+
+        let prices = provider.fetch_prices(&[Asset::SOL]).await.unwrap();
+
+        assert_eq!(prices[&Asset::SOL].price_usd, 150.5);
+        let requests = transport.requests();
+        assert!(requests[0].url.starts_with("https://primary/"));
+        assert!(requests[1].url.starts_with("https://mirror/"));
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_assets_are_not_requested() {
+        let transport = Arc::new(FakeTransport::new());
+        let provider = CoinGeckoProvider::with_transport(transport.clone());
+
+        let results = provider.fetch_prices_partial(&[Asset::XAU], None).await;
+
+        assert!(matches!(
+            results[&Asset::XAU],
+            Err(ProviderError::UnsupportedAsset(_))
+        ));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rotates_rejected_api_keys() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(401, "");
+        transport.push_response(200, r#"{"solana":{"usd":150.5}}"#);
+        let credentials = Credentials::new(vec!["first".to_string(), "second".to_string()]);
+        let provider = CoinGeckoProvider::with_transport(transport.clone())
+            .with_credentials(credentials.unwrap());
+
+        let prices = provider.fetch_prices(&[Asset::SOL]).await.unwrap();
+
+        assert_eq!(prices[&Asset::SOL].price_usd, 150.5);
+        let requests = transport.requests();
+        assert!(requests[0].url.starts_with(COINGECKO_PRO_API_URL));
+        assert!(!requests[0].url.contains("first"));
+        assert_eq!(
+            requests[0].headers,
+            [("x-cg-pro-api-key".to_string(), "first".to_string())]
+        );
+        assert_eq!(
+            requests[1].headers,
+            [("x-cg-pro-api-key".to_string(), "second".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_demo_environment_uses_demo_keys() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(200, r#"{"solana":{"usd":150.5}}"#);
+        let credentials = Credentials::new(vec!["demo".to_string()]).unwrap();
+        let provider = CoinGeckoProvider::with_transport(transport.clone())
+            .with_credentials(credentials)
+            .with_environment(Environment::Demo);
+
+        provider.fetch_prices(&[Asset::SOL]).await.unwrap();
+
+        let request = &transport.requests()[0];
+        assert!(request.url.starts_with(COINGECKO_API_URL));
+        assert_eq!(
+            request.headers,
+            [("x-cg-demo-api-key".to_string(), "demo".to_string())]
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Cross-checking price provider implementation
+
+use crate::{
+    error::ProviderError,
+    event_id::{EventIdGenerator, UuidV7Generator},
+    metrics::{ConnectionStats, FetchHealth, QuotaUsage, StreamMetrics},
+    provider::{self, MarketPriceProvider, ProviderCapabilities},
+    runtime::Runtime,
+    types::{Asset, MarketPriceEvent, PriceData, SourceMethod},
+};
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+use tokio::sync::broadcast;
+
+/// Price provider that validates a primary source against a reference
+///
+/// Both providers are queried concurrently and the primary's prices are
+/// returned. When the two disagree on an asset by more than the threshold,
+/// a [`MarketPriceEvent::ProviderDivergence`] is emitted, the asset is
+/// marked degraded, and the price's confidence interval is widened to at
+/// least the gap between the two quotes. A failing reference is logged and
+/// does not fail the fetch.
+pub struct CrossCheckProvider {
+    primary: Arc<dyn MarketPriceProvider>,
+    reference: Arc<dyn MarketPriceProvider>,
+    max_deviation_bps: f64,
+    degraded: RwLock<HashSet<Asset>>,
+    event_tx: broadcast::Sender<MarketPriceEvent>,
+    event_ids: Arc<dyn EventIdGenerator>,
+    runtime: Runtime,
+}
+
+impl CrossCheckProvider {
+    /// Creates a cross-checking provider
+    ///
+    /// # Arguments
+    /// * `primary` - Provider whose prices are returned (e.g. a failover chain)
+    /// * `reference` - Independent provider used only for comparison
+    /// * `max_deviation_bps` - Largest tolerated disagreement in basis points
+    pub fn new(
+        primary: Arc<dyn MarketPriceProvider>,
+        reference: Arc<dyn MarketPriceProvider>,
+        max_deviation_bps: f64,
+    ) -> Self {
+        let (event_tx, _) = broadcast::channel(100);
+        Self {
+            primary,
+            reference,
+            max_deviation_bps,
+            degraded: RwLock::new(HashSet::new()),
+            event_tx,
+            event_ids: Arc::new(UuidV7Generator),
+            runtime: Runtime::default(),
+        }
+    }
+
+    /// Draws divergence event ids from `event_ids` instead of UUIDv7s
+    pub fn with_event_ids(mut self, event_ids: Arc<dyn EventIdGenerator>) -> Self {
+        self.event_ids = event_ids;
+        self
+    }
+
+    /// Times divergence events with `runtime`'s clock instead of the
+    /// system clock
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    /// Returns true if the providers currently disagree on this asset
+    pub fn is_degraded(&self, asset: Asset) -> bool {
+        self.degraded
+            .read()
+            .expect("degraded lock poisoned")
+            .contains(&asset)
+    }
+
+    /// Lists assets on which the providers currently disagree
+    pub fn degraded_assets(&self) -> Vec<Asset> {
+        self.degraded
+            .read()
+            .expect("degraded lock poisoned")
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    /// Compares primary prices against the reference, widening confidence
+    /// and emitting events for diverging assets
+    fn check(&self, prices: &mut HashMap<Asset, PriceData>, reference: &HashMap<Asset, PriceData>) {
+        let mut degraded = self.degraded.write().expect("degraded lock poisoned");
+
+        for (asset, price) in prices.iter_mut() {
+            let Some(reference_price) = reference.get(asset) else {
+                continue;
+            };
+            if reference_price.price_usd == 0.0 {
+                continue;
+            }
+
+            let gap = (price.price_usd - reference_price.price_usd).abs();
+            let deviation_bps = gap / reference_price.price_usd.abs() * 10_000.0;
+            price.deviation_bps = Some(deviation_bps);
+            let mut source_info = price.attribution();
+            source_info
+                .contributors
+                .push(reference_price.source.clone());
+            source_info.method = SourceMethod::CrossChecked;
+            price.source_info = Some(source_info);
+
+            if deviation_bps <= self.max_deviation_bps {
+                degraded.remove(asset);
+                continue;
+            }
+
+            tracing::warn!(
+                asset = asset.symbol(),
+                primary = self.primary.provider_name(),
+                primary_price_usd = price.price_usd,
+                reference = self.reference.provider_name(),
+                reference_price_usd = reference_price.price_usd,
+                deviation_bps = deviation_bps,
+                max_deviation_bps = self.max_deviation_bps,
+                "Providers disagree on price"
+            );
+
+            degraded.insert(*asset);
+            price.confidence = Some(price.confidence.unwrap_or(0.0).max(gap));
+
+            let _ = self.event_tx.send(MarketPriceEvent::ProviderDivergence {
+                id: self.event_ids.next_id(),
+                asset: *asset,
+                primary: self.primary.provider_name().to_string(),
+                primary_price_usd: price.price_usd,
+                reference: self.reference.provider_name().to_string(),
+                reference_price_usd: reference_price.price_usd,
+                deviation_bps,
+                timestamp: self.runtime.now(),
+            });
+        }
+    }
+}
+
+#[async_trait]
+impl MarketPriceProvider for CrossCheckProvider {
+    async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+        let prices = self.fetch_prices(&[asset]).await?;
+        prices
+            .get(&asset)
+            .cloned()
+            .ok_or_else(|| ProviderError::UnsupportedAsset(asset.symbol().to_string()))
+    }
+
+    async fn fetch_prices(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        let (primary, reference) = tokio::join!(
+            self.primary.fetch_prices(assets),
+            self.reference.fetch_prices(assets)
+        );
+        let mut prices = primary?;
+
+        match reference {
+            Ok(reference) => self.check(&mut prices, &reference),
+            Err(e) => tracing::warn!(
+                provider = self.reference.provider_name(),
+                error = %e,
+                "Reference provider failed, skipping cross-check"
+            ),
+        }
+
+        Ok(prices)
+    }
+
+    /// Fetches the primary per asset, cross-checking the prices it returned
+    async fn fetch_prices_partial(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+        let (mut results, reference) = tokio::join!(
+            self.primary.fetch_prices_partial(assets, deadline),
+            self.reference.fetch_prices_with_deadline(assets, deadline)
+        );
+
+        match reference {
+            Ok(reference) => {
+                let mut prices: HashMap<Asset, PriceData> = results
+                    .iter()
+                    .filter_map(|(asset, result)| Some((*asset, result.as_ref().ok()?.clone())))
+                    .collect();
+                self.check(&mut prices, &reference);
+                results.extend(prices.into_iter().map(|(asset, price)| (asset, Ok(price))));
+            }
+            Err(e) => tracing::warn!(
+                provider = self.reference.provider_name(),
+                error = %e,
+                "Reference provider failed, skipping cross-check"
+            ),
+        }
+
+        results
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "cross-check"
+    }
+
+    fn connection_stats(&self) -> Option<ConnectionStats> {
+        self.primary.connection_stats()
+    }
+
+    fn stream_metrics(&self) -> Option<StreamMetrics> {
+        self.primary.stream_metrics()
+    }
+
+    fn quota_usage(&self) -> Option<QuotaUsage> {
+        self.primary.quota_usage()
+    }
+
+    fn failover_health(&self) -> Option<Vec<FetchHealth>> {
+        self.primary.failover_health()
+    }
+
+    /// The primary's capabilities, limited by the reference since every
+    /// fetch queries both
+    fn capabilities(&self) -> ProviderCapabilities {
+        let primary = self.primary.capabilities();
+        let reference = self.reference.capabilities();
+        ProviderCapabilities {
+            streaming: false,
+            max_requests_per_minute: provider::tighter(
+                primary.max_requests_per_minute,
+                reference.max_requests_per_minute,
+            ),
+            max_batch_size: provider::tighter(primary.max_batch_size, reference.max_batch_size),
+            ..primary
+        }
+    }
+
+    fn subscribe_events(&self) -> Option<broadcast::Receiver<MarketPriceEvent>> {
+        Some(self.event_tx.subscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::mock::MockProvider;
+
+    #[tokio::test]
+    async fn test_divergence_marks_degraded() {
+        let primary = Arc::new(MockProvider::new());
+        let reference = Arc::new(MockProvider::new());
+        primary.set_price(Asset::SOL, 150.0);
+        primary.set_price(Asset::BTC, 60000.0);
+        reference.set_price(Asset::SOL, 153.0);
+        reference.set_price(Asset::BTC, 60010.0);
+        let provider = CrossCheckProvider::new(primary, reference, 50.0);
+        let mut events = provider.subscribe_events().unwrap();
+
+        let prices = provider
+            .fetch_prices(&[Asset::SOL, Asset::BTC])
+            .await
+            .unwrap();
+
+        assert_eq!(prices[&Asset::SOL].price_usd, 150.0);
+        assert_eq!(prices[&Asset::SOL].confidence, Some(3.0));
+        assert_eq!(prices[&Asset::BTC].confidence, None);
+        assert_eq!(provider.degraded_assets(), vec![Asset::SOL]);
+        match events.try_recv().unwrap() {
+            MarketPriceEvent::ProviderDivergence {
+                asset,
+                deviation_bps,
+                ..
+            } => {
+                assert_eq!(asset, Asset::SOL);
+                assert!((deviation_bps - 196.08).abs() < 0.01);
+            }
+            other => panic!("unexpected event: {}", other),
+        }
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_partial_fetch_checks_the_prices_returned() {
+        let primary = Arc::new(MockProvider::new());
+        let reference = Arc::new(MockProvider::new());
+        primary.set_price(Asset::SOL, 150.0);
+        primary.set_error(Asset::BTC, ProviderError::Timeout);
+        reference.set_price(Asset::SOL, 153.0);
+        reference.set_price(Asset::BTC, 60000.0);
+        let provider = CrossCheckProvider::new(primary, reference, 50.0);
+
+        let results = provider
+            .fetch_prices_partial(&[Asset::SOL, Asset::BTC], None)
+            .await;
+
+        assert_eq!(results[&Asset::SOL].as_ref().unwrap().confidence, Some(3.0));
+        assert!(matches!(results[&Asset::BTC], Err(ProviderError::Timeout)));
+        assert_eq!(provider.degraded_assets(), vec![Asset::SOL]);
+    }
+}
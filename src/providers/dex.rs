@@ -0,0 +1,327 @@
+//! DEX pool price provider (Solana JSON-RPC `getMultipleAccounts`)
+//!
+//! Derives spot prices from on-chain AMM state rather than off-chain
+//! quotes, so the tracker can compare what a swap would actually execute
+//! at against aggregator prices. Two pool layouts are supported:
+//!
+//! - [`PoolKind::ConstantProduct`]: Raydium AMM v4 and similar pools, priced
+//!   as the ratio of the quote vault balance to the base vault balance.
+//!   Raydium v4 also keeps part of its liquidity on the order book, so this
+//!   is an approximation of the pool price.
+//! - [`PoolKind::Whirlpool`]: Orca concentrated-liquidity pools, priced from
+//!   the pool's `sqrt_price`.
+//!
+//! Pools must be quoted in a USD stablecoin; the quote is taken as 1 USD.
+//! Reads go through a [`SolanaRpc`] client, which can spread them over
+//! several endpoints.
+
+use crate::{
+    error::ProviderError,
+    http::HttpTransport,
+    parse,
+    provider::{MarketPriceProvider, ProviderCapabilities},
+    rpc::{RpcConfig, SolanaRpc},
+    types::{Asset, PriceData},
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Byte offset of `amount` in an SPL token account
+const TOKEN_AMOUNT_OFFSET: usize = 64;
+
+/// Byte offset of `sqrt_price` (Q64.64) in an Orca Whirlpool account
+const WHIRLPOOL_SQRT_PRICE_OFFSET: usize = 65;
+
+/// On-chain layout of a pool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolKind {
+    /// Constant-product pool priced from its two token vaults
+    ConstantProduct {
+        /// Token account holding the base asset
+        base_vault: String,
+        /// Token account holding the quote stablecoin
+        quote_vault: String,
+    },
+    /// Orca Whirlpool priced from its `sqrt_price`; the base asset must be
+    /// the pool's token A
+    Whirlpool {
+        /// Whirlpool account
+        pool: String,
+    },
+}
+
+/// A pool used to price one asset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DexPool {
+    /// Asset priced by the pool
+    pub asset: Asset,
+    /// Pool layout and accounts
+    pub kind: PoolKind,
+    /// Decimals of the base asset's mint
+    pub base_decimals: u8,
+    /// Decimals of the quote stablecoin's mint
+    pub quote_decimals: u8,
+}
+
+impl DexPool {
+    /// Orca SOL/USDC Whirlpool
+    pub fn orca_sol_usdc() -> Self {
+        Self {
+            asset: Asset::SOL,
+            kind: PoolKind::Whirlpool {
+                pool: "Czfq3xZZDmsdGdUyrNLtRhGc47cXcZtLG4crryfu44zE".to_string(),
+            },
+            base_decimals: 9,
+            quote_decimals: 6,
+        }
+    }
+
+    /// Raydium AMM v4 SOL/USDC pool
+    pub fn raydium_sol_usdc() -> Self {
+        Self {
+            asset: Asset::SOL,
+            kind: PoolKind::ConstantProduct {
+                base_vault: "DQyrAcCrDXQ7NeoqGgDCZwBvWDcYmFCjSb9JtteuvPpz".to_string(),
+                quote_vault: "HLmqeL62xR1QoZ1HKKbXRrdN1p3phKpxRMb2VVopvBBz".to_string(),
+            },
+            base_decimals: 9,
+            quote_decimals: 6,
+        }
+    }
+
+    /// Accounts to fetch for this pool
+    fn accounts(&self) -> Vec<&str> {
+        match &self.kind {
+            PoolKind::ConstantProduct {
+                base_vault,
+                quote_vault,
+            } => vec![base_vault, quote_vault],
+            PoolKind::Whirlpool { pool } => vec![pool],
+        }
+    }
+
+    /// Computes the price from the fetched account data, in `accounts` order
+    fn price(&self, data: &[Vec<u8>]) -> Result<f64, ProviderError> {
+        let decimals = i32::from(self.base_decimals) - i32::from(self.quote_decimals);
+        match &self.kind {
+            PoolKind::ConstantProduct { .. } => {
+                let base = read_u64(&data[0], TOKEN_AMOUNT_OFFSET)?;
+                let quote = read_u64(&data[1], TOKEN_AMOUNT_OFFSET)?;
+                if base == 0 {
+                    return Err(ProviderError::InvalidResponse(format!(
+                        "Empty base vault in {} pool",
+                        self.asset.symbol()
+                    )));
+                }
+                Ok(quote as f64 / base as f64 * 10f64.powi(decimals))
+            }
+            PoolKind::Whirlpool { .. } => {
+                let sqrt_price = read_u128(&data[0], WHIRLPOOL_SQRT_PRICE_OFFSET)?;
+                let sqrt = sqrt_price as f64 / 2f64.powi(64);
+                Ok(sqrt * sqrt * 10f64.powi(decimals))
+            }
+        }
+    }
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, ProviderError> {
+    data.get(offset..offset + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().expect("slice of 8 bytes")))
+        .ok_or_else(|| ProviderError::InvalidResponse("Account data too short".to_string()))
+}
+
+fn read_u128(data: &[u8], offset: usize) -> Result<u128, ProviderError> {
+    data.get(offset..offset + 16)
+        .map(|bytes| u128::from_le_bytes(bytes.try_into().expect("slice of 16 bytes")))
+        .ok_or_else(|| ProviderError::InvalidResponse("Account data too short".to_string()))
+}
+
+/// DEX pool price provider
+///
+/// Pools are read together through `getMultipleAccounts`.
+/// Assets without a pool are reported as unsupported.
+pub struct DexPoolProvider {
+    rpc: Arc<SolanaRpc>,
+    pools: Vec<DexPool>,
+}
+
+impl DexPoolProvider {
+    /// Creates a provider for the public mainnet RPC pricing SOL from the
+    /// Orca SOL/USDC Whirlpool
+    pub fn new() -> Result<Self, ProviderError> {
+        Ok(Self::with_rpc(Arc::new(SolanaRpc::new(
+            RpcConfig::default(),
+        )?)))
+    }
+
+    /// Creates a provider for a single RPC endpoint and custom HTTP transport
+    pub fn with_transport(transport: Arc<dyn HttpTransport>, rpc_url: impl Into<String>) -> Self {
+        Self::with_rpc(Arc::new(SolanaRpc::with_transport(
+            transport,
+            RpcConfig {
+                endpoints: vec![rpc_url.into()],
+                ..Default::default()
+            },
+        )))
+    }
+
+    /// Creates a provider reading through a shared RPC client
+    pub fn with_rpc(rpc: Arc<SolanaRpc>) -> Self {
+        Self {
+            rpc,
+            pools: vec![DexPool::orca_sol_usdc()],
+        }
+    }
+
+    /// Replaces the pools used for pricing; the first pool for an asset wins
+    pub fn with_pools(mut self, pools: Vec<DexPool>) -> Self {
+        self.pools = pools;
+        self
+    }
+}
+
+#[async_trait]
+impl MarketPriceProvider for DexPoolProvider {
+    async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+        let prices = self.fetch_prices(&[asset]).await?;
+        prices
+            .get(&asset)
+            .cloned()
+            .ok_or_else(|| ProviderError::UnsupportedAsset(asset.symbol().to_string()))
+    }
+
+    async fn fetch_prices(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        let mut pools: Vec<&DexPool> = Vec::new();
+        for asset in assets {
+            if let Some(pool) = self.pools.iter().find(|p| p.asset == *asset) {
+                pools.push(pool);
+            }
+        }
+        if pools.is_empty() {
+            return Err(ProviderError::UnsupportedAsset(
+                "No DEX pool configured for the requested assets".to_string(),
+            ));
+        }
+
+        let accounts: Vec<&str> = pools.iter().flat_map(|pool| pool.accounts()).collect();
+        let data = self
+            .rpc
+            .get_multiple_accounts(&accounts)
+            .await?
+            .into_iter()
+            .zip(&accounts)
+            .map(|(data, account)| {
+                data.ok_or_else(|| {
+                    ProviderError::InvalidResponse(format!("Account {} not found", account))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut result = HashMap::new();
+        let mut offset = 0;
+        for pool in pools {
+            let count = pool.accounts().len();
+            let price_usd =
+                parse::check_price(pool.asset, pool.price(&data[offset..offset + count])?)?;
+            offset += count;
+            result.insert(
+                pool.asset,
+                PriceData::new(pool.asset, price_usd, self.provider_name().to_string()),
+            );
+        }
+
+        Ok(result)
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "dex"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        let mut supported_assets: Vec<Asset> = Vec::new();
+        for pool in &self.pools {
+            if !supported_assets.contains(&pool.asset) {
+                supported_assets.push(pool.asset);
+            }
+        }
+        ProviderCapabilities {
+            supported_assets,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SOLANA_RPC_URL;
+    use crate::http::fake::FakeTransport;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    fn account_json(data: &[u8]) -> String {
+        format!(
+            r#"{{"data":["{}","base64"],"executable":false,"lamports":1,"owner":"11111111111111111111111111111111","rentEpoch":0}}"#,
+            STANDARD.encode(data)
+        )
+    }
+
+    #[tokio::test]
+    async fn test_prices_from_pool_state() {
+        // Whirlpool with sqrt_price for 150 USDC per SOL
+        let mut whirlpool = vec![0u8; 653];
+        let sqrt_price = ((150e-3f64).sqrt() * 2f64.powi(64)) as u128;
+        whirlpool[65..81].copy_from_slice(&sqrt_price.to_le_bytes());
+
+        // Vaults holding 10 BTC (8 decimals) and 640,000 USDC
+        let mut base_vault = vec![0u8; 165];
+        base_vault[64..72].copy_from_slice(&1_000_000_000u64.to_le_bytes());
+        let mut quote_vault = vec![0u8; 165];
+        quote_vault[64..72].copy_from_slice(&640_000_000_000u64.to_le_bytes());
+
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(
+            200,
+            &format!(
+                r#"{{"jsonrpc":"2.0","result":{{"context":{{"slot":1}},"value":[{},{},{}]}},"id":1}}"#,
+                account_json(&whirlpool),
+                account_json(&base_vault),
+                account_json(&quote_vault)
+            ),
+        );
+        let provider = DexPoolProvider::with_transport(transport.clone(), SOLANA_RPC_URL)
+            .with_pools(vec![
+                DexPool::orca_sol_usdc(),
+                DexPool {
+                    asset: Asset::BTC,
+                    kind: PoolKind::ConstantProduct {
+                        base_vault: "base".to_string(),
+                        quote_vault: "quote".to_string(),
+                    },
+                    base_decimals: 8,
+                    quote_decimals: 6,
+                },
+            ]);
+
+        let prices = provider
+            .fetch_prices(&[Asset::SOL, Asset::BTC, Asset::ETH])
+            .await
+            .unwrap();
+
+        assert_eq!(prices.len(), 2);
+        assert!((prices[&Asset::SOL].price_usd - 150.0).abs() < 1e-6);
+        assert!((prices[&Asset::BTC].price_usd - 64_000.0).abs() < 1e-9);
+        let body = transport.requests()[0].body.clone().unwrap();
+        assert_eq!(body["method"], "getMultipleAccounts");
+        assert_eq!(body["params"][0].as_array().unwrap().len(), 3);
+
+        assert!(matches!(
+            provider.fetch_prices(&[Asset::ETH]).await,
+            Err(ProviderError::UnsupportedAsset(_))
+        ));
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
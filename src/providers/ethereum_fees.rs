@@ -0,0 +1,170 @@
+//! Ethereum base and priority fee provider (JSON-RPC `eth_feeHistory`)
+
+use crate::{
+    constants::ETHEREUM_RPC_URL,
+    error::ProviderError,
+    fees::{FeeEstimate, FeeProvider, FeeUnit, Percentile},
+    http::{HttpClientFactory, HttpTransport},
+    metrics::percentile,
+    rpc::RpcResponse,
+    runtime::Runtime,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// Number of recent blocks sampled by eth_feeHistory
+const FEE_HISTORY_BLOCKS: u64 = 20;
+
+/// Wei per gwei
+const WEI_PER_GWEI: f64 = 1e9;
+
+/// eth_feeHistory result; all quantities are hex-encoded wei
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeeHistory {
+    base_fee_per_gas: Vec<String>,
+    #[serde(default)]
+    reward: Vec<Vec<String>>,
+}
+
+fn parse_gwei(hex: &str) -> Option<f64> {
+    u128::from_str_radix(hex.trim_start_matches("0x"), 16)
+        .ok()
+        .map(|wei| wei as f64 / WEI_PER_GWEI)
+}
+
+/// Ethereum fee provider
+///
+/// Reports the next block's base fee and, per percentile, the median
+/// priority fee paid over the last 20 blocks, in gwei.
+pub struct EthereumFeeProvider {
+    transport: Arc<dyn HttpTransport>,
+    rpc_url: String,
+    runtime: Runtime,
+}
+
+impl EthereumFeeProvider {
+    /// Creates a provider for a public mainnet RPC using the shared HTTP client
+    pub fn new() -> Result<Self, ProviderError> {
+        Ok(Self::with_transport(
+            Arc::new(HttpClientFactory::global().client()?),
+            ETHEREUM_RPC_URL,
+        ))
+    }
+
+    /// Creates a provider for a custom RPC endpoint and HTTP transport
+    pub fn with_transport(transport: Arc<dyn HttpTransport>, rpc_url: impl Into<String>) -> Self {
+        Self {
+            transport,
+            rpc_url: rpc_url.into(),
+
+            runtime: Runtime::default(),
+        }
+    }
+
+    /// Stamps fetched estimates with `runtime`'s clock instead of the system
+    /// clock
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = runtime;
+        self
+    }
+}
+
+#[async_trait]
+impl FeeProvider for EthereumFeeProvider {
+    async fn fetch_fees(&self) -> Result<FeeEstimate, ProviderError> {
+        let percentiles: Vec<f64> = Percentile::all().iter().map(|p| p.value()).collect();
+        let request_body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_feeHistory",
+            "params": [format!("0x{:x}", FEE_HISTORY_BLOCKS), "latest", percentiles],
+        });
+
+        let response_text = self
+            .transport
+            .post_json(&self.rpc_url, &request_body)
+            .await?
+            .error_for_status()?
+            .body;
+
+        let response: RpcResponse<FeeHistory> =
+            serde_json::from_str(&response_text).map_err(|e| {
+                ProviderError::InvalidResponse(format!(
+                    "Failed to parse eth_feeHistory response: {}",
+                    e
+                ))
+            })?;
+
+        if let Some(error) = response.error {
+            return Err(ProviderError::ApiError(format!(
+                "RPC error {}: {}",
+                error.code, error.message
+            )));
+        }
+        let history = response.result.ok_or_else(|| {
+            ProviderError::InvalidResponse("eth_feeHistory returned no result".to_string())
+        })?;
+
+        // The last entry is the base fee of the next (pending) block
+        let base_fee = history
+            .base_fee_per_gas
+            .last()
+            .and_then(|fee| parse_gwei(fee));
+
+        let mut priority_fees = std::collections::HashMap::new();
+        for (i, p) in Percentile::all().iter().enumerate() {
+            let mut rewards: Vec<f64> = history
+                .reward
+                .iter()
+                .filter_map(|block| block.get(i).and_then(|r| parse_gwei(r)))
+                .collect();
+            if rewards.is_empty() {
+                continue;
+            }
+            rewards.sort_by(|a, b| a.total_cmp(b));
+            priority_fees.insert(*p, percentile(&rewards, 50.0));
+        }
+
+        Ok(FeeEstimate {
+            priority_fees,
+            base_fee,
+            unit: FeeUnit::Gwei,
+            last_updated: self.runtime.now(),
+            source: "ethereum-rpc".to_string(),
+        })
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "ethereum-rpc"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::fake::FakeTransport;
+
+    #[tokio::test]
+    async fn test_fetch_fee_history() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(
+            200,
+            r#"{"jsonrpc":"2.0","id":1,"result":{
+                "oldestBlock":"0x1",
+                "baseFeePerGas":["0x3b9aca00","0x77359400"],
+                "gasUsedRatio":[0.5],
+                "reward":[["0x5f5e100","0xbebc200","0x1dcd6500","0x3b9aca00","0x77359400"]]
+            }}"#,
+        );
+        let provider = EthereumFeeProvider::with_transport(transport, ETHEREUM_RPC_URL);
+
+        let estimate = provider.fetch_fees().await.unwrap();
+
+        assert_eq!(estimate.base_fee, Some(2.0));
+        assert_eq!(estimate.priority_fees[&Percentile::P50], 0.2);
+        assert_eq!(estimate.priority_fees[&Percentile::P99], 2.0);
+        assert_eq!(estimate.unit, FeeUnit::Gwei);
+    }
+}
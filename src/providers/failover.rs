@@ -2,25 +2,201 @@
 
 use crate::{
     error::ProviderError,
-    provider::MarketPriceProvider,
-    types::{Asset, PriceData},
+    event_id::{EventIdGenerator, UuidV7Generator},
+    metrics::{ConnectionStats, FetchHealth, QuotaUsage, StatusThresholds, StreamMetrics},
+    provider::{self, MarketPriceProvider, ProviderCapabilities},
+    runtime::Runtime,
+    types::{Asset, MarketPriceEvent, PriceData, SourceInfo, SourceMethod},
 };
 use async_trait::async_trait;
+use futures::future::{self, FutureExt};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::broadcast;
+
+/// How [`FailoverProvider`] seeds the store on startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BootstrapStrategy {
+    /// Query every provider at once and use the first successful response
+    #[default]
+    First,
+    /// Wait for every provider and use the median price per asset
+    Median,
+    /// Walk the failover chain in order, as regular fetches do
+    Sequential,
+}
 
 /// Price provider that attempts to fetch from multiple providers in order
 /// until one succeeds.
+///
+/// Each provider's status is tracked from its fetch outcomes, see
+/// [`StatusThresholds`], and every change is emitted as a
+/// [`MarketPriceEvent::ProviderStatusChanged`]. An unavailable provider is
+/// moved to the back of the chain until
+/// [`retry_unavailable_after`](StatusThresholds::retry_unavailable_after)
+/// has passed since its last failure.
 pub struct FailoverProvider {
     providers: Vec<Arc<dyn MarketPriceProvider>>,
+    /// Fetch health of each provider, by index
+    health: Vec<Mutex<FetchHealth>>,
+    thresholds: StatusThresholds,
+    bootstrap: BootstrapStrategy,
+    event_tx: broadcast::Sender<MarketPriceEvent>,
+    event_ids: Arc<dyn EventIdGenerator>,
+    runtime: Runtime,
 }
 
 impl FailoverProvider {
     /// Creates a new failover provider with a list of providers
-    /// 
+    ///
     /// The providers are tried in the order they are provided.
     pub fn new(providers: Vec<Arc<dyn MarketPriceProvider>>) -> Self {
-        Self { providers }
+        let health = providers
+            .iter()
+            .map(|provider| Mutex::new(FetchHealth::new(provider.provider_name())))
+            .collect();
+        let (event_tx, _) = broadcast::channel(100);
+        Self {
+            providers,
+            health,
+            thresholds: StatusThresholds::default(),
+            bootstrap: BootstrapStrategy::default(),
+            event_tx,
+            event_ids: Arc::new(UuidV7Generator),
+            runtime: Runtime::default(),
+        }
+    }
+
+    /// Sets when fetch outcomes change a provider's status
+    pub fn with_status_thresholds(mut self, thresholds: StatusThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Draws status event ids from `event_ids` instead of UUIDv7s
+    pub fn with_event_ids(mut self, event_ids: Arc<dyn EventIdGenerator>) -> Self {
+        self.event_ids = event_ids;
+        self
+    }
+
+    /// Times cooldowns and status events with `runtime`'s clock instead of
+    /// the system clock
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    /// Sets how the initial prices are fetched
+    pub fn with_bootstrap(mut self, strategy: BootstrapStrategy) -> Self {
+        self.bootstrap = strategy;
+        self
+    }
+
+    /// The providers in failover order, with unavailable ones moved to the
+    /// back, and behind them those close to their daily quota so their
+    /// reserve is only used as a last resort
+    fn chain(&self) -> Vec<(&Arc<dyn MarketPriceProvider>, &Mutex<FetchHealth>)> {
+        let now = self.runtime.now();
+        let mut chain: Vec<_> = self.providers.iter().zip(&self.health).collect();
+        chain.sort_by_key(|(provider, health)| {
+            let nearly_exhausted = provider
+                .quota_usage()
+                .is_some_and(|quota| quota.is_nearly_exhausted());
+            let cooling_down = health
+                .lock()
+                .unwrap()
+                .is_cooling_down(self.thresholds.retry_unavailable_after, now);
+            (nearly_exhausted, cooling_down)
+        });
+        chain
+    }
+
+    /// Records a provider's fetch outcome, announcing any status change
+    fn record(&self, health: &Mutex<FetchHealth>, error: Option<&ProviderError>) {
+        let now = self.runtime.now();
+        let (provider, changed) = {
+            let mut health = health.lock().unwrap();
+            let changed = health.record(error, &self.thresholds, now);
+            (health.provider_name.clone(), changed)
+        };
+        if let Some(status) = changed {
+            tracing::info!(
+                provider = %provider,
+                status = ?status,
+                "Failover provider status changed"
+            );
+            let _ = self.event_tx.send(MarketPriceEvent::ProviderStatusChanged {
+                id: self.event_ids.next_id(),
+                provider,
+                status,
+                timestamp: now,
+            });
+        }
+    }
+
+    /// Returns the first successful response from all providers queried at once
+    async fn bootstrap_first(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        let fetches = self
+            .providers
+            .iter()
+            .map(|provider| provider.fetch_prices(assets).boxed());
+        let (prices, _pending) = future::select_ok(fetches).await?;
+        Ok(prices
+            .into_iter()
+            .map(|(asset, price)| (asset, attribute(price, SourceMethod::FirstResponse)))
+            .collect())
+    }
+
+    /// Combines every provider's response, taking the median price per asset
+    async fn bootstrap_median(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        let responses =
+            future::join_all(self.providers.iter().map(|p| p.fetch_prices(assets))).await;
+
+        let mut candidates: HashMap<Asset, Vec<PriceData>> = HashMap::new();
+        let mut last_error = None;
+        for (response, health) in responses.into_iter().zip(&self.health) {
+            self.record(health, response.as_ref().err());
+            match response {
+                Ok(prices) => {
+                    for (asset, price) in prices {
+                        candidates.entry(asset).or_default().push(price);
+                    }
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        if candidates.is_empty() {
+            return Err(last_error.unwrap_or_else(|| {
+                ProviderError::InvalidResponse("No providers configured for failover".to_string())
+            }));
+        }
+
+        Ok(candidates
+            .into_iter()
+            .map(|(asset, mut prices)| {
+                prices.sort_by(|a, b| a.price_usd.total_cmp(&b.price_usd));
+                // Lower median for even counts, so the seed is a real sample
+                let median = prices.swap_remove((prices.len() - 1) / 2);
+                let contributors = std::iter::once(&median)
+                    .chain(&prices)
+                    .map(|price| price.source.clone())
+                    .collect();
+                let source_info = SourceInfo {
+                    primary: median.source.clone(),
+                    contributors,
+                    method: SourceMethod::Median,
+                };
+                (asset, median.with_source_info(source_info))
+            })
+            .collect())
     }
 }
 
@@ -29,8 +205,16 @@ impl MarketPriceProvider for FailoverProvider {
     async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
         let mut last_error = None;
 
-        for provider in &self.providers {
-            match provider.fetch_price(asset).await {
+        for (provider, health) in self.chain() {
+            if !provider.capabilities().supports(asset) {
+                continue;
+            }
+            let result = provider.fetch_price(asset).await;
+            self.record(health, result.as_ref().err());
+            match result {
+                Ok(price) if last_error.is_some() => {
+                    return Ok(attribute(price, SourceMethod::Failover))
+                }
                 Ok(price) => return Ok(price),
                 Err(e) => {
                     tracing::warn!(
@@ -45,7 +229,11 @@ impl MarketPriceProvider for FailoverProvider {
         }
 
         Err(last_error.unwrap_or_else(|| {
-            ProviderError::InvalidResponse("No providers configured for failover".to_string())
+            if self.providers.is_empty() {
+                ProviderError::InvalidResponse("No providers configured for failover".to_string())
+            } else {
+                ProviderError::UnsupportedAsset(asset.symbol().to_string())
+            }
         }))
     }
 
@@ -55,8 +243,20 @@ impl MarketPriceProvider for FailoverProvider {
     ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
         let mut last_error = None;
 
-        for provider in &self.providers {
-            match provider.fetch_prices(assets).await {
+        for (provider, health) in self.chain() {
+            let capabilities = provider.capabilities();
+            if !assets.iter().any(|asset| capabilities.supports(*asset)) {
+                continue;
+            }
+            let result = provider.fetch_prices(assets).await;
+            self.record(health, result.as_ref().err());
+            match result {
+                Ok(prices) if last_error.is_some() => {
+                    return Ok(prices
+                        .into_iter()
+                        .map(|(asset, price)| (asset, attribute(price, SourceMethod::Failover)))
+                        .collect())
+                }
                 Ok(prices) => return Ok(prices),
                 Err(e) => {
                     tracing::warn!(
@@ -70,13 +270,284 @@ impl MarketPriceProvider for FailoverProvider {
         }
 
         Err(last_error.unwrap_or_else(|| {
-            ProviderError::InvalidResponse("No providers configured for failover".to_string())
+            if self.providers.is_empty() {
+                ProviderError::InvalidResponse("No providers configured for failover".to_string())
+            } else {
+                ProviderError::UnsupportedAsset(
+                    "No provider supports the requested assets".to_string(),
+                )
+            }
         }))
     }
 
+    /// Walks the chain like [`fetch_prices`](Self::fetch_prices), asking
+    /// each provider only for the assets still failed or missing
+    async fn fetch_prices_partial(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+        let mut results: HashMap<Asset, Result<PriceData, ProviderError>> = HashMap::new();
+        let mut pending = assets.to_vec();
+
+        for (provider, health) in self.chain() {
+            let capabilities = provider.capabilities();
+            let batch: Vec<Asset> = pending
+                .iter()
+                .copied()
+                .filter(|asset| capabilities.supports(*asset))
+                .collect();
+            if batch.is_empty() {
+                continue;
+            }
+
+            let mut fetched = provider.fetch_prices_partial(&batch, deadline).await;
+            let failed = fetched.values().all(Result::is_err);
+            self.record(
+                health,
+                fetched
+                    .values()
+                    .find_map(|result| result.as_ref().err())
+                    .filter(|_| failed),
+            );
+
+            for asset in batch {
+                let result = fetched.remove(&asset).unwrap_or_else(|| {
+                    Err(ProviderError::MissingPrice(asset.symbol().to_string()))
+                });
+                match result {
+                    Ok(price) => {
+                        // An earlier provider failed this asset
+                        let price = match results.get(&asset) {
+                            Some(Err(_)) => attribute(price, SourceMethod::Failover),
+                            _ => price,
+                        };
+                        results.insert(asset, Ok(price));
+                        pending.retain(|pending| *pending != asset);
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            provider = provider.provider_name(),
+                            asset = asset.symbol(),
+                            error = %e,
+                            "Provider failed to fetch price"
+                        );
+                        results.insert(asset, Err(e));
+                    }
+                }
+            }
+            if pending.is_empty() {
+                break;
+            }
+        }
+
+        for asset in assets {
+            results.entry(*asset).or_insert_with(|| {
+                Err(if self.providers.is_empty() {
+                    ProviderError::InvalidResponse(
+                        "No providers configured for failover".to_string(),
+                    )
+                } else {
+                    ProviderError::UnsupportedAsset(asset.symbol().to_string())
+                })
+            });
+        }
+        results
+    }
+
+    async fn bootstrap(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        if self.providers.is_empty() {
+            return Err(ProviderError::InvalidResponse(
+                "No providers configured for failover".to_string(),
+            ));
+        }
+
+        match self.bootstrap {
+            BootstrapStrategy::First => self.bootstrap_first(assets).await,
+            BootstrapStrategy::Median => self.bootstrap_median(assets).await,
+            BootstrapStrategy::Sequential => self.fetch_prices(assets).await,
+        }
+    }
+
     fn provider_name(&self) -> &'static str {
         // We return the name of the first provider as the primary identifier,
         // or "failover" if we want to be explicit.
         "failover"
     }
+
+    fn connection_stats(&self) -> Option<ConnectionStats> {
+        self.providers.iter().find_map(|p| p.connection_stats())
+    }
+
+    fn stream_metrics(&self) -> Option<StreamMetrics> {
+        self.providers.iter().find_map(|p| p.stream_metrics())
+    }
+
+    fn quota_usage(&self) -> Option<QuotaUsage> {
+        self.providers.iter().find_map(|p| p.quota_usage())
+    }
+
+    fn subscribe_events(&self) -> Option<broadcast::Receiver<MarketPriceEvent>> {
+        Some(self.event_tx.subscribe())
+    }
+
+    fn failover_health(&self) -> Option<Vec<FetchHealth>> {
+        Some(
+            self.health
+                .iter()
+                .map(|health| health.lock().unwrap().clone())
+                .collect(),
+        )
+    }
+
+    /// Assets any provider in the chain supports; optional fields only if
+    /// every provider reports them, and the primary's rate limit
+    fn capabilities(&self) -> ProviderCapabilities {
+        let mut capabilities = ProviderCapabilities {
+            supported_assets: Vec::new(),
+            change_24h: !self.providers.is_empty(),
+            volume_24h: !self.providers.is_empty(),
+            ..Default::default()
+        };
+        for (i, provider) in self.providers.iter().enumerate() {
+            let caps = provider.capabilities();
+            for asset in caps.supported_assets {
+                if !capabilities.supports(asset) {
+                    capabilities.supported_assets.push(asset);
+                }
+            }
+            capabilities.change_24h &= caps.change_24h;
+            capabilities.volume_24h &= caps.volume_24h;
+            if i == 0 {
+                capabilities.max_requests_per_minute = caps.max_requests_per_minute;
+            }
+            capabilities.max_batch_size =
+                provider::tighter(capabilities.max_batch_size, caps.max_batch_size);
+        }
+        capabilities
+    }
+}
+
+/// Marks how a provider's price was picked, keeping the sources it
+/// already attributed
+fn attribute(price: PriceData, method: SourceMethod) -> PriceData {
+    let SourceInfo {
+        primary,
+        contributors,
+        ..
+    } = price.attribution();
+    price.with_source_info(SourceInfo {
+        primary,
+        contributors,
+        method,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::mock::MockProvider;
+    use crate::types::ProviderStatus;
+
+    fn mock(price: Option<f64>) -> Arc<dyn MarketPriceProvider> {
+        let provider = MockProvider::new();
+        match price {
+            Some(price) => provider.set_price(Asset::SOL, price),
+            None => provider.set_error(Asset::SOL, ProviderError::Timeout),
+        }
+        Arc::new(provider)
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_strategies() {
+        let first = FailoverProvider::new(vec![mock(None), mock(Some(150.0))]);
+        let prices = first.bootstrap(&[Asset::SOL]).await.unwrap();
+        assert_eq!(prices[&Asset::SOL].price_usd, 150.0);
+
+        let median = FailoverProvider::new(vec![
+            mock(Some(151.0)),
+            mock(None),
+            mock(Some(149.0)),
+            mock(Some(150.0)),
+        ])
+        .with_bootstrap(BootstrapStrategy::Median);
+        let prices = median.bootstrap(&[Asset::SOL]).await.unwrap();
+        assert_eq!(prices[&Asset::SOL].price_usd, 150.0);
+        let source_info = prices[&Asset::SOL].attribution();
+        assert_eq!(source_info.method, SourceMethod::Median);
+        assert_eq!(source_info.contributors.len(), 3);
+
+        let failing = FailoverProvider::new(vec![mock(None), mock(None)]);
+        assert!(failing.bootstrap(&[Asset::SOL]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_skips_providers_without_the_asset() {
+        let btc_only = Arc::new(MockProvider::new());
+        btc_only.set_price(Asset::BTC, 60_000.0);
+        btc_only.set_supported(&[Asset::BTC]);
+        let failover = FailoverProvider::new(vec![btc_only.clone(), mock(Some(150.0))]);
+
+        let price = failover.fetch_price(Asset::SOL).await.unwrap();
+        assert_eq!(price.price_usd, 150.0);
+        assert_eq!(btc_only.call_count(), 0);
+        let health = failover.failover_health().unwrap();
+        assert!(health[0].last_successful_fetch.is_none());
+        assert!(health[1].last_successful_fetch.is_some());
+        assert!(failover.capabilities().supports(Asset::ETH));
+    }
+
+    #[tokio::test]
+    async fn test_partial_fetch_retries_only_failed_assets() {
+        let primary = Arc::new(MockProvider::new());
+        primary.set_price(Asset::SOL, 150.0);
+        primary.set_error(Asset::BTC, ProviderError::Timeout);
+        let backup = Arc::new(MockProvider::new());
+        backup.set_price(Asset::SOL, 151.0);
+        backup.set_price(Asset::BTC, 60_000.0);
+        let failover = FailoverProvider::new(vec![primary, backup.clone()]);
+
+        let results = failover
+            .fetch_prices_partial(&[Asset::SOL, Asset::BTC, Asset::ETH], None)
+            .await;
+
+        assert_eq!(results[&Asset::SOL].as_ref().unwrap().price_usd, 150.0);
+        let btc = results[&Asset::BTC].as_ref().unwrap();
+        assert_eq!(btc.price_usd, 60_000.0);
+        assert_eq!(btc.attribution().method, SourceMethod::Failover);
+        assert!(results[&Asset::ETH].is_err());
+        // One batch call plus a lookup each for BTC and ETH, none for SOL
+        assert_eq!(backup.call_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_provider_moves_to_the_back() {
+        let primary = Arc::new(MockProvider::new());
+        primary.set_error(Asset::SOL, ProviderError::Timeout);
+        let failover = FailoverProvider::new(vec![primary.clone(), mock(Some(150.0))])
+            .with_status_thresholds(StatusThresholds {
+                degraded_after_failures: 1,
+                unavailable_after_failures: 1,
+                ..Default::default()
+            });
+        let mut events = failover.subscribe_events().unwrap();
+
+        let price = failover.fetch_price(Asset::SOL).await.unwrap();
+        assert_eq!(price.attribution().method, SourceMethod::Failover);
+        failover.fetch_price(Asset::SOL).await.unwrap();
+        assert_eq!(primary.call_count(), 2);
+        for expected in [ProviderStatus::Degraded, ProviderStatus::Unavailable] {
+            assert!(matches!(
+                events.try_recv().unwrap(),
+                MarketPriceEvent::ProviderStatusChanged { status, .. } if status == expected
+            ));
+        }
+
+        // Skipped while the backup answers
+        failover.fetch_price(Asset::SOL).await.unwrap();
+        assert_eq!(primary.call_count(), 2);
+    }
 }
@@ -0,0 +1,157 @@
+//! Fiat exchange rate provider (Frankfurter, serving ECB reference rates)
+
+use crate::{
+    constants::FRANKFURTER_API_URL,
+    error::ProviderError,
+    fx::{Currency, FxProvider, FxRate},
+    http::{HttpClientFactory, HttpTransport},
+    parse,
+    runtime::Runtime,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Response from `/latest`, with rates quoted as units per base currency
+#[derive(Debug, Deserialize)]
+struct LatestResponse {
+    date: NaiveDate,
+    rates: HashMap<String, Box<RawValue>>,
+}
+
+/// Exchange rate provider for the ECB reference rates published through
+/// the Frankfurter API
+///
+/// The ECB publishes once per working day around 16:00 CET, so rates do
+/// not move on weekends and holidays.
+pub struct FrankfurterProvider {
+    transport: Arc<dyn HttpTransport>,
+    api_url: String,
+    runtime: Runtime,
+}
+
+impl FrankfurterProvider {
+    /// Creates a provider for the public Frankfurter API using the shared HTTP client
+    pub fn new() -> Result<Self, ProviderError> {
+        Ok(Self::with_transport(
+            Arc::new(HttpClientFactory::global().client()?),
+            FRANKFURTER_API_URL,
+        ))
+    }
+
+    /// Creates a provider for a custom (e.g. self-hosted) endpoint and HTTP transport
+    pub fn with_transport(transport: Arc<dyn HttpTransport>, api_url: impl Into<String>) -> Self {
+        Self {
+            transport,
+            api_url: api_url.into(),
+
+            runtime: Runtime::default(),
+        }
+    }
+
+    /// Stamps fetched rates with `runtime`'s clock instead of the system
+    /// clock
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = runtime;
+        self
+    }
+}
+
+#[async_trait]
+impl FxProvider for FrankfurterProvider {
+    async fn fetch_rates(
+        &self,
+        currencies: &[Currency],
+    ) -> Result<HashMap<Currency, FxRate>, ProviderError> {
+        let foreign: Vec<&str> = currencies
+            .iter()
+            .filter(|currency| **currency != Currency::USD)
+            .map(Currency::code)
+            .collect();
+        if foreign.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let url = format!("{}/latest?from=USD&to={}", self.api_url, foreign.join(","));
+
+        tracing::debug!(provider = "frankfurter", url = %url, "Fetching exchange rates");
+
+        let response_text = self.transport.get(&url).await?.error_for_status()?.body;
+        let response: LatestResponse = serde_json::from_str(&response_text).map_err(|e| {
+            ProviderError::InvalidResponse(format!(
+                "Failed to parse Frankfurter response: {}. Response: {}",
+                e, response_text
+            ))
+        })?;
+
+        let now = self.runtime.now();
+        let mut rates = HashMap::new();
+        for (code, raw) in &response.rates {
+            let Some(currency) = Currency::from_code(code) else {
+                continue;
+            };
+            // Quoted as units of the currency per USD
+            let per_usd = match parse::number(raw) {
+                Ok(per_usd) if per_usd > 0.0 => per_usd,
+                _ => {
+                    tracing::debug!(provider = "frankfurter", currency = %code, raw = raw.get(), "Skipping invalid exchange rate");
+                    continue;
+                }
+            };
+            rates.insert(
+                currency,
+                FxRate {
+                    currency,
+                    usd_per_unit: 1.0 / per_usd,
+                    as_of: response.date,
+                    last_updated: now,
+                    source: self.provider_name().to_string(),
+                },
+            );
+        }
+
+        if rates.is_empty() {
+            return Err(ProviderError::InvalidResponse(
+                "No exchange rates returned".to_string(),
+            ));
+        }
+        Ok(rates)
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "frankfurter"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fx::{FxConfig, FxStore};
+    use crate::http::fake::FakeTransport;
+
+    #[tokio::test]
+    async fn test_fetch_rates_and_convert() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(
+            200,
+            r#"{"amount":1.0,"base":"USD","date":"2024-05-10","rates":{"EUR":0.8,"GBP":"NaN","JPY":155.0}}"#,
+        );
+        let provider = FrankfurterProvider::with_transport(transport.clone(), FRANKFURTER_API_URL);
+        let fx = FxStore::new(Arc::new(provider), FxConfig::default());
+
+        fx.refresh_now().await.unwrap();
+
+        assert_eq!(
+            transport.requests()[0].url,
+            "https://api.frankfurter.app/latest?from=USD&to=EUR,GBP,JPY"
+        );
+        let eur = fx.get_rate(Currency::EUR).unwrap();
+        assert_eq!(eur.usd_per_unit, 1.25);
+        assert_eq!(eur.as_of, NaiveDate::from_ymd_opt(2024, 5, 10).unwrap());
+        assert_eq!(fx.convert(100.0, Currency::EUR).unwrap(), 80.0);
+        assert_eq!(fx.convert(100.0, Currency::USD).unwrap(), 100.0);
+        assert!(fx.get_rate(Currency::GBP).is_err());
+    }
+}
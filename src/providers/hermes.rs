@@ -1,87 +1,153 @@
+//! Pyth Hermes (V2) streaming price provider implementation
+
+use crate::constants::{HERMES_API_URL, HERMES_LATEST_ENDPOINT, HERMES_STREAM_ENDPOINT};
+use crate::feeds;
+use crate::http::{HttpClientConfig, HttpClientFactory, HttpTransport};
+use crate::metrics::{
+    ConnectionEvent, ConnectionMonitor, ConnectionStats, StreamMetrics, StreamMonitor,
+};
+use crate::parse;
+use crate::provider::ProviderCapabilities;
 use crate::store::MarketPriceStore;
+use crate::symbols::SymbolRegistry;
 use crate::types::{Asset, PriceData};
 use crate::ProviderError;
 use async_trait::async_trait;
+use chrono::DateTime;
 use eventsource_stream::Eventsource;
 use futures::stream::StreamExt;
 use reqwest::Client;
 use serde::Deserialize;
+use serde_json::value::RawValue;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tokio::sync::broadcast;
-use tracing::{error, info};
 
 #[derive(Debug, Deserialize)]
 struct HermesPriceUpdate {
     id: String,
     price: HermesPrice,
+    #[serde(default)]
+    ema_price: Option<HermesPrice>,
 }
 
 #[derive(Debug, Deserialize)]
 struct HermesPrice {
     price: String,
-    #[allow(dead_code)]
     conf: String,
     expo: i32,
-    #[allow(dead_code)]
     publish_time: i64,
 }
 
+/// A batch of updates, decoded one by one so a malformed update is
+/// skipped without losing the rest
 #[derive(Debug, Deserialize)]
 struct HermesMessage {
-    parsed: Vec<HermesPriceUpdate>,
+    parsed: Vec<Box<RawValue>>,
 }
 
-#[allow(dead_code)]
-struct HermesStats {
-    total_updates: u64,
-    last_update: std::time::Instant,
+impl HermesMessage {
+    /// Decodes the updates, skipping malformed ones
+    fn updates(&self) -> impl Iterator<Item = HermesPriceUpdate> + '_ {
+        self.parsed.iter().filter_map(|update| {
+            serde_json::from_str(update.get())
+                .inspect_err(|e| {
+                    tracing::debug!(provider = "hermes", error = %e, "Skipping malformed Hermes update");
+                })
+                .ok()
+        })
+    }
 }
 
 pub struct HermesProvider {
-    client: reqwest::Client,
+    stream_client: reqwest::Client,
     prices: Arc<RwLock<HashMap<Asset, PriceData>>>,
-    #[allow(dead_code)]
-    stats: Arc<RwLock<HermesStats>>,
+    updates: Arc<StreamMonitor>,
+    connection: Arc<ConnectionMonitor>,
+    rest: Arc<dyn HttpTransport>,
+    api_url: String,
+    /// Set once the REST snapshot has served the cold start
+    rest_fallback_used: AtomicBool,
 }
 
 impl HermesProvider {
+    /// Creates a new Hermes provider using the shared HTTP clients
     pub async fn new() -> Result<Arc<Self>, ProviderError> {
-        let client = reqwest::Client::new();
-        let prices = Arc::new(RwLock::new(HashMap::new()));
-        let stats = Arc::new(RwLock::new(HermesStats {
-            total_updates: 0,
-            last_update: std::time::Instant::now(),
-        }));
-
-        let provider = Arc::new(Self {
-            client,
-            prices,
-            stats,
-        });
+        let factory = HttpClientFactory::global();
+        Ok(Self::with_clients(
+            factory.streaming_client()?,
+            factory.client()?,
+        ))
+    }
 
-        Ok(provider)
+    /// Creates a new Hermes provider with custom HTTP settings (proxy, user agent)
+    ///
+    /// The configured timeout only applies to REST requests; the SSE stream
+    /// is long-lived and never times out.
+    pub async fn with_http_config(config: &HttpClientConfig) -> Result<Arc<Self>, ProviderError> {
+        let factory = HttpClientFactory::new(config.clone());
+        Ok(Self::with_clients(
+            factory.streaming_client()?,
+            factory.client()?,
+        ))
     }
 
-    async fn stream_prices(
-        client: Client,
-        prices: Arc<RwLock<HashMap<Asset, PriceData>>>,
-        global_store: Option<Arc<MarketPriceStore>>,
-        update_tx: Option<broadcast::Sender<PriceData>>,
-        stats: Arc<RwLock<HermesStats>>,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        // Build URL
-        let mut url = "https://hermes.pyth.network/v2/updates/price/stream?".to_string();
-        let assets = Asset::all();
-        let mut asset_map = HashMap::new();
+    /// Creates a new Hermes provider from existing HTTP clients
+    ///
+    /// # Arguments
+    /// * `stream_client` - Client for the SSE stream; must not set an overall timeout
+    /// * `rest_client` - Client for REST fallback requests
+    pub fn with_clients(stream_client: Client, rest_client: Client) -> Arc<Self> {
+        Self::with_api_url(HERMES_API_URL, stream_client, rest_client)
+    }
+
+    /// Creates a new Hermes provider that talks to another Hermes
+    /// deployment, such as a self-hosted instance or a test server
+    ///
+    /// # Arguments
+    /// * `api_url` - Base URL the stream and REST endpoints are appended to
+    /// * `stream_client` - Client for the SSE stream; must not set an overall timeout
+    /// * `rest_client` - Client for REST fallback requests
+    pub fn with_api_url(api_url: &str, stream_client: Client, rest_client: Client) -> Arc<Self> {
+        Arc::new(Self {
+            stream_client,
+            rest: Arc::new(rest_client),
+            prices: Arc::new(RwLock::new(HashMap::new())),
+            updates: Arc::new(StreamMonitor::new()),
+            connection: Arc::new(ConnectionMonitor::new()),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            rest_fallback_used: AtomicBool::new(false),
+        })
+    }
+
+    /// Subscribes to stream connection lifecycle events
+    pub fn subscribe_connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.connection.subscribe()
+    }
+
+    /// Builds a Hermes URL requesting the feeds of the given assets
+    ///
+    /// Returns the URL together with a map from feed ID (without the `0x`
+    /// prefix) back to the assets priced by that feed. Several assets may
+    /// share a feed (e.g. ETH and WETH).
+    fn build_url(
+        api_url: &str,
+        endpoint: &str,
+        assets: &[Asset],
+    ) -> (String, HashMap<String, Vec<Asset>>) {
+        let mut url = format!("{}{}?", api_url, endpoint);
+        let mut asset_map: HashMap<String, Vec<Asset>> = HashMap::new();
 
         for asset in assets {
-            if let Some(id) = asset.pyth_feed_id() {
-                url.push_str(&format!("ids[]={}&", id));
-                // Store normalized ID to map back to Asset (Hermes returns 0x prefix usually)
-                asset_map.insert(id.to_string(), *asset);
-                asset_map.insert(id.replace("0x", ""), *asset); // Handle both formats just in case
+            if let Some(id) = feeds::pyth_feed_id(*asset) {
+                let key = id.trim_start_matches("0x").to_string();
+                let entry = asset_map.entry(key).or_default();
+                if entry.is_empty() {
+                    url.push_str(&format!("ids[]={}&", id));
+                }
+                entry.push(*asset);
             }
         }
 
@@ -89,81 +155,207 @@ impl HermesProvider {
             url.pop();
         }
 
-        println!("DEBUG: Connecting to Hermes URL: {}", url);
+        (url, asset_map)
+    }
+
+    /// Converts a parsed Hermes update into price data for every asset
+    /// priced by the update's feed
+    fn parse_update(
+        asset_map: &HashMap<String, Vec<Asset>>,
+        update: &HermesPriceUpdate,
+        source: &str,
+    ) -> Vec<PriceData> {
+        let Some(assets) = asset_map.get(update.id.trim_start_matches("0x")) else {
+            return Vec::new();
+        };
+        let Some(&feed_asset) = assets.first() else {
+            return Vec::new();
+        };
+        let price = match parse::fixed_point_price(
+            feed_asset,
+            &update.price.price,
+            update.price.expo,
+        ) {
+            Ok(price) => price,
+            Err(e) => {
+                tracing::debug!(provider = "hermes", feed = %update.id, error = %e, "Skipping malformed Hermes price");
+                return Vec::new();
+            }
+        };
+        let confidence = parse::fixed_point(&update.price.conf, update.price.expo).ok();
+        #[cfg(feature = "decimal")]
+        let price_decimal = pyth_decimal(&update.price.price, update.price.expo);
+        let ema_price = update
+            .ema_price
+            .as_ref()
+            .and_then(|ema| parse::fixed_point(&ema.price, ema.expo).ok());
+
+        assets
+            .iter()
+            .map(|asset| {
+                let mut price_data = PriceData::new(*asset, price, source.to_string());
+                if let Some(confidence) = confidence {
+                    price_data = price_data.with_confidence(confidence);
+                }
+                if let Some(ema_price) = ema_price {
+                    price_data = price_data.with_ema_price(ema_price);
+                }
+                #[cfg(feature = "decimal")]
+                if let Some(price_decimal) = price_decimal {
+                    price_data = price_data.with_decimal_price(price_decimal);
+                }
+                price_data
+            })
+            .collect()
+    }
+
+    /// Fetches the latest prices from the Hermes REST endpoint
+    ///
+    /// Used as a one-shot fallback while the SSE stream has not yet
+    /// delivered data. Results are written into the local cache.
+    async fn fetch_latest(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        let (url, asset_map) = Self::build_url(&self.api_url, HERMES_LATEST_ENDPOINT, assets);
+        if asset_map.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        tracing::debug!(provider = "hermes", url = %url, "Fetching latest prices from Hermes REST");
+
+        // Rate limiting and other HTTP errors are mapped by error_for_status
+        let response_text = self.rest.get(&url).await?.error_for_status()?.body;
+
+        let msg: HermesMessage = serde_json::from_str(&response_text).map_err(|e| {
+            ProviderError::InvalidResponse(format!(
+                "Failed to parse Hermes response: {}. Response: {}",
+                e, response_text
+            ))
+        })?;
+
+        let result: HashMap<Asset, PriceData> = msg
+            .updates()
+            .flat_map(|update| {
+                // The snapshot may lag, so it is dated by Pyth's publish
+                // time rather than when it was received
+                let published = DateTime::from_timestamp(update.price.publish_time, 0);
+                Self::parse_update(&asset_map, &update, "hermes-rest")
+                    .into_iter()
+                    .map(move |mut price_data| {
+                        if let Some(published) = published {
+                            price_data.last_updated = published;
+                        }
+                        price_data
+                    })
+            })
+            .map(|price_data| (price_data.asset, price_data))
+            .collect();
+
+        // Seed the local cache without overwriting fresher stream updates
+        {
+            let mut prices = self.prices.write().unwrap();
+            for (asset, price_data) in &result {
+                prices.entry(*asset).or_insert_with(|| price_data.clone());
+            }
+        }
+
+        tracing::debug!(
+            provider = "hermes",
+            count = result.len(),
+            "Fetched latest prices from Hermes REST"
+        );
+
+        Ok(result)
+    }
+
+    async fn stream_prices(
+        client: Client,
+        api_url: &str,
+        prices: Arc<RwLock<HashMap<Asset, PriceData>>>,
+        store: Arc<MarketPriceStore>,
+        update_tx: Option<broadcast::Sender<PriceData>>,
+        updates: Arc<StreamMonitor>,
+        connection: Arc<ConnectionMonitor>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let (url, asset_map) = Self::build_url(api_url, HERMES_STREAM_ENDPOINT, Asset::all());
+
+        tracing::debug!(provider = "hermes", url = %url, "Connecting to Hermes stream");
 
         let response = client.get(&url).send().await?;
 
         if !response.status().is_success() {
             let status = response.status();
             let text = response.text().await?;
-            error!("Hermes returned error: {} - {}", status, text);
+            tracing::error!(
+                provider = "hermes",
+                status = %status,
+                body = %text,
+                "Hermes returned an error response"
+            );
             return Err(Box::new(std::io::Error::other(format!(
                 "Hermes error: {} - {}",
                 status, text
             ))));
         }
 
+        connection.record(ConnectionEvent::Connected, store.runtime().now());
+        tracing::info!(provider = "hermes", "Connected to Hermes stream");
+
         let mut stream = response.bytes_stream().eventsource();
 
         while let Some(event) = stream.next().await {
             match event {
                 Ok(event) => {
-                    // tracing::trace!("Event Type: {}, Data Len: {}", event.event, event.data.len());
                     if event.event == "price_update" || event.event == "message" {
-                        tracing::trace!("Received Hermes event: {}", event.data);
+                        tracing::trace!(provider = "hermes", data = %event.data, "Received Hermes event");
                         if let Ok(msg) = serde_json::from_str::<HermesMessage>(&event.data) {
-                            for update in msg.parsed {
-                                let id = update.id;
-                                if let Some(asset) = asset_map
-                                    .get(&id)
-                                    .or_else(|| asset_map.get(&id.replace("0x", "")))
+                            for update in msg.updates() {
+                                for price_data in
+                                    Self::parse_update(&asset_map, &update, "hermes-sse")
                                 {
-                                    if let Ok(price) = update.price.price.parse::<f64>() {
-                                        let final_price = price * 10f64.powi(update.price.expo);
-                                        let price_data = PriceData::new(
-                                            *asset,
-                                            final_price,
-                                            "hermes-sse".to_string(),
-                                        );
-
-                                        // Update local cache
-                                        {
-                                            let mut prices_lock = prices.write().unwrap();
-                                            prices_lock.insert(*asset, price_data.clone());
-                                        }
-
-                                        // Update global store if available
-                                        if let Some(ref store) = global_store {
-                                            store.update_price(*asset, price_data.clone()).await;
-                                        }
-
-                                        // Broadcast if channel available
-                                        if let Some(ref tx) = update_tx {
-                                            let _ = tx.send(price_data);
-                                        }
-
-                                        tracing::debug!(
-                                            "Updated {} to ${:.2} (Hermes)",
-                                            asset.symbol(),
-                                            final_price
-                                        );
-
-                                        // Update stats
-                                        {
-                                            let mut stats_lock = stats.write().unwrap();
-                                            stats_lock.total_updates += 1;
-                                            stats_lock.last_update = std::time::Instant::now();
-                                        }
+                                    let asset = price_data.asset;
+                                    updates.record_update(asset);
+
+                                    // Update global store, skipping updates it
+                                    // rejects and keeping what its hooks stored
+                                    let Some(price_data) =
+                                        store.store_price(asset, price_data).await
+                                    else {
+                                        continue;
+                                    };
+
+                                    // Update local cache
+                                    {
+                                        let mut prices_lock = prices.write().unwrap();
+                                        prices_lock.insert(asset, price_data.clone());
+                                    }
+
+                                    tracing::debug!(
+                                        provider = "hermes",
+                                        asset = asset.symbol(),
+                                        price_usd = price_data.price_usd,
+                                        "Updated price from Hermes"
+                                    );
+
+                                    // Broadcast if channel available
+                                    if let Some(ref tx) = update_tx {
+                                        let _ = tx.send(price_data);
                                     }
                                 }
                             }
                         } else {
-                            tracing::warn!("Failed to parse Hermes message: {}", event.data);
+                            connection.record(ConnectionEvent::ParseError, store.runtime().now());
+                            tracing::warn!(
+                                provider = "hermes",
+                                data = %event.data,
+                                "Failed to parse Hermes message"
+                            );
                         }
                     }
                 }
                 Err(e) => {
-                    error!("Error in Hermes stream: {}", e);
+                    tracing::error!(provider = "hermes", error = %e, "Error in Hermes stream");
                     return Err(Box::new(e));
                 }
             }
@@ -176,26 +368,49 @@ impl HermesProvider {
 #[async_trait]
 impl crate::provider::MarketPriceProvider for HermesProvider {
     async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
-        let prices = self.prices.read().unwrap();
-        if let Some(data) = prices.get(&asset) {
-            Ok(data.clone())
-        } else {
-            Err(ProviderError::UnsupportedAsset(format!(
-                "Price not available for {}",
-                asset.symbol()
-            )))
-        }
+        let prices = self.fetch_prices(&[asset]).await?;
+        prices.get(&asset).cloned().ok_or_else(|| {
+            ProviderError::UnsupportedAsset(format!("Price not available for {}", asset.symbol()))
+        })
     }
 
     async fn fetch_prices(
         &self,
         assets: &[Asset],
     ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
-        let prices = self.prices.read().unwrap();
         let mut result = HashMap::new();
-        for asset in assets {
-            if let Some(data) = prices.get(asset) {
-                result.insert(*asset, data.clone());
+        let mut missing = Vec::new();
+        {
+            let prices = self.prices.read().unwrap();
+            for asset in assets {
+                match prices.get(asset) {
+                    Some(data) => {
+                        result.insert(*asset, data.clone());
+                    }
+                    None => missing.push(*asset),
+                }
+            }
+        }
+
+        // The stream is still cold for some assets: fall back to the REST
+        // snapshot until it succeeds once, so later misses wait for the
+        // stream
+        if !missing.is_empty() && !self.rest_fallback_used.load(Ordering::Relaxed) {
+            match self.fetch_latest(&missing).await {
+                Ok(latest) => {
+                    self.rest_fallback_used.store(true, Ordering::Relaxed);
+                    result.extend(latest);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        provider = "hermes",
+                        error = %e,
+                        "Hermes REST fallback failed"
+                    );
+                    if result.is_empty() {
+                        return Err(e);
+                    }
+                }
             }
         }
 
@@ -216,31 +431,161 @@ impl crate::provider::MarketPriceProvider for HermesProvider {
         true
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        let registry = SymbolRegistry::global();
+        ProviderCapabilities {
+            supported_assets: Asset::all()
+                .iter()
+                .copied()
+                .filter(|asset| registry.pyth_feed_id(*asset).is_some())
+                .collect(),
+            streaming: true,
+            // 30 requests per 10 seconds per IP
+            max_requests_per_minute: Some(180),
+            ..Default::default()
+        }
+    }
+
     fn start_streaming(
         &self,
         store: Arc<MarketPriceStore>,
         update_tx: broadcast::Sender<PriceData>,
     ) {
         let prices = self.prices.clone();
-        let stats = self.stats.clone();
-        let client = self.client.clone();
+        let updates = self.updates.clone();
+        let client = self.stream_client.clone();
+        let connection = self.connection.clone();
+        let api_url = self.api_url.clone();
+        let runtime = store.runtime().clone();
 
-        tokio::spawn(async move {
+        runtime.spawn_named("hermes.stream", async move {
             loop {
-                info!("Connecting to Hermes real-time stream...");
-                if let Err(e) = Self::stream_prices(
+                tracing::info!(provider = "hermes", "Connecting to Hermes real-time stream");
+                let reason = match Self::stream_prices(
                     client.clone(),
+                    &api_url,
                     prices.clone(),
-                    Some(store.clone()),
+                    store.clone(),
                     Some(update_tx.clone()),
-                    stats.clone(),
+                    updates.clone(),
+                    connection.clone(),
                 )
                 .await
                 {
-                    error!("Hermes stream disconnected: {}. Reconnecting in 5s...", e);
-                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    Ok(()) => "stream ended".to_string(),
+                    Err(e) => e.to_string(),
+                };
+
+                if connection.stats().connected {
+                    connection.record(
+                        ConnectionEvent::Disconnected {
+                            reason: reason.clone(),
+                        },
+                        store.runtime().now(),
+                    );
                 }
+                tracing::error!(
+                    provider = "hermes",
+                    reason = %reason,
+                    reconnect_delay_secs = 5,
+                    "Hermes stream disconnected, reconnecting"
+                );
+                store.runtime().sleep(Duration::from_secs(5)).await;
             }
         });
     }
+
+    fn connection_stats(&self) -> Option<ConnectionStats> {
+        Some(self.connection.stats())
+    }
+
+    fn stream_metrics(&self) -> Option<StreamMetrics> {
+        Some(self.updates.metrics(&self.connection.stats()))
+    }
+}
+
+/// Converts a Pyth fixed-point price (integer mantissa and exponent) into a
+/// decimal without going through f64
+#[cfg(feature = "decimal")]
+fn pyth_decimal(price: &str, expo: i32) -> Option<rust_decimal::Decimal> {
+    use rust_decimal::Decimal;
+
+    let mantissa = price.parse::<i64>().ok()?;
+    if expo <= 0 {
+        Decimal::try_from_i128_with_scale(mantissa as i128, expo.unsigned_abs()).ok()
+    } else {
+        Decimal::from(mantissa).checked_mul(Decimal::from(10u64.checked_pow(expo as u32)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse_latest_response() {
+        let (url, asset_map) =
+            HermesProvider::build_url(HERMES_API_URL, HERMES_LATEST_ENDPOINT, &[Asset::SOL]);
+        assert!(url.starts_with("https://hermes.pyth.network/v2/updates/price/latest?ids[]=0x"));
+
+        let body = r#"{"parsed":[{"id":"ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d","price":{"price":"14523000000","conf":"1200000","expo":-8,"publish_time":1700000000}}]}"#;
+        let msg: HermesMessage = serde_json::from_str(body).unwrap();
+        let update = msg.updates().next().unwrap();
+        let price = HermesProvider::parse_update(&asset_map, &update, "hermes-rest")
+            .pop()
+            .unwrap();
+
+        assert_eq!(price.asset, Asset::SOL);
+        assert!((price.price_usd - 145.23).abs() < 1e-9);
+        assert_eq!(price.source, "hermes-rest");
+        assert!((price.confidence.unwrap() - 0.012).abs() < 1e-9);
+        #[cfg(feature = "decimal")]
+        assert_eq!(
+            price.price_decimal,
+            Some(rust_decimal::Decimal::new(14523, 2))
+        );
+    }
+
+    #[test]
+    fn test_malformed_update_is_skipped() {
+        let (_, asset_map) =
+            HermesProvider::build_url(HERMES_API_URL, HERMES_LATEST_ENDPOINT, &[Asset::SOL]);
+        let id = "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+        let body = format!(
+            r#"{{"parsed":[
+                {{"id":"{id}","price":{{"price":"1","conf":"1","expo":400,"publish_time":1}}}},
+                {{"id":"{id}","price":{{"price":"NaN","conf":"1","expo":-8,"publish_time":1}}}},
+                {{"id":"{id}"}},
+                {{"id":"{id}","price":{{"price":"14523000000","conf":"1200000","expo":-8,"publish_time":1}}}}
+            ]}}"#
+        );
+        let msg: HermesMessage = serde_json::from_str(&body).unwrap();
+
+        let prices: Vec<PriceData> = msg
+            .updates()
+            .flat_map(|update| HermesProvider::parse_update(&asset_map, &update, "hermes-rest"))
+            .collect();
+
+        assert_eq!(prices.len(), 1);
+        assert!((prices[0].price_usd - 145.23).abs() < 1e-9);
+    }
+
+    proptest! {
+        #[test]
+        fn truncated_messages_never_panic(cut in 0usize..200, expo in any::<i32>(), mantissa in "-?[0-9]{0,40}") {
+            let (_, asset_map) = HermesProvider::build_url(HERMES_API_URL, HERMES_LATEST_ENDPOINT, &[Asset::SOL]);
+            let body = format!(
+                r#"{{"parsed":[{{"id":"ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d","price":{{"price":"{mantissa}","conf":"1","expo":{expo},"publish_time":1}}}}]}}"#
+            );
+            let body = &body[..cut.min(body.len())];
+            if let Ok(msg) = serde_json::from_str::<HermesMessage>(body) {
+                for update in msg.updates() {
+                    for price in HermesProvider::parse_update(&asset_map, &update, "hermes-rest") {
+                        prop_assert!(price.price_usd.is_finite());
+                    }
+                }
+            }
+        }
+    }
 }
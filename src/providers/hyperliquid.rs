@@ -1,68 +1,299 @@
 //! Hyperliquid price provider implementation
 
 use crate::{
-    constants::{HYPERLIQUID_API_URL, REQUEST_TIMEOUT_SECS, USER_AGENT},
+    config::Environment,
+    constants::{HYPERLIQUID_API_URL, HYPERLIQUID_TESTNET_API_URL},
+    derivatives::{DerivativesData, DerivativesProvider},
     error::ProviderError,
-    provider::MarketPriceProvider,
+    http::{BaseUrls, HttpClientConfig, HttpClientFactory, HttpResponse, HttpTransport},
+    parse,
+    provider::{self, MarketPriceProvider, ProviderCapabilities},
+    symbols::SymbolRegistry,
     types::{Asset, PriceData},
 };
 use async_trait::async_trait;
+use chrono::Utc;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Assets with a Hyperliquid perp; stablecoins, wrapped tokens and LSTs
+/// are not listed
+const HYPERLIQUID_PERPS: &[Asset] = &[
+    Asset::SOL,
+    Asset::BTC,
+    Asset::ETH,
+    Asset::BNB,
+    Asset::XRP,
+    Asset::ADA,
+    Asset::DOGE,
+    Asset::AVAX,
+    Asset::LINK,
+    Asset::JUP,
+    Asset::BONK,
+];
 
 /// Hyperliquid API request for info
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 enum HyperliquidRequest {
     AllMids,
+    MetaAndAssetCtxs,
 }
 
 /// Hyperliquid API response for allMids
 /// Returns a map of symbol to mid price as string
+///
+/// Mids are parsed one by one, so a malformed mid only fails its own asset.
+#[derive(Debug, Deserialize)]
+struct AllMidsResponse(HashMap<String, Box<RawValue>>);
+
+/// Perp universe entry from metaAndAssetCtxs
+#[derive(Debug, Deserialize)]
+struct PerpMeta {
+    name: String,
+}
+
+/// Perp universe listing from metaAndAssetCtxs
+#[derive(Debug, Deserialize)]
+struct PerpUniverse {
+    universe: Vec<PerpMeta>,
+}
+
+/// Per-perp market context, in the same order as the universe
 #[derive(Debug, Deserialize)]
-struct AllMidsResponse(HashMap<String, String>);
+#[serde(rename_all = "camelCase")]
+struct PerpAssetCtx {
+    funding: String,
+    open_interest: String,
+    mark_px: Option<String>,
+}
+
+/// Hyperliquid API response for metaAndAssetCtxs
+#[derive(Debug, Deserialize)]
+struct MetaAndAssetCtxsResponse(PerpUniverse, Vec<PerpAssetCtx>);
 
 /// Hyperliquid price provider
 pub struct HyperliquidProvider {
-    client: Client,
+    transport: Arc<dyn HttpTransport>,
+    api_urls: BaseUrls,
+    symbols: Option<Arc<SymbolRegistry>>,
 }
 
 impl HyperliquidProvider {
-    /// Creates a new Hyperliquid provider
+    /// Creates a new Hyperliquid provider using the shared HTTP client
     pub fn new() -> Result<Self, ProviderError> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-            .user_agent(USER_AGENT)
-            .build()
-            .map_err(ProviderError::NetworkError)?;
+        Ok(Self::with_client(HttpClientFactory::global().client()?))
+    }
+
+    /// Creates a new Hyperliquid provider with custom HTTP settings (proxy, timeout)
+    pub fn with_http_config(config: &HttpClientConfig) -> Result<Self, ProviderError> {
+        Ok(Self::with_client(config.build()?))
+    }
+
+    /// Creates a new Hyperliquid provider using an existing HTTP client
+    pub fn with_client(client: Client) -> Self {
+        Self::with_transport(Arc::new(client))
+    }
+
+    /// Creates a new Hyperliquid provider on top of a custom HTTP transport
+    pub fn with_transport(transport: Arc<dyn HttpTransport>) -> Self {
+        Self {
+            transport,
+            api_urls: BaseUrls::new([HYPERLIQUID_API_URL]),
+            symbols: None,
+        }
+    }
+
+    /// Targets the mainnet or testnet API
+    pub fn with_environment(self, environment: Environment) -> Self {
+        let api_url = if environment.is_test() {
+            HYPERLIQUID_TESTNET_API_URL
+        } else {
+            HYPERLIQUID_API_URL
+        };
+        self.with_api_urls(BaseUrls::new([api_url]))
+    }
+
+    /// Sends requests to these info endpoints, failing over in order
+    pub fn with_api_urls(mut self, api_urls: BaseUrls) -> Self {
+        self.api_urls = api_urls;
+        self
+    }
 
-        Ok(Self { client })
+    /// Resolves perp symbols through `symbols` instead of the global
+    /// [`SymbolRegistry`]
+    pub fn with_symbols(mut self, symbols: Arc<SymbolRegistry>) -> Self {
+        self.symbols = Some(symbols);
+        self
     }
 
-    /// Parses the Hyperliquid response into price data
+    /// Registry perp symbols are resolved through
+    fn symbols(&self) -> &SymbolRegistry {
+        self.symbols.as_deref().unwrap_or(SymbolRegistry::global())
+    }
+
+    /// Posts a request to the current info endpoint
+    async fn post(&self, request_body: &serde_json::Value) -> Result<HttpResponse, ProviderError> {
+        self.api_urls
+            .send(|url| async move { self.transport.post_json(&url, request_body).await })
+            .await
+    }
+
+    /// Parses the Hyperliquid response into a result per requested asset
     fn parse_response(
         &self,
         response: AllMidsResponse,
         assets: &[Asset],
-    ) -> HashMap<Asset, PriceData> {
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
         let mut result = HashMap::new();
 
         for asset in assets {
-            let symbol = asset.hyperliquid_symbol();
-            if let Some(price_str) = response.0.get(symbol) {
-                if let Ok(price_usd) = price_str.parse::<f64>() {
-                    result.insert(
-                        *asset,
-                        PriceData::new(*asset, price_usd, self.provider_name().to_string()),
-                    );
-                }
-            }
+            let Some(symbol) = self.symbols().hyperliquid_symbol(*asset) else {
+                result.insert(
+                    *asset,
+                    Err(ProviderError::UnsupportedAsset(asset.symbol().to_string())),
+                );
+                continue;
+            };
+            let price = match response.0.get(&symbol) {
+                Some(mid) => self.parse_mid(*asset, mid).map_err(|e| match e {
+                    ProviderError::InvalidPrice { .. } => e,
+                    e => ProviderError::InvalidResponse(format!(
+                        "Malformed Hyperliquid mid for {}: {}",
+                        symbol, e
+                    )),
+                }),
+                None => Err(ProviderError::MissingPrice(asset.symbol().to_string())),
+            };
+            result.insert(*asset, price);
         }
 
         result
     }
+
+    /// Parses one mid, quoted per lot, into a per-token price
+    fn parse_mid(&self, asset: Asset, mid: &RawValue) -> Result<PriceData, ProviderError> {
+        let lot_size = asset.hyperliquid_lot_size();
+        let price_usd = parse::price(asset, mid)?;
+        let price_data = PriceData::new(
+            asset,
+            price_usd / lot_size as f64,
+            MarketPriceProvider::provider_name(self).to_string(),
+        );
+        #[cfg(feature = "decimal")]
+        let price_data = match serde_json::from_str::<String>(mid.get())
+            .ok()
+            .and_then(|mid| mid.parse::<rust_decimal::Decimal>().ok())
+        {
+            Some(price) => {
+                price_data.with_decimal_price(price / rust_decimal::Decimal::from(lot_size))
+            }
+            None => price_data,
+        };
+        Ok(price_data)
+    }
+
+    /// Requests all mids and parses a result per requested asset
+    async fn fetch_mids(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, Result<PriceData, ProviderError>>, ProviderError> {
+        let request_body = serde_json::to_value(HyperliquidRequest::AllMids)
+            .map_err(|e| ProviderError::InvalidResponse(e.to_string()))?;
+
+        // Rate limiting and other HTTP errors are mapped by error_for_status
+        let response_text = self.post(&request_body).await?.error_for_status()?.body;
+
+        let mids: AllMidsResponse = serde_json::from_str(&response_text).map_err(|e| {
+            ProviderError::InvalidResponse(format!(
+                "Failed to parse Hyperliquid response: {}. Response: {}",
+                e, response_text
+            ))
+        })?;
+
+        Ok(self.parse_response(mids, assets))
+    }
+}
+
+#[async_trait]
+impl DerivativesProvider for HyperliquidProvider {
+    async fn fetch_derivatives(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, DerivativesData>, ProviderError> {
+        if assets.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let request_body = serde_json::to_value(HyperliquidRequest::MetaAndAssetCtxs)
+            .map_err(|e| ProviderError::InvalidResponse(e.to_string()))?;
+
+        let response_text = self.post(&request_body).await?.error_for_status()?.body;
+
+        let MetaAndAssetCtxsResponse(meta, ctxs) =
+            serde_json::from_str(&response_text).map_err(|e| {
+                ProviderError::InvalidResponse(format!(
+                    "Failed to parse Hyperliquid metaAndAssetCtxs response: {}",
+                    e
+                ))
+            })?;
+
+        let now = Utc::now();
+        // Several assets may share a perp, e.g. WETH mapped onto ETH
+        let symbols: Vec<(Asset, String)> = assets
+            .iter()
+            .filter_map(|asset| Some((*asset, self.symbols().hyperliquid_symbol(*asset)?)))
+            .collect();
+        let mut result = HashMap::new();
+        for (perp, ctx) in meta.universe.iter().zip(ctxs.iter()) {
+            let (Ok(funding_rate), Ok(open_interest)) = (
+                parse::decimal(&ctx.funding),
+                parse::decimal(&ctx.open_interest),
+            ) else {
+                tracing::warn!(
+                    provider = "hyperliquid",
+                    perp = %perp.name,
+                    "Skipping perp with malformed funding or open interest"
+                );
+                continue;
+            };
+
+            for (asset, _) in symbols.iter().filter(|(_, symbol)| *symbol == perp.name) {
+                let lot_size = asset.hyperliquid_lot_size() as f64;
+                result.insert(
+                    *asset,
+                    DerivativesData {
+                        asset: *asset,
+                        funding_rate,
+                        open_interest: open_interest * lot_size,
+                        mark_price_usd: ctx.mark_px.as_deref().and_then(|mark_px| {
+                            parse::decimal(mark_px)
+                                .and_then(|price| parse::check_price(*asset, price / lot_size))
+                                .ok()
+                        }),
+                        last_updated: now,
+                        source: "hyperliquid".to_string(),
+                    },
+                );
+            }
+        }
+
+        tracing::debug!(
+            provider = "hyperliquid",
+            count = result.len(),
+            "Fetched funding and open interest from Hyperliquid"
+        );
+
+        Ok(result)
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "hyperliquid"
+    }
 }
 
 impl Default for HyperliquidProvider {
@@ -89,55 +320,152 @@ impl MarketPriceProvider for HyperliquidProvider {
             return Ok(HashMap::new());
         }
 
-        tracing::debug!(url = HYPERLIQUID_API_URL, "Fetching prices from Hyperliquid");
+        tracing::debug!(
+            provider = "hyperliquid",
+            url = self.api_urls.current(),
+            "Fetching prices from Hyperliquid"
+        );
 
-        let request_body = HyperliquidRequest::AllMids;
+        let prices: HashMap<Asset, PriceData> = self
+            .fetch_mids(assets)
+            .await?
+            .into_iter()
+            .filter_map(|(asset, price)| Some((asset, price.ok()?)))
+            .collect();
 
-        let response = self
-            .client
-            .post(HYPERLIQUID_API_URL)
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(ProviderError::NetworkError)?;
+        if prices.is_empty() {
+            return Err(ProviderError::InvalidResponse(
+                "No prices returned from Hyperliquid".to_string(),
+            ));
+        }
+
+        tracing::debug!(
+            provider = "hyperliquid",
+            count = prices.len(),
+            "Successfully fetched prices from Hyperliquid"
+        );
 
-        // Check for rate limiting
-        if response.status().as_u16() == 429 {
-            return Err(ProviderError::RateLimitExceeded);
+        Ok(prices)
+    }
+
+    async fn fetch_prices_partial(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+        if assets.is_empty() {
+            return HashMap::new();
+        }
+        match provider::within_deadline(deadline, self.fetch_mids(assets)).await {
+            Ok(prices) => prices,
+            Err(e) => provider::per_asset(assets, Err(e)),
         }
+    }
 
-        // Check for other errors
-        if !response.status().is_success() {
-            return Err(ProviderError::ApiError(format!(
-                "HTTP {}: {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            )));
+    fn provider_name(&self) -> &'static str {
+        "hyperliquid"
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            supported_assets: HYPERLIQUID_PERPS.to_vec(),
+            // 1200 request weight per minute, at a weight of 2 per allMids
+            max_requests_per_minute: Some(600),
+            ..Default::default()
         }
+    }
+}
 
-        let response_text = response.text().await.map_err(ProviderError::NetworkError)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::fake::FakeTransport;
+    use crate::symbols::SymbolSource;
 
-        let mids: AllMidsResponse = serde_json::from_str(&response_text).map_err(|e| {
-            ProviderError::InvalidResponse(format!(
-                "Failed to parse Hyperliquid response: {}. Response: {}",
-                e, response_text
-            ))
-        })?;
+    #[tokio::test]
+    async fn test_fetch_prices_posts_all_mids() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(
+            200,
+            r#"{"SOL":"150.25","BTC":"64000.5","DOGE":"0.1","kBONK":"0.025"}"#,
+        );
+        let provider = HyperliquidProvider::with_transport(transport.clone());
 
-        let prices = self.parse_response(mids, assets);
+        let prices = provider
+            .fetch_prices(&[Asset::SOL, Asset::BTC, Asset::USDC, Asset::BONK])
+            .await
+            .unwrap();
 
-        if prices.is_empty() {
-            return Err(ProviderError::InvalidResponse(
-                "No prices returned from Hyperliquid".to_string(),
+        assert_eq!(prices.len(), 3);
+        assert_eq!(prices[&Asset::SOL].price_usd, 150.25);
+        assert_eq!(prices[&Asset::BONK].price_usd, 0.000025);
+        #[cfg(feature = "decimal")]
+        assert_eq!(
+            prices[&Asset::SOL].price_decimal,
+            Some(rust_decimal::Decimal::new(15025, 2))
+        );
+        let requests = transport.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].url, HYPERLIQUID_API_URL);
+        assert_eq!(
+            requests[0].body,
+            Some(serde_json::json!({ "type": "allMids" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_malformed_mid_fails_only_its_asset() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(200, r#"{"SOL":"150.25","BTC":"NaN","ETH":1e400}"#);
+        let provider = HyperliquidProvider::with_transport(transport);
+
+        let prices = provider
+            .fetch_prices_partial(&[Asset::SOL, Asset::BTC, Asset::ETH], None)
+            .await;
+
+        assert_eq!(prices[&Asset::SOL].as_ref().unwrap().price_usd, 150.25);
+        for asset in [Asset::BTC, Asset::ETH] {
+            assert!(matches!(
+                prices[&asset],
+                Err(ProviderError::InvalidPrice { .. })
             ));
         }
+    }
 
-        tracing::debug!(count = prices.len(), "Successfully fetched prices from Hyperliquid");
+    #[tokio::test]
+    async fn test_fetch_derivatives() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(
+            200,
+            r#"[{"universe":[{"name":"BTC","szDecimals":5},{"name":"SOL","szDecimals":2},{"name":"ETH","szDecimals":4}]},
+                [{"funding":"NaN","openInterest":"25000.5","markPx":"64000.0"},
+                 {"funding":"-0.00002","openInterest":"1000000","markPx":"150.0"},
+                 {"funding":"0.00001","openInterest":"500","markPx":"inf"}]]"#,
+        );
+        let symbols = Arc::new(SymbolRegistry::new());
+        symbols
+            .set(SymbolSource::Hyperliquid, Asset::WETH, "ETH")
+            .unwrap();
+        let provider = HyperliquidProvider::with_transport(transport.clone()).with_symbols(symbols);
 
-        Ok(prices)
-    }
+        let data = provider
+            .fetch_derivatives(&[Asset::SOL, Asset::BTC, Asset::ETH, Asset::WETH])
+            .await
+            .unwrap();
 
-    fn provider_name(&self) -> &'static str {
-        "hyperliquid"
+        // BTC's funding is malformed and ETH's mark price is not a price
+        assert_eq!(data.len(), 3);
+        assert!(!data.contains_key(&Asset::BTC));
+        assert_eq!(data[&Asset::ETH].mark_price_usd, None);
+        assert_eq!(
+            data[&Asset::WETH].funding_rate,
+            data[&Asset::ETH].funding_rate
+        );
+        assert_eq!(data[&Asset::SOL].funding_rate, -0.00002);
+        assert_eq!(data[&Asset::SOL].open_interest_usd(), Some(150_000_000.0));
+        assert_eq!(
+            transport.requests()[0].body,
+            Some(serde_json::json!({ "type": "metaAndAssetCtxs" }))
+        );
     }
 }
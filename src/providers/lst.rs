@@ -0,0 +1,266 @@
+//! Liquid staking token (LST) price provider
+//!
+//! Derives USD prices for mSOL, jitoSOL and bSOL as SOL/USD times each
+//! token's stake-pool exchange rate (SOL per LST), as reported by the
+//! Sanctum API. Direct market quotes for LSTs tend to lag the redemption
+//! value; this tracks it on every refresh.
+
+use crate::{
+    constants::{SANCTUM_API_URL, SANCTUM_SOL_VALUE_ENDPOINT},
+    error::ProviderError,
+    format::lamports_to_sol,
+    http::{HttpClientFactory, HttpTransport},
+    parse,
+    provider::{self, MarketPriceProvider, ProviderCapabilities},
+    types::{Asset, PriceData, SourceInfo, SourceMethod},
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Sanctum API response for sol-value/current
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SolValueResponse {
+    /// Lamports redeemable per whole LST, keyed by mint
+    sol_values: HashMap<String, String>,
+}
+
+/// LST price provider
+///
+/// Wraps another provider for SOL (and any non-LST assets) and prices LSTs
+/// from it. Requests that include no LSTs are passed straight through.
+pub struct LstProvider {
+    inner: Arc<dyn MarketPriceProvider>,
+    transport: Arc<dyn HttpTransport>,
+    api_url: String,
+}
+
+impl LstProvider {
+    /// Creates an LST provider on top of `inner`, using the shared HTTP client
+    pub fn new(inner: Arc<dyn MarketPriceProvider>) -> Result<Self, ProviderError> {
+        Ok(Self::with_transport(
+            inner,
+            Arc::new(HttpClientFactory::global().client()?),
+        ))
+    }
+
+    /// Creates an LST provider on top of a custom HTTP transport
+    pub fn with_transport(
+        inner: Arc<dyn MarketPriceProvider>,
+        transport: Arc<dyn HttpTransport>,
+    ) -> Self {
+        Self {
+            inner,
+            transport,
+            api_url: SANCTUM_API_URL.to_string(),
+        }
+    }
+
+    /// Fetches the exchange rate (SOL per LST) for each LST
+    async fn fetch_exchange_rates(
+        &self,
+        lsts: &[Asset],
+    ) -> Result<HashMap<Asset, f64>, ProviderError> {
+        let query = lsts
+            .iter()
+            .filter_map(|asset| asset.mint_address())
+            .map(|mint| format!("lst={}", mint))
+            .collect::<Vec<_>>()
+            .join("&");
+        let url = format!("{}{}?{}", self.api_url, SANCTUM_SOL_VALUE_ENDPOINT, query);
+
+        tracing::debug!(provider = "lst", url = %url, "Fetching LST exchange rates");
+
+        let response_text = self.transport.get(&url).await?.error_for_status()?.body;
+        let response: SolValueResponse = serde_json::from_str(&response_text).map_err(|e| {
+            ProviderError::InvalidResponse(format!(
+                "Failed to parse Sanctum response: {}. Response: {}",
+                e, response_text
+            ))
+        })?;
+
+        let mut rates = HashMap::new();
+        for asset in lsts {
+            let lamports = asset
+                .mint_address()
+                .and_then(|mint| response.sol_values.get(&mint.to_string()))
+                .and_then(|value| value.parse::<u64>().ok());
+            if let Some(lamports) = lamports {
+                rates.insert(*asset, lamports_to_sol(lamports));
+            }
+        }
+
+        Ok(rates)
+    }
+
+    /// Prices an LST from the SOL price and its exchange rate, failing if
+    /// the product is not a valid price
+    fn derive(sol: &PriceData, asset: Asset, rate: f64) -> Result<PriceData, ProviderError> {
+        let price_usd = parse::check_price(asset, sol.price_usd * rate)?;
+        let mut price_data = PriceData::new(asset, price_usd, format!("{}+sanctum", sol.source))
+            .with_source_info(SourceInfo {
+                primary: sol.source.clone(),
+                contributors: vec![sol.source.clone(), "sanctum".to_string()],
+                method: SourceMethod::Derived,
+            });
+        price_data.last_updated = sol.last_updated;
+        if let Some(confidence) = sol.confidence {
+            price_data = price_data.with_confidence(confidence * rate);
+        }
+        Ok(price_data)
+    }
+}
+
+#[async_trait]
+impl MarketPriceProvider for LstProvider {
+    async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+        let prices = self.fetch_prices(&[asset]).await?;
+        prices
+            .get(&asset)
+            .cloned()
+            .ok_or_else(|| ProviderError::UnsupportedAsset(asset.symbol().to_string()))
+    }
+
+    async fn fetch_prices(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        if !assets.iter().any(|asset| asset.is_liquid_staking_token()) {
+            return self.inner.fetch_prices(assets).await;
+        }
+        provider::any_prices(assets, self.fetch_prices_partial(assets, None).await)
+    }
+
+    /// Fetches the inner provider per asset, failing each LST on its own
+    /// when SOL or its exchange rate is unavailable
+    async fn fetch_prices_partial(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+        let (lsts, mut others): (Vec<Asset>, Vec<Asset>) = assets
+            .iter()
+            .partition(|asset| asset.is_liquid_staking_token());
+        if lsts.is_empty() {
+            return self.inner.fetch_prices_partial(assets, deadline).await;
+        }
+        if !others.contains(&Asset::SOL) {
+            others.push(Asset::SOL);
+        }
+
+        let (mut results, rates) = tokio::join!(
+            self.inner.fetch_prices_partial(&others, deadline),
+            provider::within_deadline(deadline, self.fetch_exchange_rates(&lsts))
+        );
+
+        let sol = match results.get(&Asset::SOL) {
+            Some(Ok(sol)) => Ok(sol.clone()),
+            Some(Err(e)) => Err(e.replicate()),
+            None => Err(ProviderError::MissingPrice(Asset::SOL.symbol().to_string())),
+        };
+        if !assets.contains(&Asset::SOL) {
+            results.remove(&Asset::SOL);
+        }
+
+        for asset in lsts {
+            let price = match (&sol, &rates) {
+                (Err(e), _) | (_, Err(e)) => Err(e.replicate()),
+                (Ok(sol), Ok(rates)) => rates
+                    .get(&asset)
+                    .ok_or_else(|| ProviderError::MissingPrice(asset.symbol().to_string()))
+                    .and_then(|rate| Self::derive(sol, asset, *rate)),
+            };
+            results.insert(asset, price);
+        }
+
+        results
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "lst"
+    }
+
+    /// The inner provider's capabilities, plus the LSTs when it prices SOL
+    fn capabilities(&self) -> ProviderCapabilities {
+        let mut capabilities = self.inner.capabilities();
+        capabilities.streaming = false;
+        if capabilities.supports(Asset::SOL) {
+            for asset in Asset::all() {
+                if asset.is_liquid_staking_token() && !capabilities.supports(*asset) {
+                    capabilities.supported_assets.push(*asset);
+                }
+            }
+        }
+        capabilities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::fake::FakeTransport;
+    use crate::provider::mock::MockProvider;
+
+    #[tokio::test]
+    async fn test_lst_price_from_exchange_rate() {
+        let inner = Arc::new(MockProvider::new());
+        inner.set_price(Asset::SOL, 100.0);
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(
+            200,
+            r#"{"solValues":{"J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn":"1150000000"},"errs":{}}"#,
+        );
+        let provider = LstProvider::with_transport(inner, transport.clone());
+
+        let prices = provider.fetch_prices(&[Asset::JITOSOL]).await.unwrap();
+
+        assert_eq!(prices.len(), 1);
+        assert!((prices[&Asset::JITOSOL].price_usd - 115.0).abs() < 1e-9);
+        assert!(transport.requests()[0]
+            .url
+            .ends_with("?lst=J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn"));
+
+        // No rate in the response
+        transport.push_response(200, r#"{"solValues":{},"errs":{}}"#);
+        assert!(matches!(
+            provider.fetch_price(Asset::JITOSOL).await,
+            Err(ProviderError::MissingPrice(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_partial_fetch_keeps_inner_failures_per_asset() {
+        let inner = Arc::new(MockProvider::new());
+        inner.set_price(Asset::SOL, 100.0);
+        inner.set_error(Asset::BTC, ProviderError::Timeout);
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(
+            200,
+            r#"{"solValues":{"J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn":"1150000000","mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So":"0"},"errs":{}}"#,
+        );
+        let provider = LstProvider::with_transport(inner, transport);
+
+        let results = provider
+            .fetch_prices_partial(
+                &[Asset::JITOSOL, Asset::MSOL, Asset::BSOL, Asset::BTC],
+                None,
+            )
+            .await;
+
+        assert_eq!(results.len(), 4);
+        assert!((results[&Asset::JITOSOL].as_ref().unwrap().price_usd - 115.0).abs() < 1e-9);
+        // A zero rate derives no price; a missing one is reported as such
+        assert!(matches!(
+            results[&Asset::MSOL],
+            Err(ProviderError::InvalidPrice { .. })
+        ));
+        assert!(matches!(
+            results[&Asset::BSOL],
+            Err(ProviderError::MissingPrice(_))
+        ));
+        assert!(matches!(results[&Asset::BTC], Err(ProviderError::Timeout)));
+    }
+}
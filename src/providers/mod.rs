@@ -1,11 +1,31 @@
 //! Market price provider implementations
 
+pub mod chaos;
 pub mod coingecko;
+pub mod cross_check;
+pub mod dex;
 pub mod failover;
 pub mod hyperliquid;
+pub mod routed;
+pub mod shadow;
 
+pub use chaos::{ChaosConfig, ChaosProvider, ChaosStats};
 pub use coingecko::CoinGeckoProvider;
-pub use failover::FailoverProvider;
+pub use cross_check::CrossCheckProvider;
+pub use dex::{DexPool, DexPoolProvider};
+pub use failover::{BootstrapStrategy, FailoverProvider};
 pub use hyperliquid::HyperliquidProvider;
+pub use routed::RoutedProvider;
+pub use shadow::{ShadowProvider, ShadowStats};
 pub mod hermes;
 pub use hermes::HermesProvider;
+pub mod ethereum_fees;
+pub mod frankfurter;
+pub mod lst;
+pub mod quota;
+pub mod solana_fees;
+pub use ethereum_fees::EthereumFeeProvider;
+pub use frankfurter::FrankfurterProvider;
+pub use lst::LstProvider;
+pub use quota::QuotaProvider;
+pub use solana_fees::SolanaFeeProvider;
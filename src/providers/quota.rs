@@ -0,0 +1,395 @@
+//! Daily request quota accounting for price providers
+
+use crate::{
+    constants::COINGECKO_DEMO_REQUESTS_PER_DAY,
+    error::ProviderError,
+    http::{HttpResponse, HttpTransport},
+    metrics::{ConnectionStats, FetchHealth, QuotaUsage, StreamMetrics},
+    provider::{MarketPriceProvider, ProviderCapabilities},
+    runtime::Runtime,
+    store::MarketPriceStore,
+    types::{Asset, MarketPriceEvent, PriceData},
+};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::broadcast;
+
+/// Daily request budget for one provider
+#[derive(Debug, Clone)]
+pub struct QuotaConfig {
+    /// Requests allowed per UTC day
+    pub requests_per_day: u64,
+    /// Share of the daily budget, from 0.0 to 1.0, held back once the rest
+    /// is used: past that point the provider reports itself nearly
+    /// exhausted so [`FailoverProvider`](super::FailoverProvider) tries it
+    /// last
+    pub reserve_ratio: f64,
+}
+
+impl Default for QuotaConfig {
+    /// CoinGecko's free Demo plan
+    fn default() -> Self {
+        Self {
+            requests_per_day: COINGECKO_DEMO_REQUESTS_PER_DAY,
+            reserve_ratio: 0.1,
+        }
+    }
+}
+
+/// Requests counted on the current UTC day
+struct Usage {
+    day: NaiveDate,
+    used: u64,
+}
+
+/// Daily request budget shared by a [`QuotaTransport`] and the
+/// [`QuotaProvider`] reporting it
+pub struct Quota {
+    name: String,
+    config: QuotaConfig,
+    usage: Mutex<Usage>,
+    runtime: Runtime,
+}
+
+impl Quota {
+    /// Creates an unused quota; `name` identifies it in
+    /// [`ProviderError::QuotaExhausted`]
+    pub fn new(name: impl Into<String>, config: QuotaConfig) -> Self {
+        let runtime = Runtime::default();
+        Self {
+            name: name.into(),
+            config,
+            usage: Mutex::new(Usage {
+                day: runtime.now().date_naive(),
+                used: 0,
+            }),
+            runtime,
+        }
+    }
+
+    /// Rolls the quota over at midnight by `runtime`'s clock instead of the
+    /// system clock
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.usage.get_mut().expect("quota lock poisoned").day = runtime.now().date_naive();
+        self.runtime = runtime;
+        self
+    }
+
+    /// Requests counted today, starting the day over if it has changed
+    fn used_today<'a>(&self, usage: &'a mut Usage) -> &'a mut u64 {
+        let today = self.runtime.now().date_naive();
+        if usage.day != today {
+            usage.day = today;
+            usage.used = 0;
+        }
+        &mut usage.used
+    }
+
+    /// Fails if the quota is used up, without counting a request
+    fn check(&self) -> Result<(), ProviderError> {
+        let mut usage = self.usage.lock().expect("quota lock poisoned");
+        if *self.used_today(&mut usage) >= self.config.requests_per_day {
+            return Err(ProviderError::QuotaExhausted(self.name.clone()));
+        }
+        Ok(())
+    }
+
+    /// Counts one request, failing if the quota is used up
+    fn consume(&self) -> Result<(), ProviderError> {
+        let mut usage = self.usage.lock().expect("quota lock poisoned");
+        let used = self.used_today(&mut usage);
+        if *used >= self.config.requests_per_day {
+            return Err(ProviderError::QuotaExhausted(self.name.clone()));
+        }
+        *used += 1;
+        Ok(())
+    }
+
+    /// Returns the current usage against the quota
+    pub fn usage(&self) -> QuotaUsage {
+        let usage = self.usage.lock().expect("quota lock poisoned");
+        let now = self.runtime.now();
+        let today = now.date_naive();
+        let used = if usage.day == today { usage.used } else { 0 };
+        let limit = self.config.requests_per_day;
+        let resets_at: DateTime<Utc> = today
+            .succ_opt()
+            .and_then(|tomorrow| tomorrow.and_hms_opt(0, 0, 0))
+            .map_or(now, |midnight| midnight.and_utc());
+
+        QuotaUsage {
+            limit,
+            used,
+            remaining: limit.saturating_sub(used),
+            reserve: (limit as f64 * self.config.reserve_ratio.clamp(0.0, 1.0)).ceil() as u64,
+            resets_at,
+        }
+    }
+}
+
+/// HTTP transport that charges every request against a [`Quota`]
+///
+/// Retries, failovers between base URLs and paginated reads each count,
+/// as they do against the provider's own limit. Once the quota is used up,
+/// requests fail with [`ProviderError::QuotaExhausted`] without being sent.
+pub struct QuotaTransport {
+    inner: Arc<dyn HttpTransport>,
+    quota: Arc<Quota>,
+}
+
+impl QuotaTransport {
+    /// Wraps `inner`, charging its requests against `quota`
+    pub fn new(inner: Arc<dyn HttpTransport>, quota: Arc<Quota>) -> Self {
+        Self { inner, quota }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for QuotaTransport {
+    async fn get_with_headers(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<HttpResponse, ProviderError> {
+        self.quota.consume()?;
+        self.inner.get_with_headers(url, headers).await
+    }
+
+    async fn post_json(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<HttpResponse, ProviderError> {
+        self.quota.consume()?;
+        self.inner.post_json(url, body).await
+    }
+}
+
+/// Price provider reporting a [`Quota`] its HTTP requests are charged to
+///
+/// Requests are counted by a [`QuotaTransport`] the wrapped provider was
+/// built on, so a fetch costs as many requests as it sends. Once the quota
+/// is used up, fetches fail with [`ProviderError::QuotaExhausted`] without
+/// reaching the provider until the next UTC day.
+///
+/// # Example
+/// ```no_run
+/// # use market_price_sdk::http::HttpClientFactory;
+/// # use market_price_sdk::providers::{CoinGeckoProvider, FailoverProvider, HyperliquidProvider, QuotaProvider};
+/// # use market_price_sdk::providers::quota::{Quota, QuotaConfig, QuotaTransport};
+/// # use std::sync::Arc;
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// // CoinGecko first until its free tier runs low, then Hyperliquid
+/// let quota = Arc::new(Quota::new("coingecko", QuotaConfig::default()));
+/// let transport = QuotaTransport::new(
+///     Arc::new(HttpClientFactory::global().client()?),
+///     quota.clone(),
+/// );
+/// let coingecko = CoinGeckoProvider::with_transport(Arc::new(transport));
+/// let provider = FailoverProvider::new(vec![
+///     Arc::new(QuotaProvider::new(Arc::new(coingecko), quota)),
+///     Arc::new(HyperliquidProvider::new()?),
+/// ]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct QuotaProvider {
+    inner: Arc<dyn MarketPriceProvider>,
+    quota: Arc<Quota>,
+}
+
+impl QuotaProvider {
+    /// Wraps `inner`, whose requests are charged against `quota`
+    pub fn new(inner: Arc<dyn MarketPriceProvider>, quota: Arc<Quota>) -> Self {
+        Self { inner, quota }
+    }
+
+    /// Returns the current usage against the quota
+    pub fn usage(&self) -> QuotaUsage {
+        self.quota.usage()
+    }
+}
+
+#[async_trait]
+impl MarketPriceProvider for QuotaProvider {
+    async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+        self.quota.check()?;
+        self.inner.fetch_price(asset).await
+    }
+
+    async fn fetch_prices(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        self.quota.check()?;
+        self.inner.fetch_prices(assets).await
+    }
+
+    async fn fetch_prices_partial(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+        match self.quota.check() {
+            Ok(()) => self.inner.fetch_prices_partial(assets, deadline).await,
+            Err(e) => assets
+                .iter()
+                .map(|asset| (*asset, Err(e.replicate())))
+                .collect(),
+        }
+    }
+
+    async fn bootstrap(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        self.quota.check()?;
+        self.inner.bootstrap(assets).await
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.inner.provider_name()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn is_streaming(&self) -> bool {
+        self.inner.is_streaming()
+    }
+
+    fn start_streaming(
+        &self,
+        store: Arc<MarketPriceStore>,
+        update_tx: broadcast::Sender<PriceData>,
+    ) {
+        self.inner.start_streaming(store, update_tx);
+    }
+
+    fn connection_stats(&self) -> Option<ConnectionStats> {
+        self.inner.connection_stats()
+    }
+
+    fn stream_metrics(&self) -> Option<StreamMetrics> {
+        self.inner.stream_metrics()
+    }
+
+    fn quota_usage(&self) -> Option<QuotaUsage> {
+        Some(self.usage())
+    }
+
+    fn failover_health(&self) -> Option<Vec<FetchHealth>> {
+        self.inner.failover_health()
+    }
+
+    fn subscribe_events(&self) -> Option<broadcast::Receiver<MarketPriceEvent>> {
+        self.inner.subscribe_events()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{fake::FakeTransport, BaseUrls};
+    use crate::provider::mock::MockProvider;
+    use crate::providers::{FailoverProvider, HyperliquidProvider};
+
+    const MIDS: &str = r#"{"SOL":"150.0"}"#;
+
+    fn metered(
+        transport: Arc<FakeTransport>,
+        config: QuotaConfig,
+    ) -> (Arc<Quota>, HyperliquidProvider) {
+        let quota = Arc::new(Quota::new("hyperliquid", config));
+        let provider = HyperliquidProvider::with_transport(Arc::new(QuotaTransport::new(
+            transport,
+            quota.clone(),
+        )));
+        (quota, provider)
+    }
+
+    #[tokio::test]
+    async fn test_shifts_load_before_quota_runs_out() {
+        let transport = Arc::new(FakeTransport::new());
+        for _ in 0..4 {
+            transport.push_response(200, MIDS);
+        }
+        let (quota, primary) = metered(
+            transport.clone(),
+            QuotaConfig {
+                requests_per_day: 4,
+                reserve_ratio: 0.5,
+            },
+        );
+        let backup = Arc::new(MockProvider::new());
+        backup.set_price(Asset::SOL, 151.0);
+
+        let metered = Arc::new(QuotaProvider::new(Arc::new(primary), quota));
+        let failover = FailoverProvider::new(vec![metered.clone(), backup.clone()]);
+
+        for _ in 0..2 {
+            assert_eq!(
+                failover.fetch_price(Asset::SOL).await.unwrap().price_usd,
+                150.0
+            );
+        }
+        let usage = metered.usage();
+        assert_eq!((usage.used, usage.remaining), (2, 2));
+        assert!(usage.is_nearly_exhausted());
+
+        // Within the reserve, the backup takes the load
+        assert_eq!(
+            failover.fetch_price(Asset::SOL).await.unwrap().price_usd,
+            151.0
+        );
+        assert_eq!(metered.usage().used, 2);
+
+        // The reserve is still there if the backup fails too
+        backup.set_error(Asset::SOL, ProviderError::Timeout);
+        assert_eq!(
+            failover.fetch_price(Asset::SOL).await.unwrap().price_usd,
+            150.0
+        );
+        metered.fetch_price(Asset::SOL).await.unwrap();
+        assert!(matches!(
+            metered.fetch_price(Asset::SOL).await,
+            Err(ProviderError::QuotaExhausted(_))
+        ));
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_charges_every_http_request() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(503, "unavailable");
+        transport.push_response(200, MIDS);
+        transport.push_response(200, MIDS);
+        let (quota, provider) = metered(
+            transport.clone(),
+            QuotaConfig {
+                requests_per_day: 3,
+                reserve_ratio: 0.0,
+            },
+        );
+        let provider = provider.with_api_urls(BaseUrls::new(["http://primary", "http://mirror"]));
+
+        // One fetch, failed over to the mirror, costs two requests
+        provider.fetch_price(Asset::SOL).await.unwrap();
+        assert_eq!(quota.usage().used, 2);
+
+        provider.fetch_price(Asset::SOL).await.unwrap();
+        assert_eq!(quota.usage().used, 3);
+
+        // Past the quota nothing is sent
+        assert!(matches!(
+            provider.fetch_price(Asset::SOL).await,
+            Err(ProviderError::QuotaExhausted(_))
+        ));
+        assert_eq!(quota.usage().used, 3);
+        assert_eq!(transport.requests().len(), 3);
+    }
+}
@@ -0,0 +1,271 @@
+//! Asset-routing price provider implementation
+
+use crate::{
+    error::ProviderError,
+    metrics::{ConnectionStats, FetchHealth, QuotaUsage, StreamMetrics},
+    provider::{self, MarketPriceProvider, ProviderCapabilities},
+    store::MarketPriceStore,
+    types::{Asset, PriceData},
+};
+use async_trait::async_trait;
+use futures::future;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::broadcast;
+
+/// A provider and the assets sent to it
+struct Route {
+    provider: Arc<dyn MarketPriceProvider>,
+    assets: Vec<Asset>,
+}
+
+/// Price provider that sends each asset to the provider assigned to it
+///
+/// Requests are split by route and the routes are queried concurrently, so
+/// one provider's gap or outage only costs the assets routed to it. Assets
+/// without a route are left out of every request. The first route is the
+/// primary one: its name identifies this provider.
+pub struct RoutedProvider {
+    routes: Vec<Route>,
+}
+
+impl RoutedProvider {
+    /// Pairs each route with the requested assets sent to it, skipping
+    /// routes with none
+    fn requests(&self, assets: &[Asset]) -> Vec<(&Route, Vec<Asset>)> {
+        self.routes
+            .iter()
+            .map(|route| {
+                let requested = assets
+                    .iter()
+                    .copied()
+                    .filter(|asset| route.assets.contains(asset))
+                    .collect::<Vec<_>>();
+                (route, requested)
+            })
+            .filter(|(_, requested)| !requested.is_empty())
+            .collect()
+    }
+
+    /// Creates a provider with no routes
+    pub fn new() -> Self {
+        Self { routes: Vec::new() }
+    }
+
+    /// Sends `assets` to `provider`; assets already routed keep their route
+    pub fn with_route(mut self, provider: Arc<dyn MarketPriceProvider>, assets: &[Asset]) -> Self {
+        let assets: Vec<Asset> = assets
+            .iter()
+            .copied()
+            .filter(|asset| self.route(*asset).is_none())
+            .collect();
+        if !assets.is_empty() {
+            self.routes.push(Route { provider, assets });
+        }
+        self
+    }
+
+    /// Returns the provider an asset is routed to
+    pub fn route(&self, asset: Asset) -> Option<&Arc<dyn MarketPriceProvider>> {
+        self.routes
+            .iter()
+            .find(|route| route.assets.contains(&asset))
+            .map(|route| &route.provider)
+    }
+
+    /// Returns the routes served by polling, or `None` if every route
+    /// streams
+    pub fn polled_routes(&self) -> Option<Self> {
+        let routes: Vec<Route> = self
+            .routes
+            .iter()
+            .filter(|route| !route.provider.is_streaming())
+            .map(|route| Route {
+                provider: route.provider.clone(),
+                assets: route.assets.clone(),
+            })
+            .collect();
+        (!routes.is_empty()).then_some(Self { routes })
+    }
+}
+
+impl Default for RoutedProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl MarketPriceProvider for RoutedProvider {
+    async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+        match self.route(asset) {
+            Some(provider) => provider.fetch_price(asset).await,
+            None => Err(ProviderError::UnsupportedAsset(asset.symbol().to_string())),
+        }
+    }
+
+    async fn fetch_prices(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        let requests = self.requests(assets);
+        if requests.is_empty() {
+            return Err(ProviderError::UnsupportedAsset(
+                "No route for the requested assets".to_string(),
+            ));
+        }
+
+        let responses = future::join_all(
+            requests
+                .iter()
+                .map(|(route, requested)| route.provider.fetch_prices(requested)),
+        )
+        .await;
+
+        let mut prices = HashMap::new();
+        let mut last_error = None;
+        for ((route, _), response) in requests.iter().zip(responses) {
+            match response {
+                Ok(fetched) => prices.extend(fetched),
+                Err(e) => {
+                    tracing::warn!(
+                        provider = route.provider.provider_name(),
+                        error = %e,
+                        "Routed provider failed to fetch prices"
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match last_error {
+            Some(e) if prices.is_empty() => Err(e),
+            _ => Ok(prices),
+        }
+    }
+
+    async fn fetch_prices_partial(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+        let requests = self.requests(assets);
+        let responses =
+            future::join_all(requests.iter().map(|(route, requested)| {
+                route.provider.fetch_prices_partial(requested, deadline)
+            }))
+            .await;
+
+        let mut results: HashMap<Asset, Result<PriceData, ProviderError>> =
+            responses.into_iter().flatten().collect();
+        for asset in assets {
+            results.entry(*asset).or_insert_with(|| {
+                Err(ProviderError::UnsupportedAsset(asset.symbol().to_string()))
+            });
+        }
+        results
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.routes
+            .first()
+            .map_or("routed", |route| route.provider.provider_name())
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        let mut capabilities = ProviderCapabilities {
+            supported_assets: Vec::new(),
+            streaming: self.is_streaming(),
+            ..Default::default()
+        };
+        for route in &self.routes {
+            let caps = route.provider.capabilities();
+            capabilities.supported_assets.extend(&route.assets);
+            capabilities.max_batch_size =
+                provider::tighter(capabilities.max_batch_size, caps.max_batch_size);
+        }
+        capabilities
+    }
+
+    /// True if any route streams; polled routes must then be driven
+    /// separately, see [`polled_routes`](Self::polled_routes)
+    fn is_streaming(&self) -> bool {
+        self.routes
+            .iter()
+            .any(|route| route.provider.is_streaming())
+    }
+
+    fn start_streaming(
+        &self,
+        store: Arc<MarketPriceStore>,
+        update_tx: broadcast::Sender<PriceData>,
+    ) {
+        for route in &self.routes {
+            if route.provider.is_streaming() {
+                route
+                    .provider
+                    .start_streaming(store.clone(), update_tx.clone());
+            }
+        }
+    }
+
+    fn connection_stats(&self) -> Option<ConnectionStats> {
+        self.routes
+            .iter()
+            .find_map(|route| route.provider.connection_stats())
+    }
+
+    fn stream_metrics(&self) -> Option<StreamMetrics> {
+        self.routes
+            .iter()
+            .find_map(|route| route.provider.stream_metrics())
+    }
+
+    fn quota_usage(&self) -> Option<QuotaUsage> {
+        self.routes
+            .iter()
+            .find_map(|route| route.provider.quota_usage())
+    }
+
+    fn failover_health(&self) -> Option<Vec<FetchHealth>> {
+        self.routes
+            .iter()
+            .find_map(|route| route.provider.failover_health())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::mock::MockProvider;
+
+    #[tokio::test]
+    async fn test_splits_requests_by_route() {
+        let primary = Arc::new(MockProvider::new());
+        primary.set_price(Asset::SOL, 150.0);
+        let fallback = Arc::new(MockProvider::new());
+        fallback.set_error(Asset::BTC, ProviderError::Timeout);
+
+        let routed = RoutedProvider::new()
+            .with_route(primary.clone(), &[Asset::SOL])
+            .with_route(fallback.clone(), &[Asset::SOL, Asset::BTC]);
+        assert!(Arc::ptr_eq(
+            routed.route(Asset::SOL).unwrap(),
+            &(primary.clone() as Arc<dyn MarketPriceProvider>)
+        ));
+
+        // A failing route only costs its own assets
+        let prices = routed
+            .fetch_prices(&[Asset::SOL, Asset::BTC, Asset::ETH])
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[&Asset::SOL].price_usd, 150.0);
+        assert!(matches!(
+            routed.fetch_price(Asset::ETH).await,
+            Err(ProviderError::UnsupportedAsset(_))
+        ));
+        assert_eq!(routed.provider_name(), "mock");
+    }
+}
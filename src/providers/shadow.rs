@@ -0,0 +1,388 @@
+//! Shadow price provider implementation
+
+use crate::{
+    error::ProviderError,
+    metrics::{
+        ConnectionStats, FetchHealth, MetricsCollector, ProviderMetrics, QuotaUsage, StreamMetrics,
+    },
+    provider::{MarketPriceProvider, ProviderCapabilities},
+    store::MarketPriceStore,
+    tasks,
+    types::{Asset, MarketPriceEvent, PriceData},
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::{broadcast, oneshot};
+
+/// How a candidate provider's prices compared to the active provider's
+#[derive(Debug, Clone, Default)]
+pub struct ShadowStats {
+    /// Fetches sent to both providers
+    pub requests: u64,
+    /// Fetches not sent to the candidate because its previous fetch was
+    /// still running
+    pub skipped: u64,
+    /// Fetches the active provider failed, which were not compared
+    pub active_failures: u64,
+    /// Fetches the candidate failed while the active provider succeeded
+    pub candidate_failures: u64,
+    /// Prices the active provider returned and the candidate did not
+    pub missing_prices: u64,
+    /// Prices both providers returned
+    pub compared_prices: u64,
+    /// Compared prices further apart than the divergence threshold
+    pub diverging_prices: u64,
+    /// Mean gap between compared prices, in basis points of the active price
+    pub mean_deviation_bps: f64,
+    /// Largest gap between compared prices, in basis points of the active
+    /// price
+    pub max_deviation_bps: f64,
+}
+
+/// Price provider that trials a candidate alongside the active provider
+///
+/// Every fetch is also sent to the candidate, concurrently, and only the
+/// active provider's prices are returned; the candidate never delays or
+/// changes what is served. At most one candidate fetch runs at a time:
+/// while it does, further fetches skip the comparison, so a slow candidate
+/// cannot pile up background tasks. Its prices are compared with the active ones
+/// once both have arrived: gaps above the divergence threshold are logged,
+/// and [`stats`](Self::stats) and [`candidate_metrics`](Self::candidate_metrics)
+/// summarize how it would have done. Everything else, streaming included,
+/// is the active provider's.
+///
+/// # Example
+/// ```no_run
+/// use market_price_sdk::providers::{CoinGeckoProvider, HyperliquidProvider, ShadowProvider};
+/// use market_price_sdk::{MarketPriceTracker, TrackerConfig};
+/// use std::sync::Arc;
+///
+/// # async fn example() {
+/// let shadow = Arc::new(ShadowProvider::new(
+///     Arc::new(CoinGeckoProvider::default()),
+///     Arc::new(HyperliquidProvider::default()),
+///     50.0,
+/// ));
+/// let tracker =
+///     MarketPriceTracker::global_named_with("prices", shadow.clone(), TrackerConfig::default())
+///         .await;
+///
+/// // Later, before switching
+/// let stats = shadow.stats();
+/// let latency = shadow.candidate_metrics().await.latency_p99_ms;
+/// # }
+/// ```
+pub struct ShadowProvider {
+    active: Arc<dyn MarketPriceProvider>,
+    candidate: Arc<dyn MarketPriceProvider>,
+    max_deviation_bps: f64,
+    stats: Arc<Mutex<ShadowStats>>,
+    /// Set while a candidate fetch is running
+    candidate_busy: Arc<AtomicBool>,
+    active_metrics: Arc<MetricsCollector>,
+    candidate_metrics: Arc<MetricsCollector>,
+}
+
+impl ShadowProvider {
+    /// Creates a shadow provider
+    ///
+    /// # Arguments
+    /// * `active` - Provider whose prices are served
+    /// * `candidate` - Provider under trial, only compared
+    /// * `max_deviation_bps` - Gap in basis points above which a price is
+    ///   counted and logged as diverging
+    pub fn new(
+        active: Arc<dyn MarketPriceProvider>,
+        candidate: Arc<dyn MarketPriceProvider>,
+        max_deviation_bps: f64,
+    ) -> Self {
+        Self {
+            active_metrics: Arc::new(MetricsCollector::new(active.provider_name())),
+            candidate_metrics: Arc::new(MetricsCollector::new(candidate.provider_name())),
+            active,
+            candidate,
+            max_deviation_bps,
+            stats: Arc::new(Mutex::new(ShadowStats::default())),
+            candidate_busy: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns the comparison so far
+    pub fn stats(&self) -> ShadowStats {
+        self.stats.lock().unwrap().clone()
+    }
+
+    /// Latencies and success rate of the active provider's fetches
+    pub async fn active_metrics(&self) -> ProviderMetrics {
+        self.active_metrics.get_metrics().await
+    }
+
+    /// Latencies and success rate the candidate's fetches would have had
+    pub async fn candidate_metrics(&self) -> ProviderMetrics {
+        self.candidate_metrics.get_metrics().await
+    }
+
+    /// Sends the fetch to the candidate in the background, returning where
+    /// to deliver the active provider's prices for comparison
+    ///
+    /// Returns `None`, skipping the comparison, while the previous
+    /// candidate fetch is still running.
+    fn shadow(
+        &self,
+        assets: &[Asset],
+    ) -> Option<oneshot::Sender<Option<HashMap<Asset, PriceData>>>> {
+        if self.candidate_busy.swap(true, Ordering::AcqRel) {
+            self.stats.lock().unwrap().skipped += 1;
+            return None;
+        }
+
+        let (active_tx, active_rx) = oneshot::channel::<Option<HashMap<Asset, PriceData>>>();
+        let candidate = self.candidate.clone();
+        let metrics = self.candidate_metrics.clone();
+        let stats = self.stats.clone();
+        let busy = self.candidate_busy.clone();
+        let max_deviation_bps = self.max_deviation_bps;
+        let assets = assets.to_vec();
+
+        tasks::spawn("shadow.fetch", async move {
+            let start = Instant::now();
+            let result = candidate.fetch_prices(&assets).await;
+            metrics
+                .record_request(start.elapsed(), result.is_ok())
+                .await;
+            // Dropped without a send if the served fetch was cancelled
+            if let Ok(active) = active_rx.await {
+                compare(
+                    candidate.provider_name(),
+                    active.as_ref(),
+                    result,
+                    max_deviation_bps,
+                    &mut stats.lock().unwrap(),
+                );
+            }
+            busy.store(false, Ordering::Release);
+        });
+        Some(active_tx)
+    }
+}
+
+/// Adds one fetch's comparison to `stats`
+fn compare(
+    candidate: &str,
+    active: Option<&HashMap<Asset, PriceData>>,
+    result: Result<HashMap<Asset, PriceData>, ProviderError>,
+    max_deviation_bps: f64,
+    stats: &mut ShadowStats,
+) {
+    stats.requests += 1;
+    let Some(active) = active else {
+        stats.active_failures += 1;
+        return;
+    };
+    let prices = match result {
+        Ok(prices) => prices,
+        Err(e) => {
+            tracing::debug!(candidate = candidate, error = %e, "Shadow provider failed to fetch prices");
+            stats.candidate_failures += 1;
+            return;
+        }
+    };
+
+    for (asset, price) in active {
+        let Some(shadow) = prices.get(asset) else {
+            stats.missing_prices += 1;
+            continue;
+        };
+        if price.price_usd == 0.0 {
+            continue;
+        }
+        let deviation_bps =
+            (shadow.price_usd - price.price_usd).abs() / price.price_usd.abs() * 10_000.0;
+        stats.compared_prices += 1;
+        stats.mean_deviation_bps +=
+            (deviation_bps - stats.mean_deviation_bps) / stats.compared_prices as f64;
+        stats.max_deviation_bps = stats.max_deviation_bps.max(deviation_bps);
+
+        if deviation_bps > max_deviation_bps {
+            stats.diverging_prices += 1;
+            tracing::warn!(
+                asset = asset.symbol(),
+                candidate = candidate,
+                active_price_usd = price.price_usd,
+                candidate_price_usd = shadow.price_usd,
+                deviation_bps = deviation_bps,
+                max_deviation_bps = max_deviation_bps,
+                "Shadow provider disagrees with the active provider"
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl MarketPriceProvider for ShadowProvider {
+    async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+        let prices = self.fetch_prices(&[asset]).await?;
+        prices
+            .get(&asset)
+            .cloned()
+            .ok_or_else(|| ProviderError::UnsupportedAsset(asset.symbol().to_string()))
+    }
+
+    async fn fetch_prices(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        let active_tx = self.shadow(assets);
+        let start = Instant::now();
+        let result = self.active.fetch_prices(assets).await;
+        self.active_metrics
+            .record_request(start.elapsed(), result.is_ok())
+            .await;
+        if let Some(active_tx) = active_tx {
+            let _ = active_tx.send(result.as_ref().ok().cloned());
+        }
+        result
+    }
+
+    async fn fetch_prices_partial(
+        &self,
+        assets: &[Asset],
+        deadline: Option<Instant>,
+    ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+        let active_tx = self.shadow(assets);
+        let start = Instant::now();
+        let results = self.active.fetch_prices_partial(assets, deadline).await;
+        let prices: HashMap<Asset, PriceData> = results
+            .iter()
+            .filter_map(|(asset, result)| Some((*asset, result.as_ref().ok()?.clone())))
+            .collect();
+        self.active_metrics
+            .record_request(start.elapsed(), !prices.is_empty())
+            .await;
+        if let Some(active_tx) = active_tx {
+            let _ = active_tx.send(Some(prices).filter(|prices| !prices.is_empty()));
+        }
+        results
+    }
+
+    async fn bootstrap(
+        &self,
+        assets: &[Asset],
+    ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+        self.active.bootstrap(assets).await
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.active.provider_name()
+    }
+
+    fn is_streaming(&self) -> bool {
+        self.active.is_streaming()
+    }
+
+    fn start_streaming(
+        &self,
+        store: Arc<MarketPriceStore>,
+        update_tx: broadcast::Sender<PriceData>,
+    ) {
+        self.active.start_streaming(store, update_tx);
+    }
+
+    fn connection_stats(&self) -> Option<ConnectionStats> {
+        self.active.connection_stats()
+    }
+
+    fn stream_metrics(&self) -> Option<StreamMetrics> {
+        self.active.stream_metrics()
+    }
+
+    fn quota_usage(&self) -> Option<QuotaUsage> {
+        self.active.quota_usage()
+    }
+
+    fn failover_health(&self) -> Option<Vec<FetchHealth>> {
+        self.active.failover_health()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.active.capabilities()
+    }
+
+    fn subscribe_events(&self) -> Option<broadcast::Receiver<MarketPriceEvent>> {
+        self.active.subscribe_events()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::mock::MockProvider;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_partial_fetch_is_served_by_the_active_provider() {
+        let active = Arc::new(MockProvider::new());
+        let candidate = Arc::new(MockProvider::new());
+        active.set_price(Asset::SOL, 150.0);
+        active.set_error(Asset::BTC, ProviderError::Timeout);
+        candidate.set_price(Asset::BTC, 60000.0);
+        let shadow = ShadowProvider::new(active, candidate, 50.0);
+
+        let results = shadow
+            .fetch_prices_partial(&[Asset::SOL, Asset::BTC], None)
+            .await;
+
+        assert_eq!(results[&Asset::SOL].as_ref().unwrap().price_usd, 150.0);
+        assert!(matches!(results[&Asset::BTC], Err(ProviderError::Timeout)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_candidate_is_compared_but_never_served() {
+        let active = Arc::new(MockProvider::new());
+        let candidate = Arc::new(MockProvider::new());
+        active.set_price(Asset::SOL, 150.0);
+        active.set_price(Asset::BTC, 60000.0);
+        candidate.set_price(Asset::SOL, 153.0);
+        let shadow = ShadowProvider::new(active, candidate, 50.0);
+
+        let prices = shadow
+            .fetch_prices(&[Asset::SOL, Asset::BTC])
+            .await
+            .unwrap();
+        assert_eq!(prices[&Asset::SOL].price_usd, 150.0);
+
+        // Paused time only advances once the comparison task is idle
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let stats = shadow.stats();
+        assert_eq!(stats.requests, 1);
+        assert_eq!(stats.compared_prices, 1);
+        assert_eq!(stats.diverging_prices, 1);
+        assert_eq!(stats.missing_prices, 1);
+        assert!((stats.max_deviation_bps - 200.0).abs() < 1e-9);
+        assert_eq!(shadow.candidate_metrics().await.total_requests, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_candidate_runs_one_fetch_at_a_time() {
+        let active = Arc::new(MockProvider::new());
+        let candidate = Arc::new(MockProvider::new());
+        active.set_price(Asset::SOL, 150.0);
+        candidate.set_price(Asset::SOL, 150.0);
+        candidate.set_delay(Duration::from_secs(1));
+        let shadow = ShadowProvider::new(active, candidate.clone(), 50.0);
+
+        shadow.fetch_price(Asset::SOL).await.unwrap();
+        shadow.fetch_price(Asset::SOL).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        shadow.fetch_price(Asset::SOL).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        let stats = shadow.stats();
+        assert_eq!(stats.requests, 2);
+        assert_eq!(stats.skipped, 1);
+        assert_eq!(stats.compared_prices, 2);
+    }
+}
@@ -0,0 +1,171 @@
+//! Solana priority fee provider (JSON-RPC `getRecentPrioritizationFees`)
+//!
+//! Requests go through a [`SolanaRpc`] client, which fails over between
+//! its endpoints.
+
+use crate::{
+    error::ProviderError,
+    fees::{FeeEstimate, FeeProvider, FeeUnit, Percentile},
+    http::HttpTransport,
+    metrics::percentile,
+    rpc::{RpcConfig, SolanaRpc},
+    runtime::Runtime,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// One sample from getRecentPrioritizationFees
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrioritizationFee {
+    prioritization_fee: u64,
+}
+
+/// Solana priority fee provider
+///
+/// Percentiles are computed over the per-slot minimum fees the RPC node
+/// reports for recent slots (up to 150), in micro-lamports per compute unit.
+pub struct SolanaFeeProvider {
+    rpc: Arc<SolanaRpc>,
+    accounts: Vec<String>,
+    runtime: Runtime,
+}
+
+impl SolanaFeeProvider {
+    /// Creates a provider for the public mainnet RPC using the shared HTTP client
+    pub fn new() -> Result<Self, ProviderError> {
+        Ok(Self::with_rpc(Arc::new(SolanaRpc::new(
+            RpcConfig::default(),
+        )?)))
+    }
+
+    /// Creates a provider for a single RPC endpoint and custom HTTP transport
+    pub fn with_transport(transport: Arc<dyn HttpTransport>, rpc_url: impl Into<String>) -> Self {
+        Self::with_rpc(Arc::new(SolanaRpc::with_transport(
+            transport,
+            RpcConfig {
+                endpoints: vec![rpc_url.into()],
+                ..Default::default()
+            },
+        )))
+    }
+
+    /// Creates a provider reading through a shared RPC client
+    pub fn with_rpc(rpc: Arc<SolanaRpc>) -> Self {
+        Self {
+            rpc,
+            accounts: Vec::new(),
+            runtime: Runtime::default(),
+        }
+    }
+
+    /// Restricts samples to transactions that write-lock these accounts
+    /// (base58 addresses), e.g. a specific AMM pool
+    pub fn with_accounts(mut self, accounts: Vec<String>) -> Self {
+        self.accounts = accounts;
+        self
+    }
+
+    /// Stamps fetched estimates with `runtime`'s clock instead of the system
+    /// clock
+    pub fn with_runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = runtime;
+        self
+    }
+}
+
+#[async_trait]
+impl FeeProvider for SolanaFeeProvider {
+    async fn fetch_fees(&self) -> Result<FeeEstimate, ProviderError> {
+        let fees: Vec<PrioritizationFee> = self
+            .rpc
+            .call(
+                "getRecentPrioritizationFees",
+                serde_json::json!([self.accounts]),
+            )
+            .await?;
+
+        let mut samples: Vec<f64> = fees
+            .iter()
+            .map(|fee| fee.prioritization_fee as f64)
+            .collect();
+        if samples.is_empty() {
+            return Err(ProviderError::InvalidResponse(
+                "No prioritization fee samples returned".to_string(),
+            ));
+        }
+        samples.sort_by(|a, b| a.total_cmp(b));
+
+        Ok(FeeEstimate {
+            priority_fees: Percentile::all()
+                .iter()
+                .map(|p| (*p, percentile(&samples, p.value())))
+                .collect(),
+            base_fee: None,
+            unit: FeeUnit::MicroLamportsPerComputeUnit,
+            last_updated: self.runtime.now(),
+            source: "solana-rpc".to_string(),
+        })
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "solana-rpc"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SOLANA_RPC_URL;
+    use crate::http::fake::FakeTransport;
+
+    #[tokio::test]
+    async fn test_fetch_priority_fee_percentiles() {
+        let transport = Arc::new(FakeTransport::new());
+        let samples: Vec<String> = (0..=100)
+            .map(|i| format!(r#"{{"slot":{},"prioritizationFee":{}}}"#, i, i * 10))
+            .collect();
+        transport.push_response(
+            200,
+            &format!(
+                r#"{{"jsonrpc":"2.0","result":[{}],"id":1}}"#,
+                samples.join(",")
+            ),
+        );
+        let provider = SolanaFeeProvider::with_transport(transport.clone(), SOLANA_RPC_URL);
+
+        let estimate = provider.fetch_fees().await.unwrap();
+
+        assert_eq!(estimate.priority_fees[&Percentile::P50], 500.0);
+        assert_eq!(estimate.priority_fees[&Percentile::P75], 750.0);
+        assert_eq!(estimate.base_fee, None);
+        assert_eq!(
+            transport.requests()[0].body.as_ref().unwrap()["method"],
+            "getRecentPrioritizationFees"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fails_over_to_next_endpoint() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(429, "rate limited");
+        transport.push_response(
+            200,
+            r#"{"jsonrpc":"2.0","result":[{"slot":1,"prioritizationFee":42}],"id":1}"#,
+        );
+        let rpc = SolanaRpc::with_transport(
+            transport.clone(),
+            RpcConfig {
+                endpoints: vec!["http://primary".to_string(), "http://backup".to_string()],
+                ..Default::default()
+            },
+        );
+        let provider = SolanaFeeProvider::with_rpc(Arc::new(rpc));
+
+        let estimate = provider.fetch_fees().await.unwrap();
+
+        assert_eq!(estimate.priority_fees[&Percentile::P50], 42.0);
+        assert_eq!(transport.requests()[1].url, "http://backup");
+    }
+}
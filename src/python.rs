@@ -0,0 +1,268 @@
+//! Python bindings (`python` feature)
+//!
+//! Builds the `market_price_sdk` extension module with PyO3, so notebooks
+//! and research scripts read the same prices, from the same providers and
+//! with the same validation, as the Rust services. The module wraps the
+//! [`blocking`](crate::blocking) facade: every call releases the GIL while
+//! it waits, and the tracker keeps polling on its own threads between calls.
+//!
+//! Build and install into the active virtualenv with
+//! [maturin](https://www.maturin.rs), which picks up `pyproject.toml`:
+//!
+//! ```text
+//! maturin develop --release
+//! ```
+//!
+//! Assets are passed by symbol, in any case the Rust parser accepts
+//! (`"SOL"`, `"btc"`, `"gold"`), and timestamps are timezone-aware
+//! `datetime`s.
+//!
+//! ```python
+//! from datetime import datetime, timedelta, timezone
+//! import market_price_sdk as mps
+//!
+//! tracker = mps.MarketPriceTracker.global_()
+//! print(tracker.get_price("SOL").price_usd)
+//!
+//! hour_ago = datetime.now(timezone.utc) - timedelta(hours=1)
+//! print(tracker.get_price_at("BTC", hour_ago))
+//! closes = [p.price_usd for p in tracker.get_history("ETH", 3600)]
+//!
+//! for price in tracker.subscribe(["SOL", "BTC"]):
+//!     print(price.asset, price.price_usd)
+//! ```
+
+use crate::{
+    blocking::{MarketPriceTracker, Subscription},
+    subscription::SubscriptionConfig,
+    types::{Asset, PriceData},
+};
+use chrono::{DateTime, Utc};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod exceptions {
+    use pyo3::create_exception;
+    use pyo3::exceptions::PyException;
+
+    create_exception!(
+        market_price_sdk,
+        PriceError,
+        PyException,
+        "No fresh price is available for the asset"
+    );
+    create_exception!(
+        market_price_sdk,
+        ProviderError,
+        PyException,
+        "The price provider request failed"
+    );
+}
+
+/// A price as stored by the tracker
+#[pyclass(name = "PriceData", module = "market_price_sdk", frozen, get_all)]
+#[derive(Clone)]
+struct PyPriceData {
+    asset: String,
+    price_usd: f64,
+    price_change_24h: Option<f64>,
+    last_updated: DateTime<Utc>,
+    source: String,
+    confidence: Option<f64>,
+    sequence: u64,
+}
+
+#[pymethods]
+impl PyPriceData {
+    fn __repr__(&self) -> String {
+        format!(
+            "PriceData(asset={:?}, price_usd={}, last_updated={:?}, source={:?})",
+            self.asset,
+            self.price_usd,
+            self.last_updated.to_rfc3339(),
+            self.source
+        )
+    }
+}
+
+impl From<PriceData> for PyPriceData {
+    fn from(price: PriceData) -> Self {
+        Self {
+            asset: price.asset.symbol().to_string(),
+            price_usd: price.price_usd,
+            price_change_24h: price.price_change_24h,
+            last_updated: price.last_updated,
+            source: price.source,
+            confidence: price.confidence,
+            sequence: price.sequence,
+        }
+    }
+}
+
+/// Market price tracker
+#[pyclass(name = "MarketPriceTracker", module = "market_price_sdk", frozen)]
+struct PyTracker {
+    inner: Arc<MarketPriceTracker>,
+}
+
+#[pymethods]
+impl PyTracker {
+    /// Returns the process-wide tracker, starting it on first call
+    #[staticmethod]
+    #[pyo3(name = "global_")]
+    fn global(py: Python<'_>) -> Self {
+        Self {
+            inner: py.allow_threads(MarketPriceTracker::global),
+        }
+    }
+
+    /// Gets the current price of an asset, raising `PriceError` if it is
+    /// missing or stale
+    fn get_price(&self, py: Python<'_>, asset: &str) -> PyResult<PyPriceData> {
+        let asset = parse_asset(asset)?;
+        py.allow_threads(|| self.inner.get_price(asset))
+            .map(Into::into)
+            .map_err(|e| exceptions::PriceError::new_err(e.to_string()))
+    }
+
+    /// Gets every fresh price, keyed by symbol
+    fn get_all_prices(&self, py: Python<'_>) -> HashMap<String, PyPriceData> {
+        py.allow_threads(|| self.inner.get_all_prices())
+            .into_iter()
+            .map(|(asset, price)| (asset.symbol().to_string(), price.into()))
+            .collect()
+    }
+
+    /// Gets the price held for an asset at a point in time
+    fn get_price_at(&self, asset: &str, timestamp: DateTime<Utc>) -> PyResult<PyPriceData> {
+        self.inner
+            .get_price_at(parse_asset(asset)?, timestamp)
+            .map(Into::into)
+            .map_err(|e| exceptions::PriceError::new_err(e.to_string()))
+    }
+
+    /// Returns the recorded prices of an asset over the last
+    /// `window_secs` seconds, oldest first
+    fn get_history(&self, asset: &str, window_secs: f64) -> PyResult<Vec<PyPriceData>> {
+        let window = Duration::try_from_secs_f64(window_secs)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(self
+            .inner
+            .get_history(parse_asset(asset)?, window)
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Subscribes to updates of `assets`, or of every asset
+    #[pyo3(signature = (assets=None, capacity=256))]
+    fn subscribe(&self, assets: Option<Vec<String>>, capacity: usize) -> PyResult<PySubscription> {
+        let assets = assets
+            .map(|symbols| symbols.iter().map(|s| parse_asset(s)).collect())
+            .transpose()?;
+        let subscription = self.inner.subscribe_with(SubscriptionConfig {
+            name: "python".to_string(),
+            capacity,
+            assets,
+            ..Default::default()
+        });
+        Ok(PySubscription {
+            inner: Mutex::new(subscription),
+        })
+    }
+
+    /// Fetches every enabled asset now, raising `ProviderError` on failure
+    fn refresh_now(&self, py: Python<'_>) -> PyResult<()> {
+        py.allow_threads(|| self.inner.refresh_now())
+            .map_err(|e| exceptions::ProviderError::new_err(e.to_string()))
+    }
+
+    /// Name of the active provider
+    #[getter]
+    fn provider_name(&self) -> String {
+        self.inner.provider_name().to_string()
+    }
+
+    /// Stops the tracker's background polling
+    fn shutdown(&self) {
+        self.inner.shutdown();
+    }
+}
+
+/// Queue of price updates; iterate it, or call `recv`
+#[pyclass(name = "PriceSubscription", module = "market_price_sdk", frozen)]
+struct PySubscription {
+    inner: Mutex<Subscription>,
+}
+
+#[pymethods]
+impl PySubscription {
+    /// Waits for the next update, up to `timeout` seconds if given;
+    /// returns `None` on timeout or once the tracker has shut down
+    #[pyo3(signature = (timeout=None))]
+    fn recv(&self, py: Python<'_>, timeout: Option<f64>) -> PyResult<Option<PyPriceData>> {
+        let timeout = timeout
+            .map(Duration::try_from_secs_f64)
+            .transpose()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let price = py.allow_threads(|| {
+            let mut subscription = self.inner.lock().expect("subscription lock poisoned");
+            match timeout {
+                Some(timeout) => subscription.recv_timeout(timeout),
+                None => subscription.recv(),
+            }
+        });
+        Ok(price.map(Into::into))
+    }
+
+    /// Returns the next queued update without waiting
+    fn try_recv(&self) -> Option<PyPriceData> {
+        let mut subscription = self.inner.lock().expect("subscription lock poisoned");
+        subscription.try_recv().map(Into::into)
+    }
+
+    /// Updates dropped because the queue was full
+    #[getter]
+    fn missed(&self) -> u64 {
+        self.inner
+            .lock()
+            .expect("subscription lock poisoned")
+            .missed()
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<PyPriceData>> {
+        self.recv(py, None)
+    }
+}
+
+fn parse_asset(symbol: &str) -> PyResult<Asset> {
+    symbol.parse().map_err(PyValueError::new_err)
+}
+
+/// The `market_price_sdk` Python module
+#[pymodule]
+fn market_price_sdk(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyTracker>()?;
+    m.add_class::<PySubscription>()?;
+    m.add_class::<PyPriceData>()?;
+    m.add("PriceError", m.py().get_type::<exceptions::PriceError>())?;
+    m.add(
+        "ProviderError",
+        m.py().get_type::<exceptions::ProviderError>(),
+    )?;
+    m.add(
+        "ASSETS",
+        Asset::all()
+            .iter()
+            .map(|asset| asset.symbol())
+            .collect::<Vec<_>>(),
+    )?;
+    Ok(())
+}
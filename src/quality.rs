@@ -0,0 +1,118 @@
+//! Composite price quality scoring
+//!
+//! Every price the store serves carries a [`Quality`] grade derived from a
+//! score in `[0, 1]`. The score is the product of four factors, each in
+//! `[0, 1]`; a factor whose input is unknown counts as 1.0:
+//!
+//! | Factor | Input | 1.0 when | 0.0 when |
+//! |--------|-------|----------|----------|
+//! | Source reliability | `source` | Pyth/Hermes | never (see [`source_reliability`]) |
+//! | Freshness | age vs. the asset's stale threshold | younger than half the threshold | at the threshold |
+//! | Precision | confidence / price (Pyth) | 0 | ≥ 1% |
+//! | Agreement | `deviation_bps` from a reference provider | 0 bps | ≥ 100 bps |
+//!
+//! Scores map to grades: ≥ 0.9 `Excellent`, ≥ 0.75 `Good`, ≥ 0.5 `Fair`,
+//! otherwise `Poor`. Ticks flagged by anomaly detection stay `Suspect`
+//! regardless of score.
+//!
+//! Freshness changes as a price ages, so the store re-grades prices each
+//! time it serves them, as of its configured clock.
+//!
+//! ```
+//! use market_price_sdk::{Asset, PriceData, Quality};
+//!
+//! let price = PriceData::new(Asset::SOL, 150.0, "hermes".to_string()).with_confidence(0.05);
+//! let now = price.last_updated;
+//! assert_eq!(market_price_sdk::quality::assess(&price, now), Quality::Excellent);
+//! ```
+
+use crate::types::{PriceData, Quality};
+use chrono::{DateTime, Utc};
+
+/// Confidence ratio at which the precision factor reaches zero
+const MAX_CONFIDENCE_RATIO: f64 = 0.01;
+
+/// Cross-provider deviation at which the agreement factor reaches zero
+const MAX_DEVIATION_BPS: f64 = 100.0;
+
+/// Reliability weight for a price source
+///
+/// Sources are matched by prefix, so derived sources such as
+/// `"hermes+sanctum"` inherit the weight of their base feed.
+pub fn source_reliability(source: &str) -> f64 {
+    if source.starts_with("hermes") || source.starts_with("pyth") {
+        1.0
+    } else if source.starts_with("hyperliquid") {
+        0.95
+    } else if source.starts_with("coingecko") {
+        0.8
+    } else {
+        0.9
+    }
+}
+
+/// Computes the composite quality score in `[0, 1]` as of `now`
+pub fn score(price: &PriceData, now: DateTime<Utc>) -> f64 {
+    let threshold = price.asset.stale_threshold_secs() as f64;
+    let age = price.age_at(now).as_secs_f64();
+    let freshness = if threshold <= 0.0 {
+        1.0
+    } else {
+        (2.0 * (1.0 - age / threshold)).clamp(0.0, 1.0)
+    };
+
+    let precision = price
+        .confidence_ratio()
+        .map(|ratio| (1.0 - ratio / MAX_CONFIDENCE_RATIO).clamp(0.0, 1.0))
+        .unwrap_or(1.0);
+
+    let agreement = price
+        .deviation_bps
+        .map(|bps| (1.0 - bps.abs() / MAX_DEVIATION_BPS).clamp(0.0, 1.0))
+        .unwrap_or(1.0);
+
+    source_reliability(&price.source) * freshness * precision * agreement
+}
+
+/// Grades a price as of `now`, keeping anomaly-detection `Suspect` flags
+pub fn assess(price: &PriceData, now: DateTime<Utc>) -> Quality {
+    if price.quality == Quality::Suspect {
+        return Quality::Suspect;
+    }
+
+    match score(price, now) {
+        s if s >= 0.9 => Quality::Excellent,
+        s if s >= 0.75 => Quality::Good,
+        s if s >= 0.5 => Quality::Fair,
+        _ => Quality::Poor,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Asset;
+
+    #[test]
+    fn test_quality_grades() {
+        let fresh = PriceData::new(Asset::SOL, 100.0, "coingecko".to_string());
+        let now = fresh.last_updated;
+        assert_eq!(assess(&fresh, now), Quality::Good);
+        assert_eq!(
+            assess(&fresh, now + chrono::Duration::seconds(100)),
+            Quality::Poor
+        );
+
+        let wide = PriceData::new(Asset::SOL, 100.0, "hermes".to_string()).with_confidence(0.4);
+        assert_eq!(assess(&wide, now), Quality::Fair);
+
+        let mut diverging = PriceData::new(Asset::SOL, 100.0, "hermes".to_string());
+        diverging.deviation_bps = Some(20.0);
+        assert_eq!(assess(&diverging, now), Quality::Good);
+
+        let mut suspect = PriceData::new(Asset::SOL, 100.0, "hermes".to_string());
+        suspect.quality = Quality::Suspect;
+        assert_eq!(assess(&suspect, now), Quality::Suspect);
+        assert!(Quality::Excellent > Quality::Good && Quality::Poor > Quality::Suspect);
+    }
+}
@@ -0,0 +1,223 @@
+//! Read-through fetches for cache misses
+//!
+//! With [`TrackerConfig::read_through`](crate::TrackerConfig::read_through)
+//! set, [`MarketPriceTracker::get_price`](crate::MarketPriceTracker::get_price)
+//! answers a missing or stale asset by fetching it from the provider on
+//! demand, within [`ReadThroughConfig::timeout`], and stores the result so
+//! later reads hit the cache again.
+//!
+//! Concurrent misses for the same asset share one fetch, and an asset whose
+//! fetch failed is not fetched again until [`ReadThroughConfig::cooldown`]
+//! has passed; reads in between fail straight away. A burst of interactive
+//! reads during a provider outage therefore costs one request per asset
+//! per cooldown rather than one per read.
+//!
+//! # Example
+//! ```
+//! use market_price_sdk::read_through::ReadThroughConfig;
+//! use market_price_sdk::TrackerConfig;
+//! use std::time::Duration;
+//!
+//! let config = TrackerConfig {
+//!     read_through: Some(ReadThroughConfig {
+//!         timeout: Duration::from_secs(3),
+//!         ..Default::default()
+//!     }),
+//!     ..Default::default()
+//! };
+//! ```
+
+use crate::{
+    error::{PriceError, ProviderError},
+    runtime::Runtime,
+    types::{Asset, PriceData},
+};
+use chrono::{DateTime, Utc};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Bounds on read-through fetches
+#[derive(Debug, Clone)]
+pub struct ReadThroughConfig {
+    /// Time allowed for one on-demand fetch
+    pub timeout: Duration,
+    /// How long an asset whose fetch failed is answered with an error
+    /// instead of another fetch
+    pub cooldown: Duration,
+}
+
+impl Default for ReadThroughConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(2),
+            cooldown: Duration::from_secs(5),
+        }
+    }
+}
+
+type Flight = Shared<BoxFuture<'static, Result<PriceData, PriceError>>>;
+
+/// In-flight fetches and failure cooldowns per asset
+///
+/// Timeouts and cooldowns follow the runtime's timer and clock.
+pub(crate) struct ReadThrough {
+    config: ReadThroughConfig,
+    runtime: Runtime,
+    in_flight: Mutex<HashMap<Asset, Flight>>,
+    failed_at: Mutex<HashMap<Asset, DateTime<Utc>>>,
+}
+
+impl ReadThrough {
+    pub(crate) fn new(config: ReadThroughConfig, runtime: Runtime) -> Self {
+        Self {
+            config,
+            runtime,
+            in_flight: Mutex::new(HashMap::new()),
+            failed_at: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Runs `fetch` for `asset`, joining a fetch already in flight
+    ///
+    /// `fetch` is only called when no fetch is in flight and the asset is
+    /// not cooling down after a failure.
+    pub(crate) async fn get<F, Fut>(&self, asset: Asset, fetch: F) -> Result<PriceData, PriceError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<PriceData, ProviderError>> + Send + 'static,
+    {
+        if let Some(failed_at) = self.failed_at(asset) {
+            // A clock that went backwards leaves the asset cooling down
+            let elapsed = (self.runtime.now() - failed_at)
+                .to_std()
+                .unwrap_or_default();
+            if elapsed < self.config.cooldown {
+                return Err(PriceError::not_available(&format!(
+                    "{} (read-through cooling down after a failed fetch)",
+                    asset.symbol()
+                )));
+            }
+        }
+
+        let flight = {
+            let mut in_flight = self.in_flight.lock().expect("read-through lock poisoned");
+            in_flight
+                .entry(asset)
+                .or_insert_with(|| {
+                    let fetch = fetch();
+                    let runtime = self.runtime.clone();
+                    let timeout = self.config.timeout;
+                    async move {
+                        // The timer cuts off the whole fetch, requests in
+                        // flight included
+                        let result = runtime
+                            .timeout(timeout, fetch)
+                            .await
+                            .unwrap_or(Err(ProviderError::Timeout));
+                        result.map_err(|e| {
+                            PriceError::not_available(&format!(
+                                "{} (Provider error: {})",
+                                asset.symbol(),
+                                e
+                            ))
+                        })
+                    }
+                    .boxed()
+                    .shared()
+                })
+                .clone()
+        };
+
+        let result = flight.clone().await;
+
+        // The first waiter to finish retires the flight
+        {
+            let mut in_flight = self.in_flight.lock().expect("read-through lock poisoned");
+            if in_flight
+                .get(&asset)
+                .is_some_and(|current| current.ptr_eq(&flight))
+            {
+                in_flight.remove(&asset);
+                let mut failed_at = self.failed_at.lock().expect("read-through lock poisoned");
+                if result.is_err() {
+                    failed_at.insert(asset, self.runtime.now());
+                } else {
+                    failed_at.remove(&asset);
+                }
+            }
+        }
+        result
+    }
+
+    fn failed_at(&self, asset: Asset) -> Option<DateTime<Utc>> {
+        self.failed_at
+            .lock()
+            .expect("read-through lock poisoned")
+            .get(&asset)
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::MockClock;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shares_fetches_and_cools_down_after_failure() {
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let read_through = ReadThrough::new(
+            ReadThroughConfig {
+                timeout: Duration::from_millis(200),
+                cooldown: Duration::from_secs(60),
+            },
+            Runtime::default().with_clock(clock.clone()),
+        );
+        let calls = Arc::new(AtomicUsize::new(0));
+        let fetch = || {
+            let calls = calls.clone();
+            move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(PriceData::new(Asset::SOL, 150.0, "test".to_string()))
+            }
+        };
+
+        let (a, b) = tokio::join!(
+            read_through.get(Asset::SOL, fetch()),
+            read_through.get(Asset::SOL, fetch())
+        );
+        assert_eq!(a.unwrap().price_usd, 150.0);
+        assert_eq!(b.unwrap().price_usd, 150.0);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // A fetch past the timeout fails, and the asset then cools down
+        let slow = read_through
+            .get(Asset::BTC, || async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(PriceData::new(Asset::BTC, 64000.0, "test".to_string()))
+            })
+            .await;
+        assert!(slow.is_err());
+        let cooling = read_through
+            .get(Asset::BTC, || async {
+                panic!("fetched during cooldown");
+            })
+            .await;
+        assert!(cooling.unwrap_err().to_string().contains("cooling down"));
+
+        // The cooldown runs on the runtime's clock
+        clock.advance(Duration::from_secs(60));
+        let retried = read_through
+            .get(Asset::BTC, || async {
+                Ok(PriceData::new(Asset::BTC, 64000.0, "test".to_string()))
+            })
+            .await;
+        assert_eq!(retried.unwrap().price_usd, 64000.0);
+    }
+}
@@ -0,0 +1,214 @@
+//! Provider factories selectable by name
+//!
+//! `MARKET_PRICE_PROVIDER` names the provider [`MarketPriceTracker::new`]
+//! and the singletons start with. The name is resolved through the global
+//! [`ProviderRegistry`], which comes with the built-in providers
+//! (`hermes`, `coingecko`, `hyperliquid`, `failover`) and accepts more at
+//! runtime, so a downstream crate can plug in its own provider without
+//! patching the SDK.
+//!
+//! Register factories before the first tracker is created; a name that is
+//! registered again replaces the earlier factory, built-ins included.
+//! Names are case-insensitive.
+//!
+//! # Example
+//! ```no_run
+//! use market_price_sdk::registry::ProviderRegistry;
+//! use market_price_sdk::providers::HyperliquidProvider;
+//! use market_price_sdk::{MarketPriceProvider, MarketPriceTracker};
+//! use std::sync::Arc;
+//!
+//! # async fn example() {
+//! ProviderRegistry::global().register("kraken", |environment| async move {
+//!     // Build the proprietary provider here
+//!     let provider = HyperliquidProvider::default().with_environment(environment);
+//!     Ok(Arc::new(provider) as Arc<dyn MarketPriceProvider>)
+//! });
+//!
+//! // With MARKET_PRICE_PROVIDER=kraken
+//! let tracker = MarketPriceTracker::global().await;
+//! # }
+//! ```
+//!
+//! [`MarketPriceTracker::new`]: crate::MarketPriceTracker::new
+
+use crate::{
+    config::Environment,
+    error::ProviderError,
+    provider::MarketPriceProvider,
+    providers::{CoinGeckoProvider, FailoverProvider, HermesProvider, HyperliquidProvider},
+};
+use futures::future::{BoxFuture, FutureExt};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// Provider selected when `MARKET_PRICE_PROVIDER` is unset
+pub const DEFAULT_PROVIDER: &str = "hermes";
+
+static GLOBAL_REGISTRY: LazyLock<ProviderRegistry> = LazyLock::new(ProviderRegistry::with_builtins);
+
+/// Creates a provider for an environment
+///
+/// Implemented for any `Fn(Environment) -> impl Future<Output =
+/// Result<Arc<dyn MarketPriceProvider>, ProviderError>>`.
+pub trait ProviderFactory: Send + Sync {
+    /// Builds a new provider instance
+    fn create(
+        &self,
+        environment: Environment,
+    ) -> BoxFuture<'static, Result<Arc<dyn MarketPriceProvider>, ProviderError>>;
+}
+
+impl<F, Fut> ProviderFactory for F
+where
+    F: Fn(Environment) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<Arc<dyn MarketPriceProvider>, ProviderError>> + Send + 'static,
+{
+    fn create(
+        &self,
+        environment: Environment,
+    ) -> BoxFuture<'static, Result<Arc<dyn MarketPriceProvider>, ProviderError>> {
+        self(environment).boxed()
+    }
+}
+
+/// Provider factories keyed by lowercase name
+#[derive(Default)]
+pub struct ProviderRegistry {
+    factories: RwLock<HashMap<String, Arc<dyn ProviderFactory>>>,
+}
+
+impl ProviderRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry holding the built-in providers
+    pub fn with_builtins() -> Self {
+        let registry = Self::new();
+        registry.register("hermes", |environment| async move {
+            Ok(hermes_or_coingecko(environment).await)
+        });
+        registry.register("default", |environment| async move {
+            Ok(hermes_or_coingecko(environment).await)
+        });
+        registry.register("coingecko", |environment| async move {
+            Ok(coingecko(environment))
+        });
+        registry.register("hyperliquid", |environment| async move {
+            let provider = HyperliquidProvider::default().with_environment(environment);
+            Ok(Arc::new(provider) as Arc<dyn MarketPriceProvider>)
+        });
+        // Hermes (primary) -> CoinGecko (backup)
+        registry.register("failover", |environment| async move {
+            let backup = coingecko(environment);
+            Ok(match HermesProvider::new().await {
+                Ok(primary) => Arc::new(FailoverProvider::new(vec![primary, backup])),
+                Err(_) => backup,
+            })
+        });
+        registry
+    }
+
+    /// Returns the process-wide registry used for environment selection
+    pub fn global() -> &'static ProviderRegistry {
+        &GLOBAL_REGISTRY
+    }
+
+    /// Registers `factory` under `name`, replacing any earlier factory
+    pub fn register(&self, name: &str, factory: impl ProviderFactory + 'static) {
+        self.factories
+            .write()
+            .expect("provider registry lock poisoned")
+            .insert(name.to_lowercase(), Arc::new(factory));
+    }
+
+    /// Returns true if a factory is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories
+            .read()
+            .expect("provider registry lock poisoned")
+            .contains_key(&name.to_lowercase())
+    }
+
+    /// Returns the registered names, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .factories
+            .read()
+            .expect("provider registry lock poisoned")
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Creates the provider registered under `name`
+    ///
+    /// # Errors
+    /// [`ProviderError::UnknownProvider`] if nothing is registered under
+    /// `name`, or the factory's own error.
+    pub async fn create(
+        &self,
+        name: &str,
+        environment: Environment,
+    ) -> Result<Arc<dyn MarketPriceProvider>, ProviderError> {
+        let factory = self
+            .factories
+            .read()
+            .expect("provider registry lock poisoned")
+            .get(&name.to_lowercase())
+            .cloned()
+            .ok_or_else(|| ProviderError::UnknownProvider(name.to_string()))?;
+        factory.create(environment).await
+    }
+}
+
+fn coingecko(environment: Environment) -> Arc<dyn MarketPriceProvider> {
+    Arc::new(CoinGeckoProvider::default().with_environment(environment))
+}
+
+/// Hermes, or CoinGecko if Hermes' clients cannot be built
+async fn hermes_or_coingecko(environment: Environment) -> Arc<dyn MarketPriceProvider> {
+    match HermesProvider::new().await {
+        Ok(provider) => provider,
+        Err(e) => {
+            tracing::error!(
+                error = %e,
+                provider = "hermes",
+                "Failed to initialize Hermes provider. Falling back to CoinGecko."
+            );
+            coingecko(environment)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::mock::MockProvider;
+
+    #[tokio::test]
+    async fn test_registered_factory_is_resolved_by_name() {
+        let registry = ProviderRegistry::new();
+        registry.register("Kraken", |_| async {
+            Ok(Arc::new(MockProvider::new()) as Arc<dyn MarketPriceProvider>)
+        });
+
+        assert!(registry.contains("kraken"));
+        assert_eq!(registry.names(), vec!["kraken"]);
+        let provider = registry
+            .create("KRAKEN", Environment::Mainnet)
+            .await
+            .unwrap();
+        assert_eq!(provider.provider_name(), "mock");
+        assert!(matches!(
+            registry.create("krakn", Environment::Mainnet).await,
+            Err(ProviderError::UnknownProvider(name)) if name == "krakn"
+        ));
+        assert!(ProviderRegistry::global().contains("failover"));
+    }
+}
@@ -0,0 +1,331 @@
+//! Replicated trackers that share prices with a peer
+//!
+//! A [`ReplicatedTracker`] pairs a local [`MarketPriceTracker`] with a peer
+//! tracker running elsewhere, typically in another region or process.
+//! Every [`ReplicationConfig::interval`] the two swap their latest prices
+//! over a [`ReplicaPeer`], and reads return whichever side holds the
+//! fresher price. If one side's egress to the providers degrades, its
+//! prices go stale and the peer's keep serving.
+//!
+//! Only locally fetched prices are ever sent: the peer's view is kept
+//! apart from the local store, so a price never echoes back to the side
+//! it came from and cannot outlive its source.
+//!
+//! [`HttpPeer`] posts the local [`PriceSnapshot`] as a versioned JSON
+//! payload and expects the peer's snapshot in the response. The receiving
+//! side answers with [`ReplicatedTracker::handle_exchange`] from whatever
+//! HTTP server the application already runs.
+
+use crate::{
+    error::{PriceError, ProviderError, SchemaError},
+    http::{HttpClientFactory, HttpTransport},
+    schema::Versioned,
+    tracker::MarketPriceTracker,
+    types::{Asset, PriceData, PriceSnapshot},
+};
+use arc_swap::{ArcSwap, ArcSwapOption};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Channel to the peer tracker
+#[async_trait]
+pub trait ReplicaPeer: Send + Sync {
+    /// Sends the local prices and returns the peer's
+    async fn exchange(&self, local: &PriceSnapshot) -> Result<PriceSnapshot, ProviderError>;
+}
+
+/// Peer reached over HTTP
+///
+/// The snapshot is posted to `url`, which must answer with the body
+/// returned by the peer's [`ReplicatedTracker::handle_exchange`].
+pub struct HttpPeer {
+    transport: Arc<dyn HttpTransport>,
+    url: String,
+}
+
+impl HttpPeer {
+    /// Creates a peer using the shared HTTP client
+    pub fn new(url: impl Into<String>) -> Result<Self, ProviderError> {
+        Ok(Self::with_transport(
+            Arc::new(HttpClientFactory::global().client()?),
+            url,
+        ))
+    }
+
+    /// Creates a peer on top of a custom HTTP transport
+    pub fn with_transport(transport: Arc<dyn HttpTransport>, url: impl Into<String>) -> Self {
+        Self {
+            transport,
+            url: url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ReplicaPeer for HttpPeer {
+    async fn exchange(&self, local: &PriceSnapshot) -> Result<PriceSnapshot, ProviderError> {
+        let body = serde_json::to_value(Versioned::new(local))
+            .map_err(|e| ProviderError::InvalidResponse(e.to_string()))?;
+        let response = self
+            .transport
+            .post_json(&self.url, &body)
+            .await?
+            .error_for_status()?;
+        Versioned::<PriceSnapshot>::from_json(&response.body)
+            .map(|versioned| versioned.data)
+            .map_err(|e| ProviderError::InvalidResponse(e.to_string()))
+    }
+}
+
+/// Replication settings
+#[derive(Debug, Clone)]
+pub struct ReplicationConfig {
+    /// Time between exchanges with the peer
+    pub interval: Duration,
+}
+
+impl Default for ReplicationConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A tracker serving the fresher of its own and a peer's prices
+///
+/// # Example
+/// ```no_run
+/// # use market_price_sdk::{MarketPriceTracker, Asset};
+/// # use market_price_sdk::replication::{HttpPeer, ReplicatedTracker};
+/// # use std::sync::Arc;
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let local = Arc::new(MarketPriceTracker::new().await);
+/// let peer = Arc::new(HttpPeer::new("https://prices.eu.internal/replicate")?);
+/// let replicated = ReplicatedTracker::new(local, peer);
+/// replicated.start();
+///
+/// let sol = replicated.get_price(Asset::SOL)?;
+/// println!("SOL: ${:.2} from {}", sol.price_usd, sol.source);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ReplicatedTracker {
+    local: Arc<MarketPriceTracker>,
+    peer: Arc<dyn ReplicaPeer>,
+    config: ReplicationConfig,
+    peer_prices: Arc<ArcSwap<HashMap<Asset, PriceData>>>,
+    last_exchange: Arc<ArcSwapOption<DateTime<Utc>>>,
+    shutdown_tx: broadcast::Sender<()>,
+}
+
+impl ReplicatedTracker {
+    /// Replicates `local` with `peer` using the default settings
+    pub fn new(local: Arc<MarketPriceTracker>, peer: Arc<dyn ReplicaPeer>) -> Self {
+        Self::with_config(local, peer, ReplicationConfig::default())
+    }
+
+    /// Replicates `local` with `peer` using custom settings
+    pub fn with_config(
+        local: Arc<MarketPriceTracker>,
+        peer: Arc<dyn ReplicaPeer>,
+        config: ReplicationConfig,
+    ) -> Self {
+        let (shutdown_tx, _) = broadcast::channel(1);
+        Self {
+            local,
+            peer,
+            config,
+            peer_prices: Arc::new(ArcSwap::from_pointee(HashMap::new())),
+            last_exchange: Arc::new(ArcSwapOption::empty()),
+            shutdown_tx,
+        }
+    }
+
+    /// Returns the local tracker
+    pub fn local(&self) -> &Arc<MarketPriceTracker> {
+        &self.local
+    }
+
+    /// Starts exchanging prices with the peer in the background
+    ///
+    /// Failed exchanges are logged and retried on the next interval; the
+    /// peer's last known prices keep serving until they go stale.
+    pub fn start(&self) {
+        let local = self.local.clone();
+        let peer = self.peer.clone();
+        let peer_prices = self.peer_prices.clone();
+        let last_exchange = self.last_exchange.clone();
+        let interval = self.config.interval;
+        let runtime = self.local.runtime().clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        self.local
+            .runtime()
+            .spawn_named("replication.gossip", async move {
+                loop {
+                    tokio::select! {
+                        _ = shutdown_rx.recv() => break,
+                        _ = runtime.sleep(interval) => {
+                            if let Err(e) =
+                                exchange(&local, peer.as_ref(), &peer_prices, &last_exchange).await
+                            {
+                                tracing::warn!(error = %e, "Price exchange with peer failed");
+                            }
+                        }
+                    }
+                }
+            });
+    }
+
+    /// Exchanges prices with the peer once
+    pub async fn sync_now(&self) -> Result<(), ProviderError> {
+        exchange(
+            &self.local,
+            self.peer.as_ref(),
+            &self.peer_prices,
+            &self.last_exchange,
+        )
+        .await
+    }
+
+    /// Answers an exchange initiated by the peer
+    ///
+    /// Takes the body posted by the peer's [`HttpPeer`], records its
+    /// prices and returns the body to respond with.
+    pub fn handle_exchange(&self, payload: &str) -> Result<String, SchemaError> {
+        let remote = Versioned::<PriceSnapshot>::from_json(payload)?.data;
+        self.peer_prices.store(Arc::new(remote.prices));
+        self.last_exchange
+            .store(Some(Arc::new(self.local.runtime().now())));
+        Versioned::new(self.local.snapshot()).to_json()
+    }
+
+    /// Returns when prices were last received from the peer
+    pub fn last_exchange(&self) -> Option<DateTime<Utc>> {
+        self.last_exchange.load().as_deref().copied()
+    }
+
+    /// Returns the prices last received from the peer, including stale ones
+    pub fn peer_prices(&self) -> Arc<HashMap<Asset, PriceData>> {
+        self.peer_prices.load_full()
+    }
+
+    /// Gets the fresher of the local and peer price for an asset
+    ///
+    /// Unlike [`MarketPriceTracker::get_price`] this never calls a
+    /// provider; it only reads what either side already holds.
+    pub fn get_price(&self, asset: Asset) -> Result<PriceData, PriceError> {
+        let local = self.local.get_all_prices_arc();
+        let peer = self.peer_prices.load();
+        let price = freshest(local.get(&asset), peer.get(&asset))
+            .ok_or_else(|| PriceError::not_available(asset.symbol()))?;
+
+        let now = self.local.runtime().now();
+        if price.is_stale_at(asset.stale_threshold_secs(), now) {
+            return Err(PriceError::stale(asset.symbol(), price.age_at(now)));
+        }
+        Ok(price.clone())
+    }
+
+    /// Gets the fresher of the local and peer price for every asset,
+    /// leaving out stale ones
+    pub fn get_all_prices(&self) -> HashMap<Asset, PriceData> {
+        let local = self.local.get_all_prices_arc();
+        let peer = self.peer_prices.load();
+        let now = self.local.runtime().now();
+        local
+            .keys()
+            .chain(peer.keys())
+            .filter_map(|asset| freshest(local.get(asset), peer.get(asset)))
+            .filter(|price| !price.is_stale_at(price.asset.stale_threshold_secs(), now))
+            .map(|price| (price.asset, price.clone()))
+            .collect()
+    }
+
+    /// Stops the background exchange
+    pub fn shutdown(&self) {
+        let _ = self.shutdown_tx.send(());
+    }
+}
+
+/// Sends the local snapshot to the peer and records the peer's prices
+async fn exchange(
+    local: &MarketPriceTracker,
+    peer: &dyn ReplicaPeer,
+    peer_prices: &ArcSwap<HashMap<Asset, PriceData>>,
+    last_exchange: &ArcSwapOption<DateTime<Utc>>,
+) -> Result<(), ProviderError> {
+    let remote = peer.exchange(&local.snapshot()).await?;
+    peer_prices.store(Arc::new(remote.prices));
+    last_exchange.store(Some(Arc::new(local.runtime().now())));
+    Ok(())
+}
+
+/// Picks the more recently updated of two prices
+fn freshest<'a>(a: Option<&'a PriceData>, b: Option<&'a PriceData>) -> Option<&'a PriceData> {
+    match (a, b) {
+        (Some(a), Some(b)) if b.last_updated > a.last_updated => Some(b),
+        (Some(a), _) => Some(a),
+        (None, b) => b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::mock::MockProvider;
+    use std::sync::OnceLock;
+
+    /// Peer answering in-process through the other side's handler
+    #[derive(Default)]
+    struct LoopbackPeer(OnceLock<Arc<ReplicatedTracker>>);
+
+    #[async_trait]
+    impl ReplicaPeer for LoopbackPeer {
+        async fn exchange(&self, local: &PriceSnapshot) -> Result<PriceSnapshot, ProviderError> {
+            let payload = Versioned::new(local).to_json().unwrap();
+            let response = self.0.get().unwrap().handle_exchange(&payload).unwrap();
+            Ok(Versioned::<PriceSnapshot>::from_json(&response)
+                .unwrap()
+                .data)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serves_fresher_of_local_and_peer() {
+        let east_provider = Arc::new(MockProvider::new());
+        east_provider.set_price(Asset::SOL, 150.0);
+        let east = Arc::new(MarketPriceTracker::with_provider(east_provider));
+        east.refresh_now().await.unwrap();
+
+        let west_provider = Arc::new(MockProvider::new());
+        west_provider.set_price(Asset::BTC, 64000.0);
+        let west = Arc::new(MarketPriceTracker::with_provider(west_provider));
+        west.refresh_now().await.unwrap();
+
+        let to_west = Arc::new(LoopbackPeer::default());
+        let east = ReplicatedTracker::new(east, to_west.clone());
+        let west = Arc::new(ReplicatedTracker::new(
+            west,
+            Arc::new(LoopbackPeer::default()),
+        ));
+        to_west.0.set(west.clone()).ok();
+
+        assert!(east.get_price(Asset::BTC).is_err());
+        east.sync_now().await.unwrap();
+
+        // Both sides now serve both assets, each from its source
+        assert_eq!(east.get_price(Asset::BTC).unwrap().price_usd, 64000.0);
+        assert_eq!(west.get_price(Asset::SOL).unwrap().price_usd, 150.0);
+        assert_eq!(east.get_all_prices().len(), 2);
+        assert!(west.last_exchange().is_some());
+
+        // Peer prices are never sent back
+        assert!(!west.local().snapshot().prices.contains_key(&Asset::SOL));
+    }
+}
@@ -0,0 +1,435 @@
+//! Solana JSON-RPC client shared by the on-chain providers
+//!
+//! A single public RPC node is usually the first thing to fail in
+//! production: it rate limits, falls behind the cluster or drops
+//! connections. [`SolanaRpc`] spreads reads over several endpoints:
+//!
+//! - requests go to the current endpoint and fail over to the next one on
+//!   network errors, timeouts, 429/5xx responses and "node unhealthy" RPC
+//!   errors;
+//! - an endpoint that fails [`RpcConfig::failure_threshold`] times in a row
+//!   is skipped for [`RpcConfig::unhealthy_cooldown`], unless every
+//!   endpoint is unhealthy;
+//! - [`get_multiple_accounts`](SolanaRpc::get_multiple_accounts) batches
+//!   account reads, splitting them into requests of at most
+//!   [`RpcConfig::max_accounts_per_request`] accounts.
+//!
+//! Reads use the configured [`Commitment`].
+
+use crate::{
+    constants::SOLANA_RPC_URL,
+    error::ProviderError,
+    http::{HttpClientFactory, HttpTransport},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// RPC error codes meaning the node, not the request, is at fault
+///
+/// -32004 block not available, -32005 node unhealthy (behind the cluster),
+/// -32014 block status not yet available, -32016 minimum context slot not
+/// reached.
+const NODE_ERROR_CODES: &[i64] = &[-32004, -32005, -32014, -32016];
+
+/// Commitment level for reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Commitment {
+    /// Latest block seen by the node, may be rolled back
+    Processed,
+    /// Voted on by a supermajority of the cluster
+    #[default]
+    Confirmed,
+    /// Rooted, cannot be rolled back
+    Finalized,
+}
+
+impl Commitment {
+    /// Returns the name used in RPC requests
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Commitment::Processed => "processed",
+            Commitment::Confirmed => "confirmed",
+            Commitment::Finalized => "finalized",
+        }
+    }
+}
+
+/// Configuration for a [`SolanaRpc`] client
+#[derive(Debug, Clone)]
+pub struct RpcConfig {
+    /// RPC endpoints, primary first
+    pub endpoints: Vec<String>,
+    /// Commitment level for reads
+    pub commitment: Commitment,
+    /// Maximum accounts per `getMultipleAccounts` request (the RPC limit is 100)
+    pub max_accounts_per_request: usize,
+    /// Consecutive failures after which an endpoint is considered unhealthy
+    pub failure_threshold: u32,
+    /// How long an unhealthy endpoint is skipped
+    pub unhealthy_cooldown: Duration,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: vec![SOLANA_RPC_URL.to_string()],
+            commitment: Commitment::default(),
+            max_accounts_per_request: 100,
+            failure_threshold: 3,
+            unhealthy_cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Health of one RPC endpoint
+#[derive(Debug, Clone)]
+pub struct EndpointHealth {
+    /// Endpoint URL
+    pub url: String,
+    /// False while the endpoint is being skipped
+    pub healthy: bool,
+    /// Failures since the last success
+    pub consecutive_failures: u32,
+    /// Successful requests so far
+    pub total_requests: u64,
+    /// Failed requests so far
+    pub total_failures: u64,
+    /// Most recent failure
+    pub last_error: Option<String>,
+}
+
+/// JSON-RPC response envelope, shared with the non-Solana RPC providers
+#[derive(Debug, Deserialize)]
+pub(crate) struct RpcResponse<T> {
+    pub(crate) result: Option<T>,
+    pub(crate) error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RpcError {
+    pub(crate) code: i64,
+    pub(crate) message: String,
+}
+
+/// `getMultipleAccounts` result
+#[derive(Debug, Deserialize)]
+struct MultipleAccounts {
+    value: Vec<Option<AccountInfo>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountInfo {
+    /// `[data, encoding]`
+    data: (String, String),
+}
+
+/// Counters for one endpoint
+struct Endpoint {
+    url: String,
+    consecutive_failures: AtomicU32,
+    total_requests: AtomicU64,
+    total_failures: AtomicU64,
+    unhealthy_until: Mutex<Option<Instant>>,
+    last_error: Mutex<Option<String>>,
+}
+
+impl Endpoint {
+    fn is_healthy(&self) -> bool {
+        self.unhealthy_until
+            .lock()
+            .expect("endpoint lock poisoned")
+            .is_none_or(|until| Instant::now() >= until)
+    }
+
+    fn record_success(&self) {
+        self.total_requests.fetch_add(1, Ordering::Relaxed);
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        *self.unhealthy_until.lock().expect("endpoint lock poisoned") = None;
+    }
+
+    fn record_failure(&self, error: &ProviderError, config: &RpcConfig) {
+        self.total_requests.fetch_add(1, Ordering::Relaxed);
+        self.total_failures.fetch_add(1, Ordering::Relaxed);
+        *self.last_error.lock().expect("endpoint lock poisoned") = Some(error.to_string());
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= config.failure_threshold {
+            tracing::warn!(
+                endpoint = %self.url,
+                failures,
+                cooldown_secs = config.unhealthy_cooldown.as_secs(),
+                "RPC endpoint unhealthy"
+            );
+            *self.unhealthy_until.lock().expect("endpoint lock poisoned") =
+                Some(Instant::now() + config.unhealthy_cooldown);
+        }
+    }
+}
+
+/// Solana JSON-RPC client with endpoint failover and health tracking
+pub struct SolanaRpc {
+    transport: Arc<dyn HttpTransport>,
+    config: RpcConfig,
+    endpoints: Vec<Endpoint>,
+    current: AtomicUsize,
+}
+
+impl SolanaRpc {
+    /// Creates a client using the shared HTTP client
+    ///
+    /// # Panics
+    /// If `config.endpoints` is empty
+    pub fn new(config: RpcConfig) -> Result<Self, ProviderError> {
+        Ok(Self::with_transport(
+            Arc::new(HttpClientFactory::global().client()?),
+            config,
+        ))
+    }
+
+    /// Creates a client on top of a custom HTTP transport
+    ///
+    /// # Panics
+    /// If `config.endpoints` is empty
+    pub fn with_transport(transport: Arc<dyn HttpTransport>, config: RpcConfig) -> Self {
+        assert!(
+            !config.endpoints.is_empty(),
+            "at least one RPC endpoint is required"
+        );
+        let endpoints = config
+            .endpoints
+            .iter()
+            .map(|url| Endpoint {
+                url: url.clone(),
+                consecutive_failures: AtomicU32::new(0),
+                total_requests: AtomicU64::new(0),
+                total_failures: AtomicU64::new(0),
+                unhealthy_until: Mutex::new(None),
+                last_error: Mutex::new(None),
+            })
+            .collect();
+        Self {
+            transport,
+            config,
+            endpoints,
+            current: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the endpoint requests currently go to
+    pub fn current(&self) -> &str {
+        &self.endpoints[self.current.load(Ordering::Relaxed) % self.endpoints.len()].url
+    }
+
+    /// Returns the commitment level used for reads
+    pub fn commitment(&self) -> Commitment {
+        self.config.commitment
+    }
+
+    /// Reports the health of every endpoint, primary first
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| EndpointHealth {
+                url: endpoint.url.clone(),
+                healthy: endpoint.is_healthy(),
+                consecutive_failures: endpoint.consecutive_failures.load(Ordering::Relaxed),
+                total_requests: endpoint.total_requests.load(Ordering::Relaxed),
+                total_failures: endpoint.total_failures.load(Ordering::Relaxed),
+                last_error: endpoint
+                    .last_error
+                    .lock()
+                    .expect("endpoint lock poisoned")
+                    .clone(),
+            })
+            .collect()
+    }
+
+    /// Endpoint indices in the order to try them: healthy endpoints from
+    /// the current one onwards, then unhealthy ones
+    fn candidates(&self) -> Vec<usize> {
+        let start = self.current.load(Ordering::Relaxed);
+        let count = self.endpoints.len();
+        let (healthy, unhealthy): (Vec<usize>, Vec<usize>) = (0..count)
+            .map(|offset| (start + offset) % count)
+            .partition(|index| self.endpoints[*index].is_healthy());
+        healthy.into_iter().chain(unhealthy).collect()
+    }
+
+    /// Calls an RPC method, failing over between endpoints
+    pub async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, ProviderError> {
+        let request_body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let mut last_error = None;
+        for index in self.candidates() {
+            let endpoint = &self.endpoints[index];
+            match self.send(&endpoint.url, method, &request_body).await {
+                Ok(result) => {
+                    endpoint.record_success();
+                    self.current.store(index, Ordering::Relaxed);
+                    return Ok(result);
+                }
+                Err((error, false)) => {
+                    endpoint.record_success();
+                    return Err(error);
+                }
+                Err((error, true)) => {
+                    tracing::warn!(
+                        endpoint = %endpoint.url,
+                        method,
+                        error = %error,
+                        "RPC request failed, trying next endpoint"
+                    );
+                    endpoint.record_failure(&error, &self.config);
+                    last_error = Some(error);
+                }
+            }
+        }
+        Err(last_error.expect("at least one RPC endpoint"))
+    }
+
+    /// Sends one request, flagging errors the next endpoint might not have
+    async fn send<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        method: &str,
+        request_body: &serde_json::Value,
+    ) -> Result<T, (ProviderError, bool)> {
+        let response = self
+            .transport
+            .post_json(url, request_body)
+            .await
+            .map_err(|e| {
+                let retryable =
+                    matches!(e, ProviderError::NetworkError(_) | ProviderError::Timeout);
+                (e, retryable)
+            })?;
+        let retryable = response.status == 429 || response.status >= 500;
+        let body = response
+            .error_for_status()
+            .map_err(|e| (e, retryable))?
+            .body;
+
+        let response: RpcResponse<T> = serde_json::from_str(&body).map_err(|e| {
+            let error = ProviderError::InvalidResponse(format!(
+                "Failed to parse {} response: {}",
+                method, e
+            ));
+            (error, false)
+        })?;
+
+        if let Some(error) = response.error {
+            return Err((
+                ProviderError::ApiError(format!("RPC error {}: {}", error.code, error.message)),
+                NODE_ERROR_CODES.contains(&error.code),
+            ));
+        }
+        response.result.ok_or_else(|| {
+            (
+                ProviderError::InvalidResponse(format!("Empty {} response", method)),
+                false,
+            )
+        })
+    }
+
+    /// Fetches the raw data of `accounts`, in order; missing accounts are `None`
+    pub async fn get_multiple_accounts(
+        &self,
+        accounts: &[&str],
+    ) -> Result<Vec<Option<Vec<u8>>>, ProviderError> {
+        let chunks = accounts.chunks(self.config.max_accounts_per_request.max(1));
+        let results = futures::future::try_join_all(chunks.map(|chunk| async move {
+            let result: MultipleAccounts = self
+                .call(
+                    "getMultipleAccounts",
+                    serde_json::json!([
+                        chunk,
+                        {
+                            "encoding": "base64",
+                            "commitment": self.config.commitment.as_str(),
+                        }
+                    ]),
+                )
+                .await?;
+            if result.value.len() != chunk.len() {
+                return Err(ProviderError::InvalidResponse(format!(
+                    "Requested {} accounts, got {}",
+                    chunk.len(),
+                    result.value.len()
+                )));
+            }
+            result
+                .value
+                .into_iter()
+                .zip(chunk)
+                .map(|(info, account)| {
+                    info.map(|info| {
+                        STANDARD.decode(&info.data.0).map_err(|e| {
+                            ProviderError::InvalidResponse(format!(
+                                "Invalid data for account {}: {}",
+                                account, e
+                            ))
+                        })
+                    })
+                    .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()
+        }))
+        .await?;
+
+        Ok(results.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::fake::FakeTransport;
+
+    #[tokio::test]
+    async fn test_failover_and_batching() {
+        let transport = Arc::new(FakeTransport::new());
+        transport.push_response(503, "unavailable");
+        transport.push_response(
+            200,
+            r#"{"jsonrpc":"2.0","result":{"context":{"slot":1},"value":[{"data":["AQI=","base64"]},null]},"id":1}"#,
+        );
+        transport.push_response(
+            200,
+            r#"{"jsonrpc":"2.0","result":{"context":{"slot":1},"value":[{"data":["Aw==","base64"]}]},"id":1}"#,
+        );
+        let rpc = SolanaRpc::with_transport(
+            transport.clone(),
+            RpcConfig {
+                endpoints: vec!["http://primary".to_string(), "http://backup".to_string()],
+                max_accounts_per_request: 2,
+                failure_threshold: 1,
+                ..Default::default()
+            },
+        );
+
+        let accounts = rpc.get_multiple_accounts(&["a", "b", "c"]).await.unwrap();
+
+        assert_eq!(accounts, vec![Some(vec![1, 2]), None, Some(vec![3])]);
+        assert_eq!(rpc.current(), "http://backup");
+        let health = rpc.health();
+        assert!(!health[0].healthy);
+        assert_eq!(health[1].total_requests, 2);
+        let requests = transport.requests();
+        assert_eq!(requests[0].url, "http://primary");
+        assert_eq!(
+            requests[1].body.as_ref().unwrap()["params"][1]["commitment"],
+            "confirmed"
+        );
+    }
+}
@@ -0,0 +1,350 @@
+//! Executor abstraction for the tracker's background work
+//!
+//! The tracker spawns its polling loop and waits between attempts through
+//! the [`Spawner`] and [`Timer`] traits instead of calling tokio directly.
+//! Tokio is the default; enable the `smol` feature for [`Runtime::smol`].
+//!
+//! Staleness checks and event timestamps read the time from the runtime's
+//! [`Clock`] rather than `Utc::now()`, so tests can drive expiry with a
+//! [`MockClock`], or with [`TokioClock`] under `tokio::time::pause`,
+//! instead of waiting in real time.
+//!
+//! The store and channels use `tokio::sync` primitives, which are
+//! executor-agnostic. The bundled HTTP providers are built on reqwest,
+//! which still needs a tokio reactor for network I/O.
+
+use crate::tasks;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::{Instant, MissedTickBehavior};
+
+/// Spawns detached background tasks
+pub trait Spawner: Send + Sync {
+    /// Runs the future to completion in the background
+    fn spawn(&self, future: BoxFuture<'static, ()>);
+
+    /// Runs the future in the background under a name the executor can
+    /// report; defaults to [`spawn`](Self::spawn)
+    fn spawn_named(&self, _name: &str, future: BoxFuture<'static, ()>) {
+        self.spawn(future);
+    }
+}
+
+/// Provides async sleeps
+pub trait Timer: Send + Sync {
+    /// Completes after the given duration
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    /// Returns the current time
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system clock (the default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock following tokio's timer, so it stands still under
+/// `tokio::time::pause` and jumps with `tokio::time::advance`
+///
+/// Starts at the system time it was created at. Must be created inside a
+/// tokio runtime.
+#[derive(Debug, Clone, Copy)]
+pub struct TokioClock {
+    start: DateTime<Utc>,
+    started: tokio::time::Instant,
+}
+
+impl TokioClock {
+    /// Creates a clock reading the system time now
+    pub fn new() -> Self {
+        Self {
+            start: Utc::now(),
+            started: tokio::time::Instant::now(),
+        }
+    }
+}
+
+impl Default for TokioClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for TokioClock {
+    fn now(&self) -> DateTime<Utc> {
+        let elapsed = chrono::Duration::from_std(self.started.elapsed()).unwrap_or_default();
+        self.start + elapsed
+    }
+}
+
+/// Clock that only moves when told to
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl MockClock {
+    /// Creates a clock stopped at `now`
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Moves the clock forward
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.now.lock().expect("clock lock poisoned");
+        *now += chrono::Duration::from_std(duration).unwrap_or_default();
+    }
+
+    /// Sets the clock to `now`
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().expect("clock lock poisoned") = now;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().expect("clock lock poisoned")
+    }
+}
+
+/// Tokio-backed spawner and timer
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioExecutor;
+
+impl Spawner for TokioExecutor {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        tokio::spawn(future);
+    }
+
+    #[cfg(all(tokio_unstable, feature = "tokio-console"))]
+    fn spawn_named(&self, name: &str, future: BoxFuture<'static, ()>) {
+        if let Err(e) = tokio::task::Builder::new().name(name).spawn(future) {
+            tracing::error!(task = name, error = %e, "Failed to spawn task");
+        }
+    }
+}
+
+impl Timer for TokioExecutor {
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// smol-backed spawner and timer
+#[cfg(feature = "smol")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmolExecutor;
+
+#[cfg(feature = "smol")]
+impl Spawner for SmolExecutor {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        smol::spawn(future).detach();
+    }
+}
+
+#[cfg(feature = "smol")]
+impl Timer for SmolExecutor {
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(async move {
+            smol::Timer::after(duration).await;
+        })
+    }
+}
+
+/// The spawner, timer and clock used by a tracker
+#[derive(Clone)]
+pub struct Runtime {
+    spawner: Arc<dyn Spawner>,
+    timer: Arc<dyn Timer>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Runtime {
+    /// Creates a runtime from a custom spawner and timer, reading the
+    /// system clock
+    pub fn new(spawner: Arc<dyn Spawner>, timer: Arc<dyn Timer>) -> Self {
+        Self {
+            spawner,
+            timer,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Reads the time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Tokio runtime (the default)
+    pub fn tokio() -> Self {
+        Self::new(Arc::new(TokioExecutor), Arc::new(TokioExecutor))
+    }
+
+    /// smol runtime
+    #[cfg(feature = "smol")]
+    pub fn smol() -> Self {
+        Self::new(Arc::new(SmolExecutor), Arc::new(SmolExecutor))
+    }
+
+    /// Spawns a detached background task
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.spawner.spawn(Box::pin(future));
+    }
+
+    /// Spawns a detached background task, registered in [`crate::tasks`]
+    pub fn spawn_named<F>(&self, name: impl Into<String>, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let name = name.into();
+        self.spawner
+            .spawn_named(&name, Box::pin(tasks::instrument(name.clone(), future)));
+    }
+
+    /// Sleeps for the given duration
+    pub fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        self.timer.sleep(duration)
+    }
+
+    /// Runs `future` for at most `duration`, returning `None` if the
+    /// runtime's timer fires first
+    ///
+    /// Unlike an HTTP deadline this bounds the whole future, including
+    /// work that never reaches the network.
+    pub async fn timeout<F: Future>(&self, duration: Duration, future: F) -> Option<F::Output> {
+        tokio::select! {
+            // A future that is already ready wins over an expired timer
+            biased;
+            output = future => Some(output),
+            _ = self.sleep(duration) => None,
+        }
+    }
+
+    /// Returns the current time according to the runtime's clock
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Self::tokio()
+    }
+}
+
+/// How late a tick may fire before it counts as missed, as in tokio's
+/// `Interval`
+const MISSED_TICK_TOLERANCE: Duration = Duration::from_millis(5);
+
+/// Fixed-rate schedule on a runtime's timer
+///
+/// Ticks are due at whole multiples of the period from the start, so time
+/// spent between ticks does not push later ticks back. Ticks missed
+/// because the caller was busy past the following deadline are handled as
+/// `missed` says, with the same meaning as for tokio's `Interval`.
+pub(crate) struct Ticker {
+    runtime: Runtime,
+    period: Duration,
+    missed: MissedTickBehavior,
+    next: Instant,
+}
+
+impl Ticker {
+    /// Creates a schedule whose first tick is due at `start`
+    pub(crate) fn starting_at(
+        runtime: Runtime,
+        start: Instant,
+        period: Duration,
+        missed: MissedTickBehavior,
+    ) -> Self {
+        Self {
+            runtime,
+            period: period.max(Duration::from_millis(1)),
+            missed,
+            next: start,
+        }
+    }
+
+    /// Waits for the next tick
+    pub(crate) async fn tick(&mut self) {
+        let due = self.next;
+        let now = Instant::now();
+        if due > now {
+            self.runtime.sleep(due - now).await;
+        }
+
+        let now = Instant::now();
+        self.next = if now > due + MISSED_TICK_TOLERANCE {
+            match self.missed {
+                MissedTickBehavior::Burst => due + self.period,
+                MissedTickBehavior::Delay => now + self.period,
+                _ => {
+                    // Skip: the first deadline on the original grid after now
+                    let behind = (now - due).as_nanos() / self.period.as_nanos();
+                    due + self.period * (behind as u32 + 1)
+                }
+            }
+        } else {
+            due + self.period
+        };
+    }
+}
+
+impl std::fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Runtime").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tokio_clock_follows_paused_time() {
+        let runtime = Runtime::default().with_clock(Arc::new(TokioClock::new()));
+        let start = runtime.now();
+        runtime.sleep(Duration::from_secs(300)).await;
+        assert_eq!(runtime.now() - start, chrono::Duration::seconds(300));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_ticker_keeps_cadence_despite_slow_work() {
+        let period = Duration::from_secs(10);
+        let start = Instant::now();
+        let mut ticker = Ticker::starting_at(
+            Runtime::default(),
+            start + period,
+            period,
+            MissedTickBehavior::Skip,
+        );
+
+        let mut fired = Vec::new();
+        for work in [3, 3, 25, 3, 3] {
+            ticker.tick().await;
+            fired.push((Instant::now() - start).as_secs());
+            tokio::time::sleep(Duration::from_secs(work)).await;
+        }
+        // The 25s of work overran the 40s and 50s deadlines: the late tick
+        // fires at once and the schedule resumes on the original grid
+        assert_eq!(fired, vec![10, 20, 30, 55, 60]);
+    }
+}
@@ -0,0 +1,167 @@
+//! Versioned JSON wire format
+//!
+//! Payloads meant to be persisted or sent to other services (Kafka topics,
+//! files, HTTP) should be wrapped in [`Versioned`], which adds a top-level
+//! `schema_version` field next to the payload's own fields:
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "asset": "SOL",
+//!   "price_usd": 150.25,
+//!   "price_change_24h": null,
+//!   "last_updated": "2025-01-01T00:00:00Z",
+//!   "source": "hermes",
+//!   "confidence": 0.05,
+//!   "quality": "excellent"
+//! }
+//! ```
+//!
+//! # Version 1
+//!
+//! | Type | Shape |
+//! |------|-------|
+//! | [`PriceData`] | object; `asset`, `price_usd`, `last_updated`, `source` required; `price_change_24h` nullable; `source_info` (object with `primary`, `contributors` and `method`), `confidence`, `ema_price_usd`, `deviation_bps` and `price_decimal` (`decimal` feature, as a string) omitted when absent; `quality` defaults to `"fair"`; `sequence` defaults to 0 |
+//! | [`MarketPriceEvent`] | object tagged by `type` (`PRICE_UPDATED`, `PRICE_FETCH_FAILED`, `PROVIDER_STATUS_CHANGED`, `PROVIDER_DIVERGENCE`, `PRICE_EXPIRED`, `HEARTBEAT`, `SUBSCRIBER_LAGGED`, `PRICE_WENT_STALE`, `PRICE_RECOVERED`), with the variant's fields alongside; asset-scoped price events carry the `sequence` of the price they refer to |
+//! | [`PriceSnapshot`] | object with `taken_at` and `prices`, a map from asset symbol to [`PriceData`] |
+//! | [`FeedMessage`] | object tagged by `type`: `SNAPSHOT` with the [`PriceSnapshot`] fields, or `DELTA` with the [`PriceData`] fields |
+//!
+//! Assets are upper-case identifiers (`"SOL"`, `"JITOSOL"`), timestamps are
+//! RFC 3339 strings and event ids are UUIDs.
+//!
+//! # Compatibility
+//!
+//! Within a schema version, changes are additive only: new optional fields,
+//! new event types and new assets. Consumers should ignore unknown fields
+//! and tolerate unknown event types and assets. Removing or renaming a
+//! field, or changing its type or meaning, bumps [`SCHEMA_VERSION`], and
+//! [`Versioned::from_json`] rejects payloads from a newer version than it
+//! understands.
+//!
+//! [`PriceData`]: crate::types::PriceData
+//! [`MarketPriceEvent`]: crate::types::MarketPriceEvent
+//! [`PriceSnapshot`]: crate::types::PriceSnapshot
+//! [`FeedMessage`]: crate::subscription::FeedMessage
+
+use crate::error::SchemaError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Current version of the wire format
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A payload tagged with the schema version it was written with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Versioned<T> {
+    /// Wire format version of `data`
+    pub schema_version: u32,
+    /// The payload, flattened into the same JSON object
+    #[serde(flatten)]
+    pub data: T,
+}
+
+impl<T> Versioned<T> {
+    /// Tags a payload with the current schema version
+    pub fn new(data: T) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            data,
+        }
+    }
+
+    /// Returns the payload
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+impl<T: Serialize> Versioned<T> {
+    /// Serializes the payload with its schema version
+    pub fn to_json(&self) -> Result<String, SchemaError> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+impl<T: DeserializeOwned> Versioned<T> {
+    /// Parses a versioned payload, rejecting versions newer than this build
+    pub fn from_json(json: &str) -> Result<Self, SchemaError> {
+        #[derive(Deserialize)]
+        struct Header {
+            schema_version: u32,
+        }
+
+        let header: Header = serde_json::from_str(json)?;
+        if header.schema_version > SCHEMA_VERSION {
+            return Err(SchemaError::UnsupportedVersion {
+                found: header.schema_version,
+                supported: SCHEMA_VERSION,
+            });
+        }
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Asset, MarketPriceEvent, PriceData, PriceSnapshot, Quality};
+
+    // Fixtures written with schema version 1. Never edit them: new fields
+    // must stay optional so these keep parsing.
+    const PRICE_V1: &str = r#"{"schema_version":1,"asset":"SOL","price_usd":150.25,"price_change_24h":null,"last_updated":"2025-01-01T00:00:00Z","source":"hermes","confidence":0.05}"#;
+    const EVENT_V1: &str = r#"{"schema_version":1,"type":"PROVIDER_DIVERGENCE","id":"67e55044-10b1-426f-9247-bb680e5fe0c8","asset":"BTC","primary":"hermes","primary_price_usd":64000.0,"reference":"coingecko","reference_price_usd":64500.0,"deviation_bps":77.5,"timestamp":"2025-01-01T00:00:00Z"}"#;
+    const SNAPSHOT_V1: &str = r#"{"schema_version":1,"taken_at":"2025-01-01T00:00:01Z","prices":{"SOL":{"asset":"SOL","price_usd":150.25,"price_change_24h":1.5,"last_updated":"2025-01-01T00:00:00Z","source":"coingecko","quality":"good"}}}"#;
+
+    #[test]
+    fn test_v1_fixtures_parse() {
+        let price = Versioned::<PriceData>::from_json(PRICE_V1).unwrap().data;
+        assert_eq!(price.asset, Asset::SOL);
+        assert_eq!(price.confidence, Some(0.05));
+        assert_eq!(price.quality, Quality::Fair);
+
+        let event = Versioned::<MarketPriceEvent>::from_json(EVENT_V1).unwrap();
+        assert_eq!(event.data.event_type(), "PROVIDER_DIVERGENCE");
+
+        let snapshot = Versioned::<PriceSnapshot>::from_json(SNAPSHOT_V1).unwrap();
+        assert_eq!(snapshot.data.prices[&Asset::SOL].quality, Quality::Good);
+    }
+
+    #[test]
+    fn test_round_trip_and_newer_versions() {
+        let price = PriceData::new(Asset::ETH, 3000.0, "hyperliquid".to_string());
+        let json = Versioned::new(price).to_json().unwrap();
+        assert!(json.starts_with(r#"{"schema_version":1,"#));
+        let parsed = Versioned::<PriceData>::from_json(&json).unwrap();
+        assert_eq!(parsed.data.price_usd, 3000.0);
+
+        let newer = PRICE_V1.replace(r#""schema_version":1"#, r#""schema_version":2"#);
+        assert!(matches!(
+            Versioned::<PriceData>::from_json(&newer),
+            Err(SchemaError::UnsupportedVersion { found: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_stale_event_age_round_trips_as_millis() {
+        let event = MarketPriceEvent::PriceWentStale {
+            id: uuid::Uuid::nil(),
+            asset: Asset::SOL,
+            age_ms: 61_500,
+            last_updated: "2025-01-01T00:00:00Z".parse().unwrap(),
+            sequence: 7,
+            timestamp: "2025-01-01T00:01:01.500Z".parse().unwrap(),
+        };
+        let json = Versioned::new(event).to_json().unwrap();
+        assert!(json.contains(r#""age_ms":61500"#));
+
+        let parsed = Versioned::<MarketPriceEvent>::from_json(&json).unwrap();
+        assert!(matches!(
+            parsed.data,
+            MarketPriceEvent::PriceWentStale {
+                age_ms: 61_500,
+                sequence: 7,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,149 @@
+//! Startup self-test of the configured providers
+//!
+//! [`MarketPriceTracker::self_test`](crate::MarketPriceTracker::self_test)
+//! sends one request to every provider the tracker is configured with and
+//! checks that each answer parses into a usable price. Nothing is stored,
+//! so it can run against a live tracker, e.g. as a deploy-time smoke check.
+
+use crate::{
+    provider::MarketPriceProvider,
+    types::{Asset, PriceData},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Outcome of one provider's self-test request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderCheck {
+    /// Name of the provider
+    pub provider: String,
+    /// Assets the provider reports it supports
+    pub supported_assets: Vec<Asset>,
+    /// Time the request took, in milliseconds
+    pub latency_ms: u64,
+    /// Prices that parsed and passed validation
+    pub prices: HashMap<Asset, PriceData>,
+    /// Why each remaining requested asset failed
+    pub errors: HashMap<Asset, String>,
+}
+
+impl ProviderCheck {
+    /// Returns true if every requested asset returned a valid price
+    pub fn passed(&self) -> bool {
+        self.errors.is_empty() && !self.prices.is_empty()
+    }
+}
+
+/// Result of [`MarketPriceTracker::self_test`](crate::MarketPriceTracker::self_test)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfTestReport {
+    /// When the self-test started
+    pub started_at: DateTime<Utc>,
+    /// One check per configured provider, primary first
+    pub providers: Vec<ProviderCheck>,
+}
+
+impl SelfTestReport {
+    /// Returns true if every provider passed
+    pub fn passed(&self) -> bool {
+        !self.providers.is_empty() && self.providers.iter().all(ProviderCheck::passed)
+    }
+}
+
+/// Fetches the supported subset of `assets` from `provider` once and
+/// validates the answer
+pub(crate) async fn check_provider(
+    provider: &dyn MarketPriceProvider,
+    assets: &[Asset],
+    timeout: Duration,
+) -> ProviderCheck {
+    let capabilities = provider.capabilities();
+    let requested: Vec<Asset> = assets
+        .iter()
+        .copied()
+        .filter(|asset| capabilities.supports(*asset))
+        .collect();
+
+    let start = Instant::now();
+    let results = if requested.is_empty() {
+        HashMap::new()
+    } else {
+        provider
+            .fetch_prices_partial(&requested, Some(start + timeout))
+            .await
+    };
+    let latency_ms = start.elapsed().as_millis() as u64;
+
+    let mut prices = HashMap::new();
+    let mut errors = HashMap::new();
+    for asset in &requested {
+        match results.get(asset) {
+            Some(Ok(price)) => match validate(*asset, price) {
+                Ok(()) => {
+                    prices.insert(*asset, price.clone());
+                }
+                Err(reason) => {
+                    errors.insert(*asset, reason);
+                }
+            },
+            Some(Err(e)) => {
+                errors.insert(*asset, e.to_string());
+            }
+            None => {
+                errors.insert(*asset, "no result returned".to_string());
+            }
+        }
+    }
+
+    ProviderCheck {
+        provider: provider.provider_name().to_string(),
+        supported_assets: capabilities.supported_assets,
+        latency_ms,
+        prices,
+        errors,
+    }
+}
+
+/// Checks that a parsed price is usable
+fn validate(asset: Asset, price: &PriceData) -> Result<(), String> {
+    if price.asset != asset {
+        return Err(format!("returned a price for {}", price.asset));
+    }
+    if !price.price_usd.is_finite() || price.price_usd <= 0.0 {
+        return Err(format!("invalid price {}", price.price_usd));
+    }
+    if price.is_stale(asset.stale_threshold_secs()) {
+        return Err(format!("price is stale (age: {:?})", price.age()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ProviderError;
+    use crate::provider::mock::MockProvider;
+
+    #[tokio::test]
+    async fn test_reports_valid_and_failed_assets() {
+        let provider = MockProvider::new();
+        provider.set_price(Asset::SOL, 150.0);
+        provider.set_price(Asset::ETH, -1.0);
+        provider.set_error(Asset::BTC, ProviderError::Timeout);
+
+        let check = check_provider(
+            &provider,
+            &[Asset::SOL, Asset::BTC, Asset::ETH],
+            Duration::from_secs(1),
+        )
+        .await;
+
+        assert_eq!(check.provider, "mock");
+        assert_eq!(check.prices[&Asset::SOL].price_usd, 150.0);
+        assert!(check.errors[&Asset::ETH].contains("invalid price"));
+        assert!(check.errors.contains_key(&Asset::BTC));
+        assert!(!check.passed());
+    }
+}
@@ -0,0 +1,218 @@
+//! InfluxDB line-protocol sink for prices and provider metrics
+//!
+//! Prices are written to the `market_price` measurement and provider
+//! metrics to `market_provider`:
+//!
+//! ```text
+//! market_price,asset=SOL,source=hermes,quality=excellent price_usd=150.25,confidence=0.05 1735689600000000000
+//! market_provider,provider=hermes latency_p50_ms=12,latency_p90_ms=41,latency_p95_ms=57,latency_p99_ms=80,latency_p999_ms=143,success_rate=1,total_requests=42i,failed_requests=0i,requests_per_min=6,errors_per_min=0 1735689600000000000
+//! ```
+//!
+//! Streaming providers add `connected`, `connects`, `disconnects` and
+//! `parse_errors` fields to `market_provider`.
+
+use crate::{
+    error::SinkError,
+    metrics::ProviderMetrics,
+    sinks::{MetricsSink, PriceSink},
+    types::PriceData,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+
+/// Measurement holding price points
+pub const PRICE_MEASUREMENT: &str = "market_price";
+
+/// Measurement holding provider metrics
+pub const PROVIDER_MEASUREMENT: &str = "market_provider";
+
+/// Writes prices and provider metrics to the InfluxDB v2 write API
+///
+/// Works with InfluxDB 2.x/3.x and anything else accepting line protocol
+/// at `/api/v2/write` (e.g. Telegraf's `influxdb_v2_listener`).
+pub struct InfluxSink {
+    client: Client,
+    write_url: String,
+    token: Option<String>,
+}
+
+impl InfluxSink {
+    /// Creates a sink writing to `bucket` in `org` on the server at `url`
+    pub fn new(url: &str, org: &str, bucket: &str) -> Self {
+        Self {
+            client: Client::new(),
+            write_url: format!(
+                "{}/api/v2/write?org={}&bucket={}&precision=ns",
+                url.trim_end_matches('/'),
+                org,
+                bucket
+            ),
+            token: None,
+        }
+    }
+
+    /// Authenticates with an API token
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Uses an existing HTTP client (proxy, TLS settings)
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Posts a block of line protocol
+    pub async fn write_lines(&self, body: String) -> Result<(), SinkError> {
+        let mut request = self.client.post(&self.write_url).body(body);
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", format!("Token {}", token));
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| SinkError::Publish(e.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(SinkError::Publish(format!("HTTP {}: {}", status, body)));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PriceSink for InfluxSink {
+    async fn write_batch(&self, prices: &[PriceData]) -> Result<(), SinkError> {
+        if prices.is_empty() {
+            return Ok(());
+        }
+        let body = prices.iter().map(price_line).collect::<Vec<_>>().join("\n");
+        self.write_lines(body).await
+    }
+
+    fn name(&self) -> &'static str {
+        "influx"
+    }
+}
+
+#[async_trait]
+impl MetricsSink for InfluxSink {
+    async fn write_metrics(&self, metrics: &ProviderMetrics) -> Result<(), SinkError> {
+        self.write_lines(metrics_line(metrics, Utc::now())).await
+    }
+
+    fn name(&self) -> &'static str {
+        "influx"
+    }
+}
+
+/// Formats a price as a line-protocol point, timestamped at `last_updated`
+pub fn price_line(price: &PriceData) -> String {
+    let mut fields = vec![format!("price_usd={}", price.price_usd)];
+    let optional = [
+        ("confidence", price.confidence),
+        ("ema_price_usd", price.ema_price_usd),
+        ("price_change_24h", price.price_change_24h),
+        ("deviation_bps", price.deviation_bps),
+    ];
+    for (name, value) in optional {
+        if let Some(value) = value {
+            fields.push(format!("{}={}", name, value));
+        }
+    }
+
+    format!(
+        "{},asset={},source={},quality={} {} {}",
+        PRICE_MEASUREMENT,
+        escape_tag(price.asset.symbol()),
+        escape_tag(&price.source),
+        price.quality.as_str(),
+        fields.join(","),
+        timestamp_ns(price.last_updated)
+    )
+}
+
+/// Formats provider metrics as a line-protocol point
+pub fn metrics_line(metrics: &ProviderMetrics, timestamp: DateTime<Utc>) -> String {
+    let mut fields = vec![
+        format!("latency_p50_ms={}", metrics.latency_p50_ms),
+        format!("latency_p90_ms={}", metrics.latency_p90_ms),
+        format!("latency_p95_ms={}", metrics.latency_p95_ms),
+        format!("latency_p99_ms={}", metrics.latency_p99_ms),
+        format!("latency_p999_ms={}", metrics.latency_p999_ms),
+        format!("success_rate={}", metrics.success_rate),
+        format!("total_requests={}i", metrics.total_requests),
+        format!("failed_requests={}i", metrics.failed_requests),
+        format!("requests_per_min={}", metrics.requests_per_min),
+        format!("errors_per_min={}", metrics.errors_per_min),
+    ];
+    if let Some(connection) = &metrics.connection {
+        fields.push(format!("connected={}", connection.connected));
+        fields.push(format!("connects={}i", connection.connects));
+        fields.push(format!("disconnects={}i", connection.disconnects));
+        fields.push(format!("parse_errors={}i", connection.parse_errors));
+    }
+    if let Some(quota) = &metrics.quota {
+        fields.push(format!("quota_used={}i", quota.used));
+        fields.push(format!("quota_remaining={}i", quota.remaining));
+    }
+
+    format!(
+        "{},provider={} {} {}",
+        PROVIDER_MEASUREMENT,
+        escape_tag(&metrics.provider_name),
+        fields.join(","),
+        timestamp_ns(timestamp)
+    )
+}
+
+/// Escapes commas, equals signs and spaces in tag values
+fn escape_tag(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, ',' | '=' | ' ') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn timestamp_ns(timestamp: DateTime<Utc>) -> i64 {
+    // Out of range only after the year 2262
+    timestamp
+        .timestamp_nanos_opt()
+        .unwrap_or(timestamp.timestamp_micros() * 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Asset, Quality};
+
+    #[test]
+    fn test_line_protocol() {
+        let mut price = PriceData::new(Asset::SOL, 150.25, "my source".to_string());
+        price.last_updated = DateTime::from_timestamp(1_735_689_600, 0).unwrap();
+        price.confidence = Some(0.05);
+        price.quality = Quality::Excellent;
+        assert_eq!(
+            price_line(&price),
+            "market_price,asset=SOL,source=my\\ source,quality=excellent \
+             price_usd=150.25,confidence=0.05 1735689600000000000"
+        );
+
+        let mut metrics = ProviderMetrics::empty("hermes");
+        metrics.total_requests = 42;
+        assert_eq!(
+            metrics_line(&metrics, price.last_updated),
+            "market_provider,provider=hermes latency_p50_ms=0,latency_p90_ms=0,latency_p95_ms=0,\
+             latency_p99_ms=0,latency_p999_ms=0,\
+             success_rate=1,total_requests=42i,failed_requests=0i,requests_per_min=0,errors_per_min=0 \
+             1735689600000000000"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! Kafka event sink
+
+use crate::{error::SinkError, sinks::PriceEventSink, types::MarketPriceEvent};
+use async_trait::async_trait;
+use chrono::Utc;
+use rskafka::client::{
+    partition::{Compression, PartitionClient, UnknownTopicHandling},
+    ClientBuilder,
+};
+use rskafka::record::Record;
+use std::collections::BTreeMap;
+
+/// Publishes events to a single partition of a Kafka topic
+///
+/// Records are keyed by event type and carry an `event_type` header, so
+/// consumers can filter without parsing the payload. Writing to one
+/// partition keeps events in the order the tracker raised them.
+pub struct KafkaSink {
+    client: PartitionClient,
+}
+
+impl KafkaSink {
+    /// Connects to the bootstrap brokers and publishes to partition 0 of `topic`
+    pub async fn connect(brokers: Vec<String>, topic: &str) -> Result<Self, SinkError> {
+        Self::connect_partition(brokers, topic, 0).await
+    }
+
+    /// Connects to the bootstrap brokers and publishes to one partition of `topic`
+    pub async fn connect_partition(
+        brokers: Vec<String>,
+        topic: &str,
+        partition: i32,
+    ) -> Result<Self, SinkError> {
+        let client = ClientBuilder::new(brokers)
+            .build()
+            .await
+            .map_err(|e| SinkError::Connect(e.to_string()))?
+            .partition_client(topic, partition, UnknownTopicHandling::Retry)
+            .await
+            .map_err(|e| SinkError::Connect(e.to_string()))?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl PriceEventSink for KafkaSink {
+    async fn publish(&self, event: &MarketPriceEvent) -> Result<(), SinkError> {
+        let event_type = event.event_type();
+        let record = Record {
+            key: Some(event_type.as_bytes().to_vec()),
+            value: Some(crate::sinks::encode(event)?.into_bytes()),
+            headers: BTreeMap::from([("event_type".to_string(), event_type.as_bytes().to_vec())]),
+            timestamp: Utc::now(),
+        };
+        self.client
+            .produce(vec![record], Compression::NoCompression)
+            .await
+            .map(|_offsets| ())
+            .map_err(|e| SinkError::Publish(e.to_string()))
+    }
+
+    fn name(&self) -> &'static str {
+        "kafka"
+    }
+}
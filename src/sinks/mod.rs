@@ -0,0 +1,264 @@
+//! Event sinks for publishing tracker events to external systems
+//!
+//! A [`PriceEventSink`] receives every [`MarketPriceEvent`] raised by a
+//! tracker once registered with
+//! [`MarketPriceTracker::add_event_sink`](crate::MarketPriceTracker::add_event_sink).
+//! Each sink runs on its own task, so a slow broker never blocks the update
+//! path; if a sink falls behind the event channel, the skipped events are
+//! logged and dropped.
+//!
+//! Payloads are encoded with [`encode`], the [`Versioned`] JSON wire format,
+//! so consumers can parse them with the same types.
+//!
+//! A [`PriceSink`] instead receives accepted price updates in batches, via
+//! [`MarketPriceTracker::add_price_sink`](crate::MarketPriceTracker::add_price_sink).
+//! Batches are flushed when they reach [`BatchConfig::max_batch_size`] or
+//! [`BatchConfig::flush_interval`] has passed. Failed batches are kept and
+//! retried on the next flush; once more than [`BatchConfig::max_buffered`]
+//! prices are pending, the oldest are dropped so a down database cannot
+//! exhaust memory.
+//!
+//! A [`MetricsSink`] receives the tracker's [`ProviderMetrics`] on a fixed
+//! interval, via
+//! [`MarketPriceTracker::add_metrics_sink`](crate::MarketPriceTracker::add_metrics_sink).
+//!
+//! | Sink | Feature | Receives | Destination |
+//! |------|---------|----------|-------------|
+//! | `KafkaSink` | `sink-kafka` | events | one partition of a Kafka topic, keyed by event type |
+//! | `NatsSink` | `sink-nats` | events | `<prefix>.<EVENT_TYPE>` subjects |
+//! | `PostgresSink` | `sink-postgres` | prices | a PostgreSQL table or TimescaleDB hypertable |
+//! | `InfluxSink` | `sink-influx` | prices, metrics | InfluxDB line protocol over the v2 write API |
+
+#[cfg(feature = "sink-influx")]
+pub mod influx;
+#[cfg(feature = "sink-kafka")]
+pub mod kafka;
+#[cfg(feature = "sink-nats")]
+pub mod nats;
+#[cfg(feature = "sink-postgres")]
+pub mod postgres;
+
+#[cfg(feature = "sink-influx")]
+pub use influx::InfluxSink;
+#[cfg(feature = "sink-kafka")]
+pub use kafka::KafkaSink;
+#[cfg(feature = "sink-nats")]
+pub use nats::NatsSink;
+#[cfg(feature = "sink-postgres")]
+pub use postgres::PostgresSink;
+
+use crate::error::SinkError;
+use crate::metrics::ProviderMetrics;
+use crate::schema::Versioned;
+use crate::types::{MarketPriceEvent, PriceData};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Destination for tracker events
+#[async_trait]
+pub trait PriceEventSink: Send + Sync {
+    /// Publishes a single event
+    ///
+    /// Errors are logged by the tracker and the event is dropped; sinks
+    /// that need delivery guarantees should retry internally.
+    async fn publish(&self, event: &MarketPriceEvent) -> Result<(), SinkError>;
+
+    /// Returns the sink name, used in logs
+    fn name(&self) -> &'static str;
+}
+
+/// Encodes an event as versioned JSON
+pub fn encode(event: &MarketPriceEvent) -> Result<String, SinkError> {
+    Ok(Versioned::new(event).to_json()?)
+}
+
+/// Destination for batches of accepted price updates
+#[async_trait]
+pub trait PriceSink: Send + Sync {
+    /// Writes a batch of prices, oldest first
+    ///
+    /// On error the whole batch is retried on the next flush, so writes
+    /// should be idempotent or all-or-nothing.
+    async fn write_batch(&self, prices: &[PriceData]) -> Result<(), SinkError>;
+
+    /// Returns the sink name, used in logs
+    fn name(&self) -> &'static str;
+}
+
+/// Destination for periodic provider metrics
+#[async_trait]
+pub trait MetricsSink: Send + Sync {
+    /// Writes one sample of the provider metrics
+    async fn write_metrics(&self, metrics: &ProviderMetrics) -> Result<(), SinkError>;
+
+    /// Returns the sink name, used in logs
+    fn name(&self) -> &'static str;
+}
+
+/// Batching and backpressure settings for a [`PriceSink`]
+#[derive(Debug, Clone)]
+pub struct BatchConfig {
+    /// Flush as soon as this many prices are pending
+    pub max_batch_size: usize,
+    /// Flush pending prices at least this often
+    pub flush_interval: Duration,
+    /// Pending prices kept while the sink is failing before the oldest are dropped
+    pub max_buffered: usize,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 500,
+            flush_interval: Duration::from_secs(1),
+            max_buffered: 50_000,
+        }
+    }
+}
+
+/// Pending prices for one [`PriceSink`]
+pub(crate) struct Batcher {
+    config: BatchConfig,
+    pending: VecDeque<PriceData>,
+    last_flush: Instant,
+}
+
+impl Batcher {
+    pub(crate) fn new(config: BatchConfig) -> Self {
+        Self {
+            config,
+            pending: VecDeque::new(),
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// Queues a price, returning true if a flush is due
+    pub(crate) fn push(&mut self, price: PriceData) -> bool {
+        self.pending.push_back(price);
+        self.pending.len() >= self.config.max_batch_size || self.until_flush().is_zero()
+    }
+
+    /// Time left until the interval flush
+    pub(crate) fn until_flush(&self) -> Duration {
+        self.config
+            .flush_interval
+            .saturating_sub(self.last_flush.elapsed())
+    }
+
+    /// Writes pending prices in batches, keeping them if the sink fails
+    pub(crate) async fn flush(&mut self, sink: &dyn PriceSink) {
+        self.last_flush = Instant::now();
+        while !self.pending.is_empty() {
+            let len = self.pending.len().min(self.config.max_batch_size.max(1));
+            let batch = &self.pending.make_contiguous()[..len];
+            if let Err(e) = sink.write_batch(batch).await {
+                tracing::warn!(
+                    sink = sink.name(),
+                    pending = self.pending.len(),
+                    error = %e,
+                    "Failed to write price batch"
+                );
+                self.shed();
+                return;
+            }
+            self.pending.drain(..len);
+        }
+    }
+
+    /// Drops the oldest prices beyond `max_buffered`
+    fn shed(&mut self) {
+        let excess = self.pending.len().saturating_sub(self.config.max_buffered);
+        if excess > 0 {
+            self.pending.drain(..excess);
+            tracing::warn!(
+                dropped = excess,
+                "Price sink buffer full, dropped oldest prices"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Asset;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct FlakySink {
+        failing: AtomicBool,
+        batches: Mutex<Vec<Vec<f64>>>,
+    }
+
+    #[async_trait]
+    impl PriceSink for FlakySink {
+        async fn write_batch(&self, prices: &[PriceData]) -> Result<(), SinkError> {
+            if self.failing.load(Ordering::Relaxed) {
+                return Err(SinkError::Publish("down".to_string()));
+            }
+            let batch = prices.iter().map(|p| p.price_usd).collect();
+            self.batches.lock().unwrap().push(batch);
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batches_and_sheds_while_failing() {
+        let sink = FlakySink::default();
+        let mut batcher = Batcher::new(BatchConfig {
+            max_batch_size: 2,
+            flush_interval: Duration::from_secs(60),
+            max_buffered: 3,
+        });
+        let price = |usd| PriceData::new(Asset::SOL, usd, "test".to_string());
+
+        sink.failing.store(true, Ordering::Relaxed);
+        assert!(!batcher.push(price(1.0)));
+        assert!(batcher.push(price(2.0)));
+        batcher.flush(&sink).await;
+        batcher.push(price(3.0));
+        batcher.push(price(4.0));
+        batcher.push(price(5.0));
+        batcher.flush(&sink).await;
+
+        sink.failing.store(false, Ordering::Relaxed);
+        batcher.flush(&sink).await;
+        assert_eq!(
+            *sink.batches.lock().unwrap(),
+            vec![vec![3.0, 4.0], vec![5.0]]
+        );
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::sync::Notify;
+
+    /// Sink that records every published event
+    #[derive(Default)]
+    pub struct RecordingSink {
+        pub events: Mutex<Vec<MarketPriceEvent>>,
+        pub published: Notify,
+    }
+
+    #[async_trait]
+    impl PriceEventSink for RecordingSink {
+        async fn publish(&self, event: &MarketPriceEvent) -> Result<(), SinkError> {
+            self.events.lock().unwrap().push(event.clone());
+            self.published.notify_one();
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+    }
+}
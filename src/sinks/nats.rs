@@ -0,0 +1,52 @@
+//! NATS event sink
+
+use crate::{error::SinkError, sinks::PriceEventSink, types::MarketPriceEvent};
+use async_trait::async_trait;
+
+/// Publishes events to NATS, one subject per event type
+///
+/// A `PriceExpired` event with the prefix `market.prices` is published to
+/// `market.prices.PRICE_EXPIRED`, so consumers can subscribe to
+/// `market.prices.>` or to a single event type.
+pub struct NatsSink {
+    client: async_nats::Client,
+    subject_prefix: String,
+}
+
+impl NatsSink {
+    /// Connects to a NATS server (e.g. `nats://localhost:4222`)
+    pub async fn connect(url: &str, subject_prefix: &str) -> Result<Self, SinkError> {
+        let client = async_nats::connect(url)
+            .await
+            .map_err(|e| SinkError::Connect(e.to_string()))?;
+        Ok(Self::with_client(client, subject_prefix))
+    }
+
+    /// Publishes through an existing client
+    pub fn with_client(client: async_nats::Client, subject_prefix: &str) -> Self {
+        Self {
+            client,
+            subject_prefix: subject_prefix.trim_end_matches('.').to_string(),
+        }
+    }
+
+    /// Subject an event is published to
+    fn subject(&self, event: &MarketPriceEvent) -> String {
+        format!("{}.{}", self.subject_prefix, event.event_type())
+    }
+}
+
+#[async_trait]
+impl PriceEventSink for NatsSink {
+    async fn publish(&self, event: &MarketPriceEvent) -> Result<(), SinkError> {
+        let payload = crate::sinks::encode(event)?;
+        self.client
+            .publish(self.subject(event), payload.into())
+            .await
+            .map_err(|e| SinkError::Publish(e.to_string()))
+    }
+
+    fn name(&self) -> &'static str {
+        "nats"
+    }
+}
@@ -0,0 +1,163 @@
+//! PostgreSQL / TimescaleDB price sink
+
+use crate::{error::SinkError, sinks::PriceSink, types::PriceData};
+use async_trait::async_trait;
+use tokio_postgres::{types::ToSql, Client, NoTls};
+
+/// Writes price batches to a PostgreSQL table, one row per price
+///
+/// Rows have the columns `time`, `asset`, `price_usd`, `confidence`,
+/// `source` and `quality`. [`create_table`](Self::create_table) creates a
+/// matching table, and [`create_hypertable`](Self::create_hypertable)
+/// turns it into a TimescaleDB hypertable partitioned on `time`.
+///
+/// Each batch is a single INSERT, so keep [`BatchConfig::max_batch_size`]
+/// below 10,000 to stay within PostgreSQL's bind parameter limit.
+///
+/// [`BatchConfig::max_batch_size`]: crate::sinks::BatchConfig::max_batch_size
+///
+/// # Example
+/// ```no_run
+/// use market_price_sdk::sinks::{BatchConfig, PostgresSink};
+/// use market_price_sdk::MarketPriceTracker;
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), market_price_sdk::SinkError> {
+/// let sink = PostgresSink::connect("host=localhost user=postgres", "price_ticks").await?;
+/// sink.create_table().await?;
+/// sink.create_hypertable().await?;
+///
+/// let tracker = MarketPriceTracker::global().await;
+/// tracker.add_price_sink(Arc::new(sink), BatchConfig::default());
+/// # Ok(())
+/// # }
+/// ```
+pub struct PostgresSink {
+    client: Client,
+    table: String,
+}
+
+impl PostgresSink {
+    /// Connects without TLS using a libpq-style connection string
+    ///
+    /// The connection is driven on a tokio task for the sink's lifetime.
+    pub async fn connect(config: &str, table: &str) -> Result<Self, SinkError> {
+        let (client, connection) = tokio_postgres::connect(config, NoTls)
+            .await
+            .map_err(|e| SinkError::Connect(e.to_string()))?;
+        crate::tasks::spawn("postgres.connection", async move {
+            if let Err(e) = connection.await {
+                tracing::warn!(error = %e, "PostgreSQL connection closed");
+            }
+        });
+        Self::with_client(client, table)
+    }
+
+    /// Writes through an existing client (e.g. one set up with TLS)
+    ///
+    /// `table` may be schema-qualified (`market.price_ticks`) and must be a
+    /// plain identifier, since it is interpolated into the SQL.
+    pub fn with_client(client: Client, table: &str) -> Result<Self, SinkError> {
+        let valid = table.split('.').all(|part| {
+            !part.is_empty()
+                && !part.starts_with(|c: char| c.is_ascii_digit())
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+        if !valid {
+            return Err(SinkError::Connect(format!("invalid table name: {}", table)));
+        }
+        Ok(Self {
+            client,
+            table: table.to_string(),
+        })
+    }
+
+    /// Creates the table if it does not exist
+    pub async fn create_table(&self) -> Result<(), SinkError> {
+        let sql = format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                time TIMESTAMPTZ NOT NULL,
+                asset TEXT NOT NULL,
+                price_usd DOUBLE PRECISION NOT NULL,
+                confidence DOUBLE PRECISION,
+                source TEXT NOT NULL,
+                quality TEXT NOT NULL
+            )",
+            self.table
+        );
+        self.execute(&sql).await
+    }
+
+    /// Converts the table into a TimescaleDB hypertable, if it is not one already
+    pub async fn create_hypertable(&self) -> Result<(), SinkError> {
+        let sql = format!(
+            "SELECT create_hypertable('{}', 'time', if_not_exists => TRUE)",
+            self.table
+        );
+        self.execute(&sql).await
+    }
+
+    async fn execute(&self, sql: &str) -> Result<(), SinkError> {
+        self.client
+            .batch_execute(sql)
+            .await
+            .map_err(|e| SinkError::Publish(e.to_string()))
+    }
+
+    /// Builds a multi-row INSERT for `rows` prices
+    fn insert_sql(&self, rows: usize) -> String {
+        let values = (0..rows)
+            .map(|row| {
+                let n = row * 6;
+                format!(
+                    "(${}, ${}, ${}, ${}, ${}, ${})",
+                    n + 1,
+                    n + 2,
+                    n + 3,
+                    n + 4,
+                    n + 5,
+                    n + 6
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "INSERT INTO {} (time, asset, price_usd, confidence, source, quality) VALUES {}",
+            self.table, values
+        )
+    }
+}
+
+#[async_trait]
+impl PriceSink for PostgresSink {
+    async fn write_batch(&self, prices: &[PriceData]) -> Result<(), SinkError> {
+        if prices.is_empty() {
+            return Ok(());
+        }
+
+        let labels: Vec<(&str, &str)> = prices
+            .iter()
+            .map(|p| (p.asset.symbol(), p.quality.as_str()))
+            .collect();
+        let mut params: Vec<&(dyn ToSql + Sync)> = Vec::with_capacity(prices.len() * 6);
+        for (price, (asset, quality)) in prices.iter().zip(&labels) {
+            params.push(&price.last_updated);
+            params.push(asset);
+            params.push(&price.price_usd);
+            params.push(&price.confidence);
+            params.push(&price.source);
+            params.push(quality);
+        }
+
+        // One statement per batch, so a failure leaves no partial batch behind
+        self.client
+            .execute(&self.insert_sql(prices.len()), &params)
+            .await
+            .map(|_rows| ())
+            .map_err(|e| SinkError::Publish(e.to_string()))
+    }
+
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+}
@@ -0,0 +1,265 @@
+//! Smoothed price estimates maintained alongside raw ticks
+//!
+//! [`MarketPriceTracker::get_smoothed_price`](crate::MarketPriceTracker::get_smoothed_price)
+//! returns a denoised estimate for an asset under a given [`Smoother`]. The
+//! first request for an asset/smoother pair seeds the estimate from the
+//! price history; from then on the store's update path advances it with
+//! every accepted tick, so reads are O(1) and the raw last tick stays
+//! untouched.
+//!
+//! - [`Smoother::Ema`] is a time-weighted exponential moving average: a tick
+//!   arriving `dt` after the previous one moves the estimate by
+//!   `1 - 0.5^(dt / half_life)` of the gap, so irregular tick spacing does
+//!   not skew it.
+//! - [`Smoother::Kalman`] is a one-dimensional random-walk Kalman filter.
+//!   Noise levels are given in basis points of the price so the same
+//!   settings work across assets; ticks carrying a confidence interval
+//!   (Pyth) use it as their measurement noise instead.
+
+use crate::{
+    hooks::PriceUpdateHook,
+    types::{Asset, PriceData},
+};
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Smoothing method applied to an asset's ticks
+#[derive(Debug, Clone, Copy)]
+pub enum Smoother {
+    /// Time-weighted exponential moving average
+    Ema {
+        /// Time after which a tick's weight has halved
+        half_life: Duration,
+    },
+    /// Random-walk Kalman filter
+    Kalman {
+        /// Expected price drift per second, in bps of the price
+        process_noise_bps: f64,
+        /// Expected tick noise, in bps of the price
+        measurement_noise_bps: f64,
+    },
+}
+
+impl PartialEq for Smoother {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Smoother {}
+
+impl Hash for Smoother {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl Smoother {
+    /// Bitwise identity, so smoothers can key a map despite holding floats
+    fn key(&self) -> (u8, u64, u64) {
+        match self {
+            Smoother::Ema { half_life } => (0, half_life.as_nanos() as u64, 0),
+            Smoother::Kalman {
+                process_noise_bps,
+                measurement_noise_bps,
+            } => (
+                1,
+                process_noise_bps.to_bits(),
+                measurement_noise_bps.to_bits(),
+            ),
+        }
+    }
+}
+
+/// A smoothed estimate for one asset
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmoothedPrice {
+    /// The asset
+    pub asset: Asset,
+    /// Smoother that produced the estimate
+    pub smoother: Smoother,
+    /// Smoothed price in USD
+    pub price_usd: f64,
+    /// Last raw tick folded into the estimate
+    pub raw_price_usd: f64,
+    /// Timestamp of that tick
+    pub last_updated: DateTime<Utc>,
+    /// Ticks folded into the estimate
+    pub samples: u64,
+}
+
+/// Running estimate for one asset/smoother pair
+#[derive(Debug, Clone)]
+struct Estimate {
+    value: f64,
+    /// Kalman error variance; unused by the EMA
+    variance: f64,
+    raw: f64,
+    last_updated: DateTime<Utc>,
+    samples: u64,
+}
+
+impl Estimate {
+    fn new(smoother: &Smoother, price: &PriceData) -> Self {
+        Self {
+            value: price.price_usd,
+            variance: measurement_variance(smoother, price),
+            raw: price.price_usd,
+            last_updated: price.last_updated,
+            samples: 1,
+        }
+    }
+
+    /// Folds in a tick; ticks not newer than the last one are ignored
+    fn update(&mut self, smoother: &Smoother, price: &PriceData) {
+        if price.last_updated <= self.last_updated {
+            return;
+        }
+        let dt = (price.last_updated - self.last_updated)
+            .to_std()
+            .unwrap_or_default()
+            .as_secs_f64();
+
+        match smoother {
+            Smoother::Ema { half_life } => {
+                let half_life = half_life.as_secs_f64();
+                let alpha = if half_life > 0.0 {
+                    1.0 - 0.5f64.powf(dt / half_life)
+                } else {
+                    1.0
+                };
+                self.value += alpha * (price.price_usd - self.value);
+            }
+            Smoother::Kalman {
+                process_noise_bps, ..
+            } => {
+                let drift = process_noise_bps / 10_000.0 * self.value;
+                self.variance += drift * drift * dt;
+                let noise = measurement_variance(smoother, price);
+                let gain = if self.variance + noise > 0.0 {
+                    self.variance / (self.variance + noise)
+                } else {
+                    1.0
+                };
+                self.value += gain * (price.price_usd - self.value);
+                self.variance *= 1.0 - gain;
+            }
+        }
+
+        self.raw = price.price_usd;
+        self.last_updated = price.last_updated;
+        self.samples += 1;
+    }
+}
+
+/// Measurement variance of a tick under the Kalman filter
+fn measurement_variance(smoother: &Smoother, price: &PriceData) -> f64 {
+    match smoother {
+        Smoother::Kalman {
+            measurement_noise_bps,
+            ..
+        } => {
+            let noise = price
+                .confidence
+                .unwrap_or(measurement_noise_bps / 10_000.0 * price.price_usd);
+            noise * noise
+        }
+        Smoother::Ema { .. } => 0.0,
+    }
+}
+
+/// Estimates for every requested asset/smoother pair
+///
+/// Registered as an update hook so estimates advance with each stored tick.
+#[derive(Default)]
+pub(crate) struct Smoothing {
+    estimates: Mutex<HashMap<(Asset, Smoother), Estimate>>,
+}
+
+impl Smoothing {
+    /// Returns the estimate for `asset`, seeding it from `history` (oldest
+    /// first) the first time this smoother is requested
+    pub(crate) fn get(
+        &self,
+        asset: Asset,
+        smoother: Smoother,
+        history: impl FnOnce() -> Vec<PriceData>,
+    ) -> Option<SmoothedPrice> {
+        let mut estimates = self.estimates.lock().expect("smoothing lock poisoned");
+        let estimate = match estimates.entry((asset, smoother)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let mut ticks = history().into_iter();
+                let mut estimate = Estimate::new(&smoother, &ticks.next()?);
+                for tick in ticks {
+                    estimate.update(&smoother, &tick);
+                }
+                entry.insert(estimate)
+            }
+        };
+
+        Some(SmoothedPrice {
+            asset,
+            smoother,
+            price_usd: estimate.value,
+            raw_price_usd: estimate.raw,
+            last_updated: estimate.last_updated,
+            samples: estimate.samples,
+        })
+    }
+}
+
+impl PriceUpdateHook for Smoothing {
+    fn after_update(&self, price: &PriceData) {
+        let mut estimates = self.estimates.lock().expect("smoothing lock poisoned");
+        for ((asset, smoother), estimate) in estimates.iter_mut() {
+            if *asset == price.asset {
+                estimate.update(smoother, price);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(usd: f64, secs: i64) -> PriceData {
+        let mut price = PriceData::new(Asset::SOL, usd, "test".to_string());
+        price.last_updated = DateTime::<Utc>::UNIX_EPOCH + chrono::Duration::seconds(secs);
+        price
+    }
+
+    #[test]
+    fn test_ema_and_kalman_estimates() {
+        let smoothing = Smoothing::default();
+        let ema = Smoother::Ema {
+            half_life: Duration::from_secs(10),
+        };
+        let seeded = smoothing
+            .get(Asset::SOL, ema, || vec![tick(100.0, 0)])
+            .unwrap();
+        assert_eq!(seeded.price_usd, 100.0);
+
+        // One half-life later the estimate covers half the gap
+        smoothing.after_update(&tick(110.0, 10));
+        smoothing.after_update(&tick(120.0, 10));
+        let smoothed = smoothing.get(Asset::SOL, ema, Vec::new).unwrap();
+        assert!((smoothed.price_usd - 105.0).abs() < 1e-9);
+        assert_eq!(smoothed.raw_price_usd, 110.0);
+        assert_eq!(smoothed.samples, 2);
+
+        let kalman = Smoother::Kalman {
+            process_noise_bps: 1.0,
+            measurement_noise_bps: 50.0,
+        };
+        let history = (0..20).map(|i| tick(100.0 + (i % 2) as f64, i)).collect();
+        let filtered = smoothing.get(Asset::SOL, kalman, || history).unwrap();
+        assert!((filtered.price_usd - 100.5).abs() < 0.2);
+        assert!(smoothing.get(Asset::BTC, kalman, Vec::new).is_none());
+    }
+}
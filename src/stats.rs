@@ -0,0 +1,283 @@
+//! Rolling return statistics computed from the price history
+
+use crate::types::{Asset, PriceData};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Seconds in a 365-day year, used to annualize volatility
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+
+/// Number of intervals each series is resampled into for correlations
+pub const CORRELATION_GRID_INTERVALS: usize = 120;
+
+/// Minimum number of overlapping returns needed to report a correlation
+const MIN_CORRELATION_SAMPLES: usize = 3;
+
+/// Return and risk statistics for one asset over a window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceStats {
+    /// The asset
+    pub asset: Asset,
+    /// Number of price samples in the window
+    pub samples: usize,
+    /// Timestamp of the first sample
+    pub start: DateTime<Utc>,
+    /// Timestamp of the last sample
+    pub end: DateTime<Utc>,
+    /// First price in the window
+    pub open: f64,
+    /// Last price in the window
+    pub close: f64,
+    /// Lowest price in the window
+    pub min: f64,
+    /// Highest price in the window
+    pub max: f64,
+    /// Return from first to last price, as a fraction
+    pub total_return: f64,
+    /// Mean simple return between consecutive samples, as a fraction
+    pub mean_return: f64,
+    /// Standard deviation of log returns between consecutive samples
+    pub volatility: f64,
+    /// Realized volatility scaled to one year, using the elapsed time
+    /// between samples so irregular sampling does not bias it
+    pub annualized_volatility: f64,
+    /// Largest peak-to-trough decline, as a positive fraction
+    pub max_drawdown: f64,
+}
+
+impl PriceStats {
+    /// Computes statistics from prices ordered oldest first
+    ///
+    /// Returns `None` with fewer than two samples or any non-positive price.
+    pub fn from_prices(asset: Asset, prices: &[PriceData]) -> Option<Self> {
+        if prices.len() < 2 || prices.iter().any(|p| p.price_usd <= 0.0) {
+            return None;
+        }
+
+        let first = &prices[0];
+        let last = &prices[prices.len() - 1];
+
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        let mut peak = f64::NEG_INFINITY;
+        let mut max_drawdown: f64 = 0.0;
+        for price in prices.iter().map(|p| p.price_usd) {
+            min = min.min(price);
+            max = max.max(price);
+            peak = peak.max(price);
+            max_drawdown = max_drawdown.max((peak - price) / peak);
+        }
+
+        let simple: Vec<f64> = prices
+            .windows(2)
+            .map(|w| w[1].price_usd / w[0].price_usd - 1.0)
+            .collect();
+        let log: Vec<f64> = prices
+            .windows(2)
+            .map(|w| (w[1].price_usd / w[0].price_usd).ln())
+            .collect();
+
+        let mean_return = simple.iter().sum::<f64>() / simple.len() as f64;
+        let mean_log = log.iter().sum::<f64>() / log.len() as f64;
+        let volatility = if log.len() > 1 {
+            let variance =
+                log.iter().map(|r| (r - mean_log).powi(2)).sum::<f64>() / (log.len() - 1) as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+
+        let elapsed_secs =
+            (last.last_updated - first.last_updated).num_milliseconds() as f64 / 1000.0;
+        let annualized_volatility = if elapsed_secs > 0.0 {
+            let realized_variance = log.iter().map(|r| r * r).sum::<f64>();
+            (realized_variance / elapsed_secs * SECONDS_PER_YEAR).sqrt()
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            asset,
+            samples: prices.len(),
+            start: first.last_updated,
+            end: last.last_updated,
+            open: first.price_usd,
+            close: last.price_usd,
+            min,
+            max,
+            total_return: last.price_usd / first.price_usd - 1.0,
+            mean_return,
+            volatility,
+            annualized_volatility,
+            max_drawdown,
+        })
+    }
+}
+
+/// Pairwise return correlations between assets
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorrelationMatrix {
+    /// Assets in row/column order
+    pub assets: Vec<Asset>,
+    /// Correlation coefficients; `values[i][j]` pairs `assets[i]` with
+    /// `assets[j]`. `None` where the series overlap too little.
+    pub values: Vec<Vec<Option<f64>>>,
+    /// Start of the window the matrix was computed over
+    pub start: DateTime<Utc>,
+    /// End of the window the matrix was computed over
+    pub end: DateTime<Utc>,
+}
+
+impl CorrelationMatrix {
+    /// Computes return correlations from each asset's price history
+    ///
+    /// Series are resampled onto a common grid of
+    /// [`CORRELATION_GRID_INTERVALS`] steps over `[start, end]` (carrying the
+    /// last price forward), so assets updated at different rates line up.
+    pub fn from_history(
+        series: &[(Asset, Vec<PriceData>)],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Self {
+        let step = (end - start) / CORRELATION_GRID_INTERVALS as i32;
+        let grid: Vec<DateTime<Utc>> = (0..=CORRELATION_GRID_INTERVALS)
+            .map(|i| start + step * i as i32)
+            .collect();
+
+        let returns: Vec<Vec<Option<f64>>> = series
+            .iter()
+            .map(|(_, prices)| {
+                let resampled = resample(prices, &grid);
+                resampled
+                    .windows(2)
+                    .map(|w| match (w[0], w[1]) {
+                        (Some(a), Some(b)) if a > 0.0 && b > 0.0 => Some((b / a).ln()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let values = returns
+            .iter()
+            .map(|a| returns.iter().map(|b| correlation(a, b)).collect())
+            .collect();
+
+        Self {
+            assets: series.iter().map(|(asset, _)| *asset).collect(),
+            values,
+            start,
+            end,
+        }
+    }
+
+    /// Gets the correlation between two assets
+    pub fn get(&self, a: Asset, b: Asset) -> Option<f64> {
+        let i = self.assets.iter().position(|asset| *asset == a)?;
+        let j = self.assets.iter().position(|asset| *asset == b)?;
+        self.values[i][j]
+    }
+}
+
+/// Samples the last known price at each grid point (oldest-first input)
+fn resample(prices: &[PriceData], grid: &[DateTime<Utc>]) -> Vec<Option<f64>> {
+    let mut index = 0;
+    let mut last = None;
+    grid.iter()
+        .map(|t| {
+            while index < prices.len() && prices[index].last_updated <= *t {
+                last = Some(prices[index].price_usd);
+                index += 1;
+            }
+            last
+        })
+        .collect()
+}
+
+/// Pearson correlation over the positions where both series have values
+fn correlation(a: &[Option<f64>], b: &[Option<f64>]) -> Option<f64> {
+    let pairs: Vec<(f64, f64)> = a
+        .iter()
+        .zip(b)
+        .filter_map(|(x, y)| Some(((*x)?, (*y)?)))
+        .collect();
+    if pairs.len() < MIN_CORRELATION_SAMPLES {
+        return None;
+    }
+
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in &pairs {
+        cov += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+    Some(cov / (var_x.sqrt() * var_y.sqrt()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_stats() {
+        let start = Utc::now() - chrono::Duration::seconds(40);
+        let prices: Vec<PriceData> = [100.0, 110.0, 99.0, 121.0, 110.0]
+            .iter()
+            .enumerate()
+            .map(|(i, price)| {
+                let mut p = PriceData::new(Asset::SOL, *price, "test".to_string());
+                p.last_updated = start + chrono::Duration::seconds(i as i64 * 10);
+                p
+            })
+            .collect();
+
+        let stats = PriceStats::from_prices(Asset::SOL, &prices).unwrap();
+
+        assert_eq!(stats.samples, 5);
+        assert_eq!((stats.min, stats.max), (99.0, 121.0));
+        assert!((stats.total_return - 0.10).abs() < 1e-12);
+        assert!((stats.max_drawdown - 0.10).abs() < 1e-12);
+        assert!(stats.volatility > 0.0);
+        assert!(stats.annualized_volatility > stats.volatility);
+        assert!(PriceStats::from_prices(Asset::SOL, &prices[..1]).is_none());
+    }
+
+    #[test]
+    fn test_correlation_matrix() {
+        let end = Utc::now();
+        let start = end - chrono::Duration::seconds(120);
+        let series = |asset: Asset, sign: f64| {
+            let prices = (0..120)
+                .map(|i| {
+                    let wiggle = if i % 2 == 0 { 1.0 } else { -1.0 } * (i % 7) as f64;
+                    let mut p = PriceData::new(asset, 100.0 + sign * wiggle, "test".to_string());
+                    p.last_updated = start + chrono::Duration::seconds(i);
+                    p
+                })
+                .collect();
+            (asset, prices)
+        };
+
+        let matrix = CorrelationMatrix::from_history(
+            &[
+                series(Asset::SOL, 1.0),
+                series(Asset::BTC, 1.0),
+                series(Asset::ETH, -1.0),
+                (Asset::USDC, Vec::new()),
+            ],
+            start,
+            end,
+        );
+
+        assert!((matrix.get(Asset::SOL, Asset::SOL).unwrap() - 1.0).abs() < 1e-9);
+        assert!((matrix.get(Asset::SOL, Asset::BTC).unwrap() - 1.0).abs() < 1e-9);
+        assert!(matrix.get(Asset::SOL, Asset::ETH).unwrap() < -0.9);
+        assert_eq!(matrix.get(Asset::SOL, Asset::USDC), None);
+    }
+}
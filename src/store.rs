@@ -1,42 +1,325 @@
 //! In-memory price store with broadcast capabilities
 
 use crate::{
+    anomaly::AnomalyConfig,
+    calendar,
+    config::TrackerConfig,
     error::PriceError,
-    types::{Asset, PriceData},
+    history::{PriceAtOptions, PriceHistory},
+    hooks::PriceUpdateHook,
+    metrics::AssetMetrics,
+    quality,
+    runtime::Runtime,
+    types::{Asset, DetailedPrices, MarketPriceEvent, PriceData, Quality},
 };
+use arc_swap::{ArcSwap, ArcSwapOption};
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, watch};
+
+/// Storage for a single asset: the latest price, its watch channel, and
+/// recent history
+struct Slot {
+    /// Latest price, readable without locking
+    latest: ArcSwapOption<PriceData>,
+    /// Notifies per-asset watchers of every accepted update
+    watch_tx: watch::Sender<Option<PriceData>>,
+    /// Accepted prices within the retention window
+    history: Mutex<PriceHistory>,
+    /// Suspect tick withheld until another source confirms it
+    pending: Mutex<Option<PriceData>>,
+    /// Updates received, including rejected ones
+    received: AtomicU64,
+    /// Updates stored
+    accepted: AtomicU64,
+    /// When the last update was stored
+    last_accepted_at: ArcSwapOption<DateTime<Utc>>,
+    /// Polls in a row without a price for this asset
+    consecutive_misses: AtomicU64,
+    /// Polls without a price for this asset
+    total_misses: AtomicU64,
+    /// When the current run of missed polls began
+    outage_since: ArcSwapOption<DateTime<Utc>>,
+    /// Whether the latest price has been reported as gone stale
+    stale_reported: AtomicBool,
+    /// Sequence number of the last stored price
+    sequence: AtomicU64,
+}
+
+impl Slot {
+    fn new(asset: Asset, config: &TrackerConfig) -> Self {
+        let (watch_tx, _) = watch::channel(None);
+        Self {
+            latest: ArcSwapOption::empty(),
+            watch_tx,
+            history: Mutex::new(PriceHistory::new(
+                asset,
+                config.history_capacity,
+                config.history_retention,
+            )),
+            pending: Mutex::new(None),
+            received: AtomicU64::new(0),
+            accepted: AtomicU64::new(0),
+            last_accepted_at: ArcSwapOption::empty(),
+            consecutive_misses: AtomicU64::new(0),
+            total_misses: AtomicU64::new(0),
+            outage_since: ArcSwapOption::empty(),
+            stale_reported: AtomicBool::new(false),
+            sequence: AtomicU64::new(0),
+        }
+    }
+
+    fn metrics(&self, asset: Asset, now: DateTime<Utc>) -> AssetMetrics {
+        let last_update_at = self.last_accepted_at.load_full().map(|at| *at);
+        AssetMetrics {
+            asset,
+            updates_received: self.received.load(Ordering::Relaxed),
+            updates_accepted: self.accepted.load(Ordering::Relaxed),
+            last_update_at,
+            last_update_age: last_update_at.map(|at| (now - at).to_std().unwrap_or_default()),
+            consecutive_misses: self.consecutive_misses.load(Ordering::Relaxed),
+            total_misses: self.total_misses.load(Ordering::Relaxed),
+        }
+    }
+}
 
 /// Type alias for an individual price slot (optionally contains price data)
-type PriceSlot = Arc<RwLock<Option<PriceData>>>;
+type PriceSlot = Arc<Slot>;
 
 /// Type alias for the price map (asset -> price slot)
 type PriceMap = HashMap<Asset, PriceSlot>;
 
+/// Clones a stored price, regrading it as of `now` since freshness decays
+/// with age
+fn graded(price_data: &PriceData, now: DateTime<Utc>) -> PriceData {
+    let mut price_data = price_data.clone();
+    price_data.quality = quality::assess(&price_data, now);
+    price_data
+}
+
 /// In-memory store for market prices
 ///
-/// Uses tokio watch channels for efficient broadcast-style updates
-/// where multiple consumers can subscribe to price changes.
+/// Reads are lock-free: each asset has its own `ArcSwapOption` slot, and
+/// the asset map itself is copy-on-write, only replaced the first time an
+/// asset is stored.
 pub struct MarketPriceStore {
     /// Storage for price data per asset
-    prices: Arc<RwLock<PriceMap>>,
+    prices: ArcSwap<PriceMap>,
+    /// Latest price of every asset, replaced as a whole on each write
+    snapshot: ArcSwap<HashMap<Asset, PriceData>>,
+    /// Acceptance rules applied to incoming updates
+    config: TrackerConfig,
+    /// Hooks run around every write, in registration order
+    hooks: ArcSwap<Vec<Arc<dyn PriceUpdateHook>>>,
+    /// Receives freshness transitions such as `PriceRecovered`
+    event_tx: Option<broadcast::Sender<MarketPriceEvent>>,
 }
 
 impl MarketPriceStore {
     /// Creates a new market price store
     pub fn new() -> Self {
+        Self::with_config(TrackerConfig::default())
+    }
+
+    /// Creates a new market price store that applies the given configuration
+    /// to incoming updates
+    pub fn with_config(config: TrackerConfig) -> Self {
         Self {
-            prices: Arc::new(RwLock::new(HashMap::new())),
+            prices: ArcSwap::from_pointee(HashMap::new()),
+            snapshot: ArcSwap::from_pointee(HashMap::new()),
+            config,
+            hooks: ArcSwap::from_pointee(Vec::new()),
+            event_tx: None,
         }
     }
 
-    /// Initializes storage for a specific asset
-    async fn ensure_asset(&self, asset: Asset) {
-        let mut prices = self.prices.write().await;
-        prices
-            .entry(asset)
-            .or_insert_with(|| Arc::new(RwLock::new(None)));
+    /// Sends freshness transition events to `event_tx`
+    pub(crate) fn with_event_sender(
+        mut self,
+        event_tx: broadcast::Sender<MarketPriceEvent>,
+    ) -> Self {
+        self.event_tx = Some(event_tx);
+        self
+    }
+
+    /// Returns when the asset was last updated if its stored price is
+    /// stale, or was evicted after being stored
+    fn unavailable_since(&self, slot: &Slot) -> Option<DateTime<Utc>> {
+        match slot.latest.load().as_ref() {
+            Some(previous) => self
+                .stale_at(previous, self.now())
+                .then_some(previous.last_updated),
+            None => slot.last_accepted_at.load().as_deref().copied(),
+        }
+    }
+
+    /// Executor and clock the store was configured with
+    pub(crate) fn runtime(&self) -> &Runtime {
+        &self.config.runtime
+    }
+
+    /// Current time according to the configured clock
+    fn now(&self) -> DateTime<Utc> {
+        self.config.runtime.now()
+    }
+
+    /// Whether a price is past its asset's stale threshold, counting only
+    /// time its market was open
+    ///
+    /// The threshold is extended during the grace window of an outage, see
+    /// [`TrackerConfig::outage_grace`].
+    fn stale_at(&self, price_data: &PriceData, now: DateTime<Utc>) -> bool {
+        let age = calendar::trading_age(self.config.trading_calendar.as_ref(), price_data, now);
+        let grace = self.outage_extension(price_data.asset, now);
+        age.as_secs() > price_data.asset.stale_threshold_secs() + grace.as_secs()
+    }
+
+    /// How much longer than usual a price of `asset` stays fresh at `now`
+    fn outage_extension(&self, asset: Asset, now: DateTime<Utc>) -> std::time::Duration {
+        let Some(grace) = &self.config.outage_grace else {
+            return std::time::Duration::ZERO;
+        };
+        self.prices
+            .load()
+            .get(&asset)
+            .and_then(|slot| slot.outage_since.load_full())
+            .map_or(std::time::Duration::ZERO, |since| {
+                grace.extension_at(*since, now)
+            })
+    }
+
+    /// Registers a hook to run around every store write
+    pub fn add_hook(&self, hook: Box<dyn PriceUpdateHook>) {
+        self.add_shared_hook(Arc::from(hook));
+    }
+
+    /// Registers a hook the caller keeps a handle to
+    pub(crate) fn add_shared_hook(&self, hook: Arc<dyn PriceUpdateHook>) {
+        self.hooks.rcu(|hooks| {
+            let mut hooks = Vec::clone(hooks);
+            hooks.push(hook.clone());
+            hooks
+        });
+    }
+
+    /// Checks an incoming update against the configured acceptance rules
+    fn accepts(&self, price_data: &PriceData) -> bool {
+        if let (Some(max_ratio), Some(ratio)) = (
+            self.config.max_confidence_ratio,
+            price_data.confidence_ratio(),
+        ) {
+            if ratio > max_ratio {
+                tracing::warn!(
+                    asset = price_data.asset.symbol(),
+                    price_usd = price_data.price_usd,
+                    confidence_ratio = ratio,
+                    max_confidence_ratio = max_ratio,
+                    source = %price_data.source,
+                    "Rejected price update with wide confidence interval"
+                );
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns true if the tick moves the stored price by less than
+    /// `min_change_bps` and the stored price is not yet due for a refresh
+    fn is_insignificant(&self, asset: Asset, slot: &Slot, price_data: &PriceData) -> bool {
+        let Some(min_change_bps) = self.config.min_change_bps else {
+            return false;
+        };
+        let current = slot.latest.load();
+        let Some(current) = current.as_ref() else {
+            return false;
+        };
+        if current.price_usd == 0.0
+            || price_data.last_updated < current.last_updated
+            || current.is_stale_at(asset.stale_threshold_secs() / 2, self.now())
+        {
+            return false;
+        }
+
+        let change_bps =
+            ((price_data.price_usd - current.price_usd) / current.price_usd).abs() * 10_000.0;
+        change_bps < min_change_bps
+    }
+
+    /// Runs anomaly detection on a tick, flagging it as suspect
+    ///
+    /// Returns `None` when the tick is withheld pending confirmation.
+    fn screen(
+        &self,
+        anomaly: &AnomalyConfig,
+        slot: &Slot,
+        mut price_data: PriceData,
+    ) -> Option<PriceData> {
+        let recent = slot
+            .history
+            .lock()
+            .expect("history lock poisoned")
+            .recent(anomaly.detector.window());
+        let mut pending = slot.pending.lock().expect("pending lock poisoned");
+
+        if let Some(suspect) = pending.as_ref() {
+            if anomaly.confirms(suspect, &price_data) {
+                tracing::info!(
+                    asset = price_data.asset.symbol(),
+                    price_usd = price_data.price_usd,
+                    suspect_source = %suspect.source,
+                    source = %price_data.source,
+                    "Suspect price confirmed by a second source"
+                );
+                *pending = None;
+                return Some(price_data);
+            }
+        }
+
+        let Some(flag) = anomaly.detector.detect(&price_data, &recent) else {
+            *pending = None;
+            return Some(price_data);
+        };
+
+        tracing::warn!(
+            asset = price_data.asset.symbol(),
+            price_usd = price_data.price_usd,
+            source = %price_data.source,
+            detector = flag.detector,
+            score = flag.score,
+            withheld = anomaly.withhold_until_confirmed,
+            "Anomalous price tick"
+        );
+        price_data.quality = Quality::Suspect;
+
+        if anomaly.withhold_until_confirmed {
+            *pending = Some(price_data);
+            return None;
+        }
+        Some(price_data)
+    }
+
+    /// Returns the slot for an asset, initializing it on first use
+    fn slot(&self, asset: Asset) -> PriceSlot {
+        if let Some(slot) = self.prices.load().get(&asset) {
+            return slot.clone();
+        }
+
+        self.prices.rcu(|prices| {
+            let mut prices = PriceMap::clone(prices);
+            prices
+                .entry(asset)
+                .or_insert_with(|| Arc::new(Slot::new(asset, &self.config)));
+            prices
+        });
+        self.prices.load()[&asset].clone()
+    }
+
+    /// Loads the latest price for an asset without taking any lock
+    fn load(&self, asset: Asset) -> Option<Arc<PriceData>> {
+        self.prices.load().get(&asset)?.latest.load_full()
     }
 
     /// Updates the price for a specific asset
@@ -44,29 +327,180 @@ impl MarketPriceStore {
     /// # Arguments
     /// * `asset` - The asset to update
     /// * `price_data` - The new price data
-    pub async fn update_price(&self, asset: Asset, price_data: PriceData) {
-        self.ensure_asset(asset).await;
+    ///
+    /// # Returns
+    /// True if the update was accepted and stored
+    pub async fn update_price(&self, asset: Asset, price_data: PriceData) -> bool {
+        self.store_price(asset, price_data).await.is_some()
+    }
 
-        let prices = self.prices.read().await;
-        if let Some(price_slot) = prices.get(&asset) {
-            let mut slot = price_slot.write().await;
-            *slot = Some(price_data.clone());
+    /// Updates the price for a specific asset, returning what was stored
+    ///
+    /// The stored price can differ from the input when update hooks modify it.
+    /// A price older than the one already stored is rejected, so an update
+    /// that arrives late never replaces a newer one.
+    ///
+    /// # Arguments
+    /// * `asset` - The asset to update
+    /// * `price_data` - The new price data
+    ///
+    /// # Returns
+    /// The stored price, or `None` if the update was rejected
+    pub async fn store_price(&self, asset: Asset, price_data: PriceData) -> Option<PriceData> {
+        let slot = self.slot(asset);
+        slot.received.fetch_add(1, Ordering::Relaxed);
+        if !self.accepts(&price_data) || self.is_insignificant(asset, &slot, &price_data) {
+            return None;
+        }
+
+        let mut price_data = match &self.config.anomaly {
+            Some(anomaly) => self.screen(anomaly, &slot, price_data)?,
+            None => price_data,
+        };
+        price_data.quality = quality::assess(&price_data, self.now());
+
+        let hooks = self.hooks.load();
+        for hook in hooks.iter() {
+            price_data = hook.before_update(price_data)?;
+        }
+
+        if is_superseded(slot.latest.load().as_deref(), &price_data) {
             tracing::debug!(
+                asset = asset.symbol(),
+                last_updated = %price_data.last_updated,
+                "Skipped price older than the stored one"
+            );
+            return None;
+        }
+        price_data.sequence = slot.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        let fresh = !self.stale_at(&price_data, self.now());
+        let recovered_from = self.unavailable_since(&slot).filter(|_| fresh);
+
+        tracing::debug!(
+            asset = asset.symbol(),
+            price_usd = price_data.price_usd,
+            "Updated price"
+        );
+        // A racing writer may already have published a newer price
+        let stored = Arc::new(price_data.clone());
+        let mut current = slot.latest.load_full();
+        loop {
+            if is_superseded(current.as_deref(), &price_data) {
+                return None;
+            }
+            let previous = slot.latest.compare_and_swap(&current, Some(stored.clone()));
+            let swapped = match (&*previous, &current) {
+                (Some(previous), Some(current)) => Arc::ptr_eq(previous, current),
+                (None, None) => true,
+                _ => false,
+            };
+            if swapped {
+                break;
+            }
+            current = previous.clone();
+        }
+        self.snapshot.rcu(|snapshot| {
+            let mut snapshot = HashMap::clone(snapshot);
+            if !is_superseded(snapshot.get(&asset), &price_data) {
+                snapshot.insert(asset, price_data.clone());
+            }
+            snapshot
+        });
+        slot.watch_tx.send_if_modified(|current| {
+            if is_superseded(current.as_ref(), &price_data) {
+                return false;
+            }
+            *current = Some(price_data.clone());
+            true
+        });
+        slot.accepted.fetch_add(1, Ordering::Relaxed);
+        slot.last_accepted_at.store(Some(Arc::new(self.now())));
+        if fresh {
+            slot.stale_reported.store(false, Ordering::Relaxed);
+        }
+        slot.history
+            .lock()
+            .expect("history lock poisoned")
+            .push(price_data.clone(), self.now());
+
+        for hook in hooks.iter() {
+            hook.after_update(&price_data);
+        }
+
+        if let (Some(previous_update), Some(event_tx)) = (recovered_from, &self.event_tx) {
+            tracing::info!(
                 asset = asset.symbol(),
                 price_usd = price_data.price_usd,
-                "Updated price"
+                previous_update = %previous_update,
+                "Price recovered"
             );
+            let _ = event_tx.send(MarketPriceEvent::PriceRecovered {
+                id: self.config.event_ids.next_id(),
+                asset,
+                price_usd: price_data.price_usd,
+                previous_update,
+                sequence: price_data.sequence,
+                timestamp: self.now(),
+            });
+        }
+
+        Some(price_data)
+    }
+
+    /// Records the outcome of a poll for `requested` assets
+    ///
+    /// Assets missing from `returned` (all of them if the poll failed) count
+    /// a miss, and the first miss in a row starts an outage; the others
+    /// reset their consecutive miss count and end any outage.
+    pub fn record_poll(&self, requested: &[Asset], returned: Option<&HashMap<Asset, PriceData>>) {
+        let now = self.now();
+        for &asset in requested {
+            let slot = self.slot(asset);
+            if returned.is_some_and(|prices| prices.contains_key(&asset)) {
+                slot.consecutive_misses.store(0, Ordering::Relaxed);
+                slot.outage_since.store(None);
+            } else {
+                if slot.consecutive_misses.fetch_add(1, Ordering::Relaxed) == 0 {
+                    slot.outage_since.store(Some(Arc::new(now)));
+                }
+                slot.total_misses.fetch_add(1, Ordering::Relaxed);
+            }
         }
     }
 
+    /// Returns the update counters for an asset, if it was ever received or polled
+    pub fn asset_metrics(&self, asset: Asset) -> Option<AssetMetrics> {
+        Some(self.prices.load().get(&asset)?.metrics(asset, self.now()))
+    }
+
+    /// Returns the update counters for every asset received or polled so far
+    pub fn all_asset_metrics(&self) -> HashMap<Asset, AssetMetrics> {
+        let now = self.now();
+        self.prices
+            .load()
+            .iter()
+            .map(|(asset, slot)| (*asset, slot.metrics(*asset, now)))
+            .collect()
+    }
+
     /// Updates prices for multiple assets
     ///
     /// # Arguments
     /// * `prices` - HashMap of asset to price data
-    pub async fn update_prices(&self, prices: HashMap<Asset, PriceData>) {
+    ///
+    /// # Returns
+    /// The subset of prices that was accepted and stored
+    pub async fn update_prices(
+        &self,
+        prices: HashMap<Asset, PriceData>,
+    ) -> HashMap<Asset, PriceData> {
+        let mut accepted = HashMap::new();
         for (asset, price_data) in prices {
-            self.update_price(asset, price_data).await;
+            if let Some(stored) = self.store_price(asset, price_data).await {
+                accepted.insert(asset, stored);
+            }
         }
+        accepted
     }
 
     /// Gets the current price for an asset
@@ -77,24 +511,62 @@ impl MarketPriceStore {
     /// # Returns
     /// The current price data or an error if not available or stale
     pub async fn get_price(&self, asset: Asset) -> Result<PriceData, PriceError> {
-        let prices = self.prices.read().await;
-        let price_slot = prices
-            .get(&asset)
-            .ok_or_else(|| PriceError::not_available(asset.symbol()))?;
+        self.get_price_sync(asset)
+    }
 
-        let slot = price_slot.read().await;
+    /// Gets the current price for an asset without awaiting
+    ///
+    /// Reads are lock-free, so this is safe to call from synchronous code,
+    /// including code running inside an async runtime.
+    ///
+    /// # Arguments
+    /// * `asset` - The asset to get the price for
+    ///
+    /// # Returns
+    /// The current price data or an error if not available or stale
+    pub fn get_price_sync(&self, asset: Asset) -> Result<PriceData, PriceError> {
+        let prices = self.prices.load();
+        let slot = prices
+            .get(&asset)
+            .ok_or_else(|| PriceError::not_available(asset.symbol()))?
+            .latest
+            .load();
         let price_data = slot
             .as_ref()
-            .ok_or_else(|| PriceError::not_available(asset.symbol()))?
-            .clone();
+            .ok_or_else(|| PriceError::not_available(asset.symbol()))?;
 
         // Check if price is stale using per-asset threshold
-        if price_data.is_stale(asset.stale_threshold_secs()) {
-            let age = price_data.age();
+        let now = self.now();
+        if self.stale_at(price_data, now) {
+            let age = price_data.age_at(now);
             return Err(PriceError::stale(asset.symbol(), age));
         }
 
-        Ok(price_data)
+        Ok(graded(price_data, now))
+    }
+
+    /// Gets the current price for an asset if it is available and fresh
+    ///
+    /// # Arguments
+    /// * `asset` - The asset to get the price for
+    ///
+    /// # Returns
+    /// The current price data, or `None` if not available or stale
+    pub fn try_get_price_sync(&self, asset: Asset) -> Option<PriceData> {
+        self.get_price_sync(asset).ok()
+    }
+
+    /// Gets the last known price for an asset, fresh or not, marked
+    /// degraded as configured by [`TrackerConfig::degraded`]
+    ///
+    /// # Returns
+    /// `None` if degraded mode is off, the asset has no stored price, or
+    /// the price is older than the configured maximum age
+    pub fn degraded_price(&self, asset: Asset) -> Option<PriceData> {
+        let degraded = self.config.degraded.as_ref()?;
+        let slot = self.prices.load().get(&asset)?.latest.load_full()?;
+        let age = calendar::trading_age(self.config.trading_calendar.as_ref(), &slot, self.now());
+        degraded.degrade((*slot).clone(), age)
     }
 
     /// Gets all available prices
@@ -103,14 +575,14 @@ impl MarketPriceStore {
     /// HashMap of all assets with their current prices
     pub async fn get_all_prices(&self) -> HashMap<Asset, PriceData> {
         let mut result = HashMap::new();
-        let prices = self.prices.read().await;
+        let prices = self.prices.load();
+        let now = self.now();
 
         for (asset, price_slot) in prices.iter() {
-            let slot = price_slot.read().await;
-            if let Some(price_data) = slot.as_ref() {
+            if let Some(price_data) = price_slot.latest.load().as_ref() {
                 // Only include non-stale prices using per-asset threshold
-                if !price_data.is_stale(asset.stale_threshold_secs()) {
-                    result.insert(*asset, price_data.clone());
+                if !self.stale_at(price_data, now) {
+                    result.insert(*asset, graded(price_data, now));
                 }
             }
         }
@@ -118,6 +590,107 @@ impl MarketPriceStore {
         result
     }
 
+    /// Gets the latest price of every asset as a shared snapshot
+    ///
+    /// The snapshot is rebuilt copy-on-write when a price is stored, so
+    /// reading it costs one reference-count increment however many assets
+    /// it holds. Unlike [`get_all_prices`](Self::get_all_prices) it
+    /// includes stale prices, and quality grades are as of when each price
+    /// was stored.
+    pub fn get_all_prices_arc(&self) -> Arc<HashMap<Asset, PriceData>> {
+        self.snapshot.load_full()
+    }
+
+    /// Gets all prices grouped into fresh, stale, and missing
+    ///
+    /// # Arguments
+    /// * `tracked` - Assets expected to have prices; those never stored are
+    ///   reported as missing
+    ///
+    /// # Returns
+    /// Every stored price, bucketed by staleness, plus the missing assets
+    pub fn get_all_prices_detailed(&self, tracked: &[Asset]) -> DetailedPrices {
+        let mut result = DetailedPrices::default();
+        let prices = self.prices.load();
+        let now = self.now();
+
+        for (asset, price_slot) in prices.iter() {
+            if let Some(price_data) = price_slot.latest.load().as_ref() {
+                let price_data = graded(price_data, now);
+                if self.stale_at(&price_data, now) {
+                    result.stale.insert(*asset, price_data);
+                } else {
+                    result.fresh.insert(*asset, price_data);
+                }
+            }
+        }
+
+        result.missing = tracked
+            .iter()
+            .copied()
+            .filter(|asset| !result.fresh.contains_key(asset) && !result.stale.contains_key(asset))
+            .collect();
+
+        result
+    }
+
+    /// Gets the recorded price for an asset at a point in time
+    ///
+    /// # Arguments
+    /// * `asset` - The asset to look up
+    /// * `timestamp` - The point in time
+    /// * `options` - Interpolation and maximum-gap settings
+    ///
+    /// # Returns
+    /// The nearest (or interpolated) recorded price, or an error if no
+    /// sample lies within `options.max_gap` of `timestamp`
+    pub fn get_price_at(
+        &self,
+        asset: Asset,
+        timestamp: DateTime<Utc>,
+        options: &PriceAtOptions,
+    ) -> Result<PriceData, PriceError> {
+        let prices = self.prices.load();
+        let slot = prices
+            .get(&asset)
+            .ok_or_else(|| PriceError::not_available(asset.symbol()))?;
+        let history = slot.history.lock().expect("history lock poisoned");
+        history.at(timestamp, options)
+    }
+
+    /// Gets the recorded prices for an asset within a time range
+    ///
+    /// # Returns
+    /// Prices recorded within `[from, to]`, oldest first
+    pub fn get_history(
+        &self,
+        asset: Asset,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<PriceData> {
+        self.prices
+            .load()
+            .get(&asset)
+            .map(|slot| {
+                slot.history
+                    .lock()
+                    .expect("history lock poisoned")
+                    .range(from, to)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Watches the latest price for a single asset
+    ///
+    /// The receiver holds `None` until the first price is stored and is
+    /// notified on every accepted update for this asset only.
+    ///
+    /// # Arguments
+    /// * `asset` - The asset to watch
+    pub fn watch(&self, asset: Asset) -> watch::Receiver<Option<PriceData>> {
+        self.slot(asset).watch_tx.subscribe()
+    }
+
     /// Checks if price data exists for an asset
     ///
     /// # Arguments
@@ -126,13 +699,7 @@ impl MarketPriceStore {
     /// # Returns
     /// True if price data exists (regardless of staleness)
     pub async fn has_price(&self, asset: Asset) -> bool {
-        let prices = self.prices.read().await;
-        if let Some(price_slot) = prices.get(&asset) {
-            let slot = price_slot.read().await;
-            slot.is_some()
-        } else {
-            false
-        }
+        self.load(asset).is_some()
     }
 
     /// Checks if price data is stale for an asset
@@ -143,17 +710,97 @@ impl MarketPriceStore {
     /// # Returns
     /// True if price data is stale or doesn't exist
     pub async fn is_stale(&self, asset: Asset) -> bool {
-        let prices = self.prices.read().await;
-        if let Some(price_slot) = prices.get(&asset) {
-            let slot = price_slot.read().await;
-            if let Some(price_data) = slot.as_ref() {
-                price_data.is_stale(asset.stale_threshold_secs())
-            } else {
-                true
+        self.load(asset)
+            .map(|price_data| self.stale_at(&price_data, self.now()))
+            .unwrap_or(true)
+    }
+
+    /// Returns the stored prices that have crossed their stale threshold
+    /// since the last call
+    ///
+    /// Each price is returned once; an asset is reported again only after a
+    /// fresh price has been stored for it.
+    pub fn newly_stale(&self) -> Vec<PriceData> {
+        let now = self.now();
+        self.prices
+            .load()
+            .values()
+            .filter_map(|slot| {
+                let price_data = slot.latest.load_full()?;
+                if !self.stale_at(&price_data, now)
+                    || slot.stale_reported.swap(true, Ordering::Relaxed)
+                {
+                    return None;
+                }
+                Some(PriceData::clone(&price_data))
+            })
+            .collect()
+    }
+
+    /// Removes prices that have gone stale, leaving their history intact
+    ///
+    /// A price replaced by a fresh update while the sweep runs is kept.
+    ///
+    /// # Returns
+    /// The prices that were removed
+    pub fn evict_stale(&self) -> Vec<PriceData> {
+        let mut expired = Vec::new();
+        let now = self.now();
+        for (asset, slot) in self.prices.load().iter() {
+            let current = slot.latest.load_full();
+            let Some(price_data) = current.as_ref() else {
+                continue;
+            };
+            if !self.stale_at(price_data, now) {
+                continue;
             }
-        } else {
-            true
+
+            let previous = slot.latest.compare_and_swap(&current, None);
+            let swapped = matches!((&*previous, &current), (Some(a), Some(b)) if Arc::ptr_eq(a, b));
+            if swapped {
+                slot.watch_tx.send_replace(None);
+                self.snapshot.rcu(|snapshot| {
+                    let mut snapshot = HashMap::clone(snapshot);
+                    if snapshot
+                        .get(asset)
+                        .is_some_and(|current| current.last_updated <= price_data.last_updated)
+                    {
+                        snapshot.remove(asset);
+                    }
+                    snapshot
+                });
+                expired.push(PriceData::clone(price_data));
+            }
+        }
+        expired
+    }
+
+    /// Drops every asset not in `keep`, including its history and watchers
+    ///
+    /// # Returns
+    /// The latest prices of the dropped assets that had one
+    pub fn retain_assets(&self, keep: &[Asset]) -> Vec<PriceData> {
+        let prices = self.prices.load();
+        if prices.keys().all(|asset| keep.contains(asset)) {
+            return Vec::new();
         }
+
+        let previous = self.prices.rcu(|prices| {
+            let mut prices = PriceMap::clone(prices);
+            prices.retain(|asset, _| keep.contains(asset));
+            prices
+        });
+        self.snapshot.rcu(|snapshot| {
+            let mut snapshot = HashMap::clone(snapshot);
+            snapshot.retain(|asset, _| keep.contains(asset));
+            snapshot
+        });
+        previous
+            .iter()
+            .filter(|(asset, _)| !keep.contains(asset))
+            .filter_map(|(_, slot)| slot.latest.load_full())
+            .map(|price_data| PriceData::clone(&price_data))
+            .collect()
     }
 }
 
@@ -163,3 +810,377 @@ impl Default for MarketPriceStore {
     }
 }
 
+/// Read-only handle to a tracker's price store
+///
+/// Hands a subsystem the cache without the tracker's refresh, provider or
+/// shutdown controls. Clones share the same store, and every read is
+/// lock-free and never contacts a provider.
+#[derive(Clone)]
+pub struct PriceStoreReader {
+    store: Arc<MarketPriceStore>,
+}
+
+impl PriceStoreReader {
+    pub(crate) fn new(store: Arc<MarketPriceStore>) -> Self {
+        Self { store }
+    }
+
+    /// Gets the cached price for an asset, or why it is unavailable
+    pub fn get_price(&self, asset: Asset) -> Result<PriceData, PriceError> {
+        self.store.get_price_sync(asset)
+    }
+
+    /// Gets the cached price for an asset if it is available and fresh
+    pub fn try_get_price(&self, asset: Asset) -> Option<PriceData> {
+        self.store.try_get_price_sync(asset)
+    }
+
+    /// Gets every fresh cached price
+    pub fn get_all_prices(&self) -> HashMap<Asset, PriceData> {
+        self.store.get_all_prices_detailed(&[]).fresh
+    }
+
+    /// Gets the price held for an asset at a point in time
+    pub fn get_price_at(
+        &self,
+        asset: Asset,
+        timestamp: DateTime<Utc>,
+    ) -> Result<PriceData, PriceError> {
+        self.store
+            .get_price_at(asset, timestamp, &PriceAtOptions::default())
+    }
+
+    /// Watches the latest price for a single asset
+    ///
+    /// See [`MarketPriceStore::watch`].
+    pub fn watch(&self, asset: Asset) -> watch::Receiver<Option<PriceData>> {
+        self.store.watch(asset)
+    }
+
+    /// Returns true if the cached price for an asset is stale or missing
+    pub fn is_stale(&self, asset: Asset) -> bool {
+        self.store.get_price_sync(asset).is_err()
+    }
+}
+
+/// Returns true if `current` was updated after `price`, so `price` must not
+/// replace it
+fn is_superseded(current: Option<&PriceData>, price: &PriceData) -> bool {
+    current.is_some_and(|current| current.last_updated > price.last_updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::MockClock;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_rejects_wide_confidence() {
+        let store = MarketPriceStore::with_config(TrackerConfig {
+            max_confidence_ratio: Some(0.01),
+            ..Default::default()
+        });
+
+        let tight = PriceData::new(Asset::SOL, 100.0, "test".to_string()).with_confidence(0.5);
+        let wide = PriceData::new(Asset::SOL, 90.0, "test".to_string()).with_confidence(5.0);
+
+        assert!(store.update_price(Asset::SOL, tight.clone()).await);
+        assert!(!store.update_price(Asset::SOL, wide).await);
+        assert!(store.update_price(Asset::SOL, tight).await);
+        let stored = store.get_price(Asset::SOL).await.unwrap();
+        assert_eq!(stored.price_usd, 100.0);
+        // Rejected ticks do not consume a sequence number
+        assert_eq!(stored.sequence, 2);
+        assert_eq!(
+            store.try_get_price_sync(Asset::SOL).map(|p| p.price_usd),
+            Some(100.0)
+        );
+        assert!(store.try_get_price_sync(Asset::BTC).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_skips_moves_below_min_change() {
+        let store = MarketPriceStore::with_config(TrackerConfig {
+            min_change_bps: Some(10.0),
+            ..Default::default()
+        });
+        let tick = |price_usd| PriceData::new(Asset::USDC, price_usd, "test".to_string());
+
+        assert!(store.update_price(Asset::USDC, tick(1.0)).await);
+        assert!(!store.update_price(Asset::USDC, tick(1.0005)).await);
+        assert!(store.update_price(Asset::USDC, tick(0.998)).await);
+        assert_eq!(store.get_price(Asset::USDC).await.unwrap().price_usd, 0.998);
+
+        // A price halfway to stale is refreshed even if unchanged
+        let mut aging = tick(0.998);
+        aging.last_updated -= chrono::Duration::seconds(Asset::USDC.stale_threshold_secs() as i64);
+        let store = MarketPriceStore::with_config(TrackerConfig {
+            min_change_bps: Some(10.0),
+            ..Default::default()
+        });
+        store.update_price(Asset::USDC, aging).await;
+        assert!(store.update_price(Asset::USDC, tick(0.998)).await);
+    }
+
+    #[tokio::test]
+    async fn test_emits_recovery_after_stale_price() {
+        let (event_tx, mut events) = broadcast::channel(8);
+        let store = MarketPriceStore::new().with_event_sender(event_tx);
+        let mut stale = PriceData::new(Asset::SOL, 100.0, "test".to_string());
+        stale.last_updated -= chrono::Duration::seconds(600);
+        let previous_update = stale.last_updated;
+        store.update_price(Asset::SOL, stale).await;
+        assert!(events.try_recv().is_err());
+
+        for price_usd in [101.0, 102.0] {
+            store
+                .update_price(
+                    Asset::SOL,
+                    PriceData::new(Asset::SOL, price_usd, "test".to_string()),
+                )
+                .await;
+        }
+
+        match events.try_recv().unwrap() {
+            MarketPriceEvent::PriceRecovered {
+                asset,
+                price_usd,
+                previous_update: at,
+                ..
+            } => {
+                assert_eq!(asset, Asset::SOL);
+                assert_eq!(price_usd, 101.0);
+                assert_eq!(at, previous_update);
+            }
+            other => panic!("unexpected event: {}", other),
+        }
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reports_stale_transition_once() {
+        let start = Utc::now();
+        let clock = Arc::new(MockClock::new(start));
+        let store = MarketPriceStore::with_config(TrackerConfig {
+            runtime: Runtime::default().with_clock(clock.clone()),
+            ..Default::default()
+        });
+        let mut stale = PriceData::new(Asset::SOL, 100.0, "test".to_string());
+        stale.last_updated -= chrono::Duration::seconds(600);
+        store.update_price(Asset::SOL, stale.clone()).await;
+        store
+            .update_price(
+                Asset::BTC,
+                PriceData::new(Asset::BTC, 60000.0, "test".to_string()),
+            )
+            .await;
+
+        let reported = store.newly_stale();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(reported[0].asset, Asset::SOL);
+        assert!(store.newly_stale().is_empty());
+
+        // Another stale tick does not re-arm the report; a fresh one does
+        store.update_price(Asset::SOL, stale.clone()).await;
+        assert!(store.newly_stale().is_empty());
+        let mut fresh = PriceData::new(Asset::SOL, 101.0, "test".to_string());
+        fresh.last_updated = start;
+        store.update_price(Asset::SOL, fresh).await;
+        clock.advance(Duration::from_secs(600));
+        assert!(store
+            .newly_stale()
+            .iter()
+            .any(|price| price.asset == Asset::SOL));
+    }
+
+    #[tokio::test]
+    async fn test_older_price_never_replaces_newer() {
+        let store = MarketPriceStore::new();
+        let newer = PriceData::new(Asset::SOL, 101.0, "test".to_string());
+        let mut older = PriceData::new(Asset::SOL, 100.0, "test".to_string());
+        older.last_updated = newer.last_updated - chrono::Duration::seconds(5);
+        let watch = store.watch(Asset::SOL);
+
+        assert!(store.update_price(Asset::SOL, newer).await);
+        assert!(!store.update_price(Asset::SOL, older).await);
+
+        assert_eq!(store.get_price_sync(Asset::SOL).unwrap().price_usd, 101.0);
+        assert_eq!(store.get_all_prices_arc()[&Asset::SOL].price_usd, 101.0);
+        assert_eq!(watch.borrow().as_ref().unwrap().price_usd, 101.0);
+    }
+
+    #[tokio::test]
+    async fn test_staleness_follows_the_configured_clock() {
+        let price = PriceData::new(Asset::SOL, 100.0, "test".to_string());
+        let clock = Arc::new(MockClock::new(price.last_updated));
+        let store = MarketPriceStore::with_config(TrackerConfig {
+            runtime: Runtime::default().with_clock(clock.clone()),
+            ..Default::default()
+        });
+        store.update_price(Asset::SOL, price).await;
+        let threshold = Asset::SOL.stale_threshold_secs();
+        assert_eq!(
+            store.get_price_sync(Asset::SOL).unwrap().quality,
+            Quality::Excellent
+        );
+
+        // Quality is graded as of the same clock
+        clock.advance(Duration::from_secs(threshold * 3 / 4));
+        assert_eq!(
+            store.get_price_sync(Asset::SOL).unwrap().quality,
+            Quality::Poor
+        );
+
+        clock.advance(Duration::from_secs(threshold));
+        assert!(matches!(
+            store.get_price_sync(Asset::SOL),
+            Err(PriceError::Stale { .. })
+        ));
+        assert_eq!(store.newly_stale().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_evicts_stale_and_disabled_assets() {
+        let store = MarketPriceStore::new();
+        let mut stale = PriceData::new(Asset::SOL, 100.0, "test".to_string());
+        stale.last_updated -= chrono::Duration::seconds(600);
+        store.update_price(Asset::SOL, stale).await;
+        store
+            .update_price(
+                Asset::BTC,
+                PriceData::new(Asset::BTC, 60000.0, "test".to_string()),
+            )
+            .await;
+        store
+            .update_price(
+                Asset::ETH,
+                PriceData::new(Asset::ETH, 3000.0, "test".to_string()),
+            )
+            .await;
+
+        let expired = store.evict_stale();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].asset, Asset::SOL);
+        assert!(!store.has_price(Asset::SOL).await);
+        assert!(store.evict_stale().is_empty());
+
+        let removed = store.retain_assets(&[Asset::SOL, Asset::BTC]);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].asset, Asset::ETH);
+        assert!(store.has_price(Asset::BTC).await);
+        assert!(!store.has_price(Asset::ETH).await);
+
+        let snapshot = store.get_all_prices_arc();
+        assert_eq!(snapshot.len(), 1);
+        assert!(snapshot.contains_key(&Asset::BTC));
+        assert!(Arc::ptr_eq(&snapshot, &store.get_all_prices_arc()));
+    }
+
+    #[tokio::test]
+    async fn test_watch_single_asset() {
+        let store = MarketPriceStore::new();
+        let mut rx = store.watch(Asset::SOL);
+        assert!(rx.borrow().is_none());
+
+        store
+            .update_price(
+                Asset::BTC,
+                PriceData::new(Asset::BTC, 60000.0, "test".to_string()),
+            )
+            .await;
+        assert!(!rx.has_changed().unwrap());
+
+        store
+            .update_price(
+                Asset::SOL,
+                PriceData::new(Asset::SOL, 150.0, "test".to_string()),
+            )
+            .await;
+        rx.changed().await.unwrap();
+        assert_eq!(rx.borrow().as_ref().map(|p| p.price_usd), Some(150.0));
+    }
+
+    #[tokio::test]
+    async fn test_withholds_suspect_until_confirmed() {
+        use crate::anomaly::{AnomalyConfig, MadDetector};
+
+        let store = MarketPriceStore::with_config(TrackerConfig {
+            anomaly: Some(AnomalyConfig::new(Arc::new(MadDetector::default())).withholding()),
+            ..Default::default()
+        });
+        for i in 0..20 {
+            let price = 100.0 + (i % 3) as f64 * 0.1;
+            let tick = PriceData::new(Asset::SOL, price, "primary".to_string());
+            assert!(store.update_price(Asset::SOL, tick).await);
+        }
+
+        let spike = PriceData::new(Asset::SOL, 130.0, "primary".to_string());
+        assert!(!store.update_price(Asset::SOL, spike).await);
+        assert!(store.get_price(Asset::SOL).await.unwrap().price_usd < 101.0);
+
+        let confirmation = PriceData::new(Asset::SOL, 130.1, "backup".to_string());
+        let stored = store.store_price(Asset::SOL, confirmation).await.unwrap();
+        assert!(stored.quality > Quality::Suspect);
+        assert_eq!(store.get_price(Asset::SOL).await.unwrap().price_usd, 130.1);
+    }
+
+    struct TagSource;
+
+    impl PriceUpdateHook for TagSource {
+        fn before_update(&self, mut price: PriceData) -> Option<PriceData> {
+            if price.asset == Asset::BTC {
+                return None;
+            }
+            price.source.push_str("+tagged");
+            Some(price)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_hooks() {
+        let store = MarketPriceStore::new();
+        store.add_hook(Box::new(TagSource));
+
+        let stored = store
+            .store_price(
+                Asset::SOL,
+                PriceData::new(Asset::SOL, 150.0, "test".to_string()),
+            )
+            .await;
+        assert_eq!(stored.map(|p| p.source), Some("test+tagged".to_string()));
+        assert_eq!(
+            store.get_price(Asset::SOL).await.unwrap().source,
+            "test+tagged"
+        );
+
+        let btc = PriceData::new(Asset::BTC, 60000.0, "test".to_string());
+        assert!(!store.update_price(Asset::BTC, btc).await);
+        assert!(!store.has_price(Asset::BTC).await);
+    }
+
+    #[tokio::test]
+    async fn test_outage_grace_extends_stale_threshold() {
+        let price = PriceData::new(Asset::SOL, 100.0, "test".to_string());
+        let clock = Arc::new(MockClock::new(price.last_updated));
+        let store = MarketPriceStore::with_config(TrackerConfig {
+            runtime: Runtime::default().with_clock(clock.clone()),
+            outage_grace: Some(crate::outage::OutageGrace {
+                window: Duration::from_secs(180),
+                extension: Duration::from_secs(60),
+            }),
+            ..Default::default()
+        });
+        store.update_price(Asset::SOL, price).await;
+
+        clock.advance(Duration::from_secs(90));
+        store.record_poll(&[Asset::SOL], None);
+        clock.advance(Duration::from_secs(60));
+        assert!(store.get_price_sync(Asset::SOL).is_ok());
+
+        // Past the window the usual threshold applies again
+        clock.advance(Duration::from_secs(120));
+        store.record_poll(&[Asset::SOL], None);
+        assert!(store.get_price_sync(Asset::SOL).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+//! Bounded per-subscriber price queues
+//!
+//! [`MarketPriceTracker::subscribe`](crate::MarketPriceTracker::subscribe)
+//! hands out a receiver on a shared broadcast channel: a receiver that
+//! falls more than the channel capacity behind loses the oldest updates and
+//! only learns about it on its next `recv`.
+//!
+//! [`MarketPriceTracker::subscribe_with`](crate::MarketPriceTracker::subscribe_with)
+//! instead gives the subscriber its own bounded queue, fed by a forwarding
+//! task. When the queue is full, the [`OverflowPolicy`] decides what to
+//! drop, and the tracker reports the drops as
+//! [`MarketPriceEvent::SubscriberLagged`](crate::MarketPriceEvent::SubscriberLagged)
+//! events, at most once per [`LAG_REPORT_INTERVAL`].
+//!
+//! With [`SubscriptionConfig::conflate`], the queue holds at most one
+//! update per asset: a new tick replaces the queued one for its asset, so a
+//! slow consumer always gets the newest price of every asset that changed
+//! instead of a backlog of intermediate ticks.
+//! [`MarketPriceTracker::subscribe_latest`](crate::MarketPriceTracker::subscribe_latest)
+//! is a shortcut for such a subscription.
+//!
+//! [`MarketPriceTracker::subscribe_feed`](crate::MarketPriceTracker::subscribe_feed)
+//! follows the usual market-data snapshot + delta protocol, for fan-out
+//! servers relaying prices to remote clients: the first [`FeedMessage`] is
+//! a full snapshot, followed by one delta per stored update. Deltas already
+//! covered by the snapshot are skipped by sequence number, and a subscriber
+//! that falls behind gets a fresh snapshot instead of a gap.
+//!
+//! A [`PriceSubscription`] also converts into a [`Stream`] with
+//! [`PriceSubscription::into_stream`], which is what
+//! [`MarketPriceTracker::price_stream`](crate::MarketPriceTracker::price_stream)
+//! returns, for use with `StreamExt` combinators.
+
+use crate::types::{Asset, PriceData, PriceSnapshot};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, Notify};
+
+/// Minimum time between two lag reports for the same subscriber
+pub const LAG_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What a full subscriber queue drops to make room
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Drop the oldest queued update
+    #[default]
+    DropOldest,
+    /// Replace the queued update for the same asset, so the subscriber
+    /// still sees every asset; drop the oldest if that asset is not queued
+    CoalesceLatest,
+}
+
+/// Settings for a bounded subscription
+#[derive(Debug, Clone)]
+pub struct SubscriptionConfig {
+    /// Name used in lag reports and task listings
+    pub name: String,
+    /// Maximum number of queued updates
+    pub capacity: usize,
+    /// What to drop when the queue is full
+    pub overflow: OverflowPolicy,
+    /// Replace a queued update for the same asset instead of queueing
+    /// another one; replaced ticks are not counted as missed
+    pub conflate: bool,
+    /// Only queue updates for these assets; `None` queues every asset
+    pub assets: Option<Vec<Asset>>,
+}
+
+impl Default for SubscriptionConfig {
+    fn default() -> Self {
+        Self {
+            name: "subscriber".to_string(),
+            capacity: 256,
+            overflow: OverflowPolicy::default(),
+            conflate: false,
+            assets: None,
+        }
+    }
+}
+
+/// Queue shared between a subscription and its forwarding task
+pub(crate) struct Queue {
+    items: Mutex<VecDeque<PriceData>>,
+    notify: Notify,
+    capacity: usize,
+    overflow: OverflowPolicy,
+    conflate: bool,
+    assets: Option<Vec<Asset>>,
+    missed: AtomicU64,
+    /// Set by the forwarding task when no more updates will arrive
+    closed: AtomicBool,
+    /// Set when the subscription is dropped
+    abandoned: AtomicBool,
+}
+
+impl Queue {
+    pub(crate) fn new(config: &SubscriptionConfig) -> Arc<Self> {
+        Arc::new(Self {
+            items: Mutex::new(VecDeque::new()),
+            notify: Notify::new(),
+            capacity: config.capacity.max(1),
+            overflow: config.overflow,
+            conflate: config.conflate,
+            assets: config.assets.clone(),
+            missed: AtomicU64::new(0),
+            closed: AtomicBool::new(false),
+            abandoned: AtomicBool::new(false),
+        })
+    }
+
+    /// Queues an update, returning the number of updates dropped to make room
+    pub(crate) fn push(&self, price: PriceData) -> u64 {
+        if self
+            .assets
+            .as_ref()
+            .is_some_and(|assets| !assets.contains(&price.asset))
+        {
+            return 0;
+        }
+        let mut items = self.items.lock().expect("subscription queue poisoned");
+        if self.conflate {
+            if let Some(queued) = items.iter_mut().find(|p| p.asset == price.asset) {
+                *queued = price;
+                drop(items);
+                self.notify.notify_one();
+                return 0;
+            }
+        }
+        let mut dropped = 0;
+        if items.len() >= self.capacity {
+            let same_asset = match self.overflow {
+                OverflowPolicy::CoalesceLatest => items.iter().position(|p| p.asset == price.asset),
+                OverflowPolicy::DropOldest => None,
+            };
+            match same_asset {
+                Some(index) => {
+                    items[index] = price;
+                    drop(items);
+                    self.record_missed(1);
+                    self.notify.notify_one();
+                    return 1;
+                }
+                None => {
+                    items.pop_front();
+                    dropped = 1;
+                }
+            }
+        }
+        items.push_back(price);
+        drop(items);
+        self.record_missed(dropped);
+        self.notify.notify_one();
+        dropped
+    }
+
+    pub(crate) fn record_missed(&self, missed: u64) {
+        self.missed.fetch_add(missed, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_abandoned(&self) -> bool {
+        self.abandoned.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+        self.notify.notify_one();
+    }
+
+    fn pop(&self) -> Option<PriceData> {
+        self.items
+            .lock()
+            .expect("subscription queue poisoned")
+            .pop_front()
+    }
+}
+
+/// Receiving end of a bounded subscription
+///
+/// Dropping it stops the forwarding task.
+pub struct PriceSubscription {
+    queue: Arc<Queue>,
+}
+
+impl PriceSubscription {
+    pub(crate) fn new(queue: Arc<Queue>) -> Self {
+        Self { queue }
+    }
+
+    /// Waits for the next update
+    ///
+    /// Returns `None` once the tracker has shut down and the queue is empty.
+    pub async fn recv(&mut self) -> Option<PriceData> {
+        loop {
+            if let Some(price) = self.queue.pop() {
+                return Some(price);
+            }
+            if self.queue.closed.load(Ordering::Relaxed) {
+                return None;
+            }
+            self.queue.notify.notified().await;
+        }
+    }
+
+    /// Returns the next queued update without waiting
+    pub fn try_recv(&mut self) -> Option<PriceData> {
+        self.queue.pop()
+    }
+
+    /// Number of updates currently queued
+    pub fn len(&self) -> usize {
+        self.queue
+            .items
+            .lock()
+            .expect("subscription queue poisoned")
+            .len()
+    }
+
+    /// Returns true if no update is queued
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Updates dropped for this subscriber so far
+    pub fn missed(&self) -> u64 {
+        self.queue.missed.load(Ordering::Relaxed)
+    }
+
+    /// Turns the subscription into a stream of updates, which ends once
+    /// the tracker has shut down
+    pub fn into_stream(self) -> impl Stream<Item = PriceData> + Send + Unpin + 'static {
+        stream::unfold(self, |mut subscription| async move {
+            let price = subscription.recv().await?;
+            Some((price, subscription))
+        })
+        .boxed()
+    }
+}
+
+impl Drop for PriceSubscription {
+    fn drop(&mut self) {
+        self.queue.abandoned.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Message of a snapshot + delta feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FeedMessage {
+    /// Latest price of every asset; replaces all state the client holds
+    Snapshot(PriceSnapshot),
+    /// A price stored after the last snapshot
+    Delta(PriceData),
+}
+
+/// Receiving end of a snapshot + delta feed
+///
+/// Dropping it stops the forwarding task.
+pub struct FeedSubscription {
+    rx: mpsc::Receiver<FeedMessage>,
+}
+
+impl FeedSubscription {
+    pub(crate) fn new(rx: mpsc::Receiver<FeedMessage>) -> Self {
+        Self { rx }
+    }
+
+    /// Waits for the next message
+    ///
+    /// Returns `None` once the tracker has shut down and the feed is drained.
+    pub async fn recv(&mut self) -> Option<FeedMessage> {
+        self.rx.recv().await
+    }
+
+    /// Returns the next queued message without waiting
+    pub fn try_recv(&mut self) -> Option<FeedMessage> {
+        self.rx.try_recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Asset;
+
+    fn price(asset: Asset, usd: f64) -> PriceData {
+        PriceData::new(asset, usd, "test".to_string())
+    }
+
+    #[test]
+    fn test_overflow_policies() {
+        let config = SubscriptionConfig {
+            capacity: 2,
+            ..Default::default()
+        };
+        let mut oldest = PriceSubscription::new(Queue::new(&config));
+        oldest.queue.push(price(Asset::SOL, 1.0));
+        oldest.queue.push(price(Asset::BTC, 2.0));
+        assert_eq!(oldest.queue.push(price(Asset::SOL, 3.0)), 1);
+        assert_eq!(oldest.try_recv().unwrap().price_usd, 2.0);
+        assert_eq!(oldest.missed(), 1);
+
+        let mut coalesce = PriceSubscription::new(Queue::new(&SubscriptionConfig {
+            overflow: OverflowPolicy::CoalesceLatest,
+            ..config
+        }));
+        coalesce.queue.push(price(Asset::SOL, 1.0));
+        coalesce.queue.push(price(Asset::BTC, 2.0));
+        coalesce.queue.push(price(Asset::SOL, 3.0));
+        assert_eq!(coalesce.try_recv().unwrap().price_usd, 3.0);
+        assert_eq!(coalesce.try_recv().unwrap().price_usd, 2.0);
+        assert!(coalesce.is_empty());
+    }
+
+    #[test]
+    fn test_conflation_keeps_newest_per_asset() {
+        let mut latest = PriceSubscription::new(Queue::new(&SubscriptionConfig {
+            capacity: usize::MAX,
+            conflate: true,
+            ..Default::default()
+        }));
+        for usd in [1.0, 2.0, 3.0] {
+            latest.queue.push(price(Asset::SOL, usd));
+        }
+        latest.queue.push(price(Asset::BTC, 60000.0));
+        latest.queue.push(price(Asset::SOL, 4.0));
+
+        assert_eq!(latest.len(), 2);
+        assert_eq!(latest.try_recv().unwrap().price_usd, 4.0);
+        assert_eq!(latest.try_recv().unwrap().asset, Asset::BTC);
+        assert_eq!(latest.missed(), 0);
+    }
+}
@@ -0,0 +1,170 @@
+//! Provider-specific asset identifiers with runtime overrides
+//!
+//! Each provider names assets differently: CoinGecko by coin id
+//! (`solana`), Hyperliquid by perp symbol (`SOL`), Pyth by 32-byte feed ID.
+//! The built-in mappings live on [`Asset`]; providers resolve identifiers
+//! through the [`SymbolRegistry`] instead, so a wrong or missing mapping can
+//! be fixed at runtime without a new release.
+//!
+//! ```
+//! use market_price_sdk::symbols::{SymbolRegistry, SymbolSource};
+//! use market_price_sdk::Asset;
+//!
+//! let registry = SymbolRegistry::global();
+//! registry.set(SymbolSource::CoinGecko, Asset::BSOL, "blazestake-staked-sol").unwrap();
+//! assert_eq!(
+//!     registry.lookup(SymbolSource::CoinGecko, "blazestake-staked-sol"),
+//!     Some(Asset::BSOL)
+//! );
+//! ```
+
+use crate::{error::PriceError, feeds::normalize_feed_id, types::Asset};
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+static GLOBAL: LazyLock<SymbolRegistry> = LazyLock::new(SymbolRegistry::new);
+
+/// Naming scheme an identifier belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolSource {
+    /// CoinGecko coin id, e.g. `solana`
+    CoinGecko,
+    /// Hyperliquid perp symbol, e.g. `SOL`
+    Hyperliquid,
+    /// Pyth feed ID, normalized to lowercase hex with a `0x` prefix
+    Pyth,
+}
+
+impl SymbolSource {
+    /// Returns the built-in identifier for an asset
+    pub fn builtin(&self, asset: Asset) -> Option<&'static str> {
+        match self {
+            SymbolSource::CoinGecko => asset.coingecko_id(),
+            SymbolSource::Hyperliquid => asset.hyperliquid_symbol(),
+            SymbolSource::Pyth => asset.pyth_feed_id(),
+        }
+    }
+}
+
+/// Asset identifier mappings, consulted before the built-in ones
+#[derive(Debug, Default)]
+pub struct SymbolRegistry {
+    overrides: RwLock<HashMap<(SymbolSource, Asset), String>>,
+}
+
+impl SymbolRegistry {
+    /// Creates a registry with no overrides
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the process-wide registry used by the built-in providers
+    pub fn global() -> &'static SymbolRegistry {
+        &GLOBAL
+    }
+
+    /// Maps an asset to an identifier, replacing the built-in one
+    ///
+    /// Pyth feed IDs are validated and normalized; an invalid one is
+    /// rejected with [`PriceError::InvalidFeedId`].
+    pub fn set(&self, source: SymbolSource, asset: Asset, id: &str) -> Result<(), PriceError> {
+        let id = match source {
+            SymbolSource::Pyth => {
+                normalize_feed_id(id).ok_or_else(|| PriceError::invalid_feed_id(id))?
+            }
+            _ => id.trim().to_string(),
+        };
+
+        tracing::info!(
+            asset = asset.symbol(),
+            source = ?source,
+            id = %id,
+            "Overriding asset identifier"
+        );
+        self.overrides.write().unwrap().insert((source, asset), id);
+        Ok(())
+    }
+
+    /// Removes an override, restoring the built-in mapping
+    pub fn clear(&self, source: SymbolSource, asset: Asset) {
+        self.overrides.write().unwrap().remove(&(source, asset));
+    }
+
+    /// Resolves the identifier of an asset, honouring overrides
+    pub fn resolve(&self, source: SymbolSource, asset: Asset) -> Option<String> {
+        if let Some(id) = self.overrides.read().unwrap().get(&(source, asset)) {
+            return Some(id.clone());
+        }
+        source.builtin(asset).map(str::to_string)
+    }
+
+    /// Finds the asset an identifier maps to
+    ///
+    /// Overrides take precedence; a built-in identifier that was overridden
+    /// for its asset no longer matches.
+    pub fn lookup(&self, source: SymbolSource, id: &str) -> Option<Asset> {
+        let overrides = self.overrides.read().unwrap();
+        if let Some(((_, asset), _)) = overrides
+            .iter()
+            .find(|((s, _), mapped)| *s == source && mapped.as_str() == id)
+        {
+            return Some(*asset);
+        }
+        Asset::all().iter().copied().find(|asset| {
+            !overrides.contains_key(&(source, *asset)) && source.builtin(*asset) == Some(id)
+        })
+    }
+
+    /// CoinGecko coin id of an asset, if it has one
+    pub fn coingecko_id(&self, asset: Asset) -> Option<String> {
+        self.resolve(SymbolSource::CoinGecko, asset)
+    }
+
+    /// Hyperliquid perp symbol of an asset, if it has one
+    pub fn hyperliquid_symbol(&self, asset: Asset) -> Option<String> {
+        self.resolve(SymbolSource::Hyperliquid, asset)
+    }
+
+    /// Pyth feed ID of an asset, if it has one
+    pub fn pyth_feed_id(&self, asset: Asset) -> Option<String> {
+        self.resolve(SymbolSource::Pyth, asset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_override_and_reverse_lookup() {
+        let registry = SymbolRegistry::new();
+        assert_eq!(registry.coingecko_id(Asset::MSOL).as_deref(), Some("msol"));
+        assert_eq!(registry.coingecko_id(Asset::XAU), None);
+        assert_eq!(
+            registry.lookup(SymbolSource::Hyperliquid, "SOL"),
+            Some(Asset::SOL)
+        );
+
+        registry
+            .set(SymbolSource::CoinGecko, Asset::MSOL, "marinade-staked-sol")
+            .unwrap();
+        assert_eq!(
+            registry.coingecko_id(Asset::MSOL).as_deref(),
+            Some("marinade-staked-sol")
+        );
+        assert_eq!(
+            registry.lookup(SymbolSource::CoinGecko, "marinade-staked-sol"),
+            Some(Asset::MSOL)
+        );
+        assert_eq!(registry.lookup(SymbolSource::CoinGecko, "msol"), None);
+        assert!(registry
+            .set(SymbolSource::Pyth, Asset::MSOL, "not-a-feed")
+            .is_err());
+
+        registry.clear(SymbolSource::CoinGecko, Asset::MSOL);
+        assert_eq!(
+            registry.lookup(SymbolSource::CoinGecko, "msol"),
+            Some(Asset::MSOL)
+        );
+    }
+}
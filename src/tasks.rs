@@ -0,0 +1,257 @@
+//! Named, instrumented background tasks
+//!
+//! Every task the SDK spawns (polling loops, stream supervisors, sinks,
+//! metric recorders) is registered here under a stable name, such as
+//! `tracker.poll` or `hermes.stream`. [`list`] reports each live task with
+//! its state, poll count and busy time, so runtime load can be attributed
+//! to the SDK. Each poll also runs inside a `task` tracing span carrying
+//! the name.
+//!
+//! Tasks that finish normally are dropped from the registry. Tasks that
+//! panicked or were cancelled stay listed (the most recent
+//! [`MAX_FAILED_TASKS`]) so a dead loop is visible.
+//!
+//! # tokio-console
+//!
+//! With the `tokio-console` feature and `RUSTFLAGS="--cfg tokio_unstable"`,
+//! tasks spawned on tokio are also given their name through
+//! `tokio::task::Builder`, so they show up by name in tokio-console once
+//! the application installs `console-subscriber`.
+
+use crate::runtime::Runtime;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Number of panicked or cancelled tasks kept for inspection
+pub const MAX_FAILED_TASKS: usize = 16;
+
+static REGISTRY: LazyLock<Registry> = LazyLock::new(Registry::default);
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Lifecycle state of a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskState {
+    /// Waiting to be woken
+    Idle,
+    /// Currently being polled
+    Running,
+    /// Panicked while being polled
+    Panicked,
+    /// Dropped by the executor before completing (e.g. runtime shutdown)
+    Cancelled,
+}
+
+impl TaskState {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => TaskState::Running,
+            2 => TaskState::Panicked,
+            3 => TaskState::Cancelled,
+            _ => TaskState::Idle,
+        }
+    }
+}
+
+/// Point-in-time view of a registered task
+#[derive(Debug, Clone)]
+pub struct TaskInfo {
+    /// Unique id within the process
+    pub id: u64,
+    /// Stable task name, e.g. `tracker.poll`
+    pub name: String,
+    /// Current state
+    pub state: TaskState,
+    /// When the task was spawned
+    pub spawned_at: DateTime<Utc>,
+    /// Times the executor has polled the task
+    pub polls: u64,
+    /// Total time spent inside the task's polls
+    pub busy: Duration,
+}
+
+/// Shared counters for one task
+struct TaskEntry {
+    id: u64,
+    name: String,
+    spawned_at: DateTime<Utc>,
+    state: AtomicU8,
+    polls: AtomicU64,
+    busy_ns: AtomicU64,
+}
+
+impl TaskEntry {
+    fn info(&self) -> TaskInfo {
+        TaskInfo {
+            id: self.id,
+            name: self.name.clone(),
+            state: TaskState::from_u8(self.state.load(Ordering::Relaxed)),
+            spawned_at: self.spawned_at,
+            polls: self.polls.load(Ordering::Relaxed),
+            busy: Duration::from_nanos(self.busy_ns.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    live: Mutex<Vec<Arc<TaskEntry>>>,
+    failed: Mutex<VecDeque<Arc<TaskEntry>>>,
+}
+
+impl Registry {
+    fn register(&self, name: String) -> Arc<TaskEntry> {
+        let entry = Arc::new(TaskEntry {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            name,
+            spawned_at: Utc::now(),
+            state: AtomicU8::new(TaskState::Idle as u8),
+            polls: AtomicU64::new(0),
+            busy_ns: AtomicU64::new(0),
+        });
+        self.live
+            .lock()
+            .expect("task registry poisoned")
+            .push(entry.clone());
+        entry
+    }
+
+    fn finish(&self, entry: &Arc<TaskEntry>, state: Option<TaskState>) {
+        // Also reached while unwinding, so never panic on a poisoned lock
+        if let Ok(mut live) = self.live.lock() {
+            live.retain(|e| e.id != entry.id);
+        }
+        if let (Some(state), Ok(mut failed)) = (state, self.failed.lock()) {
+            entry.state.store(state as u8, Ordering::Relaxed);
+            if failed.len() >= MAX_FAILED_TASKS {
+                failed.pop_front();
+            }
+            failed.push_back(entry.clone());
+        }
+    }
+}
+
+/// Future wrapper that records polls and busy time for a registered task
+struct Instrumented<F> {
+    future: Pin<Box<F>>,
+    entry: Arc<TaskEntry>,
+    span: tracing::Span,
+    completed: bool,
+}
+
+impl<F: Future<Output = ()>> Future for Instrumented<F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = &mut *self;
+        let _enter = this.span.enter();
+        this.entry
+            .state
+            .store(TaskState::Running as u8, Ordering::Relaxed);
+        let start = Instant::now();
+        let poll = this.future.as_mut().poll(cx);
+        this.entry
+            .busy_ns
+            .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        this.entry.polls.fetch_add(1, Ordering::Relaxed);
+        this.entry
+            .state
+            .store(TaskState::Idle as u8, Ordering::Relaxed);
+
+        if poll.is_ready() {
+            this.completed = true;
+            REGISTRY.finish(&this.entry, None);
+        }
+        poll
+    }
+}
+
+impl<F> Drop for Instrumented<F> {
+    fn drop(&mut self) {
+        if !self.completed {
+            let state = if std::thread::panicking() {
+                TaskState::Panicked
+            } else {
+                TaskState::Cancelled
+            };
+            REGISTRY.finish(&self.entry, Some(state));
+        }
+    }
+}
+
+/// Registers a task and wraps its future with instrumentation
+pub(crate) fn instrument<F>(name: String, future: F) -> impl Future<Output = ()> + Send + 'static
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let span = tracing::info_span!("task", name = %name);
+    Instrumented {
+        future: Box::pin(future),
+        entry: REGISTRY.register(name),
+        span,
+        completed: false,
+    }
+}
+
+/// Spawns a named task on tokio
+///
+/// For code that always runs on tokio (e.g. reqwest-based streams). Code
+/// that honors [`TrackerConfig::runtime`](crate::TrackerConfig::runtime)
+/// uses [`Runtime::spawn_named`] instead.
+pub fn spawn<F>(name: impl Into<String>, future: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    Runtime::tokio().spawn_named(name, future);
+}
+
+/// Lists live SDK tasks, followed by recently panicked or cancelled ones
+pub fn list() -> Vec<TaskInfo> {
+    let mut tasks: Vec<TaskInfo> = REGISTRY
+        .live
+        .lock()
+        .expect("task registry poisoned")
+        .iter()
+        .map(|entry| entry.info())
+        .collect();
+    tasks.extend(
+        REGISTRY
+            .failed
+            .lock()
+            .expect("task registry poisoned")
+            .iter()
+            .map(|entry| entry.info()),
+    );
+    tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tasks_are_listed_until_done() {
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        spawn("test.waiting", async move {
+            let _ = rx.await;
+        });
+        spawn("test.panicking", async { panic!("boom") });
+        tokio::task::yield_now().await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let find = |name: &str| list().into_iter().find(|t| t.name == name);
+        let waiting = find("test.waiting").unwrap();
+        assert_eq!(waiting.state, TaskState::Idle);
+        assert!(waiting.polls >= 1);
+        assert_eq!(find("test.panicking").unwrap().state, TaskState::Panicked);
+
+        tx.send(()).unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(find("test.waiting").is_none());
+    }
+}
@@ -3,25 +3,57 @@
 //! Provides a singleton instance for tracking cryptocurrency market prices.
 
 use crate::{
+    config::{Environment, TrackerConfig, PROVIDER_ENV_VAR, SHADOW_PROVIDER_ENV_VAR},
     constants::{
-        ENABLED_ASSETS, INITIAL_BACKOFF_MS, MAX_BACKOFF_MS, MAX_RETRY_ATTEMPTS,
-        REFRESH_INTERVAL_SECS,
+        ENABLED_ASSETS, INITIAL_BACKOFF_MS, LATENCY_BUDGET_WINDOW_SECS, MAX_BACKOFF_MS,
+        MAX_RETRY_ATTEMPTS, REFRESH_INTERVAL_SECS, SHADOW_MAX_DEVIATION_BPS,
     },
     error::{PriceError, ProviderError},
-    metrics::{MetricsCollector, ProviderMetrics},
-    provider::MarketPriceProvider,
-    providers::{CoinGeckoProvider, HyperliquidProvider},
-    store::MarketPriceStore,
-    types::{Asset, ComponentHealth, HealthStatus, PriceData},
+    event_id::EventIdGenerator,
+    history::PriceAtOptions,
+    hooks::PriceUpdateHook,
+    http,
+    index::{IndexDefinition, IndexPrice},
+    metrics::{AssetMetrics, MetricsCollector, ProviderMetrics},
+    provider::{self, MarketPriceProvider},
+    providers::{CoinGeckoProvider, HermesProvider, RoutedProvider, ShadowProvider},
+    read_through::ReadThrough,
+    registry::{self, ProviderRegistry},
+    runtime::{Runtime, Ticker},
+    self_test::{self, SelfTestReport},
+    sinks::{BatchConfig, Batcher, MetricsSink, PriceEventSink, PriceSink},
+    smoothing::{SmoothedPrice, Smoother, Smoothing},
+    stats::{CorrelationMatrix, PriceStats, CORRELATION_GRID_INTERVALS},
+    store::{MarketPriceStore, PriceStoreReader},
+    subscription::{
+        FeedMessage, FeedSubscription, PriceSubscription, Queue, SubscriptionConfig,
+        LAG_REPORT_INTERVAL,
+    },
+    tasks::{self, TaskInfo},
+    types::{
+        Asset, ComponentHealth, DetailedPrices, HealthStatus, MarketPriceEvent, PriceData,
+        PriceSnapshot,
+    },
 };
+use arc_swap::ArcSwap;
+use chrono::{DateTime, Utc};
+use futures::future::{BoxFuture, FutureExt};
+use futures::Stream;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, OnceLock};
 use std::time::{Duration, Instant};
-use tokio::sync::{broadcast, OnceCell};
-use tokio::time::sleep;
+use tokio::sync::{broadcast, mpsc, watch, OnceCell, RwLock};
 
 static GLOBAL_TRACKER: OnceCell<Arc<MarketPriceTracker>> = OnceCell::const_new();
 
+/// Lazily initialized singleton slot
+type TrackerCell = Arc<OnceCell<Arc<MarketPriceTracker>>>;
+
+/// Named singletons, one cell per name so initialization never holds the map lock
+static NAMED_TRACKERS: LazyLock<Mutex<HashMap<String, TrackerCell>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 /// Global Market Price Tracker
 ///
 /// Manages fetching and storing cryptocurrency prices from external providers.
@@ -40,10 +72,27 @@ static GLOBAL_TRACKER: OnceCell<Arc<MarketPriceTracker>> = OnceCell::const_new()
 /// ```
 pub struct MarketPriceTracker {
     store: Arc<MarketPriceStore>,
-    provider: Arc<dyn MarketPriceProvider>,
+    /// Provider in use, replaced as a whole by [`set_provider`](Self::set_provider)
+    active: Arc<ArcSwap<ActiveProvider>>,
+    /// Held shared by every fetch, so a provider swap can wait out the
+    /// fetches still using the previous provider
+    fetch_gate: Arc<RwLock<()>>,
+    /// Set once the background tasks have started
+    started: AtomicBool,
     metrics: Arc<MetricsCollector>,
+    /// Latencies of fetches sent to the fallback provider on its own
+    fallback_metrics: Arc<MetricsCollector>,
+    runtime: Runtime,
+    config: TrackerConfig,
+    correlations: Mutex<Option<CorrelationMatrix>>,
+    indices: Mutex<HashMap<String, IndexDefinition>>,
+    smoothing: Arc<Smoothing>,
+    read_through: Option<ReadThrough>,
     update_tx: broadcast::Sender<PriceData>,
+    event_tx: broadcast::Sender<MarketPriceEvent>,
     shutdown_tx: broadcast::Sender<()>,
+    /// Kill switch: while set, nothing the tracker does contacts a provider
+    paused: Arc<AtomicBool>,
 }
 
 impl MarketPriceTracker {
@@ -52,9 +101,77 @@ impl MarketPriceTracker {
     /// On first call, this initializes the tracker and starts the background
     /// polling task. Subsequent calls return the same instance.
     pub async fn global() -> Arc<Self> {
+        Self::global_with(TrackerConfig::default()).await
+    }
+
+    /// Returns the global singleton once it holds its first price
+    ///
+    /// The background task fetches as soon as it starts rather than after
+    /// the first refresh interval, so this resolves as soon as that fetch
+    /// lands. Wrap it in a timeout to bound startup if providers may be
+    /// down; see [`ready`](Self::ready).
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tracker = MarketPriceTracker::global_ready().await;
+    /// let sol_price = tracker.get_price(Asset::SOL).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn global_ready() -> Arc<Self> {
+        let tracker = Self::global().await;
+        tracker.ready().await;
+        tracker
+    }
+
+    /// Returns the global singleton instance, creating it with `config`
+    ///
+    /// The configuration only applies if this call creates the instance;
+    /// once the singleton exists it is returned unchanged.
+    pub async fn global_with(config: TrackerConfig) -> Arc<Self> {
         GLOBAL_TRACKER
+            .get_or_init(|| Self::start_global(config))
+            .await
+            .clone()
+    }
+
+    /// Returns the singleton registered under `name`, creating it with the
+    /// environment-selected provider and default configuration
+    ///
+    /// Named trackers are independent of [`global`](Self::global) and of
+    /// each other, so subsystems of one binary can each own a tracker.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let trading = MarketPriceTracker::global_named("trading").await;
+    /// let sol_price = trading.get_price(Asset::SOL).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn global_named(name: &str) -> Arc<Self> {
+        Self::named_cell(name)
+            .get_or_init(|| Self::start_global(TrackerConfig::default()))
+            .await
+            .clone()
+    }
+
+    /// Returns the singleton registered under `name`, creating it with the
+    /// given provider and configuration
+    ///
+    /// As with [`global_with`](Self::global_with), the arguments only apply
+    /// if this call creates the instance.
+    pub async fn global_named_with(
+        name: &str,
+        provider: Arc<dyn MarketPriceProvider>,
+        config: TrackerConfig,
+    ) -> Arc<Self> {
+        Self::named_cell(name)
             .get_or_init(|| async {
-                let tracker = Self::new().await;
+                let tracker = Self::with_config(provider, config);
                 tracker.start_background_task();
                 Arc::new(tracker)
             })
@@ -62,77 +179,172 @@ impl MarketPriceTracker {
             .clone()
     }
 
+    /// Returns the cell holding the singleton for `name`
+    fn named_cell(name: &str) -> TrackerCell {
+        NAMED_TRACKERS
+            .lock()
+            .expect("named trackers lock poisoned")
+            .entry(name.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Creates a tracker for a singleton and starts its background task
+    async fn start_global(config: TrackerConfig) -> Arc<Self> {
+        if let Err(e) = config.validate() {
+            tracing::error!(error = %e, "Market price tracker configuration is invalid");
+        }
+        let tracker = Self::with_config(Self::provider_from_env().await, config);
+        tracker.start_background_task();
+        Arc::new(tracker)
+    }
+
     /// Creates a new market price tracker
     ///
     /// This is primarily for testing. Use `global()` in production code.
     /// By default, it uses the provider specified in the `MARKET_PRICE_PROVIDER`
-    /// environment variable ("coingecko" or "hyperliquid"). Defaults to hermes.
+    /// environment variable ("coingecko", "hyperliquid", "failover", or any
+    /// name added to the [`ProviderRegistry`]). Defaults to hermes.
+    /// `MARKET_PRICE_ENVIRONMENT` ("mainnet", "testnet" or "demo") selects
+    /// the endpoints those providers connect to.
     pub async fn new() -> Self {
-        let provider_name =
-            std::env::var("MARKET_PRICE_PROVIDER").unwrap_or_else(|_| "hermes".to_string());
-
-        let provider: Arc<dyn MarketPriceProvider> = match provider_name.to_lowercase().as_str() {
-            "hermes" | "default" => match crate::providers::HermesProvider::new().await {
-                Ok(p) => p,
-                Err(e) => {
-                    tracing::error!(
-                        error = %e,
-                        provider = "hermes",
-                        "Failed to initialize Hermes provider. Falling back to CoinGecko."
-                    );
-                    Arc::new(CoinGeckoProvider::default())
-                }
-            },
-            "failover" => {
-                // Failover: Hermes (primary) -> CoinGecko (backup)
-                let primary = match crate::providers::HermesProvider::new().await {
-                    Ok(p) => Some(p as Arc<dyn MarketPriceProvider>),
-                    Err(_) => None,
-                };
+        Self::with_provider(Self::provider_from_env().await)
+    }
 
-                let backup = Arc::new(CoinGeckoProvider::default());
+    /// Builds the provider selected by `MARKET_PRICE_PROVIDER` and
+    /// `MARKET_PRICE_ENVIRONMENT` from the global [`ProviderRegistry`]
+    async fn provider_from_env() -> Arc<dyn MarketPriceProvider> {
+        let environment = Environment::from_env();
+        let provider_name = std::env::var(PROVIDER_ENV_VAR)
+            .unwrap_or_else(|_| registry::DEFAULT_PROVIDER.to_string());
+        let registry = ProviderRegistry::global();
 
-                if let Some(p) = primary {
-                    Arc::new(crate::providers::FailoverProvider::new(vec![p, backup]))
-                } else {
-                    backup
-                }
-            }
-            "hyperliquid" => Arc::new(HyperliquidProvider::default()),
-            "coingecko" => Arc::new(CoinGeckoProvider::default()),
-            _ => {
-                tracing::warn!(
+        let provider: Arc<dyn MarketPriceProvider> = match registry
+            .create(&provider_name, environment)
+            .await
+        {
+            Ok(provider) => provider,
+            Err(e) => {
+                tracing::error!(
                     provider = %provider_name,
-                    "Unknown provider specified. Defaulting to Hermes."
+                    registered = ?registry.names(),
+                    error = %e,
+                    "Could not create the specified provider. Defaulting to Hermes."
                 );
-                match crate::providers::HermesProvider::new().await {
+                match HermesProvider::new().await {
                     Ok(p) => p,
-                    Err(_) => Arc::new(CoinGeckoProvider::default()),
+                    Err(_) => Arc::new(CoinGeckoProvider::default().with_environment(environment)),
                 }
             }
         };
 
-        Self::with_provider(provider)
+        let Ok(candidate_name) = std::env::var(SHADOW_PROVIDER_ENV_VAR) else {
+            return provider;
+        };
+        match registry.create(&candidate_name, environment).await {
+            Ok(candidate) => {
+                tracing::info!(
+                    provider = provider.provider_name(),
+                    candidate = %candidate_name,
+                    "Trialling shadow provider"
+                );
+                Arc::new(ShadowProvider::new(
+                    provider,
+                    candidate,
+                    SHADOW_MAX_DEVIATION_BPS,
+                ))
+            }
+            Err(e) => {
+                tracing::error!(
+                    candidate = %candidate_name,
+                    error = %e,
+                    "Could not create the shadow provider. Running without it."
+                );
+                provider
+            }
+        }
     }
 
     /// Creates a new market price tracker with a custom provider
     ///
     /// This is primarily for testing with mock providers.
     pub fn with_provider(provider: Arc<dyn MarketPriceProvider>) -> Self {
-        let store = Arc::new(MarketPriceStore::new());
-        let metrics = Arc::new(MetricsCollector::new(provider.provider_name()));
-        let (update_tx, _) = broadcast::channel(1000);
+        Self::with_config(provider, TrackerConfig::default())
+    }
+
+    /// Creates a new market price tracker with a custom provider and configuration
+    pub fn with_config(provider: Arc<dyn MarketPriceProvider>, config: TrackerConfig) -> Self {
+        let runtime = config.runtime.clone();
+        let (update_tx, _) = broadcast::channel(config.update_channel_capacity.max(1));
+        let (event_tx, _) = broadcast::channel(config.event_channel_capacity.max(1));
+        let store = Arc::new(
+            MarketPriceStore::with_config(config.clone()).with_event_sender(event_tx.clone()),
+        );
+        let metrics = Arc::new(
+            MetricsCollector::with_config(
+                provider.provider_name(),
+                config.latency_histogram.clone(),
+            )
+            .with_status_thresholds(config.provider_status.clone())
+            .with_event_sender(event_tx.clone(), config.event_ids.clone())
+            .with_runtime(runtime.clone()),
+        );
+        let fallback_metrics = Arc::new(
+            MetricsCollector::with_config("fallback", config.latency_histogram.clone())
+                .with_runtime(runtime.clone()),
+        );
         let (shutdown_tx, _) = broadcast::channel(1);
+        let paused = Arc::new(AtomicBool::new(config.start_paused));
+        let smoothing = Arc::new(Smoothing::default());
+        store.add_shared_hook(smoothing.clone());
+        let read_through = config
+            .read_through
+            .clone()
+            .map(|read_through| ReadThrough::new(read_through, runtime.clone()));
 
         Self {
             store,
-            provider,
+            active: Arc::new(ArcSwap::from_pointee(ActiveProvider::new(provider, None))),
+            fetch_gate: Arc::new(RwLock::new(())),
+            started: AtomicBool::new(false),
             metrics,
+            fallback_metrics,
+            runtime,
+            config,
+            correlations: Mutex::new(None),
+            indices: Mutex::new(HashMap::new()),
+            smoothing,
+            read_through,
             update_tx,
+            event_tx,
             shutdown_tx,
+            paused,
         }
     }
 
+    /// Sets the provider for enabled assets the main provider does not
+    /// support, according to its [`capabilities`](MarketPriceProvider::capabilities)
+    ///
+    /// Without one, those assets are left out of every fetch instead of
+    /// failing the batch.
+    pub fn with_fallback_provider(self, provider: Arc<dyn MarketPriceProvider>) -> Self {
+        self.active.store(Arc::new(ActiveProvider::new(
+            self.provider(),
+            Some(provider),
+        )));
+        self
+    }
+
+    /// Provider in use, without routing
+    fn provider(&self) -> Arc<dyn MarketPriceProvider> {
+        self.active.load().provider.clone()
+    }
+
+    /// Provider serving the enabled assets, after routing
+    fn routed_provider(&self) -> Arc<dyn MarketPriceProvider> {
+        self.active.load().routed()
+    }
+
     /// Subscribes to real-time price updates
     ///
     /// This is the reactive way to consume prices, especially with
@@ -141,220 +353,1660 @@ impl MarketPriceTracker {
         self.update_tx.subscribe()
     }
 
-    /// Starts the background polling task
-    fn start_background_task(&self) {
-        let store = self.store.clone();
-        let provider = self.provider.clone();
-        let metrics = self.metrics.clone();
-        let update_tx = self.update_tx.clone();
-        let mut shutdown_rx = self.shutdown_tx.subscribe();
-
-        if provider.is_streaming() {
-            tracing::info!(
-                provider = provider.provider_name(),
-                "Starting market price tracker in reactive streaming mode"
-            );
-            provider.start_streaming(store, update_tx);
-            return;
-        }
+    /// Registers a hook invoked before and after every store write
+    ///
+    /// See [`PriceUpdateHook`] for semantics.
+    pub fn add_update_hook(&self, hook: Box<dyn PriceUpdateHook>) {
+        self.store.add_hook(hook);
+    }
 
-        tokio::spawn(async move {
-            tracing::info!(
-                refresh_interval_secs = REFRESH_INTERVAL_SECS,
-                "Starting market price tracker background task"
-            );
+    /// Subscribes to market price events (e.g. provider divergence)
+    pub fn subscribe_events(&self) -> broadcast::Receiver<MarketPriceEvent> {
+        self.event_tx.subscribe()
+    }
 
-            // Initial fetch
-            if let Err(e) = Self::fetch_and_update(&provider, &store, &metrics, &update_tx).await {
-                tracing::warn!(error = %e, "Initial price fetch failed");
-            }
+    /// Subscribes to price updates through a dedicated bounded queue
+    ///
+    /// Unlike [`subscribe`](Self::subscribe), a slow subscriber never makes
+    /// the shared channel drop updates for it silently: its own queue
+    /// applies [`SubscriptionConfig::overflow`] when full, and the drops are
+    /// reported as [`MarketPriceEvent::SubscriberLagged`] events.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::MarketPriceTracker;
+    /// use market_price_sdk::subscription::{OverflowPolicy, SubscriptionConfig};
+    ///
+    /// # async fn example() {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let mut prices = tracker.subscribe_with(SubscriptionConfig {
+    ///     name: "risk-engine".to_string(),
+    ///     capacity: 64,
+    ///     overflow: OverflowPolicy::CoalesceLatest,
+    ///     ..Default::default()
+    /// });
+    /// while let Some(price) = prices.recv().await {
+    ///     println!("{}: ${:.2}", price.asset.symbol(), price.price_usd);
+    /// }
+    /// # }
+    /// ```
+    pub fn subscribe_with(&self, config: SubscriptionConfig) -> PriceSubscription {
+        let queue = Queue::new(&config);
+        let subscription = PriceSubscription::new(queue.clone());
+        let mut updates = self.update_tx.subscribe();
+        let event_tx = self.event_tx.clone();
+        let event_ids = self.config.event_ids.clone();
+        let runtime = self.runtime.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let name = config.name;
 
+        self.runtime.spawn_named(format!("tracker.subscription.{}", name), async move {
+            let mut unreported = 0;
+            let mut last_report: Option<Instant> = None;
             loop {
                 tokio::select! {
-                    _ = shutdown_rx.recv() => {
-                        tracing::info!("Market price tracker background task shutting down");
-                        break;
-                    }
-                    _ = sleep(Duration::from_secs(REFRESH_INTERVAL_SECS)) => {
-                        if let Err(e) = Self::fetch_and_update(&provider, &store, &metrics, &update_tx).await {
-                            tracing::warn!(error = %e, "Failed to fetch prices");
+                    _ = shutdown_rx.recv() => break,
+                    update = updates.recv() => match update {
+                        Ok(price) => unreported += queue.push(price),
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            queue.record_missed(skipped);
+                            unreported += skipped;
                         }
-                    }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
                 }
-            }
-        });
-    }
-
-    /// Fetches prices from provider and updates the store with metrics tracking
-    async fn fetch_and_update(
-        provider: &Arc<dyn MarketPriceProvider>,
-        store: &Arc<MarketPriceStore>,
-        metrics: &Arc<MetricsCollector>,
-        update_tx: &broadcast::Sender<PriceData>,
-    ) -> Result<(), ProviderError> {
-        let mut backoff_ms = INITIAL_BACKOFF_MS;
-        let start = Instant::now();
-
-        for attempt in 1..=MAX_RETRY_ATTEMPTS {
-            match provider.fetch_prices(ENABLED_ASSETS).await {
-                Ok(prices) => {
-                    tracing::debug!(
-                        count = prices.len(),
-                        provider = provider.provider_name(),
-                        latency_ms = start.elapsed().as_millis() as u64,
-                        "Successfully fetched prices"
-                    );
-                    store.update_prices(prices.clone()).await;
-
-                    // Broadcast updates for reactive consumers
-                    for price in prices.values() {
-                        let _ = update_tx.send(price.clone());
-                    }
-
-                    metrics.record_request(start.elapsed(), true).await;
-                    return Ok(());
+                if queue.is_abandoned() {
+                    break;
                 }
-                Err(e) => {
-                    tracing::warn!(
-                        attempt = attempt,
-                        max_attempts = MAX_RETRY_ATTEMPTS,
-                        error = %e,
-                        "Failed to fetch prices, retrying"
-                    );
-
-                    if attempt < MAX_RETRY_ATTEMPTS {
-                        sleep(Duration::from_millis(backoff_ms)).await;
-                        backoff_ms = (backoff_ms * 2).min(MAX_BACKOFF_MS);
-                    } else {
-                        metrics.record_request(start.elapsed(), false).await;
-                        return Err(e);
-                    }
+                if unreported > 0
+                    && last_report.is_none_or(|at| at.elapsed() >= LAG_REPORT_INTERVAL)
+                {
+                    tracing::warn!(subscriber = %name, missed = unreported, "Subscriber fell behind");
+                    report_lag(&runtime, &event_tx, event_ids.as_ref(), &name, unreported);
+                    unreported = 0;
+                    last_report = Some(Instant::now());
                 }
             }
-        }
+            queue.close();
+        });
 
-        Err(ProviderError::InvalidResponse(
-            "Max retries exceeded".to_string(),
-        ))
+        subscription
     }
 
-    /// Gets the current price for an asset
+    /// Subscribes to the newest price per asset, skipping intermediate ticks
     ///
-    /// # Arguments
-    /// * `asset` - The asset to get the price for
+    /// However slowly the consumer reads, it never works through a
+    /// backlog: each `recv` returns the latest price of an asset that changed
+    /// since it was last received, oldest change first. This is the usual
+    /// conflated market-data feed; use [`subscribe`](Self::subscribe) when
+    /// every tick matters.
+    pub fn subscribe_latest(&self) -> PriceSubscription {
+        self.subscribe_with(SubscriptionConfig {
+            name: "latest".to_string(),
+            capacity: usize::MAX,
+            conflate: true,
+            ..Default::default()
+        })
+    }
+
+    /// Streams the price updates of one asset
     ///
-    /// # Returns
-    /// The current price data or an error if not available or stale
+    /// Built on a [`subscribe_with`](Self::subscribe_with) queue of the
+    /// default capacity holding only `asset`, so `StreamExt` combinators
+    /// (throttling, windows, zipping two assets) apply directly. The stream
+    /// ends when the tracker shuts down.
     ///
     /// # Example
     /// ```no_run
     /// # use market_price_sdk::{MarketPriceTracker, Asset};
-    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::StreamExt;
+    ///
+    /// # async fn example() {
     /// let tracker = MarketPriceTracker::global().await;
-    /// let price = tracker.get_price(Asset::SOL).await?;
-    /// println!("SOL: ${:.2}", price.price_usd);
-    /// # Ok(())
+    /// let mut sol = tracker.price_stream(Asset::SOL).chunks(10);
+    /// while let Some(batch) = sol.next().await {
+    ///     let mean = batch.iter().map(|p| p.price_usd).sum::<f64>() / batch.len() as f64;
+    ///     println!("SOL mean of 10 ticks: ${:.2}", mean);
+    /// }
     /// # }
     /// ```
-    pub async fn get_price(&self, asset: Asset) -> Result<PriceData, PriceError> {
-        match self.store.get_price(asset).await {
-            Ok(price) => Ok(price),
-            Err(_) => {
-                // If not in store, try fetching directly from provider
-                // This is especially useful for streaming providers like Pyth gRPC
-                self.provider.fetch_price(asset).await.map_err(|e| {
-                    PriceError::not_available(&format!(
-                        "{} (Provider error: {})",
-                        asset.symbol(),
-                        e
-                    ))
-                })
-            }
-        }
+    pub fn price_stream(&self, asset: Asset) -> impl Stream<Item = PriceData> + Send + Unpin {
+        self.subscribe_with(SubscriptionConfig {
+            name: format!("stream.{}", asset.symbol()),
+            assets: Some(vec![asset]),
+            ..Default::default()
+        })
+        .into_stream()
     }
 
-    /// Gets prices for all tracked assets
+    /// Streams the price updates of every asset, merged in arrival order
+    pub fn price_stream_all(&self) -> impl Stream<Item = PriceData> + Send + Unpin {
+        self.subscribe_with(SubscriptionConfig {
+            name: "stream".to_string(),
+            ..Default::default()
+        })
+        .into_stream()
+    }
+
+    /// Subscribes to a snapshot + delta feed, buffering up to `capacity`
+    /// messages
     ///
-    /// # Returns
-    /// HashMap of all assets with their current prices (non-stale only)
+    /// The first message is a [`FeedMessage::Snapshot`] of every stored
+    /// price, followed by a [`FeedMessage::Delta`] per update not already in
+    /// it, so clients need no special cold-start handling. If the
+    /// subscriber falls behind, pending deltas are dropped and a fresh
+    /// snapshot is sent once there is room; clients simply replace their
+    /// state on every snapshot.
     ///
     /// # Example
     /// ```no_run
-    /// # use market_price_sdk::{MarketPriceTracker, Asset};
-    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use market_price_sdk::MarketPriceTracker;
+    /// use market_price_sdk::subscription::FeedMessage;
+    ///
+    /// # async fn example() {
     /// let tracker = MarketPriceTracker::global().await;
-    /// let prices = tracker.get_all_prices().await;
-    /// for (asset, price) in prices {
-    ///     println!("{}: ${:.2}", asset.symbol(), price.price_usd);
+    /// let mut feed = tracker.subscribe_feed(1024);
+    /// while let Some(message) = feed.recv().await {
+    ///     match message {
+    ///         FeedMessage::Snapshot(snapshot) => println!("{} prices", snapshot.prices.len()),
+    ///         FeedMessage::Delta(price) => println!("{} #{}", price.asset, price.sequence),
+    ///     }
     /// }
-    /// # Ok(())
     /// # }
     /// ```
-    pub async fn get_all_prices(&self) -> HashMap<Asset, PriceData> {
-        self.store.get_all_prices().await
-    }
-
-    /// Checks if price data is available for an asset
-    ///
-    /// # Arguments
-    /// * `asset` - The asset to check
-    ///
-    /// # Returns
-    /// True if price data exists (regardless of staleness)
-    pub async fn has_price(&self, asset: Asset) -> bool {
-        self.store.has_price(asset).await
-    }
+    pub fn subscribe_feed(&self, capacity: usize) -> FeedSubscription {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        // Subscribed before the first snapshot, so no update falls between
+        let mut updates = self.update_tx.subscribe();
+        let store = self.store.clone();
+        let event_tx = self.event_tx.clone();
+        let event_ids = self.config.event_ids.clone();
+        let runtime = self.runtime.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
 
-    /// Checks if price data is stale for an asset
-    ///
-    /// # Arguments
-    /// * `asset` - The asset to check
-    ///
-    /// # Returns
-    /// True if price data is stale or doesn't exist
-    pub async fn is_stale(&self, asset: Asset) -> bool {
-        self.store.is_stale(asset).await
-    }
+        self.runtime.spawn_named("tracker.feed", async move {
+            let mut resync = true;
+            let mut sequences: HashMap<Asset, u64> = HashMap::new();
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    permit = tx.reserve(), if resync => {
+                        let Ok(permit) = permit else { break };
+                        let snapshot = PriceSnapshot {
+                            taken_at: runtime.now(),
+                            prices: HashMap::clone(&store.get_all_prices_arc()),
+                        };
+                        sequences = snapshot
+                            .prices
+                            .iter()
+                            .map(|(asset, price)| (*asset, price.sequence))
+                            .collect();
+                        permit.send(FeedMessage::Snapshot(snapshot));
+                        resync = false;
+                    }
+                    update = updates.recv() => match update {
+                        Ok(price) => {
+                            let last = sequences.entry(price.asset).or_default();
+                            if resync || price.sequence <= *last {
+                                continue;
+                            }
+                            *last = price.sequence;
+                            match tx.try_send(FeedMessage::Delta(price)) {
+                                Ok(()) => {}
+                                Err(mpsc::error::TrySendError::Full(_)) => {
+                                    tracing::debug!("Feed subscriber fell behind, resyncing");
+                                    resync = true;
+                                }
+                                Err(mpsc::error::TrySendError::Closed(_)) => break,
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            report_lag(&runtime, &event_tx, event_ids.as_ref(), "feed", skipped);
+                            resync = true;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                }
+            }
+        });
 
-    /// Returns the name of the current provider
-    pub fn provider_name(&self) -> &str {
-        self.provider.provider_name()
+        FeedSubscription::new(rx)
     }
 
-    /// Forces an immediate price refresh
+    /// Calls `callback` with the fresh price each time `asset` recovers
+    /// from a stale or evicted price
     ///
-    /// This bypasses the normal polling interval and fetches fresh prices immediately.
+    /// Driven by [`MarketPriceEvent::PriceRecovered`]; the callback runs on
+    /// its own task until [`shutdown`](Self::shutdown).
     ///
-    /// # Returns
-    /// Ok if prices were successfully fetched and updated
-    pub async fn refresh_now(&self) -> Result<(), ProviderError> {
-        Self::fetch_and_update(&self.provider, &self.store, &self.metrics, &self.update_tx).await
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # async fn example() {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// tracker.on_recovered(Asset::SOL, |price| {
+    ///     println!("SOL feed back at ${:.2}, resuming trading", price.price_usd);
+    /// });
+    /// # }
+    /// ```
+    pub fn on_recovered<F>(&self, asset: Asset, callback: F)
+    where
+        F: Fn(PriceData) + Send + 'static,
+    {
+        let mut events = self.event_tx.subscribe();
+        let event_tx = self.event_tx.clone();
+        let event_ids = self.config.event_ids.clone();
+        let runtime = self.runtime.clone();
+        let store = self.store.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let name = format!("tracker.on_recovered.{}", asset.symbol());
+
+        self.runtime.spawn_named(name.clone(), async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    event = events.recv() => match event {
+                        Ok(MarketPriceEvent::PriceRecovered { asset: recovered, .. }) if recovered == asset => {
+                            if let Some(price) = store.try_get_price_sync(asset) {
+                                callback(price);
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            report_lag(&runtime, &event_tx, event_ids.as_ref(), &name, skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                }
+            }
+        });
     }
 
-    /// Gets provider metrics including latency percentiles and success rates
+    /// Calls `callback` each time `asset` moves at least `threshold_bps`
+    /// away from the price of the previous call
     ///
-    /// # Returns
-    /// ProviderMetrics with p50/p99 latencies and success rate
+    /// The first reference is the price stored when this is called, or the
+    /// first update if there is none yet. Moves are measured from the
+    /// price passed to the last call rather than tick to tick, so a slow
+    /// drift still fires once it adds up and a tick jittering around the
+    /// reference does not fire repeatedly. The callback receives the new
+    /// price and the signed move in basis points, and runs on its own task
+    /// until [`shutdown`](Self::shutdown).
     ///
     /// # Example
     /// ```no_run
-    /// # use market_price_sdk::MarketPriceTracker;
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
     /// # async fn example() {
     /// let tracker = MarketPriceTracker::global().await;
-    /// let metrics = tracker.get_provider_metrics().await;
-    /// println!("Provider {}: p50={}ms, p99={}ms, success_rate={:.1}%",
-    ///     metrics.provider_name,
-    ///     metrics.latency_p50_ms,
-    ///     metrics.latency_p99_ms,
-    ///     metrics.success_rate * 100.0
-    /// );
+    /// tracker.on_move(Asset::SOL, 50.0, |price, change_bps| {
+    ///     println!("SOL moved {:+.0} bps to ${:.2}", change_bps, price.price_usd);
+    /// });
     /// # }
     /// ```
-    pub async fn get_provider_metrics(&self) -> ProviderMetrics {
-        self.metrics.get_metrics().await
+    pub fn on_move<F>(&self, asset: Asset, threshold_bps: f64, callback: F)
+    where
+        F: Fn(PriceData, f64) + Send + 'static,
+    {
+        let mut updates = self.update_tx.subscribe();
+        let mut reference = self
+            .store
+            .try_get_price_sync(asset)
+            .map(|price| price.price_usd);
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        self.runtime
+            .spawn_named(format!("tracker.on_move.{}", asset.symbol()), async move {
+                loop {
+                    tokio::select! {
+                        _ = shutdown_rx.recv() => break,
+                        update = updates.recv() => match update {
+                            Ok(price) if price.asset == asset => {
+                                let Some(from) = reference else {
+                                    reference = Some(price.price_usd);
+                                    continue;
+                                };
+                                let change_bps = (price.price_usd / from - 1.0) * 10_000.0;
+                                if change_bps.abs() >= threshold_bps {
+                                    reference = Some(price.price_usd);
+                                    callback(price, change_bps);
+                                }
+                            }
+                            Ok(_) => {}
+                            // Moves are measured from the reference, so
+                            // missed ticks lose nothing
+                            Err(broadcast::error::RecvError::Lagged(_)) => {}
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        },
+                    }
+                }
+            });
     }
 
-    /// Perform a health check on the market price tracker
+    /// Publishes every tracker event to an external sink
+    ///
+    /// The sink runs on its own task until [`shutdown`](Self::shutdown).
+    /// Publish failures are logged and the event is dropped.
+    pub fn add_event_sink(&self, sink: Arc<dyn PriceEventSink>) {
+        let mut events = self.event_tx.subscribe();
+        let event_tx = self.event_tx.clone();
+        let event_ids = self.config.event_ids.clone();
+        let runtime = self.runtime.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        self.runtime.spawn_named(format!("tracker.event_sink.{}", sink.name()), async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    event = events.recv() => match event {
+                        Ok(event) => {
+                            if let Err(e) = sink.publish(&event).await {
+                                tracing::warn!(
+                                    sink = sink.name(),
+                                    event_type = event.event_type(),
+                                    error = %e,
+                                    "Failed to publish event"
+                                );
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!(sink = sink.name(), skipped = skipped, "Sink fell behind, dropped events");
+                            report_lag(&runtime, &event_tx, event_ids.as_ref(), sink.name(), skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                }
+            }
+        });
+    }
+
+    /// Writes every accepted price update to an external sink in batches
+    ///
+    /// See [`crate::sinks`] for the batching and backpressure rules. Pending
+    /// prices are flushed once more on [`shutdown`](Self::shutdown).
+    pub fn add_price_sink(&self, sink: Arc<dyn PriceSink>, config: BatchConfig) {
+        let mut updates = self.update_tx.subscribe();
+        let event_tx = self.event_tx.clone();
+        let event_ids = self.config.event_ids.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let runtime = self.runtime.clone();
+        let mut batcher = Batcher::new(config);
+
+        self.runtime.spawn_named(format!("tracker.price_sink.{}", sink.name()), async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => {
+                        batcher.flush(sink.as_ref()).await;
+                        break;
+                    }
+                    update = updates.recv() => match update {
+                        Ok(price) => {
+                            if batcher.push(price) {
+                                batcher.flush(sink.as_ref()).await;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            tracing::warn!(sink = sink.name(), skipped = skipped, "Sink fell behind, dropped price updates");
+                            report_lag(&runtime, &event_tx, event_ids.as_ref(), sink.name(), skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => {
+                            batcher.flush(sink.as_ref()).await;
+                            break;
+                        }
+                    },
+                    _ = runtime.sleep(batcher.until_flush()) => {
+                        batcher.flush(sink.as_ref()).await;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Writes the provider metrics to an external sink every `interval`
+    pub fn add_metrics_sink(&self, sink: Arc<dyn MetricsSink>, interval: Duration) {
+        let metrics = self.metrics.clone();
+        let active = self.active.clone();
+        let runtime = self.runtime.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        self.runtime.spawn_named(format!("tracker.metrics_sink.{}", sink.name()), async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    _ = runtime.sleep(interval) => {
+                        let provider = active.load().provider.clone();
+                        let mut sample = metrics.get_metrics().await;
+                        sample.provider_name = provider.provider_name().to_string();
+                        sample.connection = provider.connection_stats();
+                        sample.stream = provider.stream_metrics();
+                        sample.quota = provider.quota_usage();
+                        if let Err(e) = sink.write_metrics(&sample).await {
+                            tracing::warn!(sink = sink.name(), error = %e, "Failed to write provider metrics");
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Watches the latest price for a single asset
+    ///
+    /// Unlike [`subscribe`](Self::subscribe), the receiver is only notified
+    /// for updates to `asset`, and always holds the latest value rather than
+    /// a backlog. It holds `None` until the first price arrives.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # async fn example() {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let mut rx = tracker.watch(Asset::SOL);
+    /// while rx.changed().await.is_ok() {
+    ///     if let Some(price) = rx.borrow().as_ref() {
+    ///         println!("SOL: ${:.2}", price.price_usd);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn watch(&self, asset: Asset) -> watch::Receiver<Option<PriceData>> {
+        self.store.watch(asset)
+    }
+
+    /// Starts the background polling task
+    pub(crate) fn start_background_task(&self) {
+        let store = self.store.clone();
+        let active = self.active.clone();
+        let fetch_gate = self.fetch_gate.clone();
+        let metrics = self.metrics.clone();
+        let runtime = self.runtime.clone();
+        let request_timeout = self.config.request_timeout;
+        let update_tx = self.update_tx.clone();
+        let paused = self.paused.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        // Later polls keep the initial fetch's offset, so a spread-out fleet
+        // stays spread out
+        let start_delay =
+            self.config.poll_phase_offset + random_up_to(self.config.poll_start_jitter);
+        let refresh_interval = Duration::from_secs(REFRESH_INTERVAL_SECS);
+        let mut ticker = Ticker::starting_at(
+            self.runtime.clone(),
+            tokio::time::Instant::now() + start_delay + refresh_interval,
+            refresh_interval,
+            self.config.missed_poll_behavior,
+        );
+
+        self.started.store(true, Ordering::Relaxed);
+        self.start_provider(&self.provider());
+        if let Some(interval) = self.config.heartbeat_interval {
+            self.start_heartbeat(interval);
+        }
+        if let Some(interval) = self.config.staleness_check_interval {
+            self.start_staleness_check(interval);
+        }
+        if let Some(interval) = self.config.eviction_interval {
+            self.start_eviction(interval);
+        }
+
+        self.runtime.spawn_named("tracker.poll", async move {
+            tracing::info!(
+                refresh_interval_secs = REFRESH_INTERVAL_SECS,
+                "Starting market price tracker background task"
+            );
+
+            if !start_delay.is_zero() {
+                tracing::info!(
+                    delay_ms = start_delay.as_millis() as u64,
+                    "Delaying initial price fetch"
+                );
+                tokio::select! {
+                    _ = shutdown_rx.recv() => return,
+                    _ = runtime.sleep(start_delay) => {}
+                }
+            }
+
+            // Initial fetch, falling back to the retry loop if bootstrapping fails.
+            // Assets routed away from a streaming provider are still polled.
+            let in_flight = fetch_gate.read().await;
+            if paused.load(Ordering::Relaxed) {
+                tracing::warn!("Market price tracker started paused; skipping initial fetch");
+            } else if let Some(provider) = active.load().polled() {
+                if let Err(e) =
+                    Self::bootstrap(&provider, &store, &metrics, &runtime, &update_tx, request_timeout).await
+                {
+                    tracing::warn!(error = %e, "Bootstrap price fetch failed");
+                    if let Err(e) = Self::fetch_and_update(
+                        &provider,
+                        &store,
+                        &metrics,
+                        &runtime,
+                        &update_tx,
+                        request_timeout,
+                        None,
+                    )
+                    .await
+                    {
+                        tracing::warn!(error = %e, "Initial price fetch failed");
+                    }
+                }
+            }
+            drop(in_flight);
+
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => {
+                        tracing::info!("Market price tracker background task shutting down");
+                        break;
+                    }
+                    _ = ticker.tick() => {
+                        if paused.load(Ordering::Relaxed) {
+                            continue;
+                        }
+                        let _in_flight = fetch_gate.read().await;
+                        let Some(provider) = active.load().polled() else {
+                            continue;
+                        };
+                        if let Err(e) = Self::fetch_and_update(&provider, &store, &metrics, &runtime, &update_tx, request_timeout, None).await {
+                            tracing::warn!(error = %e, "Failed to fetch prices");
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Starts a provider's own streaming connection and event forwarding
+    fn start_provider(&self, provider: &Arc<dyn MarketPriceProvider>) {
+        if let Some(provider_events) = provider.subscribe_events() {
+            self.forward_events(provider_events);
+        }
+        if provider.is_streaming() {
+            tracing::info!(
+                provider = provider.provider_name(),
+                "Starting market price tracker in reactive streaming mode"
+            );
+            provider.start_streaming(self.store.clone(), self.update_tx.clone());
+        }
+    }
+
+    /// Replaces the provider, keeping the cached prices
+    ///
+    /// Every fetch started after the swap, by the polling loop or by a
+    /// caller, goes to `provider`; assets it does not support are routed to
+    /// the [fallback provider](Self::with_fallback_provider) as before.
+    /// Returns once fetches still running against the previous provider
+    /// have finished, so nothing reaches it afterwards and their prices are
+    /// already stored. Each of those fetches is cut off after
+    /// [`TrackerConfig::request_timeout`] per attempt, so a stuck provider
+    /// delays the swap rather than blocking it.
+    ///
+    /// A streaming `provider` starts streaming straight away if the
+    /// background tasks are running. Swapping away from a streaming
+    /// provider stops polling through it, but its connection stays open
+    /// until the provider is dropped.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::MarketPriceTracker;
+    /// # use market_price_sdk::providers::HyperliquidProvider;
+    /// # use std::sync::Arc;
+    /// # async fn example() {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// tracker.set_provider(Arc::new(HyperliquidProvider::default())).await;
+    /// # }
+    /// ```
+    pub async fn set_provider(&self, provider: Arc<dyn MarketPriceProvider>) {
+        let previous = self
+            .active
+            .rcu(|active| ActiveProvider::new(provider.clone(), active.fallback.clone()));
+        // Wait for fetches that picked up the previous provider
+        drop(self.fetch_gate.write().await);
+
+        if self.started.load(Ordering::Relaxed) {
+            self.start_provider(&provider);
+        }
+        tracing::info!(
+            previous = previous.provider.provider_name(),
+            provider = provider.provider_name(),
+            "Switched market price provider"
+        );
+    }
+
+    /// Forwards provider-raised events to the tracker's event subscribers
+    fn forward_events(&self, mut provider_events: broadcast::Receiver<MarketPriceEvent>) {
+        let event_tx = self.event_tx.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        self.runtime
+            .spawn_named("tracker.forward_events", async move {
+                loop {
+                    tokio::select! {
+                        _ = shutdown_rx.recv() => break,
+                        event = provider_events.recv() => match event {
+                            Ok(event) => {
+                                let _ = event_tx.send(event);
+                            }
+                            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                                tracing::warn!(skipped = skipped, "Dropped provider events");
+                            }
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        },
+                    }
+                }
+            });
+    }
+
+    /// Periodically emits a heartbeat event for every stored asset, so
+    /// consumers can tell a quiet market from a dead feed
+    fn start_heartbeat(&self, interval: Duration) {
+        let store = self.store.clone();
+        let runtime = self.runtime.clone();
+        let event_tx = self.event_tx.clone();
+        let event_ids = self.config.event_ids.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        self.runtime.spawn_named("tracker.heartbeat", async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    _ = runtime.sleep(interval) => {
+                        let prices = store.get_all_prices_detailed(&[]);
+                        for price in prices.fresh.values().chain(prices.stale.values()) {
+                            let _ = event_tx.send(MarketPriceEvent::Heartbeat {
+                                id: event_ids.next_id(),
+                                asset: price.asset,
+                                last_updated: price.last_updated,
+                                sequence: price.sequence,
+                                timestamp: runtime.now(),
+                            });
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Periodically checks stored prices for staleness, emitting a
+    /// `PriceWentStale` event the first time each one crosses its threshold
+    fn start_staleness_check(&self, interval: Duration) {
+        let store = self.store.clone();
+        let runtime = self.runtime.clone();
+        let event_tx = self.event_tx.clone();
+        let event_ids = self.config.event_ids.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        self.runtime.spawn_named("tracker.staleness", async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    _ = runtime.sleep(interval) => {
+                        for price in store.newly_stale() {
+                            let age = price.age_at(runtime.now());
+                            tracing::warn!(
+                                asset = price.asset.symbol(),
+                                age_secs = age.as_secs(),
+                                "Price went stale"
+                            );
+                            let _ = event_tx.send(MarketPriceEvent::PriceWentStale {
+                                id: event_ids.next_id(),
+                                asset: price.asset,
+                                age_ms: age.as_millis() as u64,
+                                last_updated: price.last_updated,
+                                sequence: price.sequence,
+                                timestamp: runtime.now(),
+                            });
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Periodically sweeps stale (and optionally disabled) assets out of the
+    /// store, emitting a `PriceExpired` event for each removed price
+    fn start_eviction(&self, interval: Duration) {
+        let store = self.store.clone();
+        let runtime = self.runtime.clone();
+        let event_tx = self.event_tx.clone();
+        let event_ids = self.config.event_ids.clone();
+        let evict_disabled_assets = self.config.evict_disabled_assets;
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+        self.runtime.spawn_named("tracker.eviction", async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => break,
+                    _ = runtime.sleep(interval) => {
+                        let mut expired = store.evict_stale();
+                        if evict_disabled_assets {
+                            expired.extend(store.retain_assets(ENABLED_ASSETS));
+                        }
+                        for price in expired {
+                            tracing::info!(
+                                asset = price.asset.symbol(),
+                                last_updated = %price.last_updated,
+                                "Evicted expired price"
+                            );
+                            let _ = event_tx.send(MarketPriceEvent::PriceExpired {
+                                id: event_ids.next_id(),
+                                asset: price.asset,
+                                last_price_usd: price.price_usd,
+                                last_updated: price.last_updated,
+                                sequence: price.sequence,
+                                timestamp: runtime.now(),
+                            });
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Seeds the store using the provider's bootstrap fetch
+    async fn bootstrap(
+        provider: &Arc<dyn MarketPriceProvider>,
+        store: &Arc<MarketPriceStore>,
+        metrics: &Arc<MetricsCollector>,
+        runtime: &Runtime,
+        update_tx: &broadcast::Sender<PriceData>,
+        request_timeout: Duration,
+    ) -> Result<(), ProviderError> {
+        let start = Instant::now();
+        let bootstrap =
+            http::with_deadline(start + request_timeout, provider.bootstrap(ENABLED_ASSETS));
+        let result = runtime
+            .timeout(request_timeout, bootstrap)
+            .await
+            .unwrap_or(Err(ProviderError::Timeout));
+        metrics
+            .record_request(start.elapsed(), result.is_ok())
+            .await;
+        metrics.record_fetch(provider.provider_name(), result.as_ref().err());
+        store.record_poll(ENABLED_ASSETS, result.as_ref().ok());
+
+        let accepted = store.update_prices(result?).await;
+        tracing::debug!(
+            count = accepted.len(),
+            provider = provider.provider_name(),
+            latency_ms = start.elapsed().as_millis() as u64,
+            "Bootstrapped prices"
+        );
+        for price in accepted.values() {
+            let _ = update_tx.send(price.clone());
+        }
+        Ok(())
+    }
+
+    /// Fetches prices from provider and updates the store with metrics tracking
+    ///
+    /// Assets succeed or fail on their own: the prices that arrive are
+    /// stored straight away, and only assets that failed with a retryable
+    /// error are retried. Each attempt is cut off after `request_timeout`,
+    /// failing its assets with [`ProviderError::Timeout`] even if the
+    /// provider is stuck outside an HTTP request, and no attempt or backoff
+    /// runs past `deadline`. Succeeds if any price was fetched.
+    async fn fetch_and_update(
+        provider: &Arc<dyn MarketPriceProvider>,
+        store: &Arc<MarketPriceStore>,
+        metrics: &Arc<MetricsCollector>,
+        runtime: &Runtime,
+        update_tx: &broadcast::Sender<PriceData>,
+        request_timeout: Duration,
+        deadline: Option<Instant>,
+    ) -> Result<(), ProviderError> {
+        let mut backoff_ms = INITIAL_BACKOFF_MS;
+        let start = Instant::now();
+        let mut pending = ENABLED_ASSETS.to_vec();
+        let mut fetched = HashMap::new();
+        let mut last_error = None;
+
+        for attempt in 1..=MAX_RETRY_ATTEMPTS {
+            let attempt_deadline = Instant::now() + request_timeout;
+            let attempt_deadline = deadline.map_or(attempt_deadline, |d| d.min(attempt_deadline));
+            let mut results = runtime
+                .timeout(
+                    attempt_deadline.saturating_duration_since(Instant::now()),
+                    provider.fetch_prices_partial(&pending, Some(attempt_deadline)),
+                )
+                .await
+                .unwrap_or_else(|| {
+                    pending
+                        .iter()
+                        .map(|asset| (*asset, Err(ProviderError::Timeout)))
+                        .collect()
+                });
+
+            let mut prices = HashMap::new();
+            let mut failed = Vec::new();
+            for asset in pending {
+                let result = results.remove(&asset).unwrap_or_else(|| {
+                    Err(ProviderError::MissingPrice(asset.symbol().to_string()))
+                });
+                match result {
+                    Ok(price) => {
+                        prices.insert(asset, price);
+                    }
+                    Err(e) => {
+                        if e.is_retryable() {
+                            failed.push(asset);
+                        }
+                        last_error = Some(e);
+                    }
+                }
+            }
+
+            if !prices.is_empty() {
+                tracing::debug!(
+                    count = prices.len(),
+                    provider = provider.provider_name(),
+                    latency_ms = start.elapsed().as_millis() as u64,
+                    "Successfully fetched prices"
+                );
+                let accepted = store.update_prices(prices.clone()).await;
+
+                // Broadcast updates for reactive consumers
+                for price in accepted.values() {
+                    let _ = update_tx.send(price.clone());
+                }
+                fetched.extend(prices);
+            }
+
+            pending = failed;
+            if pending.is_empty() {
+                break;
+            }
+
+            let backoff = Duration::from_millis(backoff_ms);
+            let out_of_time = deadline.is_some_and(|d| Instant::now() + backoff >= d);
+            if attempt == MAX_RETRY_ATTEMPTS || out_of_time {
+                break;
+            }
+            tracing::warn!(
+                attempt = attempt,
+                max_attempts = MAX_RETRY_ATTEMPTS,
+                failed = ?pending,
+                error = ?last_error.as_ref().map(ToString::to_string),
+                "Failed to fetch some prices, retrying them"
+            );
+            runtime.sleep(backoff).await;
+            backoff_ms = (backoff_ms * 2).min(MAX_BACKOFF_MS);
+        }
+
+        metrics
+            .record_request(start.elapsed(), !fetched.is_empty())
+            .await;
+        store.record_poll(ENABLED_ASSETS, Some(&fetched));
+
+        let result = match last_error {
+            Some(e) if fetched.is_empty() => Err(e),
+            _ => Ok(()),
+        };
+        metrics.record_fetch(provider.provider_name(), result.as_ref().err());
+        result
+    }
+
+    /// Gets the current price for an asset
+    ///
+    /// A missing or stale asset is fetched from the provider directly, cut
+    /// off after [`TrackerConfig::request_timeout`]. With
+    /// [`TrackerConfig::read_through`] set, that fetch is instead bounded by
+    /// its own timeout, shared with concurrent callers and stored, see
+    /// [`read_through`](crate::read_through).
+    ///
+    /// If that fails too and [`TrackerConfig::degraded`] is set, the last
+    /// known price is returned marked [`Quality::Degraded`](crate::types::Quality::Degraded),
+    /// see [`degraded`](crate::degraded).
+    ///
+    /// # Arguments
+    /// * `asset` - The asset to get the price for
+    ///
+    /// # Returns
+    /// The current price data or an error if not available or stale
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let price = tracker.get_price(Asset::SOL).await?;
+    /// println!("SOL: ${:.2}", price.price_usd);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_price(&self, asset: Asset) -> Result<PriceData, PriceError> {
+        let result = match (self.store.get_price(asset).await, &self.read_through) {
+            (Ok(price), _) => Ok(price),
+            (Err(e), _) if self.is_paused() => Err(e),
+            (Err(_), Some(read_through)) => {
+                let _in_flight = self.fetch_gate.read().await;
+                read_through
+                    .get(asset, self.fetch_and_store(self.routed_provider(), asset))
+                    .await
+            }
+            (Err(_), None) => {
+                // If not in store, try fetching directly from provider
+                // This is especially useful for streaming providers like Pyth gRPC
+                let _in_flight = self.fetch_gate.read().await;
+                let timeout = self.config.request_timeout;
+                let provider = self.routed_provider();
+                let fetch = provider.fetch_price(asset);
+                self.runtime
+                    .timeout(
+                        timeout,
+                        http::with_deadline(Instant::now() + timeout, fetch),
+                    )
+                    .await
+                    .unwrap_or(Err(ProviderError::Timeout))
+                    .map_err(|e| {
+                        PriceError::not_available(&format!(
+                            "{} (Provider error: {})",
+                            asset.symbol(),
+                            e
+                        ))
+                    })
+            }
+        };
+        result.or_else(|e| self.store.degraded_price(asset).ok_or(e))
+    }
+
+    /// Read-through fetch of `asset` from `provider` that stores the price
+    /// and broadcasts it
+    fn fetch_and_store(
+        &self,
+        provider: Arc<dyn MarketPriceProvider>,
+        asset: Asset,
+    ) -> impl FnOnce() -> BoxFuture<'static, Result<PriceData, ProviderError>> {
+        let store = self.store.clone();
+        let update_tx = self.update_tx.clone();
+        move || {
+            async move {
+                let price = provider.fetch_price(asset).await?;
+                let stored = store.store_price(asset, price).await.ok_or_else(|| {
+                    ProviderError::InvalidResponse("price rejected by the store".to_string())
+                })?;
+                let _ = update_tx.send(stored.clone());
+                Ok(stored)
+            }
+            .boxed()
+        }
+    }
+
+    /// Gets prices for a specific set of assets
+    ///
+    /// Each asset gets its own result, so callers can tell a stale asset
+    /// from one that was never fetched. Like [`get_price`](Self::get_price),
+    /// assets missing from the cache are fetched from the provider, in a
+    /// single batch request cut off after [`TrackerConfig::request_timeout`];
+    /// an asset the provider fails keeps the provider's error for it.
+    ///
+    /// # Arguments
+    /// * `assets` - The assets to get prices for
+    ///
+    /// # Returns
+    /// HashMap of each requested asset to its price or the reason it is unavailable
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # async fn example() {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// for (asset, result) in tracker.get_prices(&[Asset::SOL, Asset::ETH]).await {
+    ///     match result {
+    ///         Ok(price) => println!("{}: ${:.2}", asset.symbol(), price.price_usd),
+    ///         Err(e) => println!("{}: {}", asset.symbol(), e),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn get_prices(
+        &self,
+        assets: &[Asset],
+    ) -> HashMap<Asset, Result<PriceData, PriceError>> {
+        let mut result = HashMap::with_capacity(assets.len());
+        let mut missing = Vec::new();
+
+        for asset in assets {
+            let cached = self.store.get_price_sync(*asset);
+            if cached.is_err() {
+                missing.push(*asset);
+            }
+            result.insert(*asset, cached);
+        }
+
+        if missing.is_empty() || self.is_paused() {
+            return result;
+        }
+
+        // Each missing asset gets the provider's result for it
+        let _in_flight = self.fetch_gate.read().await;
+        let timeout = self.config.request_timeout;
+        let provider = self.routed_provider();
+        let deadline = Instant::now() + timeout;
+        let mut fetched = self
+            .runtime
+            .timeout(
+                timeout,
+                provider.fetch_prices_partial(&missing, Some(deadline)),
+            )
+            .await
+            .unwrap_or_else(|| provider::per_asset(&missing, Err(ProviderError::Timeout)));
+        for asset in missing {
+            let price = fetched
+                .remove(&asset)
+                .unwrap_or_else(|| Err(ProviderError::MissingPrice(asset.symbol().to_string())))
+                .map_err(|e| {
+                    tracing::debug!(
+                        provider = self.provider_name(),
+                        asset = asset.symbol(),
+                        error = %e,
+                        "Provider fallback failed for batch price request"
+                    );
+                    PriceError::not_available(&format!(
+                        "{} (Provider error: {})",
+                        asset.symbol(),
+                        e
+                    ))
+                });
+            result.insert(asset, price);
+        }
+
+        result
+    }
+
+    /// Gets the cached price for an asset without awaiting
+    ///
+    /// Intended for synchronous pricing code. Unlike [`get_price`](Self::get_price),
+    /// this never falls back to the provider: it only reads the lock-free cache.
+    ///
+    /// # Arguments
+    /// * `asset` - The asset to get the price for
+    ///
+    /// # Returns
+    /// The cached price data, or `None` if not available or stale
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # async fn example() {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// if let Some(price) = tracker.try_get_price_sync(Asset::SOL) {
+    ///     println!("SOL: ${:.2}", price.price_usd);
+    /// }
+    /// # }
+    /// ```
+    pub fn try_get_price_sync(&self, asset: Asset) -> Option<PriceData> {
+        self.store.try_get_price_sync(asset)
+    }
+
+    /// Gets the price the tracker held for an asset at a point in time
+    ///
+    /// Returns the nearest recorded price within 60 seconds of `timestamp`.
+    /// Use [`get_price_at_with`](Self::get_price_at_with) to interpolate or
+    /// change the maximum gap. History covers `TrackerConfig::history_retention`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let at = chrono::Utc::now() - chrono::Duration::minutes(5);
+    /// let price = tracker.get_price_at(Asset::SOL, at)?;
+    /// println!("SOL 5 minutes ago: ${:.2}", price.price_usd);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_price_at(
+        &self,
+        asset: Asset,
+        timestamp: DateTime<Utc>,
+    ) -> Result<PriceData, PriceError> {
+        self.get_price_at_with(asset, timestamp, &PriceAtOptions::default())
+    }
+
+    /// Gets the price for an asset at a point in time with custom lookup options
+    pub fn get_price_at_with(
+        &self,
+        asset: Asset,
+        timestamp: DateTime<Utc>,
+        options: &PriceAtOptions,
+    ) -> Result<PriceData, PriceError> {
+        self.store.get_price_at(asset, timestamp, options)
+    }
+
+    /// Gets return and volatility statistics over the trailing window
+    ///
+    /// Computed from the price history on each call.
+    ///
+    /// # Returns
+    /// The statistics, or `NotAvailable` if fewer than two prices were
+    /// recorded in the window
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let stats = tracker.get_stats(Asset::SOL, Duration::from_secs(900))?;
+    /// println!("15m vol (annualized): {:.1}%", stats.annualized_volatility * 100.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_stats(&self, asset: Asset, window: Duration) -> Result<PriceStats, PriceError> {
+        PriceStats::from_prices(asset, &self.get_history(asset, window))
+            .ok_or_else(|| PriceError::not_available(asset.symbol()))
+    }
+
+    /// Gets pairwise return correlations between enabled assets over the
+    /// trailing window
+    ///
+    /// The matrix is computed lazily from the price history and reused until
+    /// it is one resampling step (1/120 of the window) old.
+    pub fn get_correlations(&self, window: Duration) -> CorrelationMatrix {
+        let end = self.runtime.now();
+        let span = chrono::Duration::from_std(window).unwrap_or(chrono::Duration::MAX);
+        let start = end
+            .checked_sub_signed(span)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let step = span / CORRELATION_GRID_INTERVALS as i32;
+
+        let mut cached = self.correlations.lock().expect("correlation lock poisoned");
+        if let Some(matrix) = cached.as_ref() {
+            if matrix.end - matrix.start == end - start && end - matrix.end < step {
+                return matrix.clone();
+            }
+        }
+
+        let series: Vec<(Asset, Vec<PriceData>)> = ENABLED_ASSETS
+            .iter()
+            .map(|asset| (*asset, self.store.get_history(*asset, start, end)))
+            .collect();
+        let matrix = CorrelationMatrix::from_history(&series, start, end);
+        *cached = Some(matrix.clone());
+        matrix
+    }
+
+    /// Gets a smoothed estimate of an asset's price
+    ///
+    /// The first call for an asset/smoother pair seeds the estimate from
+    /// the price history; later ticks update it as they are stored. Fails
+    /// like [`get_price`](Self::get_price) when the latest raw price is
+    /// missing or stale.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{smoothing::Smoother, MarketPriceTracker, Asset};
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let smoother = Smoother::Ema { half_life: Duration::from_secs(60) };
+    /// let sol = tracker.get_smoothed_price(Asset::SOL, smoother)?;
+    /// println!("SOL: ${:.2} (raw ${:.2})", sol.price_usd, sol.raw_price_usd);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_smoothed_price(
+        &self,
+        asset: Asset,
+        smoother: Smoother,
+    ) -> Result<SmoothedPrice, PriceError> {
+        let latest = self.store.get_price_sync(asset)?;
+        self.smoothing
+            .get(asset, smoother, || {
+                let history = self.get_history(asset, self.config.history_retention);
+                if history.is_empty() {
+                    vec![latest]
+                } else {
+                    history
+                }
+            })
+            .ok_or_else(|| PriceError::not_available(asset.symbol()))
+    }
+
+    /// Gets the prices recorded for an asset over the trailing window, oldest first
+    pub fn get_history(&self, asset: Asset, window: Duration) -> Vec<PriceData> {
+        let to = self.runtime.now();
+        let from = chrono::Duration::from_std(window)
+            .ok()
+            .and_then(|window| to.checked_sub_signed(window))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        self.store.get_history(asset, from, to)
+    }
+
+    /// Gets prices for all tracked assets
+    ///
+    /// # Returns
+    /// HashMap of all assets with their current prices (non-stale only)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let prices = tracker.get_all_prices().await;
+    /// for (asset, price) in prices {
+    ///     println!("{}: ${:.2}", asset.symbol(), price.price_usd);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_all_prices(&self) -> HashMap<Asset, PriceData> {
+        self.store.get_all_prices().await
+    }
+
+    /// Gets the latest price of every asset without copying it
+    ///
+    /// Suited to consumers reading many assets at high frequency; see
+    /// [`MarketPriceStore::get_all_prices_arc`] for how it differs from
+    /// [`get_all_prices`](Self::get_all_prices).
+    pub fn get_all_prices_arc(&self) -> Arc<HashMap<Asset, PriceData>> {
+        self.store.get_all_prices_arc()
+    }
+
+    /// Registers a synthetic index, replacing any index with the same name
+    ///
+    /// The index is only valued on demand by
+    /// [`get_index_price`](Self::get_index_price) and
+    /// [`get_index_prices`](Self::get_index_prices); it is not stored, so
+    /// price reads, subscriptions, watches and snapshots never include it.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{index::IndexDefinition, MarketPriceTracker};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// tracker.add_index(IndexDefinition::stablecoin_composite());
+    /// let stable = tracker.get_index_price("STABLE")?;
+    /// println!("STABLE: ${:.4}", stable.price_usd);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_index(&self, index: IndexDefinition) {
+        self.indices
+            .lock()
+            .expect("indices lock poisoned")
+            .insert(index.name.clone(), index);
+    }
+
+    /// Removes an index, returning false if none was registered under `name`
+    pub fn remove_index(&self, name: &str) -> bool {
+        self.indices
+            .lock()
+            .expect("indices lock poisoned")
+            .remove(name)
+            .is_some()
+    }
+
+    /// Gets the current price of a registered index, computed from the
+    /// cached constituent prices without fetching
+    ///
+    /// Fails like [`get_price`](Self::get_price) would for the first
+    /// constituent that is missing or stale, or with
+    /// [`PriceError::NotAvailable`] if no index is registered under `name`.
+    pub fn get_index_price(&self, name: &str) -> Result<IndexPrice, PriceError> {
+        let index = self
+            .indices
+            .lock()
+            .expect("indices lock poisoned")
+            .get(name)
+            .cloned()
+            .ok_or_else(|| PriceError::not_available(name))?;
+        index.compute(|asset| self.store.get_price_sync(asset))
+    }
+
+    /// Gets the price of every registered index whose constituents are fresh
+    pub fn get_index_prices(&self) -> HashMap<String, IndexPrice> {
+        let indices: Vec<IndexDefinition> = self
+            .indices
+            .lock()
+            .expect("indices lock poisoned")
+            .values()
+            .cloned()
+            .collect();
+        indices
+            .iter()
+            .filter_map(|index| index.compute(|asset| self.store.get_price_sync(asset)).ok())
+            .map(|price| (price.name.clone(), price))
+            .collect()
+    }
+
+    /// Captures the latest price of every asset, e.g. for persistence
+    ///
+    /// Wrap it in [`Versioned`](crate::schema::Versioned) before writing it
+    /// anywhere other processes read from.
+    pub fn snapshot(&self) -> PriceSnapshot {
+        PriceSnapshot {
+            taken_at: self.runtime.now(),
+            prices: HashMap::clone(&self.store.get_all_prices_arc()),
+        }
+    }
+
+    /// Gets all tracked prices along with the reason any are unusable
+    ///
+    /// Unlike [`get_all_prices`](Self::get_all_prices), stale entries are
+    /// reported instead of dropped, and enabled assets that have never
+    /// received a price are listed as missing.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::MarketPriceTracker;
+    /// # async fn example() {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let prices = tracker.get_all_prices_detailed().await;
+    /// for (asset, price) in &prices.stale {
+    ///     println!("{} is stale (age: {:?})", asset.symbol(), price.age());
+    /// }
+    /// for asset in &prices.missing {
+    ///     println!("{} has no price yet", asset.symbol());
+    /// }
+    /// # }
+    /// ```
+    pub async fn get_all_prices_detailed(&self) -> DetailedPrices {
+        self.store.get_all_prices_detailed(ENABLED_ASSETS)
+    }
+
+    /// Checks if price data is available for an asset
+    ///
+    /// # Arguments
+    /// * `asset` - The asset to check
+    ///
+    /// # Returns
+    /// True if price data exists (regardless of staleness)
+    pub async fn has_price(&self, asset: Asset) -> bool {
+        self.store.has_price(asset).await
+    }
+
+    /// Checks if price data is stale for an asset
+    ///
+    /// # Arguments
+    /// * `asset` - The asset to check
+    ///
+    /// # Returns
+    /// True if price data is stale or doesn't exist
+    pub async fn is_stale(&self, asset: Asset) -> bool {
+        self.store.is_stale(asset).await
+    }
+
+    /// Returns the name of the current provider
+    pub fn provider_name(&self) -> &str {
+        self.active.load().provider.provider_name()
+    }
+
+    /// Forces an immediate price refresh
+    ///
+    /// This bypasses the normal polling interval and fetches fresh prices immediately.
+    ///
+    /// # Returns
+    /// Ok if prices were successfully fetched and updated
+    pub async fn refresh_now(&self) -> Result<(), ProviderError> {
+        if self.is_paused() {
+            return Err(ProviderError::Paused);
+        }
+        let _in_flight = self.fetch_gate.read().await;
+        Self::fetch_and_update(
+            &self.routed_provider(),
+            &self.store,
+            &self.metrics,
+            &self.runtime,
+            &self.update_tx,
+            self.config.request_timeout,
+            None,
+        )
+        .await
+    }
+
+    /// Forces an immediate price refresh that completes within `budget`
+    ///
+    /// The refresh goes to the provider, or failing that the
+    /// [fallback provider](Self::with_fallback_provider), whose recent p95
+    /// latency fits the budget; if neither does, nothing is fetched and the
+    /// cached prices stay as they are. Retries stop, and in-flight HTTP
+    /// requests are cut off, once the budget is spent.
+    ///
+    /// # Returns
+    /// Ok if prices were fetched and updated in time, otherwise the last
+    /// provider error (`ProviderError::Timeout` if the budget ran out
+    /// mid-request, `ProviderError::OverBudget` if no provider was fast enough)
+    pub async fn refresh_now_within(&self, budget: Duration) -> Result<(), ProviderError> {
+        if self.is_paused() {
+            return Err(ProviderError::Paused);
+        }
+        let _in_flight = self.fetch_gate.read().await;
+        let (provider, metrics) = self
+            .provider_within(budget)
+            .await
+            .ok_or(ProviderError::OverBudget(budget))?;
+        Self::fetch_and_update(
+            &provider,
+            &self.store,
+            metrics,
+            &self.runtime,
+            &self.update_tx,
+            self.config.request_timeout,
+            Some(Instant::now() + budget),
+        )
+        .await
+    }
+
+    /// Gets the current price for an asset, fetching it within `budget` if
+    /// the cached price is missing or stale
+    ///
+    /// Like [`get_price`](Self::get_price), except that the fetch goes to
+    /// the provider, or failing that the [fallback provider](Self::with_fallback_provider),
+    /// whose recent p95 latency fits the budget, and is cut off once the
+    /// budget is spent. If no provider is fast enough, the cache answers
+    /// alone: a stale price fails, or is served degraded if
+    /// [`TrackerConfig::degraded`] is set.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # use std::time::Duration;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let price = tracker
+    ///     .get_price_within(Asset::SOL, Duration::from_millis(250))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_price_within(
+        &self,
+        asset: Asset,
+        budget: Duration,
+    ) -> Result<PriceData, PriceError> {
+        let cached = self.store.get_price(asset).await;
+        if cached.is_ok() || self.is_paused() {
+            return cached.or_else(|e| self.store.degraded_price(asset).ok_or(e));
+        }
+
+        let _in_flight = self.fetch_gate.read().await;
+        let result = match self.provider_within(budget).await {
+            None => cached,
+            Some((provider, _)) => {
+                let fetch = async {
+                    match &self.read_through {
+                        Some(read_through) => {
+                            read_through
+                                .get(asset, self.fetch_and_store(provider, asset))
+                                .await
+                        }
+                        None => provider.fetch_price(asset).await.map_err(|e| {
+                            PriceError::not_available(&format!(
+                                "{} (Provider error: {})",
+                                asset.symbol(),
+                                e
+                            ))
+                        }),
+                    }
+                };
+                self.runtime
+                    .timeout(budget, http::with_deadline(Instant::now() + budget, fetch))
+                    .await
+                    .unwrap_or_else(|| {
+                        Err(PriceError::not_available(&format!(
+                            "{} (latency budget of {:?} spent)",
+                            asset.symbol(),
+                            budget
+                        )))
+                    })
+            }
+        };
+        result.or_else(|e| self.store.degraded_price(asset).ok_or(e))
+    }
+
+    /// The provider, or failing that the fallback, whose p95 latency over
+    /// the last [`LATENCY_BUDGET_WINDOW_SECS`] fits `budget`, with the
+    /// collector its fetches are recorded in
+    ///
+    /// A provider with no latency recorded yet fits any budget.
+    async fn provider_within(
+        &self,
+        budget: Duration,
+    ) -> Option<(Arc<dyn MarketPriceProvider>, &Arc<MetricsCollector>)> {
+        let active = self.active.load_full();
+        let candidates = std::iter::once((active.routed(), &self.metrics)).chain(
+            active
+                .fallback
+                .clone()
+                .map(|fallback| (fallback, &self.fallback_metrics)),
+        );
+        let window = Duration::from_secs(LATENCY_BUDGET_WINDOW_SECS);
+        for (provider, metrics) in candidates {
+            let p95_ms = metrics.latency_percentile(95.0, window).await;
+            if p95_ms <= budget.as_secs_f64() * 1000.0 {
+                return Some((provider, metrics));
+            }
+            tracing::debug!(
+                provider = provider.provider_name(),
+                p95_ms = p95_ms,
+                budget_ms = budget.as_millis() as u64,
+                "Provider too slow for the latency budget"
+            );
+        }
+        None
+    }
+
+    /// Gets provider metrics including latency percentiles and success rates
+    ///
+    /// # Returns
+    /// ProviderMetrics with p50/p99 latencies and success rate
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::MarketPriceTracker;
+    /// # async fn example() {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let metrics = tracker.get_provider_metrics().await;
+    /// println!("Provider {}: p50={}ms, p99={}ms, success_rate={:.1}%",
+    ///     metrics.provider_name,
+    ///     metrics.latency_p50_ms,
+    ///     metrics.latency_p99_ms,
+    ///     metrics.success_rate * 100.0
+    /// );
+    /// # }
+    /// ```
+    pub async fn get_provider_metrics(&self) -> ProviderMetrics {
+        let provider = self.provider();
+        let mut metrics = self.metrics.get_metrics().await;
+        metrics.provider_name = provider.provider_name().to_string();
+        metrics.connection = provider.connection_stats();
+        metrics.stream = provider.stream_metrics();
+        metrics.quota = provider.quota_usage();
+        metrics
+    }
+
+    /// Gets provider metrics covering only the last `window`
+    ///
+    /// Useful for alerting on recent behavior, e.g. `errors_per_min` over
+    /// the last five minutes. See [`MetricsCollector::get_metrics_for`].
+    pub async fn get_provider_metrics_for(&self, window: Duration) -> ProviderMetrics {
+        let provider = self.provider();
+        let mut metrics = self.metrics.get_metrics_for(window).await;
+        metrics.provider_name = provider.provider_name().to_string();
+        metrics.connection = provider.connection_stats();
+        metrics.stream = provider.stream_metrics();
+        metrics.quota = provider.quota_usage();
+        metrics
+    }
+
+    /// Lists the SDK's background tasks with their state and busy time
+    ///
+    /// Covers every task spawned by the SDK in this process, not only this
+    /// tracker's. See [`crate::tasks`].
+    pub fn tasks(&self) -> Vec<TaskInfo> {
+        tasks::list()
+    }
+
+    /// Gets update counters for a single asset
+    ///
+    /// Returns `None` if the asset was never received or polled.
+    pub fn get_asset_metrics(&self, asset: Asset) -> Option<AssetMetrics> {
+        self.store.asset_metrics(asset)
+    }
+
+    /// Gets update counters for every asset received or polled so far
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::MarketPriceTracker;
+    /// # async fn example() {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// for (asset, metrics) in tracker.get_all_asset_metrics() {
+    ///     if metrics.consecutive_misses >= 3 {
+    ///         println!("{} missing from the last {} polls", asset.symbol(), metrics.consecutive_misses);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn get_all_asset_metrics(&self) -> HashMap<Asset, AssetMetrics> {
+        self.store.all_asset_metrics()
+    }
+
+    /// Clears the provider's latency samples and request counters
+    pub async fn reset_provider_metrics(&self) {
+        self.metrics.reset().await;
+    }
+
+    /// Sends one request to each configured provider and validates the
+    /// answers, without storing anything
+    ///
+    /// The primary provider and the fallback, if any, are each asked for
+    /// the enabled assets they support, within `request_timeout`. Checks
+    /// run concurrently.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::MarketPriceTracker;
+    /// # async fn example() {
+    /// let tracker = MarketPriceTracker::global().await;
+    /// let report = tracker.self_test().await;
+    /// for check in &report.providers {
+    ///     println!("{}: {} ms, {} errors", check.provider, check.latency_ms, check.errors.len());
+    /// }
+    /// assert!(report.passed());
+    /// # }
+    /// ```
+    pub async fn self_test(&self) -> SelfTestReport {
+        let started_at = self.runtime.now();
+        let timeout = self.config.request_timeout;
+        let active = self.active.load_full();
+        let providers = std::iter::once(&active.provider).chain(&active.fallback);
+        let providers =
+            futures::future::join_all(providers.map(|provider| {
+                self_test::check_provider(provider.as_ref(), ENABLED_ASSETS, timeout)
+            }))
+            .await;
+
+        SelfTestReport {
+            started_at,
+            providers,
+        }
+    }
+
+    /// Perform a health check on the market price tracker
+    ///
+    /// The details include when a poll last fetched any price, the last
+    /// poll error and how many polls failed in a row, plus the same for
+    /// each provider under `providers` when a failover chain is active.
     ///
     /// # Returns
     /// ComponentHealth indicating the status of the tracker and its components
@@ -383,6 +2035,39 @@ impl MarketPriceTracker {
         }
         details.insert("stale_prices".to_string(), serde_json::json!(stale_assets));
 
+        // Outcome of the most recent polls
+        let fetch_health = self.metrics.fetch_health();
+        details.insert(
+            "last_successful_fetch".to_string(),
+            serde_json::json!(fetch_health.last_successful_fetch),
+        );
+        details.insert(
+            "last_error".to_string(),
+            serde_json::json!(fetch_health.last_error),
+        );
+        details.insert(
+            "consecutive_failures".to_string(),
+            serde_json::json!(fetch_health.consecutive_failures),
+        );
+
+        // Each provider behind a failover chain
+        if let Some(providers) = self.provider().failover_health() {
+            let providers: Vec<_> = providers
+                .into_iter()
+                .map(|health| {
+                    serde_json::json!({
+                        "provider_name": health.provider_name,
+                        "status": health.status,
+                        "last_successful_fetch": health.last_successful_fetch,
+                        "last_error": health.last_error,
+                        "last_error_at": health.last_error_at,
+                        "consecutive_failures": health.consecutive_failures,
+                    })
+                })
+                .collect();
+            details.insert("providers".to_string(), serde_json::json!(providers));
+        }
+
         // Determine overall health
         let status = if available_prices.is_empty() {
             HealthStatus::Unhealthy
@@ -410,12 +2095,827 @@ impl MarketPriceTracker {
             status,
             message: Some(message),
             details,
-            last_checked: chrono::Utc::now(),
+            last_checked: self.runtime.now(),
+        }
+    }
+
+    /// Returns the runtime background tasks are spawned on
+    pub(crate) fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    /// Waits until the store holds at least one price
+    ///
+    /// Resolves immediately if a price is already cached, otherwise on the
+    /// first stored update, whether it came from polling, streaming or an
+    /// on-demand fetch. Never resolves while every fetch fails.
+    pub async fn ready(&self) {
+        let mut updates = self.update_tx.subscribe();
+        if !self.store.get_all_prices_arc().is_empty() {
+            return;
         }
+        // The tracker holds the sender, so the channel never closes under us
+        let _ = updates.recv().await;
+    }
+
+    /// Returns a read-only handle to the price cache
+    ///
+    /// The handle is cheap to clone and can be passed to subsystems that
+    /// only read prices, without giving them refresh or shutdown powers.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use market_price_sdk::{MarketPriceTracker, Asset};
+    /// # async fn example() {
+    /// let prices = MarketPriceTracker::global().await.store_handle();
+    /// std::thread::spawn(move || {
+    ///     if let Some(sol) = prices.try_get_price(Asset::SOL) {
+    ///         println!("SOL: ${:.2}", sol.price_usd);
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub fn store_handle(&self) -> PriceStoreReader {
+        PriceStoreReader::new(self.store.clone())
+    }
+
+    /// Returns the price store
+    #[cfg(feature = "ffi")]
+    pub(crate) fn store(&self) -> &Arc<MarketPriceStore> {
+        &self.store
     }
 
     /// Shutdown the market price tracker
     pub fn shutdown(&self) {
         let _ = self.shutdown_tx.send(());
     }
+
+    /// Stops all outbound provider requests, keeping the cached prices
+    ///
+    /// Takes effect immediately: the polling loop skips its fetches,
+    /// [`get_price`](Self::get_price) and [`get_prices`](Self::get_prices)
+    /// answer from the cache only, and [`refresh_now`](Self::refresh_now)
+    /// fails with [`ProviderError::Paused`]. A request already in flight
+    /// runs to completion. Streaming connections opened by the provider
+    /// itself stay open.
+    ///
+    /// Cached prices age as usual and go stale, so pair a long pause with
+    /// [`TrackerConfig::degraded`] if readers should keep getting prices.
+    pub fn pause(&self) {
+        if !self.paused.swap(true, Ordering::Relaxed) {
+            tracing::warn!(
+                provider = self.provider_name(),
+                "Market price tracker paused; provider requests stopped"
+            );
+        }
+    }
+
+    /// Resumes provider requests after [`pause`](Self::pause)
+    ///
+    /// Polling picks up again at the next scheduled refresh; call
+    /// [`refresh_now`](Self::refresh_now) to fetch straight away.
+    pub fn resume(&self) {
+        if self.paused.swap(false, Ordering::Relaxed) {
+            tracing::info!(
+                provider = self.provider_name(),
+                "Market price tracker resumed"
+            );
+        }
+    }
+
+    /// Returns true while provider requests are paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+}
+
+/// The provider in use and its fallback, with the routing of enabled
+/// assets between them settled on first use
+struct ActiveProvider {
+    provider: Arc<dyn MarketPriceProvider>,
+    fallback: Option<Arc<dyn MarketPriceProvider>>,
+    /// `None` once settled if the provider supports every enabled asset
+    routing: OnceLock<Option<Arc<RoutedProvider>>>,
+}
+
+impl ActiveProvider {
+    fn new(
+        provider: Arc<dyn MarketPriceProvider>,
+        fallback: Option<Arc<dyn MarketPriceProvider>>,
+    ) -> Self {
+        Self {
+            provider,
+            fallback,
+            routing: OnceLock::new(),
+        }
+    }
+
+    /// Routes enabled assets the provider does not support to the fallback
+    /// provider, logging the assets no provider can serve
+    fn routing(&self) -> Option<&Arc<RoutedProvider>> {
+        self.routing
+            .get_or_init(|| {
+                let capabilities = self.provider.capabilities();
+                let (supported, unsupported): (Vec<Asset>, Vec<Asset>) = ENABLED_ASSETS
+                    .iter()
+                    .partition(|asset| capabilities.supports(**asset));
+                if unsupported.is_empty() {
+                    return None;
+                }
+
+                let mut routed =
+                    RoutedProvider::new().with_route(self.provider.clone(), &supported);
+                let mut unroutable = unsupported.clone();
+                if let Some(fallback) = &self.fallback {
+                    let fallback_capabilities = fallback.capabilities();
+                    let (fallback_assets, rest): (Vec<Asset>, Vec<Asset>) = unsupported
+                        .iter()
+                        .partition(|asset| fallback_capabilities.supports(**asset));
+                    if !fallback_assets.is_empty() {
+                        tracing::warn!(
+                            provider = self.provider.provider_name(),
+                            fallback = fallback.provider_name(),
+                            assets = ?fallback_assets,
+                            "Provider does not support some enabled assets, routing them to the fallback provider"
+                        );
+                        routed = routed.with_route(fallback.clone(), &fallback_assets);
+                    }
+                    unroutable = rest;
+                }
+                if !unroutable.is_empty() {
+                    tracing::error!(
+                        provider = self.provider.provider_name(),
+                        assets = ?unroutable,
+                        "No configured provider supports these enabled assets; they will not be fetched"
+                    );
+                }
+                Some(Arc::new(routed))
+            })
+            .as_ref()
+    }
+
+    /// Provider serving the enabled assets, after routing
+    fn routed(&self) -> Arc<dyn MarketPriceProvider> {
+        match self.routing() {
+            Some(routed) => routed.clone(),
+            None => self.provider.clone(),
+        }
+    }
+
+    /// Provider the polling loop fetches from: everything for a polled
+    /// provider, only the routes a streaming provider hands off, or
+    /// nothing if every route streams
+    fn polled(&self) -> Option<Arc<dyn MarketPriceProvider>> {
+        if !self.provider.is_streaming() {
+            return Some(self.routed());
+        }
+        self.routing()
+            .and_then(|routed| routed.polled_routes())
+            .map(|polled| Arc::new(polled) as Arc<dyn MarketPriceProvider>)
+    }
+}
+
+/// Uniformly random duration in `[0, max)`, or zero if `max` is zero
+fn random_up_to(max: Duration) -> Duration {
+    use std::hash::{BuildHasher, Hasher};
+
+    if max.is_zero() {
+        return Duration::ZERO;
+    }
+    // Each RandomState is freshly seeded from the OS, which is plenty for
+    // spreading start times
+    let random = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    Duration::from_nanos((random as u128 % max.as_nanos()) as u64)
+}
+
+/// Reports updates dropped for a subscriber or sink on the event channel
+fn report_lag(
+    runtime: &Runtime,
+    event_tx: &broadcast::Sender<MarketPriceEvent>,
+    event_ids: &dyn EventIdGenerator,
+    subscriber: &str,
+    missed: u64,
+) {
+    let _ = event_tx.send(MarketPriceEvent::SubscriberLagged {
+        id: event_ids.next_id(),
+        subscriber: subscriber.to_string(),
+        missed,
+        timestamp: runtime.now(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::mock::MockProvider;
+
+    #[tokio::test]
+    async fn test_get_prices_per_asset_results() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        provider.set_price(Asset::BTC, 60000.0);
+        let tracker = MarketPriceTracker::with_provider(provider.clone());
+        tracker.refresh_now().await.unwrap();
+
+        provider.set_price(Asset::ETH, 3000.0);
+        provider.set_error(Asset::USDC, ProviderError::Timeout);
+        let prices = tracker
+            .get_prices(&[Asset::SOL, Asset::ETH, Asset::USDC])
+            .await;
+
+        assert_eq!(prices.len(), 3);
+        assert_eq!(prices[&Asset::SOL].as_ref().unwrap().price_usd, 150.0);
+        assert_eq!(prices[&Asset::ETH].as_ref().unwrap().price_usd, 3000.0);
+        assert!(matches!(
+            &prices[&Asset::USDC],
+            Err(PriceError::NotAvailable { asset }) if asset.contains("Request timeout")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_per_asset_metrics() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_provider(provider.clone());
+        tracker.refresh_now().await.unwrap();
+        tracker.refresh_now().await.unwrap();
+
+        let sol = tracker.get_asset_metrics(Asset::SOL).unwrap();
+        assert_eq!(sol.updates_received, 2);
+        assert_eq!(sol.consecutive_misses, 0);
+        assert!(sol.last_update_age.unwrap() < Duration::from_secs(1));
+
+        let btc = tracker.get_asset_metrics(Asset::BTC).unwrap();
+        assert_eq!(btc.updates_accepted, 0);
+        assert_eq!(btc.consecutive_misses, 2);
+        assert!(btc.last_update_at.is_none());
+
+        provider.set_price(Asset::BTC, 60000.0);
+        tracker.refresh_now().await.unwrap();
+        let btc = tracker.get_asset_metrics(Asset::BTC).unwrap();
+        assert_eq!(btc.consecutive_misses, 0);
+        assert_eq!(btc.total_misses, 2);
+    }
+
+    #[tokio::test]
+    async fn test_bounded_subscription_reports_lag() {
+        let provider = Arc::new(MockProvider::new());
+        let tracker = MarketPriceTracker::with_provider(provider.clone());
+        let mut events = tracker.subscribe_events();
+        let mut prices = tracker.subscribe_with(SubscriptionConfig {
+            name: "slow".to_string(),
+            capacity: 1,
+            ..Default::default()
+        });
+
+        provider.set_price(Asset::SOL, 150.0);
+        tracker.refresh_now().await.unwrap();
+        provider.set_price(Asset::SOL, 151.0);
+        tracker.refresh_now().await.unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            event,
+            MarketPriceEvent::SubscriberLagged { ref subscriber, missed: 1, .. } if subscriber == "slow"
+        ));
+        assert_eq!(prices.recv().await.unwrap().price_usd, 151.0);
+        assert_eq!(prices.missed(), 1);
+        tracker.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_feed_starts_with_snapshot() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_provider(provider.clone());
+        tracker.refresh_now().await.unwrap();
+
+        let mut feed = tracker.subscribe_feed(16);
+        match feed.recv().await.unwrap() {
+            FeedMessage::Snapshot(snapshot) => {
+                assert_eq!(snapshot.prices[&Asset::SOL].sequence, 1);
+            }
+            other => panic!("unexpected message: {other:?}"),
+        }
+
+        provider.set_price(Asset::SOL, 151.0);
+        tracker.refresh_now().await.unwrap();
+        match tokio::time::timeout(Duration::from_secs(1), feed.recv())
+            .await
+            .unwrap()
+            .unwrap()
+        {
+            FeedMessage::Delta(price) => {
+                assert_eq!(price.price_usd, 151.0);
+                assert_eq!(price.sequence, 2);
+            }
+            other => panic!("unexpected message: {other:?}"),
+        }
+        tracker.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_routes_unsupported_assets_to_fallback() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::BTC, 60_000.0);
+        provider.set_supported(&[Asset::BTC]);
+        let fallback = Arc::new(MockProvider::new());
+        fallback.set_price(Asset::SOL, 150.0);
+        fallback.set_price(Asset::BTC, 1.0);
+
+        let tracker =
+            MarketPriceTracker::with_provider(provider).with_fallback_provider(fallback.clone());
+        tracker.refresh_now().await.unwrap();
+
+        assert_eq!(
+            tracker.get_price(Asset::SOL).await.unwrap().price_usd,
+            150.0
+        );
+        assert_eq!(
+            tracker.get_price(Asset::BTC).await.unwrap().price_usd,
+            60_000.0
+        );
+        assert_eq!(tracker.provider_name(), "mock");
+    }
+
+    /// Fails BTC with a timeout on the first call only
+    struct FlakyBtcProvider {
+        calls: Mutex<Vec<Vec<Asset>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl MarketPriceProvider for FlakyBtcProvider {
+        async fn fetch_price(&self, asset: Asset) -> Result<PriceData, ProviderError> {
+            Ok(PriceData::new(asset, 100.0, "flaky".to_string()))
+        }
+
+        async fn fetch_prices(
+            &self,
+            _assets: &[Asset],
+        ) -> Result<HashMap<Asset, PriceData>, ProviderError> {
+            unreachable!("the tracker fetches partial results")
+        }
+
+        async fn fetch_prices_partial(
+            &self,
+            assets: &[Asset],
+            _deadline: Option<Instant>,
+        ) -> HashMap<Asset, Result<PriceData, ProviderError>> {
+            let first = {
+                let mut calls = self.calls.lock().unwrap();
+                calls.push(assets.to_vec());
+                calls.len() == 1
+            };
+            assets
+                .iter()
+                .map(|asset| {
+                    let result = if *asset == Asset::BTC && first {
+                        Err(ProviderError::Timeout)
+                    } else {
+                        Ok(PriceData::new(*asset, 100.0, "flaky".to_string()))
+                    };
+                    (*asset, result)
+                })
+                .collect()
+        }
+
+        fn provider_name(&self) -> &'static str {
+            "flaky"
+        }
+    }
+
+    /// Completes sleeps immediately, so retries run without backoff
+    struct NoDelay;
+
+    impl crate::runtime::Timer for NoDelay {
+        fn sleep(&self, _duration: Duration) -> futures::future::BoxFuture<'static, ()> {
+            Box::pin(async {})
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_only_failed_assets() {
+        let provider = Arc::new(FlakyBtcProvider {
+            calls: Mutex::new(Vec::new()),
+        });
+        let tracker = MarketPriceTracker::with_config(
+            provider.clone(),
+            TrackerConfig {
+                runtime: Runtime::new(Arc::new(crate::runtime::TokioExecutor), Arc::new(NoDelay)),
+                ..Default::default()
+            },
+        );
+
+        tracker.refresh_now().await.unwrap();
+
+        assert!(tracker.has_price(Asset::SOL).await);
+        assert!(tracker.has_price(Asset::BTC).await);
+        let calls = provider.calls.lock().unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[1], vec![Asset::BTC]);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_within_spent_budget() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_provider(provider.clone());
+
+        assert!(matches!(
+            tracker.refresh_now_within(Duration::ZERO).await,
+            Err(ProviderError::Timeout)
+        ));
+        assert_eq!(provider.call_count(), 0);
+        tracker
+            .refresh_now_within(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(tracker.has_price(Asset::SOL).await);
+    }
+
+    #[tokio::test]
+    async fn test_latency_budget_picks_provider_that_fits() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_provider(provider.clone());
+        tracker
+            .metrics
+            .record_request(Duration::from_millis(800), true)
+            .await;
+
+        let budget = Duration::from_millis(200);
+        assert!(matches!(
+            tracker.refresh_now_within(budget).await,
+            Err(ProviderError::OverBudget(_))
+        ));
+        assert!(tracker.get_price_within(Asset::SOL, budget).await.is_err());
+        assert_eq!(provider.call_count(), 0);
+
+        let fallback = Arc::new(MockProvider::new());
+        fallback.set_price(Asset::SOL, 151.0);
+        let tracker = tracker.with_fallback_provider(fallback);
+        let price = tracker.get_price_within(Asset::SOL, budget).await.unwrap();
+        assert_eq!(price.price_usd, 151.0);
+        assert_eq!(provider.call_count(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_request_timeout_cuts_off_stuck_fetch() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        provider.set_delay(Duration::from_secs(3600));
+        let tracker = MarketPriceTracker::with_config(
+            provider.clone(),
+            TrackerConfig {
+                request_timeout: Duration::from_secs(1),
+                ..Default::default()
+            },
+        );
+
+        let start = tokio::time::Instant::now();
+        assert!(matches!(
+            tracker.refresh_now().await,
+            Err(ProviderError::Timeout)
+        ));
+        assert!(start.elapsed() < Duration::from_secs(60));
+        assert!(provider.call_count() > 1, "timeouts are retried");
+
+        // Cache misses fall back to the provider under the same limit
+        let start = tokio::time::Instant::now();
+        assert!(tracker.get_price(Asset::SOL).await.is_err());
+        assert!(tracker.get_prices(&[Asset::SOL]).await[&Asset::SOL].is_err());
+        assert!(start.elapsed() < Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_on_move_fires_once_threshold_is_crossed() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 100.0);
+        let tracker = MarketPriceTracker::with_provider(provider.clone());
+        tracker.refresh_now().await.unwrap();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tracker.on_move(Asset::SOL, 50.0, move |price, change_bps| {
+            let _ = tx.send((price.price_usd, change_bps.round()));
+        });
+
+        // 30 bps, then 60 bps from the reference, then 10 bps from the new one
+        for price in [100.3, 100.6, 100.7] {
+            provider.set_price(Asset::SOL, price);
+            tracker.refresh_now().await.unwrap();
+        }
+        provider.set_price(Asset::SOL, 99.0);
+        tracker.refresh_now().await.unwrap();
+
+        let mut moves = Vec::new();
+        while moves.len() < 2 {
+            let next = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await;
+            moves.push(next.unwrap().unwrap());
+        }
+        assert_eq!(moves, vec![(100.6, 60.0), (99.0, -159.0)]);
+        assert!(rx.try_recv().is_err());
+        tracker.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_price_stream_yields_only_its_asset() {
+        use futures::StreamExt;
+
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        provider.set_price(Asset::BTC, 64000.0);
+        let tracker = MarketPriceTracker::with_provider(provider.clone());
+
+        let sol = tracker.price_stream(Asset::SOL);
+        let all = tracker.price_stream_all();
+        tracker.refresh_now().await.unwrap();
+        provider.set_price(Asset::SOL, 151.0);
+        tracker.refresh_now().await.unwrap();
+
+        let timeout = Duration::from_secs(1);
+        let sol: Vec<f64> =
+            tokio::time::timeout(timeout, sol.map(|p| p.price_usd).take(2).collect())
+                .await
+                .unwrap();
+        assert_eq!(sol, vec![150.0, 151.0]);
+        let all: Vec<PriceData> = tokio::time::timeout(timeout, all.take(4).collect())
+            .await
+            .unwrap();
+        assert_eq!(all.iter().filter(|p| p.asset == Asset::BTC).count(), 2);
+
+        // Streams end with the tracker
+        let mut after = tracker.price_stream(Asset::SOL);
+        tracker.shutdown();
+        assert!(tokio::time::timeout(timeout, after.next())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_named_globals_are_independent() {
+        let provider = Arc::new(MockProvider::new());
+        let trading =
+            MarketPriceTracker::global_named_with("trading", provider.clone(), Default::default())
+                .await;
+        let again =
+            MarketPriceTracker::global_named_with("trading", provider.clone(), Default::default())
+                .await;
+        let risk =
+            MarketPriceTracker::global_named_with("risk", provider, Default::default()).await;
+
+        assert!(Arc::ptr_eq(&trading, &again));
+        assert!(!Arc::ptr_eq(&trading, &risk));
+        trading.shutdown();
+        risk.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_for_unchanged_price() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_config(
+            provider,
+            TrackerConfig {
+                heartbeat_interval: Some(Duration::from_millis(20)),
+                ..Default::default()
+            },
+        );
+        let mut events = tracker.subscribe_events();
+        tracker.start_background_task();
+        let event = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let stored = tracker.get_price(Asset::SOL).await.unwrap();
+        tracker.shutdown();
+
+        match event {
+            MarketPriceEvent::Heartbeat {
+                asset,
+                last_updated,
+                ..
+            } => {
+                assert_eq!(asset, Asset::SOL);
+                assert_eq!(last_updated, stored.last_updated);
+            }
+            other => panic!("unexpected event: {other}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_sink_receives_events() {
+        let sink = Arc::new(crate::sinks::mock::RecordingSink::default());
+        let tracker = MarketPriceTracker::with_provider(Arc::new(MockProvider::new()));
+        tracker.add_event_sink(sink.clone());
+
+        tracker
+            .event_tx
+            .send(MarketPriceEvent::Heartbeat {
+                id: uuid::Uuid::new_v4(),
+                asset: Asset::SOL,
+                last_updated: Utc::now(),
+                sequence: 1,
+                timestamp: Utc::now(),
+            })
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(1), sink.published.notified())
+            .await
+            .unwrap();
+        tracker.shutdown();
+
+        let events = sink.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type(), "HEARTBEAT");
+    }
+
+    #[tokio::test]
+    async fn test_degraded_mode_serves_last_known_price() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let clock = Arc::new(crate::runtime::MockClock::new(Utc::now()));
+        let tracker = MarketPriceTracker::with_config(
+            provider.clone(),
+            TrackerConfig {
+                runtime: crate::runtime::Runtime::default().with_clock(clock.clone()),
+                degraded: Some(crate::degraded::DegradedConfig::default()),
+                ..Default::default()
+            },
+        );
+        tracker.refresh_now().await.unwrap();
+
+        provider.set_error(Asset::SOL, ProviderError::Timeout);
+        clock.advance(Duration::from_secs(600));
+        let price = tracker.get_price(Asset::SOL).await.unwrap();
+        assert_eq!(price.quality, crate::types::Quality::Degraded);
+        assert_eq!(price.price_usd, 150.0);
+        assert!(price.confidence.unwrap() > 0.0);
+
+        clock.advance(Duration::from_secs(3600));
+        assert!(tracker.get_price(Asset::SOL).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pause_stops_provider_requests() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_provider(provider.clone());
+        tracker.refresh_now().await.unwrap();
+        let calls = provider.call_count();
+
+        tracker.pause();
+        assert!(tracker.is_paused());
+        assert!(matches!(
+            tracker.refresh_now().await,
+            Err(ProviderError::Paused)
+        ));
+        assert!(tracker.get_price(Asset::BTC).await.is_err());
+        assert_eq!(
+            tracker.get_price(Asset::SOL).await.unwrap().price_usd,
+            150.0
+        );
+        assert_eq!(provider.call_count(), calls);
+
+        tracker.resume();
+        tracker.refresh_now().await.unwrap();
+        assert!(provider.call_count() > calls);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_set_provider_drains_in_flight_fetch() {
+        let old = Arc::new(MockProvider::new());
+        old.set_price(Asset::SOL, 100.0);
+        old.set_delay(Duration::from_secs(1));
+        let tracker = Arc::new(MarketPriceTracker::with_provider(old.clone()));
+        let refresh = tokio::spawn({
+            let tracker = tracker.clone();
+            async move { tracker.refresh_now().await }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let new = Arc::new(MockProvider::new());
+        new.set_price(Asset::SOL, 200.0);
+        tracker.set_provider(new.clone()).await;
+        assert_eq!(
+            tracker.get_price(Asset::SOL).await.unwrap().price_usd,
+            100.0
+        );
+        refresh.await.unwrap().unwrap();
+
+        let old_calls = old.call_count();
+        tracker.refresh_now().await.unwrap();
+        assert_eq!(
+            tracker.get_price(Asset::SOL).await.unwrap().price_usd,
+            200.0
+        );
+        assert_eq!(old.call_count(), old_calls);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_set_provider_cuts_off_stuck_fetch() {
+        let old = Arc::new(MockProvider::new());
+        old.set_price(Asset::SOL, 100.0);
+        old.set_delay(Duration::from_secs(3600));
+        let tracker = Arc::new(MarketPriceTracker::with_config(
+            old.clone(),
+            TrackerConfig {
+                request_timeout: Duration::from_secs(1),
+                ..Default::default()
+            },
+        ));
+        let stuck = tokio::spawn({
+            let tracker = tracker.clone();
+            async move { tracker.get_price(Asset::SOL).await }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let new = Arc::new(MockProvider::new());
+        new.set_price(Asset::SOL, 200.0);
+        let start = tokio::time::Instant::now();
+        tracker.set_provider(new).await;
+        assert!(start.elapsed() < Duration::from_secs(60));
+        assert!(stuck.await.unwrap().is_err());
+        assert_eq!(
+            tracker.get_price(Asset::SOL).await.unwrap().price_usd,
+            200.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_store_handle_reads_the_tracker_cache() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_provider(provider);
+        let prices = tracker.store_handle();
+        assert!(prices.is_stale(Asset::SOL));
+
+        tracker.refresh_now().await.unwrap();
+        let copy = prices.clone();
+        assert!(!copy.is_stale(Asset::SOL));
+        assert_eq!(copy.get_price(Asset::SOL).unwrap().price_usd, 150.0);
+        assert_eq!(copy.get_all_prices().len(), 1);
+        assert!(copy.watch(Asset::SOL).borrow().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_ready_resolves_on_initial_fetch() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_provider(provider);
+
+        tracker.start_background_task();
+        tokio::time::timeout(Duration::from_secs(5), tracker.ready())
+            .await
+            .expect("initial fetch runs before the first refresh interval");
+        assert!(tracker.has_price(Asset::SOL).await);
+        tracker.ready().await;
+        tracker.shutdown();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_initial_fetch_waits_for_phase_offset() {
+        let provider = Arc::new(MockProvider::new());
+        provider.set_price(Asset::SOL, 150.0);
+        let tracker = MarketPriceTracker::with_config(
+            provider.clone(),
+            TrackerConfig {
+                poll_phase_offset: Duration::from_secs(7),
+                poll_start_jitter: Duration::from_secs(3),
+                ..Default::default()
+            },
+        );
+
+        tracker.start_background_task();
+        tokio::time::sleep(Duration::from_secs(6)).await;
+        assert_eq!(provider.call_count(), 0);
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert!(tracker.has_price(Asset::SOL).await);
+        tracker.shutdown();
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_failover_providers() {
+        let backup = Arc::new(MockProvider::new());
+        backup.set_price(Asset::SOL, 150.0);
+        let failover =
+            crate::providers::FailoverProvider::new(vec![Arc::new(MockProvider::new()), backup]);
+        let tracker = MarketPriceTracker::with_provider(Arc::new(failover));
+
+        tracker.refresh_now().await.unwrap();
+        tracker.refresh_now().await.unwrap();
+        let details = tracker.health_check().await.details;
+        assert_eq!(details["consecutive_failures"], 0);
+        assert!(details["last_error"].is_null());
+        assert!(details["last_successful_fetch"].is_string());
+        let providers = details["providers"].as_array().unwrap();
+        assert_eq!(providers[0]["status"], "degraded");
+        assert_eq!(providers[0]["consecutive_failures"], 2);
+        assert!(providers[0]["last_error"].is_string());
+        assert_eq!(providers[1]["status"], "healthy");
+    }
 }
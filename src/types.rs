@@ -1,12 +1,20 @@
 //! Types for the market price tracker
 
+use crate::error::PriceError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
 use uuid::Uuid;
 
-/// Supported cryptocurrency assets
+/// Supported assets: cryptocurrencies, plus a few TradFi reference
+/// assets (see [`is_reference`](Asset::is_reference))
+///
+/// Serializes as the uppercase variant name. Deserialization and
+/// [`FromStr`](std::str::FromStr) accept any case, the display symbol, the
+/// CoinGecko id and common aliases such as `XBT` or `wsol`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "UPPERCASE")]
+#[serde(rename_all = "UPPERCASE", try_from = "String")]
 pub enum Asset {
     /// Solana
     SOL,
@@ -22,6 +30,33 @@ pub enum Asset {
     WBTC,
     /// Wrapped Ethereum
     WETH,
+    /// Marinade staked SOL
+    MSOL,
+    /// Jito staked SOL
+    JITOSOL,
+    /// BlazeStake staked SOL
+    BSOL,
+    /// BNB
+    BNB,
+    /// XRP
+    XRP,
+    /// Cardano
+    ADA,
+    /// Dogecoin
+    DOGE,
+    /// Avalanche
+    AVAX,
+    /// Chainlink
+    LINK,
+    /// Jupiter
+    JUP,
+    /// Bonk
+    BONK,
+    /// Gold, per troy ounce
+    XAU,
+    /// SPDR S&P 500 ETF Trust, an exchange-traded fund tracking the S&P
+    /// 500; Pyth publishes the ETF, not the index itself
+    SPY,
 }
 
 impl Asset {
@@ -35,12 +70,27 @@ impl Asset {
             Asset::USDT => "USDT",
             Asset::WBTC => "WBTC",
             Asset::WETH => "WETH",
+            Asset::MSOL => "mSOL",
+            Asset::JITOSOL => "jitoSOL",
+            Asset::BSOL => "bSOL",
+            Asset::BNB => "BNB",
+            Asset::XRP => "XRP",
+            Asset::ADA => "ADA",
+            Asset::DOGE => "DOGE",
+            Asset::AVAX => "AVAX",
+            Asset::LINK => "LINK",
+            Asset::JUP => "JUP",
+            Asset::BONK => "BONK",
+            Asset::XAU => "XAU",
+            Asset::SPY => "SPY",
         }
     }
 
     /// Get the CoinGecko ID for this asset
-    pub fn coingecko_id(&self) -> &'static str {
-        match self {
+    ///
+    /// `None` for reference assets, which CoinGecko does not list.
+    pub fn coingecko_id(&self) -> Option<&'static str> {
+        let id = match self {
             Asset::SOL => "solana",
             Asset::BTC => "bitcoin",
             Asset::ETH => "ethereum",
@@ -48,12 +98,30 @@ impl Asset {
             Asset::USDT => "tether",
             Asset::WBTC => "wrapped-bitcoin",
             Asset::WETH => "weth",
-        }
+            Asset::MSOL => "msol",
+            Asset::JITOSOL => "jito-staked-sol",
+            Asset::BSOL => "blazestake-staked-sol",
+            Asset::BNB => "binancecoin",
+            Asset::XRP => "ripple",
+            Asset::ADA => "cardano",
+            Asset::DOGE => "dogecoin",
+            Asset::AVAX => "avalanche-2",
+            Asset::LINK => "chainlink",
+            Asset::JUP => "jupiter-exchange-solana",
+            Asset::BONK => "bonk",
+            Asset::XAU | Asset::SPY => return None,
+        };
+        Some(id)
     }
 
     /// Get the Hyperliquid symbol for this asset
-    pub fn hyperliquid_symbol(&self) -> &'static str {
-        match self {
+    ///
+    /// Some low-priced assets trade in lots (`kBONK` is 1,000 BONK); see
+    /// [`hyperliquid_lot_size`](Self::hyperliquid_lot_size). `None` for
+    /// reference assets, which Hyperliquid does not list (its "SPX" is an
+    /// unrelated token).
+    pub fn hyperliquid_symbol(&self) -> Option<&'static str> {
+        let symbol = match self {
             Asset::SOL => "SOL",
             Asset::BTC => "BTC",
             Asset::ETH => "ETH",
@@ -61,6 +129,28 @@ impl Asset {
             Asset::USDT => "USDT",
             Asset::WBTC => "WBTC",
             Asset::WETH => "WETH",
+            Asset::MSOL => "MSOL",
+            Asset::JITOSOL => "JITOSOL",
+            Asset::BSOL => "BSOL",
+            Asset::BNB => "BNB",
+            Asset::XRP => "XRP",
+            Asset::ADA => "ADA",
+            Asset::DOGE => "DOGE",
+            Asset::AVAX => "AVAX",
+            Asset::LINK => "LINK",
+            Asset::JUP => "JUP",
+            Asset::BONK => "kBONK",
+            Asset::XAU | Asset::SPY => return None,
+        };
+        Some(symbol)
+    }
+
+    /// Units of the asset per Hyperliquid contract; Hyperliquid prices are
+    /// divided by it to get the price of one unit
+    pub fn hyperliquid_lot_size(&self) -> u32 {
+        match self {
+            Asset::BONK => 1000,
+            _ => 1,
         }
     }
 
@@ -84,7 +174,10 @@ impl Asset {
         }
     }
 
-    /// Get the Pyth V2 Price Feed ID (Hex) for Hermes
+    /// Get the built-in Pyth V2 Price Feed ID (Hex) for Hermes
+    ///
+    /// This ignores runtime overrides; use [`crate::feeds::pyth_feed_id`]
+    /// to resolve the feed ID that providers actually subscribe to.
     pub fn pyth_feed_id(&self) -> Option<&'static str> {
         match self {
             Asset::SOL => {
@@ -102,10 +195,87 @@ impl Asset {
             Asset::USDT => {
                 Some("0x2b89b9dc8fdf9f34709a5b106b472f0f39bb6ca9ce04b0fd7f2e971688e2e53b")
             }
-            _ => None,
+            Asset::WBTC => {
+                Some("0xc9d8b075a5c69303365ae23633d4e085199bf5c520a3b90fed1322a0342ffc33")
+            }
+            // WETH is redeemable 1:1 for ETH and Pyth publishes no separate feed
+            Asset::WETH => {
+                Some("0xff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace")
+            }
+            Asset::MSOL => {
+                Some("0xc2289a6a43d2ce91c6f55caec370f4acc38a2ed477f58813334c6d03749ff2a4")
+            }
+            Asset::JITOSOL => {
+                Some("0x67be9f519b95cf24338801051f9a808eff0a578ccb388db73b7f6fe1de019ffb")
+            }
+            Asset::BSOL => {
+                Some("0x89875379e70f8fbadc17aef315adf3a8d5d160b811435537e03c97e8aac97d9c")
+            }
+            Asset::BNB => {
+                Some("0x2f95862b045670cd22bee3114c39763a4a08beeb663b145d283c31d7d1101c4f")
+            }
+            Asset::XRP => {
+                Some("0xec5d399846a9209f3fe5881d70aae9268c94339ff9817e8d18ff19fa05eea1c8")
+            }
+            Asset::ADA => {
+                Some("0x2a01deaec9e51a579277b34b122399984d0bbf57e2458a7e42fecd2829867a0d")
+            }
+            Asset::DOGE => {
+                Some("0xdcef50dd0a4cd2dcc17e45df1676dcb336a11a61c69df7a0299b0150c672d25c")
+            }
+            Asset::AVAX => {
+                Some("0x93da3352f9f1d105fdfe4971cfa80e9dd777bfc5d0f683ebb6e1294b92137bb7")
+            }
+            Asset::LINK => {
+                Some("0x8ac0c70fff57e9aefdf5edf44b51d62c2d433653cbb2cf5cc06bb115af04d221")
+            }
+            Asset::JUP => {
+                Some("0x0a0408d619e9380abad35060f9192039ed5042fa6f82301d0e48bb52be830996")
+            }
+            Asset::BONK => {
+                Some("0x72b021217ca3fe68922a19aaf990109cb9d84e9ad004b4d2025ad6f529314419")
+            }
+            // Metal.XAU/USD
+            Asset::XAU => {
+                Some("0x765d2ba906dbc32ca17cc11f5310a89e9ee1f6420508c63861f2f8ba4ee34bb2")
+            }
+            // Equity.US.SPY/USD
+            Asset::SPY => {
+                Some("0x19e09bb805456ada3979a7d1cbb4b6d63babc3a0f8e8a9509f68afa5c4c11cd5")
+            }
         }
     }
 
+    /// Get the SPL token mint for this asset on Solana mainnet
+    ///
+    /// SOL maps to the wrapped SOL mint, WBTC and WETH to their Wormhole
+    /// (Portal) mints. Assets native to other chains (BTC, ETH, BNB, ...)
+    /// have no canonical Solana mint.
+    pub fn mint_address(&self) -> Option<Pubkey> {
+        let mint = match self {
+            Asset::SOL => "So11111111111111111111111111111111111111112",
+            Asset::USDC => "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            Asset::USDT => "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+            Asset::WBTC => "3NZ9JMVBmGAqocybic2c7LQCJScmgsAZ6vQqTDzcqmJh",
+            Asset::WETH => "7vfCXTUXx5WJV5JADk17DUJ4ksgau7utNKj4b963voxs",
+            Asset::MSOL => "mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So",
+            Asset::JITOSOL => "J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn",
+            Asset::BSOL => "bSo13r4TkiE4KumL71LsHTPpL2euBYLFx6h9HP3piy1",
+            Asset::JUP => "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+            Asset::BONK => "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263",
+            _ => return None,
+        };
+        Some(Pubkey::from_str_const(mint))
+    }
+
+    /// Finds the asset whose SPL token mint is `mint`
+    pub fn from_mint(mint: &Pubkey) -> Option<Asset> {
+        Asset::all()
+            .iter()
+            .copied()
+            .find(|asset| asset.mint_address().as_ref() == Some(mint))
+    }
+
     /// Get all supported assets
     pub fn all() -> &'static [Asset] {
         &[
@@ -116,23 +286,169 @@ impl Asset {
             Asset::USDT,
             Asset::WBTC,
             Asset::WETH,
+            Asset::MSOL,
+            Asset::JITOSOL,
+            Asset::BSOL,
+            Asset::BNB,
+            Asset::XRP,
+            Asset::ADA,
+            Asset::DOGE,
+            Asset::AVAX,
+            Asset::LINK,
+            Asset::JUP,
+            Asset::BONK,
+            Asset::XAU,
+            Asset::SPY,
         ]
     }
 
+    /// Get the cryptocurrency assets, leaving out reference assets
+    pub fn crypto() -> impl Iterator<Item = Asset> {
+        Asset::all()
+            .iter()
+            .copied()
+            .filter(|asset| !asset.is_reference())
+    }
+
+    /// Returns true for TradFi reference assets (gold, the SPY ETF), which
+    /// only Pyth publishes and which trade on exchange hours rather than
+    /// 24/7
+    pub fn is_reference(&self) -> bool {
+        matches!(self, Asset::XAU | Asset::SPY)
+    }
+
+    /// Returns true for Solana liquid staking tokens, whose price tracks
+    /// SOL times a stake-pool exchange rate
+    pub fn is_liquid_staking_token(&self) -> bool {
+        matches!(self, Asset::MSOL | Asset::JITOSOL | Asset::BSOL)
+    }
+
     /// Get the stale threshold for this asset in seconds
     ///
     /// Different assets have different freshness requirements:
-    /// - High-frequency assets (SOL, ETH): 120 seconds
-    /// - Moderate frequency (BTC, WBTC, WETH): 180 seconds
+    /// - High-frequency assets (SOL, ETH, SOL LSTs, DOGE, JUP, BONK): 120 seconds
+    /// - Moderate frequency (BTC, WBTC, WETH, other majors): 180 seconds
     /// - Stablecoins (USDC, USDT): 300 seconds (price rarely changes)
+    /// - Reference assets (XAU, SPY): 300 seconds, during trading hours
     pub fn stale_threshold_secs(&self) -> u64 {
         match self {
             // High-frequency trading assets need fresher data
-            Asset::SOL | Asset::ETH => 120,
+            Asset::SOL
+            | Asset::ETH
+            | Asset::MSOL
+            | Asset::JITOSOL
+            | Asset::BSOL
+            | Asset::DOGE
+            | Asset::JUP
+            | Asset::BONK => 120,
             // Moderate frequency
-            Asset::BTC | Asset::WBTC | Asset::WETH => 180,
+            Asset::BTC
+            | Asset::WBTC
+            | Asset::WETH
+            | Asset::BNB
+            | Asset::XRP
+            | Asset::ADA
+            | Asset::AVAX
+            | Asset::LINK => 180,
             // Stablecoins - price is relatively stable
             Asset::USDC | Asset::USDT => 300,
+            // Reference assets - Pyth publishes them less often
+            Asset::XAU | Asset::SPY => 300,
+        }
+    }
+}
+
+/// Names accepted for an asset besides its symbol and CoinGecko id
+const ASSET_ALIASES: &[(&str, Asset)] = &[
+    ("wsol", Asset::SOL),
+    ("wrapped-sol", Asset::SOL),
+    ("xbt", Asset::BTC),
+    ("ether", Asset::ETH),
+    ("usdcoin", Asset::USDC),
+    ("wrapped-ether", Asset::WETH),
+    ("wrapped-ethereum", Asset::WETH),
+    ("marinade-staked-sol", Asset::MSOL),
+    ("blazestake-sol", Asset::BSOL),
+    ("bnb-chain", Asset::BNB),
+    ("avalanche", Asset::AVAX),
+    ("jupiter", Asset::JUP),
+    ("gold", Asset::XAU),
+];
+
+impl std::str::FromStr for Asset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['_', ' '], "-");
+        Asset::all()
+            .iter()
+            .copied()
+            .find(|asset| {
+                asset.symbol().eq_ignore_ascii_case(&name) || asset.coingecko_id() == Some(&name)
+            })
+            .or_else(|| {
+                ASSET_ALIASES
+                    .iter()
+                    .find(|(alias, _)| *alias == name)
+                    .map(|(_, asset)| *asset)
+            })
+            .ok_or_else(|| format!("unknown asset: {}", s))
+    }
+}
+
+impl TryFrom<String> for Asset {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl std::fmt::Display for Asset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// How a price was produced from its sources
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceMethod {
+    /// Reported by a single source
+    Direct,
+    /// Served by a later provider in a failover chain after earlier ones
+    /// failed
+    Failover,
+    /// The first response of several providers queried at once
+    FirstResponse,
+    /// Median of the contributors' prices
+    Median,
+    /// The primary's price, compared against a reference
+    CrossChecked,
+    /// Computed from the contributors' prices, such as an LST priced from
+    /// SOL and its exchange rate
+    Derived,
+}
+
+/// Where a price came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceInfo {
+    /// Source of the price that was served, or that a derived price is
+    /// based on
+    pub primary: String,
+    /// Every source that went into the price, the primary first
+    pub contributors: Vec<String>,
+    /// How the contributors were combined
+    pub method: SourceMethod,
+}
+
+impl SourceInfo {
+    /// Attribution of a price reported by `source` alone
+    pub fn direct(source: &str) -> Self {
+        Self {
+            primary: source.to_string(),
+            contributors: vec![source.to_string()],
+            method: SourceMethod::Direct,
         }
     }
 }
@@ -154,6 +470,37 @@ pub struct PriceData {
 
     /// Data source
     pub source: String,
+
+    /// How the price was produced, when more than one source was involved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_info: Option<SourceInfo>,
+
+    /// Confidence interval in USD (Pyth only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+
+    /// Exponential moving average price in USD (Pyth only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ema_price_usd: Option<f64>,
+
+    /// Composite data quality grade, assigned by the store
+    #[serde(default)]
+    pub quality: Quality,
+
+    /// Deviation from a reference provider in basis points, when cross-checked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deviation_bps: Option<f64>,
+
+    /// Per-asset update number assigned by the store, starting at 1; 0 for
+    /// prices that were never stored
+    #[serde(default)]
+    pub sequence: u64,
+
+    /// Price in USD as a lossless decimal, when the provider reports it as a
+    /// string (Hyperliquid, Pyth)
+    #[cfg(feature = "decimal")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_decimal: Option<rust_decimal::Decimal>,
 }
 
 impl PriceData {
@@ -165,6 +512,14 @@ impl PriceData {
             price_change_24h: None,
             last_updated: Utc::now(),
             source,
+            source_info: None,
+            confidence: None,
+            ema_price_usd: None,
+            quality: Quality::default(),
+            deviation_bps: None,
+            sequence: 0,
+            #[cfg(feature = "decimal")]
+            price_decimal: None,
         }
     }
 
@@ -181,24 +536,155 @@ impl PriceData {
             price_change_24h,
             last_updated: Utc::now(),
             source,
+            source_info: None,
+            confidence: None,
+            ema_price_usd: None,
+            quality: Quality::default(),
+            deviation_bps: None,
+            sequence: 0,
+            #[cfg(feature = "decimal")]
+            price_decimal: None,
         }
     }
 
+    /// Attach how the price was produced from its sources
+    pub fn with_source_info(mut self, source_info: SourceInfo) -> Self {
+        self.source_info = Some(source_info);
+        self
+    }
+
+    /// Returns how the price was produced, or direct attribution to
+    /// `source` if no provider recorded more
+    pub fn attribution(&self) -> SourceInfo {
+        self.source_info
+            .clone()
+            .unwrap_or_else(|| SourceInfo::direct(&self.source))
+    }
+
+    /// Attach a confidence interval (in USD)
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.confidence = Some(confidence);
+        self
+    }
+
+    /// Attach an exponential moving average price (in USD)
+    pub fn with_ema_price(mut self, ema_price_usd: f64) -> Self {
+        self.ema_price_usd = Some(ema_price_usd);
+        self
+    }
+
+    /// Attach the lossless decimal price (in USD)
+    #[cfg(feature = "decimal")]
+    pub fn with_decimal_price(mut self, price: rust_decimal::Decimal) -> Self {
+        self.price_decimal = Some(price);
+        self
+    }
+
+    /// Get the confidence interval as a fraction of the price
+    ///
+    /// Returns `None` when no confidence is available or the price is zero.
+    pub fn confidence_ratio(&self) -> Option<f64> {
+        let confidence = self.confidence?;
+        if self.price_usd == 0.0 {
+            return None;
+        }
+        Some(confidence / self.price_usd.abs())
+    }
+
     /// Check if the price data is stale (older than threshold seconds)
     pub fn is_stale(&self, threshold_seconds: u64) -> bool {
-        let now = Utc::now();
+        self.is_stale_at(threshold_seconds, Utc::now())
+    }
+
+    /// Checks staleness as of `now` rather than the system time
+    pub fn is_stale_at(&self, threshold_seconds: u64, now: DateTime<Utc>) -> bool {
         let age = now.signed_duration_since(self.last_updated);
         age.num_seconds() > threshold_seconds as i64
     }
 
     /// Get the age of the price data in seconds
     pub fn age(&self) -> std::time::Duration {
-        let now = Utc::now();
+        self.age_at(Utc::now())
+    }
+
+    /// Age of the price data as of `now`, in whole seconds
+    pub fn age_at(&self, now: DateTime<Utc>) -> std::time::Duration {
         let duration = now.signed_duration_since(self.last_updated);
         std::time::Duration::from_secs(duration.num_seconds().max(0) as u64)
     }
 }
 
+/// Data quality of a price, ordered from worst to best
+///
+/// See [`crate::quality`] for the scoring model.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Quality {
+    /// Last known price served past its stale threshold in degraded mode
+    /// (see [`crate::degraded`])
+    Degraded,
+    /// Flagged by anomaly detection
+    Suspect,
+    /// Score below 0.5
+    Poor,
+    /// Score of at least 0.5
+    #[default]
+    Fair,
+    /// Score of at least 0.75
+    Good,
+    /// Score of at least 0.9
+    Excellent,
+}
+
+impl Quality {
+    /// Returns the grade as it appears in serialized payloads
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Quality::Degraded => "degraded",
+            Quality::Suspect => "suspect",
+            Quality::Poor => "poor",
+            Quality::Fair => "fair",
+            Quality::Good => "good",
+            Quality::Excellent => "excellent",
+        }
+    }
+}
+
+/// All tracked prices grouped by why they are (or aren't) usable
+#[derive(Debug, Clone, Default)]
+pub struct DetailedPrices {
+    /// Prices within their asset's stale threshold
+    pub fresh: HashMap<Asset, PriceData>,
+    /// Prices older than their asset's stale threshold
+    pub stale: HashMap<Asset, PriceData>,
+    /// Tracked assets that have never received a price
+    pub missing: Vec<Asset>,
+}
+
+impl DetailedPrices {
+    /// Get the price for an asset, or the reason it is unavailable
+    pub fn get(&self, asset: Asset) -> Result<&PriceData, PriceError> {
+        if let Some(price) = self.fresh.get(&asset) {
+            return Ok(price);
+        }
+        match self.stale.get(&asset) {
+            Some(price) => Err(PriceError::stale(asset.symbol(), price.age())),
+            None => Err(PriceError::not_available(asset.symbol())),
+        }
+    }
+}
+
+/// Latest prices of all assets at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceSnapshot {
+    /// When the snapshot was taken
+    pub taken_at: DateTime<Utc>,
+    /// Latest price per asset, including stale ones
+    pub prices: HashMap<Asset, PriceData>,
+}
+
 /// Market price events for the unified event system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
@@ -210,6 +696,9 @@ pub enum MarketPriceEvent {
         old_price_usd: Option<f64>,
         new_price_usd: f64,
         price_change_24h: Option<f64>,
+        /// Sequence number of the price the event refers to
+        #[serde(default)]
+        sequence: u64,
         timestamp: DateTime<Utc>,
     },
 
@@ -228,6 +717,80 @@ pub enum MarketPriceEvent {
         status: ProviderStatus,
         timestamp: DateTime<Utc>,
     },
+
+    /// Two providers disagree on a price beyond the configured threshold
+    ProviderDivergence {
+        id: Uuid,
+        asset: Asset,
+        primary: String,
+        primary_price_usd: f64,
+        reference: String,
+        reference_price_usd: f64,
+        deviation_bps: f64,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A stale price was removed from the store
+    PriceExpired {
+        id: Uuid,
+        asset: Asset,
+        last_price_usd: f64,
+        last_updated: DateTime<Utc>,
+        /// Sequence number of the price the event refers to
+        #[serde(default)]
+        sequence: u64,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Periodic liveness signal for an asset, sent even when its price
+    /// has not changed
+    Heartbeat {
+        id: Uuid,
+        asset: Asset,
+        last_updated: DateTime<Utc>,
+        /// Sequence number of the price the event refers to
+        #[serde(default)]
+        sequence: u64,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A subscriber or sink fell behind and `missed` updates were dropped
+    /// for it since the previous report
+    SubscriberLagged {
+        id: Uuid,
+        subscriber: String,
+        missed: u64,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// An asset's price crossed its stale threshold; sent once per
+    /// transition, until a fresh price arrives
+    PriceWentStale {
+        id: Uuid,
+        asset: Asset,
+        /// Age of the price, in milliseconds, when the transition was
+        /// detected
+        age_ms: u64,
+        last_updated: DateTime<Utc>,
+        /// Sequence number of the price the event refers to
+        #[serde(default)]
+        sequence: u64,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A fresh price arrived for an asset whose price had gone stale or
+    /// been evicted
+    PriceRecovered {
+        id: Uuid,
+        asset: Asset,
+        price_usd: f64,
+        /// When the asset was last updated before the gap
+        previous_update: DateTime<Utc>,
+        /// Sequence number of the price the event refers to
+        #[serde(default)]
+        sequence: u64,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 impl MarketPriceEvent {
@@ -237,6 +800,27 @@ impl MarketPriceEvent {
             MarketPriceEvent::PriceUpdated { id, .. } => *id,
             MarketPriceEvent::PriceFetchFailed { id, .. } => *id,
             MarketPriceEvent::ProviderStatusChanged { id, .. } => *id,
+            MarketPriceEvent::ProviderDivergence { id, .. } => *id,
+            MarketPriceEvent::PriceExpired { id, .. } => *id,
+            MarketPriceEvent::Heartbeat { id, .. } => *id,
+            MarketPriceEvent::SubscriberLagged { id, .. } => *id,
+            MarketPriceEvent::PriceWentStale { id, .. } => *id,
+            MarketPriceEvent::PriceRecovered { id, .. } => *id,
+        }
+    }
+
+    /// Get when the event was emitted
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            MarketPriceEvent::PriceUpdated { timestamp, .. } => *timestamp,
+            MarketPriceEvent::PriceFetchFailed { timestamp, .. } => *timestamp,
+            MarketPriceEvent::ProviderStatusChanged { timestamp, .. } => *timestamp,
+            MarketPriceEvent::ProviderDivergence { timestamp, .. } => *timestamp,
+            MarketPriceEvent::PriceExpired { timestamp, .. } => *timestamp,
+            MarketPriceEvent::Heartbeat { timestamp, .. } => *timestamp,
+            MarketPriceEvent::SubscriberLagged { timestamp, .. } => *timestamp,
+            MarketPriceEvent::PriceWentStale { timestamp, .. } => *timestamp,
+            MarketPriceEvent::PriceRecovered { timestamp, .. } => *timestamp,
         }
     }
 
@@ -246,6 +830,12 @@ impl MarketPriceEvent {
             MarketPriceEvent::PriceUpdated { .. } => "PRICE_UPDATED",
             MarketPriceEvent::PriceFetchFailed { .. } => "PRICE_FETCH_FAILED",
             MarketPriceEvent::ProviderStatusChanged { .. } => "PROVIDER_STATUS_CHANGED",
+            MarketPriceEvent::ProviderDivergence { .. } => "PROVIDER_DIVERGENCE",
+            MarketPriceEvent::PriceExpired { .. } => "PRICE_EXPIRED",
+            MarketPriceEvent::Heartbeat { .. } => "HEARTBEAT",
+            MarketPriceEvent::SubscriberLagged { .. } => "SUBSCRIBER_LAGGED",
+            MarketPriceEvent::PriceWentStale { .. } => "PRICE_WENT_STALE",
+            MarketPriceEvent::PriceRecovered { .. } => "PRICE_RECOVERED",
         }
     }
 }
@@ -282,12 +872,83 @@ impl std::fmt::Display for MarketPriceEvent {
             } => {
                 write!(f, "Provider {} status: {:?}", provider, status)
             }
+            MarketPriceEvent::ProviderDivergence {
+                asset,
+                primary,
+                reference,
+                deviation_bps,
+                ..
+            } => {
+                write!(
+                    f,
+                    "Providers {} and {} diverge on {} by {:.1} bps",
+                    primary,
+                    reference,
+                    asset.symbol(),
+                    deviation_bps
+                )
+            }
+            MarketPriceEvent::PriceExpired {
+                asset,
+                last_updated,
+                ..
+            } => {
+                write!(
+                    f,
+                    "Price expired: {} last updated {}",
+                    asset.symbol(),
+                    last_updated
+                )
+            }
+            MarketPriceEvent::Heartbeat {
+                asset,
+                last_updated,
+                ..
+            } => {
+                write!(
+                    f,
+                    "Heartbeat: {} last updated {}",
+                    asset.symbol(),
+                    last_updated
+                )
+            }
+            MarketPriceEvent::SubscriberLagged {
+                subscriber, missed, ..
+            } => {
+                write!(
+                    f,
+                    "Subscriber {} lagged, missed {} updates",
+                    subscriber, missed
+                )
+            }
+            MarketPriceEvent::PriceWentStale { asset, age_ms, .. } => {
+                write!(
+                    f,
+                    "Price went stale: {} (age: {}ms)",
+                    asset.symbol(),
+                    age_ms
+                )
+            }
+            MarketPriceEvent::PriceRecovered {
+                asset,
+                price_usd,
+                previous_update,
+                ..
+            } => {
+                write!(
+                    f,
+                    "Price recovered: {} = ${:.2}, previously updated {}",
+                    asset.symbol(),
+                    price_usd,
+                    previous_update
+                )
+            }
         }
     }
 }
 
 /// Provider status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ProviderStatus {
     /// Provider is healthy
@@ -323,3 +984,49 @@ pub struct ComponentHealth {
     /// Last checked timestamp
     pub last_checked: DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mint_round_trip() {
+        for asset in Asset::all() {
+            if let Some(mint) = asset.mint_address() {
+                assert_eq!(Asset::from_mint(&mint), Some(*asset));
+            }
+        }
+        assert_eq!(Asset::ETH.mint_address(), None);
+        assert_eq!(Asset::from_mint(&Pubkey::default()), None);
+    }
+
+    #[test]
+    fn test_parse_asset_names() {
+        for asset in Asset::all() {
+            assert_eq!(asset.to_string().parse::<Asset>(), Ok(*asset));
+            if let Some(id) = asset.coingecko_id() {
+                assert_eq!(id.parse::<Asset>(), Ok(*asset));
+            }
+        }
+        assert_eq!("XBT".parse::<Asset>(), Ok(Asset::BTC));
+        assert_eq!("Wrapped_Bitcoin".parse::<Asset>(), Ok(Asset::WBTC));
+        assert_eq!(" jitosol ".parse::<Asset>(), Ok(Asset::JITOSOL));
+        assert!("shib".parse::<Asset>().is_err());
+
+        assert_eq!(serde_json::to_string(&Asset::MSOL).unwrap(), "\"MSOL\"");
+        let assets: Vec<Asset> = serde_json::from_str(r#"["MSOL", "wbtc", "xbt"]"#).unwrap();
+        assert_eq!(assets, vec![Asset::MSOL, Asset::WBTC, Asset::BTC]);
+    }
+
+    #[test]
+    fn test_reference_assets_are_pyth_only() {
+        assert_eq!("gold".parse::<Asset>(), Ok(Asset::XAU));
+        assert!(Asset::SPY.is_reference());
+        assert!(Asset::SPY.pyth_feed_id().is_some());
+        assert_eq!(Asset::crypto().count(), Asset::all().len() - 2);
+
+        let capabilities = crate::provider::ProviderCapabilities::default();
+        assert!(!capabilities.supports(Asset::XAU));
+        assert!(capabilities.supports(Asset::SOL));
+    }
+}
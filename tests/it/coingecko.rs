@@ -0,0 +1,55 @@
+use crate::harness::{coingecko_prices, malformed, rate_limited, slow, MockApi};
+use market_price_sdk::types::Asset;
+use market_price_sdk::{MarketPriceProvider, ProviderError};
+
+#[tokio::test]
+async fn test_fetches_prices() {
+    let api = MockApi::start().await;
+    api.coingecko(coingecko_prices(&[
+        (Asset::SOL, 150.25),
+        (Asset::BTC, 60000.0),
+    ]))
+    .await;
+
+    let prices = api
+        .coingecko_provider()
+        .fetch_prices(&[Asset::SOL, Asset::BTC, Asset::ETH])
+        .await
+        .unwrap();
+
+    assert_eq!(prices.len(), 2);
+    assert!((prices[&Asset::SOL].price_usd - 150.25).abs() < 1e-9);
+    assert_eq!(prices[&Asset::SOL].source, "coingecko");
+}
+
+#[tokio::test]
+async fn test_rate_limited() {
+    let api = MockApi::start().await;
+    api.coingecko(rate_limited()).await;
+
+    let result = api.coingecko_provider().fetch_price(Asset::SOL).await;
+
+    assert!(matches!(result, Err(ProviderError::RateLimitExceeded)));
+}
+
+#[tokio::test]
+async fn test_malformed_json() {
+    let api = MockApi::start().await;
+    api.coingecko(malformed()).await;
+
+    let result = api.coingecko_provider().fetch_price(Asset::SOL).await;
+
+    assert!(matches!(result, Err(ProviderError::InvalidResponse(_))));
+}
+
+#[tokio::test]
+async fn test_slow_response_times_out() {
+    let api = MockApi::start().await;
+    api.coingecko(slow(coingecko_prices(&[(Asset::SOL, 150.0)])))
+        .await;
+
+    let result = api.coingecko_provider().fetch_price(Asset::SOL).await;
+
+    assert!(matches!(result, Err(ProviderError::Timeout)));
+    assert_eq!(api.request_count().await, 1);
+}
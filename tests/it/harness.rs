@@ -0,0 +1,196 @@
+//! Canned provider APIs served by wiremock, and providers pointed at them
+//!
+//! A test starts a [`MockApi`], mounts the responses it needs with the
+//! per-provider methods, and fetches through a provider built by the
+//! matching `*_provider` method. Response bodies come from the fixture
+//! functions below, so a test only states prices, never wire formats.
+//!
+//! Adding a provider: give [`MockApi`] a method that mounts its endpoint,
+//! a constructor pointing the provider at [`MockApi::url`], and a fixture
+//! rendering prices in its format; then copy one of the provider test
+//! files.
+
+use market_price_sdk::http::{BaseUrls, HttpClientConfig};
+use market_price_sdk::providers::{CoinGeckoProvider, HermesProvider, HyperliquidProvider};
+use market_price_sdk::symbols::SymbolRegistry;
+use market_price_sdk::types::Asset;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Overall timeout of the clients built here, short so that slow-response
+/// tests finish quickly
+pub const REQUEST_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How long before the response the prices in [`hermes_latest`] were
+/// published
+pub const HERMES_PUBLISH_AGE: Duration = Duration::from_secs(30);
+
+/// A local HTTP server standing in for the providers' APIs
+pub struct MockApi {
+    server: MockServer,
+}
+
+impl MockApi {
+    /// Starts a server on a random local port
+    pub async fn start() -> Self {
+        Self {
+            server: MockServer::start().await,
+        }
+    }
+
+    /// Base URL of the server
+    pub fn url(&self) -> String {
+        self.server.uri()
+    }
+
+    /// Number of requests the server has received
+    pub async fn request_count(&self) -> usize {
+        self.server
+            .received_requests()
+            .await
+            .map_or(0, |requests| requests.len())
+    }
+
+    /// Answers CoinGecko's simple price endpoint with `response`
+    pub async fn coingecko(&self, response: ResponseTemplate) {
+        self.mount("GET", "/simple/price", response).await;
+    }
+
+    /// Answers Hyperliquid's info endpoint with `response`
+    pub async fn hyperliquid(&self, response: ResponseTemplate) {
+        self.mount("POST", "/info", response).await;
+    }
+
+    /// Answers Hermes' latest price endpoint with `response`
+    pub async fn hermes(&self, response: ResponseTemplate) {
+        self.mount("GET", "/v2/updates/price/latest", response)
+            .await;
+    }
+
+    /// Answers the next request to Hermes' latest price endpoint with
+    /// `response`, ahead of responses mounted with [`hermes`](Self::hermes)
+    pub async fn hermes_once(&self, response: ResponseTemplate) {
+        Mock::given(method("GET"))
+            .and(path("/v2/updates/price/latest"))
+            .respond_with(response)
+            .up_to_n_times(1)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// A CoinGecko provider sending its requests here
+    pub fn coingecko_provider(&self) -> CoinGeckoProvider {
+        CoinGeckoProvider::with_client(client(Some(REQUEST_TIMEOUT)))
+            .with_base_urls(BaseUrls::new([self.url()]))
+    }
+
+    /// A Hyperliquid provider sending its requests here
+    pub fn hyperliquid_provider(&self) -> HyperliquidProvider {
+        HyperliquidProvider::with_client(client(Some(REQUEST_TIMEOUT)))
+            .with_api_urls(BaseUrls::new([format!("{}/info", self.url())]))
+    }
+
+    /// A Hermes provider sending its requests here
+    ///
+    /// Its stream is never started, so fetches go to the REST endpoint.
+    pub fn hermes_provider(&self) -> Arc<HermesProvider> {
+        HermesProvider::with_api_url(&self.url(), client(None), client(Some(REQUEST_TIMEOUT)))
+    }
+
+    async fn mount(&self, http_method: &str, endpoint: &str, response: ResponseTemplate) {
+        Mock::given(method(http_method))
+            .and(path(endpoint))
+            .respond_with(response)
+            .mount(&self.server)
+            .await;
+    }
+}
+
+/// A client without a proxy, so the environment cannot reroute requests
+/// meant for the local server
+fn client(timeout: Option<Duration>) -> reqwest::Client {
+    HttpClientConfig {
+        timeout,
+        proxy: None,
+        ..Default::default()
+    }
+    .build()
+    .expect("test HTTP client")
+}
+
+/// CoinGecko simple price response quoting `prices`
+pub fn coingecko_prices(prices: &[(Asset, f64)]) -> ResponseTemplate {
+    let registry = SymbolRegistry::global();
+    let body: Map<String, Value> = prices
+        .iter()
+        .map(|(asset, price)| {
+            let id = registry
+                .coingecko_id(*asset)
+                .expect("asset is listed on CoinGecko");
+            (id, json!({ "usd": price }))
+        })
+        .collect();
+    ResponseTemplate::new(200).set_body_json(body)
+}
+
+/// Hyperliquid `allMids` response quoting `prices`, per lot as the API does
+pub fn hyperliquid_mids(prices: &[(Asset, f64)]) -> ResponseTemplate {
+    let registry = SymbolRegistry::global();
+    let body: Map<String, Value> = prices
+        .iter()
+        .map(|(asset, price)| {
+            let mid = price * asset.hyperliquid_lot_size() as f64;
+            let symbol = registry
+                .hyperliquid_symbol(*asset)
+                .expect("asset is listed on Hyperliquid");
+            (symbol, json!(mid.to_string()))
+        })
+        .collect();
+    ResponseTemplate::new(200).set_body_json(body)
+}
+
+/// Hermes latest price response quoting `prices` as Pyth fixed-point values
+/// published [`HERMES_PUBLISH_AGE`] ago
+pub fn hermes_latest(prices: &[(Asset, f64)]) -> ResponseTemplate {
+    let registry = SymbolRegistry::global();
+    let parsed: Vec<Value> = prices
+        .iter()
+        .map(|(asset, price)| {
+            let id = registry
+                .pyth_feed_id(*asset)
+                .expect("asset has a Pyth feed");
+            json!({
+                "id": id.trim_start_matches("0x"),
+                "price": {
+                    "price": ((price * 1e8).round() as i64).to_string(),
+                    "conf": "100000",
+                    "expo": -8,
+                    "publish_time": (chrono::Utc::now() - HERMES_PUBLISH_AGE).timestamp(),
+                },
+            })
+        })
+        .collect();
+    ResponseTemplate::new(200).set_body_json(json!({ "parsed": parsed }))
+}
+
+/// 429 response, as sent by a provider throttling the client
+pub fn rate_limited() -> ResponseTemplate {
+    ResponseTemplate::new(429)
+        .insert_header("retry-after", "60")
+        .set_body_string("Too Many Requests")
+}
+
+/// 200 response whose JSON body is cut off mid-document
+pub fn malformed() -> ResponseTemplate {
+    ResponseTemplate::new(200)
+        .insert_header("content-type", "application/json")
+        .set_body_string(r#"{"parsed": [{"id": "#)
+}
+
+/// `response`, delivered only after the client's timeout
+pub fn slow(response: ResponseTemplate) -> ResponseTemplate {
+    response.set_delay(REQUEST_TIMEOUT * 4)
+}
@@ -0,0 +1,80 @@
+use crate::harness::{hermes_latest, malformed, rate_limited, slow, MockApi, HERMES_PUBLISH_AGE};
+use market_price_sdk::types::Asset;
+use market_price_sdk::{MarketPriceProvider, ProviderError};
+
+#[tokio::test]
+async fn test_fetches_prices() {
+    let api = MockApi::start().await;
+    api.hermes(hermes_latest(&[
+        (Asset::SOL, 150.25),
+        (Asset::BTC, 60000.0),
+    ]))
+    .await;
+
+    let prices = api
+        .hermes_provider()
+        .fetch_prices(&[Asset::SOL, Asset::BTC])
+        .await
+        .unwrap();
+
+    assert!((prices[&Asset::SOL].price_usd - 150.25).abs() < 1e-9);
+    assert!((prices[&Asset::SOL].confidence.unwrap() - 0.001).abs() < 1e-9);
+    assert_eq!(prices[&Asset::SOL].source, "hermes-rest");
+    // Dated by publish time, not arrival
+    assert!(prices[&Asset::SOL].age() >= HERMES_PUBLISH_AGE);
+}
+
+#[tokio::test]
+async fn test_rest_fallback_is_one_shot() {
+    let api = MockApi::start().await;
+    api.hermes(hermes_latest(&[(Asset::SOL, 150.25)])).await;
+    let provider = api.hermes_provider();
+
+    assert!(provider.fetch_price(Asset::SOL).await.is_ok());
+    // Later misses wait for the stream instead of polling REST
+    assert!(provider.fetch_price(Asset::BTC).await.is_err());
+    assert_eq!(api.request_count().await, 1);
+}
+
+#[tokio::test]
+async fn test_failed_rest_fallback_is_retried() {
+    let api = MockApi::start().await;
+    api.hermes_once(rate_limited()).await;
+    api.hermes(hermes_latest(&[(Asset::SOL, 150.25)])).await;
+    let provider = api.hermes_provider();
+
+    assert!(provider.fetch_price(Asset::SOL).await.is_err());
+    assert!(provider.fetch_price(Asset::SOL).await.is_ok());
+    assert_eq!(api.request_count().await, 2);
+}
+
+#[tokio::test]
+async fn test_rate_limited() {
+    let api = MockApi::start().await;
+    api.hermes(rate_limited()).await;
+
+    let result = api.hermes_provider().fetch_price(Asset::SOL).await;
+
+    assert!(matches!(result, Err(ProviderError::RateLimitExceeded)));
+}
+
+#[tokio::test]
+async fn test_malformed_json() {
+    let api = MockApi::start().await;
+    api.hermes(malformed()).await;
+
+    let result = api.hermes_provider().fetch_price(Asset::SOL).await;
+
+    assert!(matches!(result, Err(ProviderError::InvalidResponse(_))));
+}
+
+#[tokio::test]
+async fn test_slow_response_times_out() {
+    let api = MockApi::start().await;
+    api.hermes(slow(hermes_latest(&[(Asset::SOL, 150.0)])))
+        .await;
+
+    let result = api.hermes_provider().fetch_price(Asset::SOL).await;
+
+    assert!(matches!(result, Err(ProviderError::Timeout)));
+}
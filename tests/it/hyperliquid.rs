@@ -0,0 +1,54 @@
+use crate::harness::{hyperliquid_mids, malformed, rate_limited, slow, MockApi};
+use market_price_sdk::types::Asset;
+use market_price_sdk::{MarketPriceProvider, ProviderError};
+
+#[tokio::test]
+async fn test_fetches_prices() {
+    let api = MockApi::start().await;
+    api.hyperliquid(hyperliquid_mids(&[
+        (Asset::SOL, 150.25),
+        (Asset::BTC, 60000.0),
+    ]))
+    .await;
+
+    let prices = api
+        .hyperliquid_provider()
+        .fetch_prices(&[Asset::SOL, Asset::BTC])
+        .await
+        .unwrap();
+
+    assert!((prices[&Asset::SOL].price_usd - 150.25).abs() < 1e-9);
+    assert!((prices[&Asset::BTC].price_usd - 60000.0).abs() < 1e-9);
+    assert_eq!(prices[&Asset::SOL].source, "hyperliquid");
+}
+
+#[tokio::test]
+async fn test_rate_limited() {
+    let api = MockApi::start().await;
+    api.hyperliquid(rate_limited()).await;
+
+    let result = api.hyperliquid_provider().fetch_price(Asset::SOL).await;
+
+    assert!(matches!(result, Err(ProviderError::RateLimitExceeded)));
+}
+
+#[tokio::test]
+async fn test_malformed_json() {
+    let api = MockApi::start().await;
+    api.hyperliquid(malformed()).await;
+
+    let result = api.hyperliquid_provider().fetch_price(Asset::SOL).await;
+
+    assert!(matches!(result, Err(ProviderError::InvalidResponse(_))));
+}
+
+#[tokio::test]
+async fn test_slow_response_times_out() {
+    let api = MockApi::start().await;
+    api.hyperliquid(slow(hyperliquid_mids(&[(Asset::SOL, 150.0)])))
+        .await;
+
+    let result = api.hyperliquid_provider().fetch_price(Asset::SOL).await;
+
+    assert!(matches!(result, Err(ProviderError::Timeout)));
+}
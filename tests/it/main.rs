@@ -0,0 +1,10 @@
+//! Black-box tests running the providers against canned APIs
+//!
+//! Each provider gets a module covering a good response, rate limiting,
+//! malformed JSON and a response slower than the client's timeout, built
+//! on the server fixtures in [`harness`].
+
+mod coingecko;
+mod harness;
+mod hermes;
+mod hyperliquid;